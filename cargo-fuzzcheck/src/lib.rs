@@ -226,6 +226,9 @@ pub fn string_from_args(args: &Arguments) -> String {
     if args.stop_after_first_failure {
         s.push_str(&format!("--{} ", STOP_AFTER_FIRST_FAILURE_FLAG));
     }
+    if let Some(max_distinct_errors) = args.max_distinct_errors {
+        s.push_str(&format!("--{} {} ", MAX_DISTINCT_ERRORS_FLAG, max_distinct_errors));
+    }
     if args.detect_infinite_loop {
         s.push_str(&format!("--{} ", DETECT_INFINITE_LOOP_FLAG));
     }
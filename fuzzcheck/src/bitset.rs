@@ -28,7 +28,7 @@
 // IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER
 // DEALINGS IN THE SOFTWARE.
 
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Range};
 
 const BITS: usize = 64;
 type Block = u64;
@@ -44,7 +44,7 @@ fn div_rem(x: usize, d: usize) -> (usize, usize) {
 ///
 /// The bit set has a fixed capacity in terms of enabling bits (and the
 /// capacity can grow using the `grow` method).
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct FixedBitSet {
     data: Vec<Block>,
     /// length in bits
@@ -84,6 +84,42 @@ impl FixedBitSet {
         }
     }
 
+    /// Shrink the length to **bits**, discarding the bits past it, and free the blocks
+    /// that are no longer needed.
+    ///
+    /// It is the complement of [`grow`](Self::grow). Unlike [`clear`](Self::clear), it
+    /// reduces the memory used by the [`FixedBitSet`], such that [`capacity_bits`](Self::capacity_bits)
+    /// is the length rounded up to a whole number of blocks.
+    ///
+    /// Does nothing if **bits** is greater than or equal to the current length.
+    #[coverage(off)]
+    pub fn shrink_to(&mut self, bits: usize) {
+        if bits < self.length {
+            self.truncate(bits);
+            self.data.shrink_to_fit();
+        }
+    }
+
+    /// Reserve capacity for at least `additional_bits` more bits than the current length.
+    ///
+    /// The length of the [`FixedBitSet`] does not change, but a subsequent call to
+    /// [`grow`](Self::grow) with a length of at most `self.len() + additional_bits`
+    /// will not reallocate.
+    #[coverage(off)]
+    pub fn reserve(&mut self, additional_bits: usize) {
+        let bits = self.length.saturating_add(additional_bits);
+        let (mut blocks, rem) = div_rem(bits, BITS);
+        blocks += (rem > 0) as usize;
+        self.data.reserve(blocks.saturating_sub(self.data.len()));
+    }
+
+    /// Return the number of bits that the [`FixedBitSet`] can hold without reallocating.
+    #[inline]
+    #[coverage(off)]
+    pub fn capacity_bits(&self) -> usize {
+        self.data.capacity() * BITS
+    }
+
     /// Return the length of the [`FixedBitSet`] in bits.
     #[inline]
     #[coverage(off)]
@@ -161,6 +197,39 @@ impl FixedBitSet {
             prev
         }
     }
+
+    /// Enable `bit`, first growing the bitset to `bit + 1` bits if it is out of bounds.
+    ///
+    /// Unlike [`insert`](FixedBitSet::insert), this method does not panic when `bit` is out of bounds.
+    ///
+    /// **Panics** if **bit** is `usize::MAX`, since the bitset cannot hold more than `usize::MAX` bits.
+    #[inline]
+    #[coverage(off)]
+    pub fn insert_grow(&mut self, bit: usize) {
+        let len = bit.checked_add(1).unwrap_or_else(
+            #[coverage(off)]
+            || panic!("insert_grow at index {} exceeds the maximum fixbitset size", bit),
+        );
+        self.grow(len);
+        self.insert(bit);
+    }
+
+    /// Enable `bit`, and return its previous value, first growing the bitset to
+    /// `bit + 1` bits if it is out of bounds.
+    ///
+    /// Unlike [`put`](FixedBitSet::put), this method does not panic when `bit` is out of bounds.
+    ///
+    /// **Panics** if **bit** is `usize::MAX`, since the bitset cannot hold more than `usize::MAX` bits.
+    #[inline]
+    #[coverage(off)]
+    pub fn put_grow(&mut self, bit: usize) -> bool {
+        let len = bit.checked_add(1).unwrap_or_else(
+            #[coverage(off)]
+            || panic!("put_grow at index {} exceeds the maximum fixbitset size", bit),
+        );
+        self.grow(len);
+        self.put(bit)
+    }
     /// Toggle `bit` (inverting its state).
     ///
     /// ***Panics*** if **bit** is out of bounds
@@ -179,6 +248,53 @@ impl FixedBitSet {
         }
     }
 
+    /// Enable or disable all the bits in `range`.
+    ///
+    /// **Panics** if the range extends past the end of the bitset.
+    #[inline]
+    #[coverage(off)]
+    pub fn set_range(&mut self, range: Range<usize>, enabled: bool) {
+        assert!(
+            range.end <= self.length,
+            "set_range up to index {} exceeds fixbitset size {}",
+            range.end,
+            self.length
+        );
+        for bit in range {
+            let (block, i) = div_rem(bit, BITS);
+            if enabled {
+                self.data[block] |= 1 << i;
+            } else {
+                self.data[block] &= !(1 << i);
+            }
+        }
+    }
+
+    /// Enable all the bits in `range`.
+    ///
+    /// **Panics** if the range extends past the end of the bitset.
+    #[inline]
+    #[coverage(off)]
+    pub fn insert_range(&mut self, range: Range<usize>) {
+        self.set_range(range, true);
+    }
+
+    /// Reduce the length of the bitset to **bits**, discarding the bits past it.
+    ///
+    /// Does nothing if **bits** is greater than or equal to the current length.
+    #[coverage(off)]
+    pub(crate) fn truncate(&mut self, bits: usize) {
+        if bits < self.length {
+            let (mut blocks, rem) = div_rem(bits, BITS);
+            if rem > 0 {
+                self.data[blocks] &= (1 << rem) - 1;
+                blocks += 1;
+            }
+            self.data.truncate(blocks);
+            self.length = bits;
+        }
+    }
+
     /// Count the number of set bits in the given bit range.
     ///
     /// Use `..` to count the whole content of the bitset.
@@ -411,3 +527,106 @@ impl BitXorAssign<&Self> for FixedBitSet {
         self.symmetric_difference_with(other);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FixedBitSet;
+
+    #[test]
+    #[coverage(off)]
+    fn test_insert_grow() {
+        let mut bits = FixedBitSet::new();
+        assert_eq!(bits.len(), 0);
+        bits.insert_grow(70);
+        assert_eq!(bits.len(), 71);
+        assert!(bits.contains(70));
+        assert_eq!(bits.count_ones(), 1);
+
+        // inserting within bounds does not change the length
+        bits.insert_grow(3);
+        assert_eq!(bits.len(), 71);
+        assert!(bits.contains(3));
+        assert_eq!(bits.count_ones(), 2);
+
+        assert!(!bits.put_grow(200));
+        assert_eq!(bits.len(), 201);
+        assert!(bits.put_grow(200));
+        assert!(bits.contains(70) && bits.contains(3) && bits.contains(200));
+        assert_eq!(bits.count_ones(), 3);
+    }
+
+    #[test]
+    #[coverage(off)]
+    #[should_panic(expected = "exceeds the maximum fixbitset size")]
+    fn test_insert_grow_at_max_index() {
+        let mut bits = FixedBitSet::new();
+        bits.insert_grow(usize::MAX);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_reserve() {
+        let mut bits = FixedBitSet::with_capacity(10);
+        bits.insert(9);
+        bits.reserve(1000);
+        assert_eq!(bits.len(), 10);
+        assert!(bits.capacity_bits() >= 1010);
+        assert!(bits.contains(9));
+
+        let capacity = bits.capacity_bits();
+        let ptr = bits.as_slice().as_ptr();
+        bits.grow(1010);
+        assert_eq!(bits.len(), 1010);
+        assert_eq!(bits.capacity_bits(), capacity);
+        assert_eq!(bits.as_slice().as_ptr(), ptr);
+        assert!(bits.contains(9));
+        assert_eq!(bits.count_ones(), 1);
+
+        // reserving less than the current capacity does nothing
+        bits.reserve(0);
+        assert_eq!(bits.capacity_bits(), capacity);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_shrink_to() {
+        let mut bits = FixedBitSet::with_capacity(10);
+        bits.insert(2);
+        bits.insert(9);
+        let mut larger = FixedBitSet::with_capacity(300);
+        larger.insert(40);
+        larger.insert(100);
+        larger.insert(299);
+        bits.union_with(&larger);
+        assert_eq!(bits.len(), 300);
+        assert_eq!(bits.as_slice().len(), 5);
+        assert_eq!(bits.count_ones(), 5);
+
+        // the bits past the new length are discarded, even within the last block
+        bits.shrink_to(70);
+        assert_eq!(bits.len(), 70);
+        assert_eq!(bits.as_slice().len(), 2);
+        assert_eq!(bits.capacity_bits(), 128);
+        assert_eq!(bits.count_ones(), 3);
+        assert!(bits.contains(2) && bits.contains(9) && bits.contains(40));
+        assert!(!bits.contains(100));
+
+        bits.shrink_to(10);
+        assert_eq!(bits.len(), 10);
+        assert_eq!(bits.as_slice().len(), 1);
+        assert_eq!(bits.count_ones(), 2);
+        // growing again does not bring back the discarded bits
+        bits.grow(300);
+        assert_eq!(bits.count_ones(), 2);
+        assert!(!bits.contains(40) && !bits.contains(299));
+
+        // shrinking to a larger length does nothing
+        bits.shrink_to(1000);
+        assert_eq!(bits.len(), 300);
+
+        bits.shrink_to(0);
+        assert!(bits.is_empty());
+        assert_eq!(bits.as_slice().len(), 0);
+        assert_eq!(bits.count_ones(), 0);
+    }
+}
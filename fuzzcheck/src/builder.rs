@@ -55,7 +55,30 @@ let _ = fuzzcheck::fuzz_test(test_function)
 
 To build a custom sensor and pool, you may want to look at the [`Sensor`], [`Pool`](crate::Pool), and [`CompatibleWithObservations`] traits.
 You can also look at the types provided in the [`sensors_and_pools`](crate::sensors_and_pools) module. But the easiest way to customize them
-is to use the [`SensorAndPoolBuilder`], although it only offers a couple limited options.
+is to use the [`SensorAndPoolBuilder`]. Additional sensors, each with a pool processing its observations, can be added
+to it without implementing any trait:
+```no_run
+# use std::ptr::addr_of_mut;
+use fuzzcheck::builder::basic_sensor_and_pool;
+use fuzzcheck::sensors_and_pools::{MaximiseObservationPool, StaticValueSensor};
+# fn test_function(x: &bool) {}
+# static mut MAX_DEPTH: usize = 0;
+#
+# fn fuzz() {
+let depth_sensor = unsafe { StaticValueSensor::new(&mut *addr_of_mut!(MAX_DEPTH), 0) };
+let (sensor, pool) = basic_sensor_and_pool()
+    .with_sensor(depth_sensor)
+    // does not compile if the pool is not compatible with the observations of `depth_sensor`
+    .with_pool(MaximiseObservationPool::<usize>::new("max_depth"))
+    .finish();
+let _ = fuzzcheck::fuzz_test(test_function)
+    .default_mutator()
+    .serde_serializer()
+    .sensor_and_pool(sensor, pool)
+    .arguments_from_cargo_fuzzcheck()
+    .launch();
+# }
+```
 */
 
 use std::borrow::Borrow;
@@ -69,9 +92,10 @@ use fuzzcheck_common::arg::{
 };
 
 use crate::code_coverage_sensor::CodeCoverageSensor;
-use crate::fuzzer::{Fuzzer, FuzzingResult};
+use crate::fuzzer::{FuzzResult, Fuzzer, FuzzingResult};
+use crate::mutators::exhaustive::ExhaustiveMutator;
 use crate::sensors_and_pools::{
-    AndPool, DifferentObservations, MaximiseEachCounterPool, MaximiseObservationPool, MostNDiversePool,
+    AndPool, AndSensor, DifferentObservations, MaximiseEachCounterPool, MaximiseObservationPool, MostNDiversePool,
     SameObservations, SimplestToActivateCounterPool, WrapperSensor,
 };
 #[cfg(feature = "serde_ron_serializer")]
@@ -271,6 +295,152 @@ where
     }
 }
 
+/**
+    Run the test function on every value of the given [`ExhaustiveMutator`], instead of fuzzing it.
+
+    The arguments are read from `cargo fuzzcheck`, as with
+    [`arguments_from_cargo_fuzzcheck`](FuzzerBuilder4::arguments_from_cargo_fuzzcheck). Every value is tested
+    exactly once, in the order in which the mutator enumerates them, and no sensor or pool is needed. The
+    failing values are saved in the artifacts folder, like the ones found by the fuzzer, and the search stops
+    at the first of them unless the fuzzer was asked not to stop after the first failure. It also stops once
+    the maximum number of iterations or the maximum duration is reached.
+
+    If the test function passes on all the values, it returns a [`FuzzingResult`] whose reason for stopping
+    is [`ReasonForStopping::ExhaustedAllPossibleMutations`]. If it fails on some of them, the reason for
+    stopping contains the first failing value.
+
+    If the mutator has too many values to be enumerated, as determined by
+    [`total_count`](ExhaustiveMutator::total_count), then it prints a warning and fuzzes the test function
+    as if it was launched with:
+    ```no_run
+    # use fuzzcheck::DefaultMutator;
+    # use fuzzcheck::mutators::exhaustive::ExhaustiveMutator;
+    # fn test_function(x: &u32) {}
+    # let mutator = ExhaustiveMutator::new(u32::default_mutator());
+    # let serializer = fuzzcheck::SerdeSerializer::default();
+    fuzzcheck::fuzz_test(test_function)
+        .mutator(mutator)
+        .serializer(serializer)
+        .default_sensor_and_pool()
+        .arguments_from_cargo_fuzzcheck()
+        .launch()
+    # ;
+    ```
+    This is also what it does when `cargo fuzzcheck` is asked to read or minify an input file.
+
+    For example:
+    ```no_run
+    use fuzzcheck::{DefaultMutator, ReasonForStopping, SerdeSerializer};
+    use fuzzcheck::mutators::exhaustive::ExhaustiveMutator;
+
+    fn test_function(x: &u16) -> bool {
+        x.checked_add(1).is_some() || *x == u16::MAX
+    }
+    let result = fuzzcheck::launch_exhaustive(
+        test_function,
+        ExhaustiveMutator::new(u16::default_mutator()),
+        SerdeSerializer::default(),
+    );
+    assert!(matches!(result.reason_for_stopping, ReasonForStopping::ExhaustedAllPossibleMutations));
+    ```
+*/
+#[coverage(off)]
+pub fn launch_exhaustive<T, F, TestFunctionKind, M, S>(
+    test_function: F,
+    mutator: ExhaustiveMutator<T::Owned, M>,
+    serializer: S,
+) -> FuzzingResult<T::Owned>
+where
+    T: ?Sized + ToOwned + 'static,
+    T::Owned: Clone,
+    F: FuzzTestFunction<T::Owned, T, TestFunctionKind>,
+    F::NormalizedFunction: 'static,
+    M: Mutator<T::Owned> + 'static,
+    S: Serializer<Value = T::Owned> + 'static,
+{
+    let test_function = test_function.test_function();
+    let arguments = arguments_from_cargo_fuzzcheck();
+    let total_count = if let FuzzerCommand::Fuzz = arguments.command {
+        mutator.total_count()
+    } else {
+        None
+    };
+    let Some(total_count) = total_count else {
+        if let FuzzerCommand::Fuzz = arguments.command {
+            eprintln!(
+                "warning: the mutator has too many values to be tested exhaustively, fuzzing the test function instead"
+            );
+        }
+        let builder = FuzzerBuilder2 {
+            test_function,
+            mutator,
+            _phantom: PhantomData,
+        };
+        return builder
+            .serializer(serializer)
+            .default_sensor_and_pool()
+            .arguments(arguments)
+            .launch();
+    };
+
+    mutator.initialize();
+    crate::fuzzer::launch_exhaustive(&test_function, &mutator, &serializer, total_count, arguments)
+}
+
+/// Parse the arguments passed by `cargo fuzzcheck` through the `FUZZCHECK_ARGS` environment variable,
+/// or print the help and exit if they are invalid
+#[coverage(off)]
+fn arguments_from_cargo_fuzzcheck() -> Arguments {
+    let parser = options_parser();
+    let mut help = format!(
+        r#""
+fuzzcheck <SUBCOMMAND> [OPTIONS]
+
+SUBCOMMANDS:
+    {fuzz}    Run the fuzz test
+    {minify}    Minify a crashing test input, requires --{input_file}
+"#,
+        fuzz = COMMAND_FUZZ,
+        minify = COMMAND_MINIFY_INPUT,
+        input_file = INPUT_FILE_FLAG,
+    );
+    help += parser.usage("").as_str();
+    help += format!(
+        r#""
+## Examples:
+
+fuzzcheck {fuzz}
+    Launch the fuzzer with default options.
+
+fuzzcheck {minify} --{input_file} "artifacts/crash.json"
+
+    Minify the test input defined in the file "artifacts/crash.json".
+    It will put minified inputs in the folder artifacts/crash.minified/
+    and name them {{complexity}}-{{hash}}.json.
+    For example, artifacts/crash.minified/4213--8cd7777109b57b8c.json
+    is a minified input of complexity 42.13.
+"#,
+        fuzz = COMMAND_FUZZ,
+        minify = COMMAND_MINIFY_INPUT,
+        input_file = INPUT_FILE_FLAG,
+    )
+    .as_str();
+
+    let arguments = std::env::var("FUZZCHECK_ARGS").unwrap();
+    let arguments = split_string_by_whitespace(&arguments);
+    let matches = parser.parse(arguments).map_err(ArgumentsError::from);
+    match matches.and_then(
+        #[coverage(off)]
+        |matches| Arguments::from_matches(&matches, false),
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            println!("{}\n\n{}", e, help);
+            std::process::exit(1);
+        }
+    }
+}
+
 #[cfg(feature = "serde_json_serializer")]
 impl<T, F> FuzzerBuilder1<T, F>
 where
@@ -542,54 +712,7 @@ where
     }
     #[coverage(off)]
     pub fn arguments_from_cargo_fuzzcheck(self) -> FuzzerBuilder5<F, M, V, Sens, P> {
-        let parser = options_parser();
-        let mut help = format!(
-            r#""
-fuzzcheck <SUBCOMMAND> [OPTIONS]
-
-SUBCOMMANDS:
-    {fuzz}    Run the fuzz test
-    {minify}    Minify a crashing test input, requires --{input_file}
-"#,
-            fuzz = COMMAND_FUZZ,
-            minify = COMMAND_MINIFY_INPUT,
-            input_file = INPUT_FILE_FLAG,
-        );
-        help += parser.usage("").as_str();
-        help += format!(
-            r#""
-## Examples:
-
-fuzzcheck {fuzz}
-    Launch the fuzzer with default options.
-
-fuzzcheck {minify} --{input_file} "artifacts/crash.json"
-
-    Minify the test input defined in the file "artifacts/crash.json".
-    It will put minified inputs in the folder artifacts/crash.minified/
-    and name them {{complexity}}-{{hash}}.json.
-    For example, artifacts/crash.minified/4213--8cd7777109b57b8c.json
-    is a minified input of complexity 42.13.
-"#,
-            fuzz = COMMAND_FUZZ,
-            minify = COMMAND_MINIFY_INPUT,
-            input_file = INPUT_FILE_FLAG,
-        )
-        .as_str();
-
-        let arguments = std::env::var("FUZZCHECK_ARGS").unwrap();
-        let arguments = split_string_by_whitespace(&arguments);
-        let matches = parser.parse(arguments).map_err(ArgumentsError::from);
-        let arguments = match matches.and_then(
-            #[coverage(off)]
-            |matches| Arguments::from_matches(&matches, false),
-        ) {
-            Ok(r) => r,
-            Err(e) => {
-                println!("{}\n\n{}", e, help);
-                std::process::exit(1);
-            }
-        };
+        let arguments = arguments_from_cargo_fuzzcheck();
         FuzzerBuilder5 {
             test_function: self.test_function,
             mutator: self.mutator,
@@ -667,6 +790,18 @@ where
         x.arguments.stop_after_first_failure = stop_after_first_test_failure;
         x
     }
+    /// Limit the number of distinct test failures that the fuzzer keeps track of.
+    ///
+    /// Once `max_distinct_errors` different failures have been found, new ones are ignored,
+    /// but simpler test cases are still searched for the failures that were already found.
+    /// This bounds the memory and disk used on targets that fail in many different ways.
+    #[must_use]
+    #[coverage(off)]
+    pub fn max_distinct_errors(self, max_distinct_errors: Option<usize>) -> Self {
+        let mut x = self;
+        x.arguments.max_distinct_errors = max_distinct_errors;
+        x
+    }
     /// Launch the fuzz test!
     #[coverage(off)]
     pub fn launch(self) -> FuzzingResult<V> {
@@ -690,6 +825,34 @@ where
             arguments,
         )
     }
+    /// Launch the fuzz test and return a summary of it, without exiting the process.
+    ///
+    /// The summary contains the number of iterations, each distinct test failure along with
+    /// the simplest input that caused it, and the final statistics of the pool. This is meant
+    /// for running fuzz tests inside a custom test harness. Note that the process still exits
+    /// if the fuzzer catches a signal, such as a segmentation fault or `SIGINT`.
+    #[coverage(off)]
+    pub fn launch_and_return(self) -> FuzzResult<V> {
+        let FuzzerBuilder5 {
+            test_function,
+            mutator,
+            serializer,
+            pool,
+            sensor,
+            arguments,
+            _phantom,
+        } = self;
+
+        mutator.initialize();
+
+        crate::fuzzer::launch_and_return(
+            Box::new(test_function),
+            mutator,
+            serializer,
+            Box::new((sensor, pool)),
+            arguments,
+        )
+    }
 }
 
 pub type BasicSensor = CodeCoverageSensor;
@@ -839,11 +1002,83 @@ where
     S: Sensor,
     P: CompatibleWithObservations<S::Observations>,
 {
+    /// Create a builder from any sensor and a pool that is compatible with its observations
+    #[coverage(off)]
+    pub fn new(sensor: S, pool: P) -> Self {
+        Self { sensor, pool }
+    }
+
     /// Obtain the sensor and pool from the builder
     #[coverage(off)]
     pub fn finish(self) -> (S, P) {
         (self.sensor, self.pool)
     }
+
+    /// Add another sensor, whose observations are given to the pool passed to
+    /// [`.with_pool(..)`](SensorAndPoolBuilderWithSensor::with_pool) on the result.
+    ///
+    /// The resulting sensor and pool are an [`AndSensor`] and an [`AndPool`] combining
+    /// the current ones with the new ones.
+    #[coverage(off)]
+    pub fn with_sensor<S2: Sensor>(self, sensor: S2) -> SensorAndPoolBuilderWithSensor<S, P, S2> {
+        SensorAndPoolBuilderWithSensor { builder: self, sensor }
+    }
+}
+
+/// A [`SensorAndPoolBuilder`] to which a sensor was added, but not yet its pool.
+///
+/// Use [`.with_pool(..)`](SensorAndPoolBuilderWithSensor::with_pool) to add the pool
+/// processing the observations of the new sensor.
+pub struct SensorAndPoolBuilderWithSensor<S, P, S2>
+where
+    S: Sensor,
+    P: CompatibleWithObservations<S::Observations>,
+    S2: Sensor,
+{
+    builder: SensorAndPoolBuilder<S, P>,
+    sensor: S2,
+}
+
+impl<S, P, S2> SensorAndPoolBuilderWithSensor<S, P, S2>
+where
+    S: Sensor,
+    P: CompatibleWithObservations<S::Observations>,
+    S2: Sensor,
+{
+    /// Add the pool processing the observations of the last added sensor.
+    ///
+    /// The pool must be [compatible with the observations](CompatibleWithObservations) of that
+    /// sensor, which is checked at compile time. Its weight is [`pool.weight()`](crate::Pool::weight).
+    #[coverage(off)]
+    pub fn with_pool<P2>(
+        self,
+        pool: P2,
+    ) -> SensorAndPoolBuilder<AndSensor<S, S2>, AndPool<P, P2, DifferentObservations>>
+    where
+        P2: CompatibleWithObservations<S2::Observations>,
+    {
+        self.with_weighted_pool(pool, None)
+    }
+
+    /// Like [`.with_pool(..)`](SensorAndPoolBuilderWithSensor::with_pool), but `override_weight`
+    /// determines the relative chance of selecting the new pool when the fuzzer asks for a test case.
+    ///
+    /// See [`PoolExt::and`] for more details.
+    #[coverage(off)]
+    pub fn with_weighted_pool<P2>(
+        self,
+        pool: P2,
+        override_weight: Option<f64>,
+    ) -> SensorAndPoolBuilder<AndSensor<S, S2>, AndPool<P, P2, DifferentObservations>>
+    where
+        P2: CompatibleWithObservations<S2::Observations>,
+    {
+        let SensorAndPoolBuilder { sensor, pool: p1 } = self.builder;
+        SensorAndPoolBuilder {
+            sensor: AndSensor(sensor, self.sensor),
+            pool: p1.and(pool, override_weight, DifferentObservations),
+        }
+    }
 }
 
 impl SensorAndPoolBuilder<BasicSensor, BasicPool> {
@@ -0,0 +1,411 @@
+//! Utilities to manipulate corpora of test cases saved on the file system.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Result};
+use std::path::{Path, PathBuf};
+
+use crate::world::is_valid_file_stem;
+use crate::{Mutator, Serializer};
+
+/// A summary of the test cases processed by [`merge`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    /// The number of test cases written to the output folder
+    pub kept: usize,
+    /// The number of test cases that were identical to an already-kept test case
+    pub duplicates: usize,
+    /// The number of files that could not be deserialized, or whose test case was rejected
+    /// by the mutator
+    pub invalid: usize,
+}
+
+/// Merge the corpora located in `dirs` into the folder `out`, removing duplicates.
+///
+/// Every file with the serializer’s [extension](Serializer::extension) is read
+/// recursively from each folder in `dirs` and decoded with [`from_data`](Serializer::from_data).
+/// The files that cannot be decoded are dropped, as well as the test cases that the mutator
+/// cannot handle, according to [`validate_value`](Mutator::validate_value). The remaining test cases are serialized
+/// again with [`to_data`](Serializer::to_data), and those whose serialized bytes are equal to
+/// an earlier one are dropped too. The survivors are written to `out`, which is created if
+/// it does not exist. Like the corpora written by the fuzzer, each file is named after the
+/// [stem suggested by the serializer](Serializer::suggested_file_stem), or after the hash of its
+/// content.
+///
+/// ```no_run
+/// use std::path::{Path, PathBuf};
+/// use fuzzcheck::{ByteSerializer, DefaultMutator};
+///
+/// let report = fuzzcheck::corpus::merge(
+///     &<Vec<u8>>::default_mutator(),
+///     &ByteSerializer::new("bin"),
+///     &[PathBuf::from("fuzz/corpus_1"), PathBuf::from("fuzz/corpus_2")],
+///     Path::new("fuzz/merged_corpus"),
+/// )
+/// .unwrap();
+/// println!("kept {} test cases", report.kept);
+/// ```
+#[coverage(off)]
+pub fn merge<T, M, S>(mutator: &M, serializer: &S, dirs: &[PathBuf], out: &Path) -> Result<MergeReport>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    S: Serializer<Value = T> + ?Sized,
+{
+    let mut files = vec![];
+    for dir in dirs {
+        collect_files(dir, serializer.extension(), &mut files)?;
+    }
+    if !out.is_dir() {
+        fs::create_dir_all(out)?;
+    }
+    let mut report = MergeReport::default();
+    let mut seen = HashSet::new();
+    for file in files {
+        let data = fs::read(&file)?;
+        let Some(value) = serializer.from_data(&data).filter(
+            #[coverage(off)]
+            |value| mutator.validate_value(value).is_some(),
+        ) else {
+            report.invalid += 1;
+            continue;
+        };
+        let content = serializer.to_data(&value);
+        if seen.insert(content.clone()) {
+            // the suggested stem is ignored if a different test case was already saved under that name
+            let path = serializer
+                .suggested_file_stem(&value, &content)
+                .filter(
+                    #[coverage(off)]
+                    |stem| is_valid_file_stem(stem),
+                )
+                .map(
+                    #[coverage(off)]
+                    |stem| out.join(stem).with_extension(serializer.extension()),
+                )
+                .filter(
+                    #[coverage(off)]
+                    |path| {
+                        fs::read(path).map_or(
+                            true,
+                            #[coverage(off)]
+                            |existing| existing == content,
+                        )
+                    },
+                )
+                .unwrap_or_else(
+                    #[coverage(off)]
+                    || {
+                        let mut hasher = DefaultHasher::new();
+                        content.hash(&mut hasher);
+                        out.join(format!("{:x}", hasher.finish()))
+                            .with_extension(serializer.extension())
+                    },
+                );
+            fs::write(path, content)?;
+            report.kept += 1;
+        } else {
+            report.duplicates += 1;
+        }
+    }
+    Ok(report)
+}
+
+/// The result of comparing two corpora with [`diff`].
+///
+/// Each list contains the paths of the test cases, in a deterministic order. When several
+/// files of the same corpus contain the same test case, only the first one is listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorpusDiff {
+    /// The test cases of the first corpus that are not in the second one
+    pub only_in_a: Vec<PathBuf>,
+    /// The test cases of the second corpus that are not in the first one
+    pub only_in_b: Vec<PathBuf>,
+    /// The test cases of the first corpus that are also in the second one
+    pub common: Vec<PathBuf>,
+    /// The files of either corpus that could not be deserialized, or whose test case was
+    /// rejected by the mutator
+    pub invalid: Vec<PathBuf>,
+}
+
+/// Compare the corpora located in `dir_a` and `dir_b`.
+///
+/// The files are read, decoded, and validated by the mutator in the same way as in [`merge`]. Two test cases are
+/// considered identical if they are serialized to the same bytes by
+/// [`to_data`](Serializer::to_data), so that files that differ only by their formatting are
+/// still matched.
+///
+/// This can be used in continuous integration to check that no test case of a reference
+/// corpus was lost:
+/// ```no_run
+/// use std::path::Path;
+/// use fuzzcheck::{ByteSerializer, DefaultMutator};
+///
+/// let diff = fuzzcheck::corpus::diff(
+///     &<Vec<u8>>::default_mutator(),
+///     &ByteSerializer::new("bin"),
+///     Path::new("fuzz/reference_corpus"),
+///     Path::new("fuzz/corpus"),
+/// )
+/// .unwrap();
+/// assert!(diff.only_in_a.is_empty(), "lost test cases: {:?}", diff.only_in_a);
+/// ```
+#[coverage(off)]
+pub fn diff<T, M, S>(mutator: &M, serializer: &S, dir_a: &Path, dir_b: &Path) -> Result<CorpusDiff>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    S: Serializer<Value = T> + ?Sized,
+{
+    let mut result = CorpusDiff::default();
+    let corpus_a = load_test_cases(mutator, serializer, dir_a, &mut result.invalid)?;
+    let corpus_b = load_test_cases(mutator, serializer, dir_b, &mut result.invalid)?;
+    let contents_a = corpus_a
+        .iter()
+        .map(
+            #[coverage(off)]
+            |(content, _)| content,
+        )
+        .collect::<HashSet<_>>();
+    let contents_b = corpus_b
+        .iter()
+        .map(
+            #[coverage(off)]
+            |(content, _)| content,
+        )
+        .collect::<HashSet<_>>();
+    for (content, path) in corpus_a.iter() {
+        if contents_b.contains(content) {
+            result.common.push(path.clone());
+        } else {
+            result.only_in_a.push(path.clone());
+        }
+    }
+    for (content, path) in corpus_b.iter() {
+        if !contents_a.contains(content) {
+            result.only_in_b.push(path.clone());
+        }
+    }
+    Ok(result)
+}
+
+/// Read and decode the test cases of a corpus, returning their serialized content and the
+/// path of the first file containing them
+#[coverage(off)]
+fn load_test_cases<T, M, S>(
+    mutator: &M,
+    serializer: &S,
+    dir: &Path,
+    invalid: &mut Vec<PathBuf>,
+) -> Result<Vec<(Vec<u8>, PathBuf)>>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    S: Serializer<Value = T> + ?Sized,
+{
+    let mut files = vec![];
+    collect_files(dir, serializer.extension(), &mut files)?;
+    let mut test_cases = vec![];
+    let mut seen = HashSet::new();
+    for file in files {
+        let data = fs::read(&file)?;
+        let Some(value) = serializer.from_data(&data).filter(
+            #[coverage(off)]
+            |value| mutator.validate_value(value).is_some(),
+        ) else {
+            invalid.push(file);
+            continue;
+        };
+        let content = serializer.to_data(&value);
+        if seen.insert(content.clone()) {
+            test_cases.push((content, file));
+        }
+    }
+    Ok(test_cases)
+}
+
+/// Recursively collect the paths of the files with the given extension, in a deterministic order
+#[coverage(off)]
+fn collect_files(dir: &Path, extension: &str, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not a directory", dir.display()),
+        ));
+    }
+    let mut entries = fs::read_dir(dir)?
+        .map(
+            #[coverage(off)]
+            |entry| entry.map(|entry| entry.path()),
+        )
+        .collect::<Result<Vec<_>>>()?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, extension, files)?;
+        } else if path.extension().is_some_and(
+            #[coverage(off)]
+            |ext| ext == extension,
+        ) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{diff, merge, CorpusDiff, MergeReport};
+    use crate::mutators::integer_within_range::U8WithinRangeMutator;
+    use crate::{ByteSerializer, DefaultMutator, HashNamedSerializer, Serializer, StringSerializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_merge() {
+        let root = std::env::temp_dir().join(format!("fuzzcheck-merge-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (a, b, out) = (root.join("a"), root.join("b"), root.join("out"));
+        fs::create_dir_all(a.join("nested")).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        fs::write(a.join("1.txt"), "1").unwrap();
+        fs::write(a.join("nested").join("2.txt"), "2").unwrap();
+        fs::write(a.join("invalid.txt"), "not a number").unwrap();
+        fs::write(a.join("1.rng"), "ignored").unwrap();
+        fs::write(b.join("1.txt"), "1").unwrap();
+        fs::write(b.join("3.txt"), "3").unwrap();
+        fs::write(b.join("200.txt"), "200").unwrap();
+
+        let serializer = StringSerializer::<u8>::new("txt");
+        // 200 is a valid u8, but it is rejected by the mutator
+        let mutator = U8WithinRangeMutator::new(0..=100);
+        let report = merge(&mutator, &serializer, &[a, b], &out).unwrap();
+        assert_eq!(
+            report,
+            MergeReport {
+                kept: 3,
+                duplicates: 1,
+                invalid: 2
+            }
+        );
+        let mut contents = fs::read_dir(&out)
+            .unwrap()
+            .map(|entry| fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect::<Vec<_>>();
+        contents.sort();
+        assert_eq!(contents, vec!["1", "2", "3"]);
+
+        assert!(merge(&mutator, &serializer, &[root.join("missing")], &out).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_merge_uses_suggested_file_stem() {
+        let root = std::env::temp_dir().join(format!("fuzzcheck-merge-stem-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (a, out) = (root.join("a"), root.join("out"));
+        fs::create_dir_all(&a).unwrap();
+        fs::write(a.join("x.bin"), "a").unwrap();
+
+        let serializer = HashNamedSerializer::new(ByteSerializer::new("bin"));
+        merge(&<Vec<u8>>::default_mutator(), &serializer, &[a], &out).unwrap();
+        assert_eq!(fs::read(out.join("af63dc4c8601ec8c.bin")).unwrap(), b"a");
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    /// A serializer of bytes that always suggests the same file stem
+    struct FixedStemSerializer(&'static str);
+
+    impl Serializer for FixedStemSerializer {
+        type Value = Vec<u8>;
+
+        #[coverage(off)]
+        fn extension(&self) -> &str {
+            "bin"
+        }
+        #[coverage(off)]
+        fn from_data(&self, data: &[u8]) -> Option<Vec<u8>> {
+            Some(data.to_vec())
+        }
+        #[coverage(off)]
+        fn to_data(&self, value: &Vec<u8>) -> Vec<u8> {
+            value.clone()
+        }
+        #[coverage(off)]
+        fn suggested_file_stem(&self, _value: &Vec<u8>, _data: &[u8]) -> Option<String> {
+            Some(self.0.to_owned())
+        }
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_merge_ignores_invalid_or_colliding_file_stems() {
+        let root = std::env::temp_dir().join(format!("fuzzcheck-merge-bad-stem-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (a, out) = (root.join("a"), root.join("out"));
+        fs::create_dir_all(&a).unwrap();
+        fs::write(a.join("1.bin"), "1").unwrap();
+        fs::write(a.join("2.bin"), "2").unwrap();
+        let dirs = [a];
+
+        for stem in ["../escaped", "/tmp/escaped", "..", ".", ""] {
+            let _ = fs::remove_dir_all(&out);
+            let report = merge(&<Vec<u8>>::default_mutator(), &FixedStemSerializer(stem), &dirs, &out).unwrap();
+            assert_eq!(report.kept, 2);
+            assert_eq!(fs::read_dir(&out).unwrap().count(), 2);
+        }
+        assert!(!root.join("escaped.bin").exists());
+
+        // the second test case does not overwrite the first one
+        let _ = fs::remove_dir_all(&out);
+        merge(&<Vec<u8>>::default_mutator(), &FixedStemSerializer("same"), &dirs, &out).unwrap();
+        let mut contents = fs::read_dir(&out)
+            .unwrap()
+            .map(
+                #[coverage(off)]
+                |entry| fs::read_to_string(entry.unwrap().path()).unwrap(),
+            )
+            .collect::<Vec<_>>();
+        contents.sort();
+        assert_eq!(contents, vec!["1", "2"]);
+        assert!(out.join("same.bin").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_diff() {
+        let root = std::env::temp_dir().join(format!("fuzzcheck-diff-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let (a, b) = (root.join("a"), root.join("b"));
+        fs::create_dir_all(a.join("nested")).unwrap();
+        fs::create_dir_all(&b).unwrap();
+        fs::write(a.join("1.txt"), "1").unwrap();
+        fs::write(a.join("nested").join("2.txt"), "2").unwrap();
+        fs::write(a.join("invalid.txt"), "not a number").unwrap();
+        fs::write(b.join("1.txt"), "1").unwrap();
+        fs::write(b.join("one.txt"), "1").unwrap();
+        fs::write(b.join("3.txt"), "3").unwrap();
+        fs::write(b.join("200.txt"), "200").unwrap();
+
+        let serializer = StringSerializer::<u8>::new("txt");
+        let mutator = U8WithinRangeMutator::new(0..=100);
+        let result = diff(&mutator, &serializer, &a, &b).unwrap();
+        assert_eq!(
+            result,
+            CorpusDiff {
+                only_in_a: vec![a.join("nested").join("2.txt")],
+                only_in_b: vec![b.join("3.txt")],
+                common: vec![a.join("1.txt")],
+                invalid: vec![a.join("invalid.txt"), b.join("200.txt")],
+            }
+        );
+
+        assert!(diff(&mutator, &serializer, &a, &root.join("missing")).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
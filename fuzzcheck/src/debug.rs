@@ -0,0 +1,80 @@
+//! Utilities to reproduce a test failure outside of the fuzzer.
+
+use std::fs;
+use std::io::{self, Result};
+use std::path::Path;
+
+use crate::Serializer;
+
+/// Read the test case saved at `path`, decode it with the serializer, and run the test function on it once.
+///
+/// It returns the value returned by the test function, or an error if the file cannot be read or
+/// cannot be decoded by [`from_data`](Serializer::from_data). Unlike the fuzzer, it does not catch
+/// panics and does not install any signal handler, which makes it convenient to reproduce a saved
+/// artifact under a debugger.
+///
+/// ```no_run
+/// use std::path::Path;
+/// use fuzzcheck::ByteSerializer;
+///
+/// fn test_function(data: &Vec<u8>) -> bool {
+///     data.first() != Some(&0)
+/// }
+///
+/// let passed = fuzzcheck::debug::load_and_run(
+///     &ByteSerializer::new("bin"),
+///     Path::new("fuzz/artifacts/0123456789abcdef.bin"),
+///     test_function,
+/// )
+/// .unwrap();
+/// ```
+#[coverage(off)]
+pub fn load_and_run<S>(serializer: &S, path: &Path, test_fn: impl Fn(&S::Value) -> bool) -> Result<bool>
+where
+    S: Serializer,
+{
+    let data = fs::read(path)?;
+    let value = serializer.from_data_result(&data).map_err(
+        #[coverage(off)]
+        |e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} could not be decoded by the serializer: {e}", path.display()),
+            )
+        },
+    )?;
+    Ok(test_fn(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::load_and_run;
+    use crate::StringSerializer;
+
+    #[test]
+    #[coverage(off)]
+    fn test_load_and_run() {
+        let root = std::env::temp_dir().join(format!("fuzzcheck-debug-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("small.txt"), "3").unwrap();
+        fs::write(root.join("large.txt"), "200").unwrap();
+        fs::write(root.join("invalid.txt"), "not a number").unwrap();
+
+        let serializer = StringSerializer::<u8>::new("txt");
+        let test_fn = |x: &u8| *x < 100;
+        assert!(load_and_run(&serializer, &root.join("small.txt"), test_fn).unwrap());
+        assert!(!load_and_run(&serializer, &root.join("large.txt"), test_fn).unwrap());
+
+        let error = load_and_run(&serializer, &root.join("invalid.txt"), test_fn).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            error.to_string().contains("the 12 bytes could not be decoded"),
+            "{error}"
+        );
+        assert!(load_and_run(&serializer, &root.join("missing.txt"), test_fn).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -6,18 +6,22 @@ use std::hash::{Hash, Hasher};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::process::exit;
 use std::result::Result;
+use std::time::{Instant, SystemTime};
 
 use fuzzcheck_common::arg::{Arguments, FuzzerCommand};
 use fuzzcheck_common::{FuzzerEvent, FuzzerStats};
 use libc::{SIGABRT, SIGALRM, SIGBUS, SIGFPE, SIGINT, SIGSEGV, SIGTERM, SIGTRAP};
 
 use crate::data_structures::RcSlab;
+use crate::mutators::exhaustive::ExhaustiveMutator;
 use crate::sensors_and_pools::{
     AndSensorAndPool, NoopSensor, TestFailure, TestFailurePool, TestFailureSensor, UnitPool, TEST_FAILURE,
 };
 use crate::signals_handler::set_signal_handlers;
 use crate::subvalue_provider::{CrossoverSubValueProvider, Generation, SubValueProviderId};
-use crate::traits::{CorpusDelta, Mutator, SaveToStatsFolder, SensorAndPool, Serializer};
+use crate::traits::{
+    ArtifactMetadata, CorpusDelta, MutateResult, Mutator, SaveToStatsFolder, SensorAndPool, Serializer,
+};
 use crate::world::World;
 use crate::{CSVField, SubValueProvider, ToCSV};
 
@@ -53,6 +57,38 @@ pub enum ReasonForStopping<T> {
     MaxDurationReached,
 }
 
+/// A summary of a fuzz test, returned by [`launch_and_return`](crate::builder::FuzzerBuilder5::launch_and_return).
+///
+/// Unlike [`FuzzingResult`], it lists the test failures that were found while fuzzing,
+/// which is useful when the fuzz test does not stop after the first test failure.
+#[derive(Debug, Clone)]
+pub struct FuzzResult<T> {
+    /// The reason why the fuzzer stopped
+    pub reason_for_stopping: ReasonForStopping<T>,
+    /// The number of times the test function was run
+    pub total_number_of_runs: usize,
+    /// The distinct test failures that were found, along with the simplest failing input for each of them
+    pub test_failures: Vec<FoundTestFailure<T>>,
+    /// The statistics of the sensor and pool at the end of the fuzz test, as displayed in the terminal
+    pub pool_stats: String,
+}
+
+impl<T> FuzzResult<T> {
+    /// Returns `true` if the fuzzer found any failing test case
+    #[coverage(off)]
+    pub fn found_test_failure(&self) -> bool {
+        matches!(self.reason_for_stopping, ReasonForStopping::TestFailure(_)) || !self.test_failures.is_empty()
+    }
+}
+
+/// A test failure found by the fuzzer, and the least complex input that caused it
+#[derive(Debug, Clone)]
+pub struct FoundTestFailure<T> {
+    pub failure: TestFailure,
+    pub input: T,
+    pub complexity: f64,
+}
+
 /// The index to a test case in the fuzzer’s storage.
 #[cfg_attr(feature = "serde_json_serializer", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -131,8 +167,8 @@ impl<T: Clone + 'static, Mut: Mutator<T>> FuzzedInput<T, Mut> {
         m: &Mut,
         subvalue_provider: &dyn SubValueProvider,
         max_cplx: f64,
-    ) -> Option<(Mut::UnmutateToken, f64)> {
-        m.ordered_mutate(
+    ) -> MutateResult<Mut::UnmutateToken> {
+        m.ordered_mutate_with_status(
             &mut self.value,
             &mut self.cache,
             &mut self.mutation_step,
@@ -141,9 +177,25 @@ impl<T: Clone + 'static, Mut: Mutator<T>> FuzzedInput<T, Mut> {
         )
     }
 
+    /// Clones the value and its cache if the mutator's `unmutate` is too expensive to restore them
+    #[coverage(off)]
+    fn snapshot(&self, m: &Mut) -> Option<(T, Mut::Cache)> {
+        if m.unmutate_is_cheap() {
+            None
+        } else {
+            Some((self.value.clone(), self.cache.clone()))
+        }
+    }
+
+    /// Restores the value as it was before the mutation, from its snapshot if there is one
     #[coverage(off)]
-    fn unmutate(&mut self, m: &Mut, t: Mut::UnmutateToken) {
-        m.unmutate(&mut self.value, &mut self.cache, t);
+    fn unmutate(&mut self, m: &Mut, t: Mut::UnmutateToken, snapshot: Option<(T, Mut::Cache)>) {
+        if let Some((value, cache)) = snapshot {
+            self.value = value;
+            self.cache = cache;
+        } else {
+            m.unmutate(&mut self.value, &mut self.cache, t);
+        }
     }
 }
 
@@ -163,8 +215,13 @@ struct FuzzerState<T: Clone + 'static, M: Mutator<T>> {
     /// The world handles effects
     world: World,
     rng: fastrand::Rng,
+    /// The state of the random number generators of the fuzzer, the pool, and the mutator
+    /// before the test case that is being tested was generated, if it is needed to save artifacts
+    rng_state: Option<Vec<u8>>,
 
     signal_handler_alt_stack: Option<(*mut u8, std::alloc::Layout)>,
+    /// The test failures found so far, if they need to be returned at the end of the fuzz test
+    test_failures: Option<Vec<FoundTestFailure<T>>>,
 }
 
 impl<T: Clone + 'static, M: Mutator<T>> Drop for FuzzerState<T, M> {
@@ -240,8 +297,13 @@ where
                 if let Some(input) = Self::get_input(&self.input_idx, &self.pool_storage) {
                     let input = input.new_source(&self.mutator, Generation(0));
                     let cplx = input.complexity(&self.mutator);
-                    let content = self.serializer.to_data(&input.value);
-                    let _ = self.world.save_artifact(content, cplx, self.serializer.extension());
+                    let _ = save_artifact(
+                        &mut self.world,
+                        self.serializer.as_ref(),
+                        &input.value,
+                        cplx,
+                        self.rng_state.clone(),
+                    );
                     self.write_stats().expect(WRITE_STATS_ERROR);
                     exit(TerminationStatus::Crash as i32);
                 } else {
@@ -259,6 +321,18 @@ where
             _ => exit(TerminationStatus::Unknown as i32),
         }
     }
+    /// Takes a snapshot of the random number generators, to be saved next to the artifacts
+    #[coverage(off)]
+    fn snapshot_rng_state(&mut self) {
+        self.rng_state = if self.settings.artifacts_folder.is_some() {
+            Some(crate::mutators::rng_states(
+                &self.rng,
+                [self.sensor_and_pool.rng_state(), self.mutator.rng_state()],
+            ))
+        } else {
+            None
+        };
+    }
     #[coverage(off)]
     fn arbitrary_input(&mut self) -> Option<(FuzzedInput<T, M>, f64)> {
         if let Some((v, cplx)) = self
@@ -321,7 +395,9 @@ where
                 serializer,
                 world,
                 rng: fastrand::Rng::new(),
+                rng_state: None,
                 signal_handler_alt_stack: None,
+                test_failures: None,
             },
             test,
         }
@@ -340,6 +416,8 @@ where
                     serializer,
                     world,
                     settings,
+                    test_failures,
+                    rng_state,
                     ..
                 },
             test,
@@ -403,14 +481,22 @@ where
             unsafe {
                 DID_FIND_ANY_TEST_FAILURE = true;
             }
+            mutator.record_test_failure(&input.value, &input.cache);
+            if let Some(test_failures) = test_failures {
+                let failure = unsafe { TEST_FAILURE.clone() }.unwrap();
+                record_test_failure(test_failures, failure, &input.value, cplx);
+            }
         }
         sensor_and_pool.stop_recording();
         if test_failure && self.state.settings.stop_after_first_failure {
-            let serialized_input = serializer.to_data(&input.value);
-            self.state
-                .world
-                .save_artifact(serialized_input, cplx, serializer.extension())
-                .expect(SAVE_ARTIFACTS_ERROR);
+            save_artifact(
+                &mut self.state.world,
+                serializer.as_ref(),
+                &input.value,
+                cplx,
+                rng_state.clone(),
+            )
+            .expect(SAVE_ARTIFACTS_ERROR);
             return Err(ReasonForStopping::TestFailure(input.value.clone()));
         }
 
@@ -428,13 +514,15 @@ where
             );
             update_fuzzer_stats(fuzzer_stats, world);
             let event = CorpusDelta::fuzzer_event(&deltas);
-            let content = if add_ref_count > 0 {
-                serializer.to_data(&input.value)
+            let (content, file_stem) = if add_ref_count > 0 {
+                let content = serializer.to_data(&input.value);
+                let file_stem = serializer.suggested_file_stem(&input.value, &content);
+                (content, file_stem)
             } else {
-                vec![]
+                (vec![], None)
             };
             world
-                .update_corpus(input_id, content, &deltas, serializer.extension())
+                .update_corpus(input_id, content, file_stem, &deltas, serializer.extension())
                 .expect(UPDATE_CORPUS_ERROR);
             world.report_event(event, Some((fuzzer_stats, sensor_and_pool.stats().as_ref())));
             if add_ref_count > 0 {
@@ -512,6 +600,7 @@ where
 
     #[coverage(off)]
     fn process_next_input(&mut self) -> Result<(), ReasonForStopping<T>> {
+        self.state.snapshot_rng_state();
         let FuzzerState {
             pool_storage,
             sensor_and_pool,
@@ -529,29 +618,35 @@ where
             let (input, subvalue_provider) =
                 Self::get_input_and_subvalue_provider(pool_storage, sensor_and_pool.as_mut(), rng, idx);
             let generation = input.generation;
-            if let Some((unmutate_token, complexity)) =
-                input.mutate(mutator, subvalue_provider, settings.max_input_cplx)
-            {
-                //drop(subvalue_provider);
-                if complexity < self.state.settings.max_input_cplx {
-                    self.test_and_process_input(complexity)?;
+            let snapshot = input.snapshot(mutator);
+            match input.mutate(mutator, subvalue_provider, settings.max_input_cplx) {
+                MutateResult::Mutated(unmutate_token, complexity) => {
+                    //drop(subvalue_provider);
+                    if complexity < self.state.settings.max_input_cplx {
+                        self.test_and_process_input(complexity)?;
+                    }
+
+                    // Retrieving the input may fail because the input may have been deleted
+                    if let Some(input) = self.state.pool_storage.get_mut(idx.0).map(
+                        #[coverage(off)]
+                        |x| &mut x.input,
+                    ) && input.generation == generation
+                    {
+                        input.unmutate(&self.state.mutator, unmutate_token, snapshot);
+                    }
+
+                    return Ok(());
                 }
-
-                // Retrieving the input may fail because the input may have been deleted
-                if let Some(input) = self.state.pool_storage.get_mut(idx.0).map(
-                    #[coverage(off)]
-                    |x| &mut x.input,
-                ) && input.generation == generation
-                {
-                    input.unmutate(&self.state.mutator, unmutate_token);
+                MutateResult::Exhausted => {
+                    world.report_event(FuzzerEvent::End, Some((fuzzer_stats, sensor_and_pool.stats().as_ref())));
+                    return Err(ReasonForStopping::ExhaustedAllPossibleMutations);
                 }
-
-                Ok(())
-            } else {
-                world.report_event(FuzzerEvent::End, Some((fuzzer_stats, sensor_and_pool.stats().as_ref())));
-                Err(ReasonForStopping::ExhaustedAllPossibleMutations)
+                // the input is too complex to be mutated, but that does not mean that the fuzzer
+                // is done, so a new input is tested instead
+                MutateResult::BudgetTooSmall => {}
             }
-        } else if let Some((input, cplx)) = self.state.arbitrary_input() {
+        }
+        if let Some((input, cplx)) = self.state.arbitrary_input() {
             self.state.input_idx = FuzzerInputIndex::Temporary(input);
 
             if cplx < self.state.settings.max_input_cplx {
@@ -570,7 +665,8 @@ where
 
     #[coverage(off)]
     fn process_initial_inputs(&mut self) -> Result<(), ReasonForStopping<T>> {
-        let mut inputs: Vec<FuzzedInput<T, M>> = self
+        // each input is paired with the state of the random number generators that produced it
+        let mut inputs: Vec<(FuzzedInput<T, M>, Option<Vec<u8>>)> = self
             .state
             .world
             .read_input_corpus()
@@ -582,28 +678,30 @@ where
                     let value = self.state.serializer.from_data(&value)?;
                     let cache = self.state.mutator.validate_value(&value)?;
                     let mutation_step = self.state.mutator.default_mutation_step(&value, &cache);
-                    Some(FuzzedInput::new(value, cache, mutation_step, Generation(0)))
+                    Some((FuzzedInput::new(value, cache, mutation_step, Generation(0)), None))
                 },
             )
             .collect();
 
         for _ in 0..100 {
+            self.state.snapshot_rng_state();
             if let Some((input, _)) = self.state.arbitrary_input() {
-                inputs.push(input);
+                inputs.push((input, self.state.rng_state.take()));
             } else {
                 break;
             }
         }
         inputs.retain(
             #[coverage(off)]
-            |i| i.complexity(&self.state.mutator) <= self.state.settings.max_input_cplx,
+            |(i, _)| i.complexity(&self.state.mutator) <= self.state.settings.max_input_cplx,
         );
         // assert!(!inputs.is_empty());
 
         self.state.world.set_checkpoint_instant();
-        for input in inputs {
+        for (input, rng_state) in inputs {
             let cplx = input.complexity(&self.state.mutator);
             self.state.input_idx = FuzzerInputIndex::Temporary(input);
+            self.state.rng_state = rng_state;
             self.test_and_process_input(cplx)?;
         }
 
@@ -645,6 +743,45 @@ where
             }
         }
     }
+
+    #[coverage(off)]
+    fn result(&mut self, reason_for_stopping: ReasonForStopping<T>) -> FuzzResult<T> {
+        FuzzResult {
+            reason_for_stopping,
+            total_number_of_runs: self.state.fuzzer_stats.total_number_of_runs,
+            test_failures: self.state.test_failures.take().unwrap_or_default(),
+            pool_stats: self.state.sensor_and_pool.stats().to_string(),
+        }
+    }
+}
+
+/// Add the failing input to the list of test failures, or replace the input of an
+/// identical failure if it is less complex.
+#[coverage(off)]
+fn record_test_failure<T: Clone>(
+    test_failures: &mut Vec<FoundTestFailure<T>>,
+    failure: TestFailure,
+    input: &T,
+    complexity: f64,
+) {
+    if let Some(previous) = test_failures.iter_mut().find(
+        #[coverage(off)]
+        |previous| previous.failure.id == failure.id,
+    ) {
+        if complexity < previous.complexity {
+            *previous = FoundTestFailure {
+                failure,
+                input: input.clone(),
+                complexity,
+            };
+        }
+    } else {
+        test_failures.push(FoundTestFailure {
+            failure,
+            input: input.clone(),
+            complexity,
+        });
+    }
 }
 
 pub enum TerminationStatus {
@@ -654,25 +791,185 @@ pub enum TerminationStatus {
     Unknown = 3,
 }
 
+/// Save the value as an artifact, along with the additional files requested by the serializer
+#[coverage(off)]
+fn save_artifact<T>(
+    world: &mut World,
+    serializer: &dyn Serializer<Value = T>,
+    value: &T,
+    cplx: f64,
+    rng_state: Option<Vec<u8>>,
+) -> std::io::Result<()>
+where
+    T: Clone + 'static,
+{
+    let content = serializer.to_data(value);
+    let metadata = ArtifactMetadata {
+        complexity: cplx,
+        rng_state: rng_state.as_deref(),
+        timestamp: SystemTime::now(),
+    };
+    let sidecar_files = serializer.sidecar_files(value, &metadata);
+    let file_stem = serializer.suggested_file_stem(value, &content);
+    world.save_artifact(
+        content,
+        cplx,
+        file_stem,
+        serializer.extension(),
+        rng_state,
+        sidecar_files,
+    )
+}
+
 #[coverage(off)]
 pub fn launch<T, M>(
     test: Box<dyn Fn(&T) -> bool>,
     mutator: M,
     serializer: Box<dyn Serializer<Value = T>>,
     sensor_and_pool: Box<dyn SensorAndPool>,
-    mut args: Arguments,
+    args: Arguments,
+) -> FuzzingResult<T>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Fuzzer<T, M>: 'static,
+{
+    let is_read_command = matches!(args.command, FuzzerCommand::Read { .. });
+    let result = run(test, mutator, serializer, sensor_and_pool, args, false);
+    let found_test_failure = result.found_test_failure() || unsafe { DID_FIND_ANY_TEST_FAILURE };
+    if is_read_command {
+        // in this case we really want to exit with a non-zero termination status here
+        // because the Read command is only used by the input minify command from cargo-fuzzcheck
+        // which checks that a crash happens by looking at the exit code
+        // so we don't want to handle any error
+        if found_test_failure {
+            exit(TerminationStatus::TestFailure as i32);
+        } else {
+            exit(TerminationStatus::Success as i32);
+        }
+    }
+    FuzzingResult {
+        found_test_failure,
+        reason_for_stopping: result.reason_for_stopping,
+    }
+}
+
+/// Like [`launch`], but returns a summary of the fuzz test instead of exiting the process
+/// when the fuzzer is given the `read` command.
+///
+/// The fuzzer keeps track of every distinct test failure, so that they can be returned in
+/// [`FuzzResult::test_failures`]. Note that the process still exits if it receives a
+/// signal, such as a segmentation fault or `SIGINT`.
+#[coverage(off)]
+pub fn launch_and_return<T, M>(
+    test: Box<dyn Fn(&T) -> bool>,
+    mutator: M,
+    serializer: Box<dyn Serializer<Value = T>>,
+    sensor_and_pool: Box<dyn SensorAndPool>,
+    args: Arguments,
+) -> FuzzResult<T>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Fuzzer<T, M>: 'static,
+{
+    run(test, mutator, serializer, sensor_and_pool, args, true)
+}
+
+/// Run the test function on every value of the exhaustive mutator, which has `total_count` values,
+/// and save the failing ones as artifacts
+///
+/// It is called by [`launch_exhaustive`](crate::launch_exhaustive) once it has checked that the values
+/// of the mutator can be enumerated.
+#[coverage(off)]
+pub(crate) fn launch_exhaustive<T, M>(
+    test: &dyn Fn(&T) -> bool,
+    mutator: &ExhaustiveMutator<T, M>,
+    serializer: &dyn Serializer<Value = T>,
+    total_count: u64,
+    args: Arguments,
 ) -> FuzzingResult<T>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    let mut world = World::new(args.clone()).expect(WORLD_NEW_ERROR);
+    let start = Instant::now();
+    let mut first_failure = None;
+    let mut reason_for_stopping = ReasonForStopping::ExhaustedAllPossibleMutations;
+    for (nbr_runs, value) in mutator.values().enumerate() {
+        if nbr_runs >= args.maximum_iterations {
+            reason_for_stopping = ReasonForStopping::MaxIterationsReached;
+            break;
+        }
+        if start.elapsed() >= args.maximum_duration {
+            reason_for_stopping = ReasonForStopping::MaxDurationReached;
+            break;
+        }
+        let passed = catch_unwind(AssertUnwindSafe(
+            #[coverage(off)]
+            || test(&value),
+        ))
+        .unwrap_or(false);
+        if passed {
+            continue;
+        }
+        println!(
+            "test failure on the value: {}",
+            String::from_utf8_lossy(&serializer.to_data(&value))
+        );
+        let cplx = mutator.validate_value(&value).map_or(
+            mutator.max_complexity(),
+            #[coverage(off)]
+            |cache| mutator.complexity(&value, &cache),
+        );
+        save_artifact(&mut world, serializer, &value, cplx, None).expect(SAVE_ARTIFACTS_ERROR);
+        if args.stop_after_first_failure {
+            first_failure = Some(value);
+            break;
+        }
+        first_failure.get_or_insert(value);
+    }
+    match first_failure {
+        Some(value) => FuzzingResult {
+            found_test_failure: true,
+            reason_for_stopping: ReasonForStopping::TestFailure(value),
+        },
+        None => {
+            if let ReasonForStopping::ExhaustedAllPossibleMutations = reason_for_stopping {
+                println!("the test function passed on all {total_count} values");
+            }
+            FuzzingResult {
+                found_test_failure: false,
+                reason_for_stopping,
+            }
+        }
+    }
+}
+
+#[coverage(off)]
+fn run<T, M>(
+    test: Box<dyn Fn(&T) -> bool>,
+    mutator: M,
+    serializer: Box<dyn Serializer<Value = T>>,
+    sensor_and_pool: Box<dyn SensorAndPool>,
+    mut args: Arguments,
+    collect_test_failures: bool,
+) -> FuzzResult<T>
 where
     T: Clone + 'static,
     M: Mutator<T>,
     Fuzzer<T, M>: 'static,
 {
+    unsafe {
+        DID_FIND_ANY_TEST_FAILURE = false;
+    }
     let command = &args.command;
-    let reason_for_stopping = match command {
+    let result = match command {
         FuzzerCommand::Fuzz => {
             if !args.stop_after_first_failure {
                 let test_failure_sensor = TestFailureSensor::default();
-                let test_failure_pool = TestFailurePool::new("test_failures");
+                let test_failure_pool = TestFailurePool::new("test_failures", args.max_distinct_errors);
                 let sensor_and_pool = AndSensorAndPool::new(
                     sensor_and_pool,
                     Box::new((test_failure_sensor, test_failure_pool)),
@@ -687,6 +984,7 @@ where
                     args.clone(),
                     World::new(args.clone()).expect(WORLD_NEW_ERROR),
                 );
+                fuzzer.state.test_failures = collect_test_failures.then(Vec::new);
 
                 let mut stats_headers = vec![CSVField::String("time".to_string())];
                 stats_headers.extend(fuzzer.state.fuzzer_stats.csv_headers());
@@ -701,7 +999,7 @@ where
                 let reason_for_stopping = fuzzer.main_loop(false).unwrap_err();
                 fuzzer.state.write_stats().expect(WRITE_STATS_ERROR);
 
-                reason_for_stopping
+                fuzzer.result(reason_for_stopping)
             } else {
                 let mut fuzzer = Fuzzer::new(
                     test,
@@ -711,6 +1009,7 @@ where
                     args.clone(),
                     World::new(args.clone()).expect(WORLD_NEW_ERROR),
                 );
+                fuzzer.state.test_failures = collect_test_failures.then(Vec::new);
                 unsafe { fuzzer.state.set_up_signal_handler() };
 
                 let mut stats_headers = vec![CSVField::String("time".to_string())];
@@ -724,7 +1023,7 @@ where
                 let reason_for_stopping = fuzzer.main_loop(false).unwrap_err();
                 fuzzer.state.write_stats().expect(WRITE_STATS_ERROR);
 
-                reason_for_stopping
+                fuzzer.result(reason_for_stopping)
             }
         }
         FuzzerCommand::MinifyInput { input_file } => {
@@ -751,6 +1050,7 @@ where
                     args.clone(),
                     world,
                 );
+                fuzzer.state.test_failures = collect_test_failures.then(Vec::new);
 
                 let mut subvalues: HashMap<TypeId, Vec<(*const dyn Any, f64)>> = HashMap::default();
 
@@ -787,7 +1087,8 @@ where
 
                 unsafe { fuzzer.state.set_up_signal_handler() };
 
-                fuzzer.main_loop(true).unwrap_err()
+                let reason_for_stopping = fuzzer.main_loop(true).unwrap_err();
+                fuzzer.result(reason_for_stopping)
             } else {
                 // TODO: send a better error message saying some inputs in the corpus cannot be read
                 // TODO: there should be an option to ignore invalid values
@@ -825,19 +1126,34 @@ where
                     || (test)(input.value.borrow()),
                 ));
 
-                if result.is_err() || !result.unwrap() {
+                let failure = match result {
+                    Ok(true) => None,
+                    Ok(false) => Some("test function returned false".to_string()),
+                    Err(_) => Some("test function panicked".to_string()),
+                };
+                if let Some(display) = failure {
                     world.report_event(FuzzerEvent::TestFailure, None);
-                    let content = serializer.to_data(&input.value);
-                    world
-                        .save_artifact(content, cplx, serializer.extension())
+                    save_artifact(&mut world, serializer.as_ref(), &input.value, cplx, None)
                         .expect(SAVE_ARTIFACTS_ERROR);
-                    // in this case we really want to exit with a non-zero termination status here
-                    // because the Read command is only used by the input minify command from cargo-fuzzcheck
-                    // which checks that a crash happens by looking at the exit code
-                    // so we don't want to handle any error
-                    exit(TerminationStatus::TestFailure as i32);
+                    let mut test_failures = vec![];
+                    if collect_test_failures {
+                        let failure = TestFailure { display, id: 0 };
+                        record_test_failure(&mut test_failures, failure, &input.value, cplx);
+                    }
+                    FuzzResult {
+                        reason_for_stopping: ReasonForStopping::TestFailure(input.value),
+                        total_number_of_runs: 1,
+                        test_failures,
+                        pool_stats: String::new(),
+                    }
                 } else {
-                    exit(TerminationStatus::Success as i32);
+                    // the only input there was to test has been tested
+                    FuzzResult {
+                        reason_for_stopping: ReasonForStopping::ExhaustedAllPossibleMutations,
+                        total_number_of_runs: 1,
+                        test_failures: vec![],
+                        pool_stats: String::new(),
+                    }
                 }
             } else {
                 // TODO: send a better error message saying some inputs in the corpus cannot be read
@@ -847,11 +1163,92 @@ where
     };
     let _ = std::panic::take_hook();
 
-    let found_test_failure =
-        unsafe { matches!(reason_for_stopping, ReasonForStopping::TestFailure(_)) || DID_FIND_ANY_TEST_FAILURE };
+    result
+}
 
-    FuzzingResult {
-        found_test_failure,
-        reason_for_stopping,
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use fuzzcheck_common::arg::Arguments;
+
+    use super::{launch_exhaustive, ReasonForStopping};
+    use crate::mutators::exhaustive::ExhaustiveMutator;
+    use crate::{DefaultMutator, StringSerializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_launch_exhaustive_saves_artifacts_and_honours_arguments() {
+        let artifacts = std::env::temp_dir().join(format!("fuzzcheck-exhaustive-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&artifacts);
+        let mutator = ExhaustiveMutator::new(u8::default_mutator());
+        let serializer = StringSerializer::<u8>::new("txt");
+        let launch = #[coverage(off)]
+        |test: fn(&u8) -> bool, configure: fn(&mut Arguments)| {
+            let mut args = Arguments::for_internal_documentation_test();
+            args.artifacts_folder = Some(artifacts.clone());
+            args.stop_after_first_failure = false;
+            configure(&mut args);
+            launch_exhaustive(&test, &mutator, &serializer, 256, args)
+        };
+        let saved_artifacts = #[coverage(off)]
+        || {
+            let mut contents = fs::read_dir(&artifacts)
+                .unwrap()
+                .map(
+                    #[coverage(off)]
+                    |entry| fs::read_to_string(entry.unwrap().path()).unwrap(),
+                )
+                .collect::<Vec<_>>();
+            contents.sort();
+            fs::remove_dir_all(&artifacts).unwrap();
+            contents
+        };
+
+        // every failing value is saved, and the first one is returned
+        let result = launch(
+            #[coverage(off)]
+            |x| x % 100 != 50,
+            #[coverage(off)]
+            |_| {},
+        );
+        assert!(result.found_test_failure);
+        assert!(matches!(result.reason_for_stopping, ReasonForStopping::TestFailure(x) if x % 100 == 50));
+        assert_eq!(saved_artifacts(), ["150", "250", "50"]);
+
+        // unless the fuzzer stops after the first failure
+        let result = launch(
+            #[coverage(off)]
+            |x| x % 100 != 50,
+            #[coverage(off)]
+            |args| args.stop_after_first_failure = true,
+        );
+        let ReasonForStopping::TestFailure(x) = result.reason_for_stopping else {
+            panic!()
+        };
+        assert_eq!(saved_artifacts(), [x.to_string()]);
+
+        let result = launch(
+            #[coverage(off)]
+            |_| true,
+            #[coverage(off)]
+            |_| {},
+        );
+        assert!(!result.found_test_failure);
+        assert!(matches!(
+            result.reason_for_stopping,
+            ReasonForStopping::ExhaustedAllPossibleMutations
+        ));
+        let result = launch(
+            #[coverage(off)]
+            |_| true,
+            #[coverage(off)]
+            |args| args.maximum_iterations = 10,
+        );
+        assert!(matches!(
+            result.reason_for_stopping,
+            ReasonForStopping::MaxIterationsReached
+        ));
+        assert!(!artifacts.exists());
     }
 }
@@ -54,10 +54,13 @@ mod bitset;
 mod bloom_filter;
 pub mod builder;
 mod code_coverage_sensor;
+pub mod corpus;
 mod data_structures;
+pub mod debug;
 mod fenwick_tree;
 mod fuzzer;
 pub mod mutators;
+pub mod optimize;
 pub mod sensors_and_pools;
 pub mod serializers;
 mod signals_handler;
@@ -66,8 +69,12 @@ pub mod subvalue_provider;
 mod traits;
 mod world;
 
+#[doc(inline)]
+pub use bitset::FixedBitSet;
 #[doc(inline)]
 pub use builder::fuzz_test;
+#[doc(inline)]
+pub use builder::launch_exhaustive;
 pub use fuzzcheck_common::arg::Arguments;
 /**
     Make a mutator for a custom type, optionally making it the type’s default mutator.
@@ -213,8 +220,53 @@ pub use fuzzcheck_mutators_derive::make_mutator;
 /// ```
 /// Similarly to [`make_mutator!`](crate::make_mutator), you can use the attributes `#[field_mutator]` and `#[ignore_variant]`
 /// to customise the generated mutator.
+///
+/// A `String` field can be restricted to the values matching a regular expression with
+/// `#[field_mutator(regex = "...")]`, which uses a
+/// [`grammar_based_string_mutator`](crate::mutators::grammar::grammar_based_string_mutator) for that field
+/// (on crate feature `regex_grammar` only). The values whose field does not match the regular
+/// expression are then rejected by `validate_value`.
+#[cfg_attr(feature = "regex_grammar", doc = "```")]
+#[cfg_attr(not(feature = "regex_grammar"), doc = "```ignore")]
+/// # #![feature(coverage_attribute)]
+/// use fuzzcheck::DefaultMutator;
+///
+/// #[derive(Clone, DefaultMutator)]
+/// struct User {
+///     #[field_mutator(regex = "[a-z]{1,8}@[a-z]{1,8}\\.com")]
+///     email: String,
+///     age: u8,
+/// }
+/// ```
+///
+/// The complexity of the generated values can be overridden with `#[fuzzcheck(complexity = "path::to::fn")]`
+/// on the type, where the function has the signature `fn(&T, f64) -> f64` and receives the complexity that
+/// would otherwise have been computed. It takes the computed complexity rather than the cache of the mutator,
+/// whose type is generated by the macro. The attributes `max_complexity` and `min_complexity` similarly take
+/// the path to a function of type `fn(f64) -> f64`. The ordered mutations and arbitrary values whose complexity
+/// exceeds the maximum are skipped, up to a fixed number of times per call.
+/// ```
+/// # #![feature(coverage_attribute)]
+/// use fuzzcheck::DefaultMutator;
+///
+/// #[derive(Clone, DefaultMutator)]
+/// #[fuzzcheck(complexity = "Message::complexity")]
+/// struct Message {
+///     header: u8,
+///     payload: Vec<u8>,
+/// }
+/// impl Message {
+///     fn complexity(&self, _cplx: f64) -> f64 {
+///         1.0 + self.payload.len() as f64
+///     }
+/// }
+/// ```
 pub use fuzzcheck_mutators_derive::DefaultMutator;
 #[doc(inline)]
+pub use fuzzer::FoundTestFailure;
+#[doc(inline)]
+pub use fuzzer::FuzzResult;
+#[doc(inline)]
 pub use fuzzer::FuzzingResult;
 #[doc(inline)]
 pub use fuzzer::PoolStorageIndex;
@@ -230,7 +282,38 @@ pub use sensors_and_pools::PoolExt;
 #[doc(inline)]
 pub use sensors_and_pools::SensorExt;
 #[doc(inline)]
+pub use serializers::Base64Serializer;
+#[doc(inline)]
 pub use serializers::ByteSerializer;
+#[cfg(feature = "serde_json_serializer")]
+#[doc(inline)]
+pub use serializers::CanonicalizingSerializer;
+#[cfg(feature = "serde_cbor_serializer")]
+#[doc(inline)]
+pub use serializers::CborSerializer;
+#[doc(inline)]
+pub use serializers::CompressedSerializer;
+#[cfg(feature = "encrypted_serializer")]
+#[doc(inline)]
+pub use serializers::EncryptedSerializer;
+#[doc(inline)]
+pub use serializers::FnSerializer;
+#[doc(inline)]
+pub use serializers::HashNamedSerializer;
+#[doc(inline)]
+pub use serializers::HexSerializer;
+#[doc(inline)]
+pub use serializers::LoggingSerializer;
+#[doc(inline)]
+pub use serializers::MapSerializer;
+#[doc(inline)]
+pub use serializers::MetadataSerializer;
+#[cfg(feature = "serde_msgpack_serializer")]
+#[doc(inline)]
+pub use serializers::MsgPackSerializer;
+#[cfg(feature = "serde_postcard_serializer")]
+#[doc(inline)]
+pub use serializers::PostcardSerializer;
 #[cfg(feature = "serde_ron_serializer")]
 #[doc(inline)]
 pub use serializers::SerdeRonSerializer;
@@ -239,16 +322,27 @@ pub use serializers::SerdeRonSerializer;
 pub use serializers::SerdeSerializer;
 #[doc(inline)]
 pub use serializers::StringSerializer;
+#[cfg(feature = "serde_toml_serializer")]
+#[doc(inline)]
+pub use serializers::TomlSerializer;
+#[doc(inline)]
+pub use serializers::VersionedSerializer;
 pub(crate) use split_string::split_string_by_whitespace;
 #[doc(inline)]
 pub use subvalue_provider::SubValueProvider;
 #[doc(inline)]
 pub use subvalue_provider::SubValueProviderId;
 #[doc(inline)]
+pub use traits::ArtifactMetadata;
+#[doc(inline)]
 pub use traits::CompatibleWithObservations;
 #[doc(inline)]
 pub use traits::CorpusDelta;
 #[doc(inline)]
+pub use traits::EffortHint;
+#[doc(inline)]
+pub use traits::MutateResult;
+#[doc(inline)]
 pub use traits::Mutator;
 #[doc(inline)]
 pub use traits::Pool;
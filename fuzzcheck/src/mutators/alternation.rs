@@ -26,6 +26,8 @@ where
     M: Mutator<T>,
 {
     mutators: Vec<M>,
+    /// The indices of the mutators used to generate new values
+    generating_mutators: Vec<usize>,
     rng: fastrand::Rng,
     added_complexity: f64,
     initialized: Cell<bool>,
@@ -45,6 +47,7 @@ where
         assert!(!mutators.is_empty());
 
         Self {
+            generating_mutators: (0..mutators.len()).collect(),
             mutators,
             rng: fastrand::Rng::default(),
             added_complexity,
@@ -55,6 +58,36 @@ where
             _phantom: PhantomData,
         }
     }
+
+    /// Only use the mutators at the given indices to generate new values.
+    ///
+    /// The other mutators are still used to validate and mutate existing values, such as those
+    /// read from a corpus, but `ordered_arbitrary` and `random_arbitrary` never use them.
+    ///
+    /// **Panics** if `indices` is empty or contains an out-of-bounds index.
+    #[coverage(off)]
+    pub fn generate_only_with(&mut self, indices: Vec<usize>) {
+        assert!(!indices.is_empty());
+        assert!(indices.iter().all(
+            #[coverage(off)]
+            |&idx| idx < self.mutators.len()
+        ));
+        self.generating_mutators = indices;
+    }
+
+    /// The mutators between which the `AlternationMutator` alternates
+    #[coverage(off)]
+    pub fn mutators(&self) -> &[M] {
+        &self.mutators
+    }
+
+    /// The mutators between which the `AlternationMutator` alternates.
+    ///
+    /// They should only be modified before the mutator is initialized.
+    #[coverage(off)]
+    pub fn mutators_mut(&mut self) -> &mut [M] {
+        &mut self.mutators
+    }
 }
 
 #[doc(hidden)]
@@ -188,7 +221,7 @@ where
                     |m| m.default_arbitrary_step(),
                 )
                 .collect(),
-            indices: (0..self.mutators.len()).collect(),
+            indices: self.generating_mutators.clone(),
             idx: 0,
         }
     }
@@ -238,7 +271,10 @@ where
                         inner: m.default_mutation_step(value, &c.inner),
                         arbitrary: {
                             let mut step = self.default_arbitrary_step();
-                            step.indices.remove(c.mutator_idx);
+                            step.indices.retain(
+                                #[coverage(off)]
+                                |&idx| idx != c.mutator_idx,
+                            );
                             step
                         },
                     }
@@ -297,7 +333,7 @@ where
     #[doc(hidden)]
     #[coverage(off)]
     fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
-        let idx = self.rng.usize(..self.mutators.len());
+        let idx = self.generating_mutators[self.rng.usize(..self.generating_mutators.len())];
         let mutator = &self.mutators[idx];
 
         let (v, c) = mutator.random_arbitrary(max_cplx);
@@ -341,6 +377,18 @@ where
         }
 
         let mutator_idx = chosen_step.mutator_idx;
+        if !self.generating_mutators.contains(&mutator_idx) {
+            // the value was built by a mutator that is not allowed to generate values,
+            // so it is replaced rather than mutated in place
+            let (mut v, cplx) = self
+                .ordered_arbitrary(&mut chosen_step.arbitrary, max_cplx)
+                .unwrap_or_else(
+                    #[coverage(off)]
+                    || self.random_arbitrary(max_cplx),
+                );
+            std::mem::swap(value, &mut v);
+            return Some((UnmutateToken::Replace(v), cplx));
+        }
         let chosen_cache = cache
             .iter_mut()
             .find(
@@ -389,7 +437,7 @@ where
         // there should be a better way to prevent this though
         // maybe it's time to give random_mutate a MutationStep too?
         // TODO: should use the global search space complexity here instead of max complexity?
-        if self.rng.usize(..100) == 0 || mutator.max_complexity() < 0.1 {
+        if self.rng.usize(..100) == 0 || mutator.max_complexity() < 0.1 || !self.generating_mutators.contains(&idx) {
             let (new_value, cplx) = self.random_arbitrary(max_cplx);
             let old_value = ::std::mem::replace(value, new_value);
             return (UnmutateToken::Replace(old_value), cplx);
@@ -429,4 +477,42 @@ where
             mutator.visit_subvalues(value, &cache.inner, visit);
         }
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        for mutator in self.mutators.iter() {
+            mutator.set_effort_hint(hint);
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutators.iter().all(
+            #[coverage(off)]
+            |m| m.unmutate_is_cheap(),
+        )
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_states(
+            &self.rng,
+            self.mutators.iter().map(
+                #[coverage(off)]
+                |mutator| mutator.rng_state(),
+            ),
+        ))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let states = crate::mutators::set_rng_states(&self.rng, state);
+        for (mutator, state) in self.mutators.iter().zip(states) {
+            mutator.set_rng_state(state);
+        }
+    }
 }
@@ -175,6 +175,31 @@ impl<T: Clone + 'static, M: Mutator<T>> Mutator<Arc<T>> for ArcMutator<M> {
     fn visit_subvalues<'a>(&self, value: &'a Arc<T>, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
         self.mutator.visit_subvalues(value, cache, visit)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
 }
 
 impl<T> DefaultMutator for Arc<T>
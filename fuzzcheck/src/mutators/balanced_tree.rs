@@ -0,0 +1,450 @@
+//! A mutator for binary trees that keeps them approximately balanced.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::balanced_tree::{BalancedTreeMutator, BinaryTree};
+//!
+//! #[derive(Clone)]
+//! enum Tree {
+//!     Leaf,
+//!     Node(Box<Tree>, u32, Box<Tree>),
+//! }
+//! impl BinaryTree for Tree {
+//!     type Item = u32;
+//!     fn empty() -> Self {
+//!         Tree::Leaf
+//!     }
+//!     fn node(left: Self, item: u32, right: Self) -> Self {
+//!         Tree::Node(Box::new(left), item, Box::new(right))
+//!     }
+//!     fn as_node(&self) -> Option<(&Self, &u32, &Self)> {
+//!         match self {
+//!             Tree::Leaf => None,
+//!             Tree::Node(left, item, right) => Some((left, item, right)),
+//!         }
+//!     }
+//!     fn into_node(self) -> Option<(Self, u32, Self)> {
+//!         match self {
+//!             Tree::Leaf => None,
+//!             Tree::Node(left, item, right) => Some((*left, item, *right)),
+//!         }
+//!     }
+//! }
+//!
+//! // impl Mutator<Tree>
+//! let m = BalancedTreeMutator::<Tree, _>::new(u32::default_mutator());
+//! ```
+use std::any::Any;
+
+use crate::mutators::vector::VecMutator;
+use crate::Mutator;
+
+/// The number of random nodes at which [`BalancedTreeMutator`] tries to perform a rotation
+/// that keeps the tree approximately balanced, before mutating its items instead
+const ROTATION_ATTEMPTS: usize = 8;
+
+/// A binary tree whose nodes each hold an item, which can be mutated by a [`BalancedTreeMutator`]
+pub trait BinaryTree: Clone + 'static {
+    type Item: Clone + 'static;
+
+    /// The empty tree
+    fn empty() -> Self;
+    /// A tree made of a root holding `item`, with the given subtrees
+    fn node(left: Self, item: Self::Item, right: Self) -> Self;
+    /// The subtrees and item of the root of the tree, or `None` if it is empty
+    fn as_node(&self) -> Option<(&Self, &Self::Item, &Self)>;
+    /// The subtrees and item of the root of the tree, or `None` if it is empty
+    fn into_node(self) -> Option<(Self, Self::Item, Self)>;
+}
+
+/// The height above which a tree of `len` items is not considered approximately balanced:
+/// about twice the height of a perfectly balanced tree, as in a red-black tree
+#[coverage(off)]
+pub fn max_balanced_height(len: usize) -> usize {
+    2 * (usize::BITS - len.leading_zeros()) as usize
+}
+
+/// The number of items and height of a tree
+///
+/// It is iterative, so that it does not overflow the stack on degenerate trees from a corpus.
+#[coverage(off)]
+fn len_and_height<Tree: BinaryTree>(tree: &Tree) -> (usize, usize) {
+    let (mut len, mut height) = (0, 0);
+    let mut stack = vec![(tree, 0)];
+    while let Some((tree, depth)) = stack.pop() {
+        height = height.max(depth);
+        if let Some((left, _, right)) = tree.as_node() {
+            len += 1;
+            stack.push((left, depth + 1));
+            stack.push((right, depth + 1));
+        }
+    }
+    (len, height)
+}
+
+/// Whether the height of the tree is at most [`max_balanced_height`] of its number of items
+#[coverage(off)]
+pub fn is_approximately_balanced<Tree: BinaryTree>(tree: &Tree) -> bool {
+    let (len, height) = len_and_height(tree);
+    height <= max_balanced_height(len)
+}
+
+/// A mutator for binary trees that generates approximately balanced trees, and whose
+/// mutations keep them approximately balanced.
+///
+/// It works on the items of the tree, listed in in-order, which are mutated by a
+/// [`VecMutator`] built from the given item mutator. When the items are generated or
+/// mutated (e.g. an item is inserted, removed, or changed), the tree is rebuilt from them:
+/// the root of each subtree is chosen close to the middle of its items, such that the
+/// height of the tree stays within [`max_balanced_height`] of its size. The size of the
+/// generated trees is therefore derived from the complexity budget, like the length of
+/// generated vectors. Some mutations instead perform a rotation at a random node, which
+/// changes the shape of the tree but not its items, when the tree stays approximately
+/// balanced afterwards.
+///
+/// [`validate_value`](Mutator::validate_value) accepts any tree, including degenerate
+/// ones from a corpus. Those are rebuilt into balanced trees when their items are mutated.
+/// The complexity of a tree is the complexity of its items as a vector, regardless of its shape.
+pub struct BalancedTreeMutator<Tree, M>
+where
+    Tree: BinaryTree,
+    M: Mutator<Tree::Item>,
+{
+    mutator: VecMutator<Tree::Item, M>,
+    rng: fastrand::Rng,
+}
+
+impl<Tree, M> BalancedTreeMutator<Tree, M>
+where
+    Tree: BinaryTree,
+    M: Mutator<Tree::Item>,
+{
+    /// Create a mutator of balanced trees whose items are mutated by `item_mutator`
+    #[coverage(off)]
+    pub fn new(item_mutator: M) -> Self {
+        Self::new_with_size_range(item_mutator, 0..=usize::MAX)
+    }
+
+    /// Create a mutator of balanced trees whose items are mutated by `item_mutator`,
+    /// and whose number of items is within `size_range`
+    #[coverage(off)]
+    pub fn new_with_size_range(item_mutator: M, size_range: std::ops::RangeInclusive<usize>) -> Self {
+        Self {
+            mutator: VecMutator::new(item_mutator, size_range),
+            rng: fastrand::Rng::new(),
+        }
+    }
+
+    /// Builds an approximately balanced tree from its items, listed in in-order
+    #[coverage(off)]
+    fn build(&self, items: &[Tree::Item]) -> Tree {
+        if items.is_empty() {
+            return Tree::empty();
+        }
+        // the root is within the middle third of the items
+        let jitter = items.len() / 6;
+        let root = items.len() / 2 - jitter + self.rng.usize(..=2 * jitter);
+        let left = self.build(&items[..root]);
+        let right = self.build(&items[root + 1..]);
+        Tree::node(left, items[root].clone(), right)
+    }
+
+    /// Performs a rotation at the node at the end of `path`, which lists the directions taken
+    /// from the root to reach it, `true` meaning right. A right rotation is performed if
+    /// `rotate_right` is true, otherwise a left rotation.
+    ///
+    /// Only the nodes along the path are rebuilt. It returns whether the rotation was
+    /// performed, which is not the case if the node has no child to rotate with. A right
+    /// rotation is undone by a left rotation at the same path, and vice versa.
+    #[coverage(off)]
+    fn rotate_at(tree: Tree, path: &[bool], rotate_right: bool) -> (Tree, bool) {
+        let Some((left, item, right)) = tree.into_node() else {
+            return (Tree::empty(), false);
+        };
+        match path.split_first() {
+            None if rotate_right => match left.into_node() {
+                Some((a, x, b)) => (Tree::node(a, x, Tree::node(b, item, right)), true),
+                None => (Tree::node(Tree::empty(), item, right), false),
+            },
+            None => match right.into_node() {
+                Some((b, y, c)) => (Tree::node(Tree::node(left, item, b), y, c), true),
+                None => (Tree::node(left, item, Tree::empty()), false),
+            },
+            Some((true, path)) => {
+                let (right, rotated) = Self::rotate_at(right, path, rotate_right);
+                (Tree::node(left, item, right), rotated)
+            }
+            Some((false, path)) => {
+                let (left, rotated) = Self::rotate_at(left, path, rotate_right);
+                (Tree::node(left, item, right), rotated)
+            }
+        }
+    }
+
+    /// The path to a random node of the tree that has a child, at a depth of at most
+    /// [`max_balanced_height`] of its number of items
+    #[coverage(off)]
+    fn random_path(&self, tree: &Tree, len: usize) -> Vec<bool> {
+        let depth = self.rng.usize(..max_balanced_height(len));
+        let mut path = vec![];
+        let mut tree = tree;
+        while path.len() < depth {
+            let Some((left, _, right)) = tree.as_node() else { break };
+            let go_right = match (left.as_node().is_some(), right.as_node().is_some()) {
+                (true, true) => self.rng.bool(),
+                (false, false) => break,
+                (has_left, _) => !has_left,
+            };
+            path.push(go_right);
+            tree = if go_right { right } else { left };
+        }
+        path
+    }
+
+    /// Performs a rotation at a random node of the tree that keeps it approximately balanced,
+    /// and returns its path and direction, if one was found
+    #[coverage(off)]
+    fn balanced_rotation(&self, tree: &mut Tree, len: usize) -> Option<(Vec<bool>, bool)> {
+        if len < 2 {
+            return None;
+        }
+        for _ in 0..ROTATION_ATTEMPTS {
+            let path = self.random_path(tree, len);
+            let rotate_right = self.rng.bool();
+            let (rotated, has_rotated) = Self::rotate_at(std::mem::replace(tree, Tree::empty()), &path, rotate_right);
+            *tree = rotated;
+            if !has_rotated {
+                continue;
+            }
+            if is_approximately_balanced(tree) {
+                return Some((path, rotate_right));
+            }
+            let (unrotated, _) = Self::rotate_at(std::mem::replace(tree, Tree::empty()), &path, !rotate_right);
+            *tree = unrotated;
+        }
+        None
+    }
+}
+
+/// The in-order items of a tree
+///
+/// It is iterative, so that it does not overflow the stack on degenerate trees from a corpus.
+#[coverage(off)]
+fn in_order_items<Tree: BinaryTree>(tree: &Tree, items: &mut Vec<Tree::Item>) {
+    let mut right_subtrees = vec![];
+    let mut tree = tree;
+    loop {
+        while let Some((left, item, right)) = tree.as_node() {
+            right_subtrees.push((item, right));
+            tree = left;
+        }
+        let Some((item, right)) = right_subtrees.pop() else {
+            return;
+        };
+        items.push(item.clone());
+        tree = right;
+    }
+}
+
+#[doc(hidden)]
+pub struct Cache<T, C> {
+    items: Vec<T>,
+    items_cache: C,
+}
+impl<T: Clone, C: Clone> Clone for Cache<T, C> {
+    #[coverage(off)]
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            items_cache: self.items_cache.clone(),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub enum UnmutateToken<Tree, U> {
+    /// The items were mutated and the tree was rebuilt from them
+    Items { tree: Tree, token: U },
+    /// A rotation was performed at the node at the end of `path`
+    Rotation { path: Vec<bool>, rotate_right: bool },
+}
+
+impl<Tree, M> Mutator<Tree> for BalancedTreeMutator<Tree, M>
+where
+    Tree: BinaryTree,
+    M: Mutator<Tree::Item>,
+{
+    #[doc(hidden)]
+    type Cache = Cache<Tree::Item, <VecMutator<Tree::Item, M> as Mutator<Vec<Tree::Item>>>::Cache>;
+    #[doc(hidden)]
+    type MutationStep = <VecMutator<Tree::Item, M> as Mutator<Vec<Tree::Item>>>::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = <VecMutator<Tree::Item, M> as Mutator<Vec<Tree::Item>>>::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken<Tree, <VecMutator<Tree::Item, M> as Mutator<Vec<Tree::Item>>>::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &Tree) -> bool {
+        let mut items = vec![];
+        in_order_items(value, &mut items);
+        self.mutator.is_valid(&items)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &Tree) -> Option<Self::Cache> {
+        let mut items = vec![];
+        in_order_items(value, &mut items);
+        let items_cache = self.mutator.validate_value(&items)?;
+        Some(Cache { items, items_cache })
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &Tree, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(&cache.items, &cache.items_cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, _value: &Tree, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(&cache.items, &cache.items_cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(Tree, f64)> {
+        let (items, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+        Some((self.build(&items), cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (Tree, f64) {
+        let (items, cplx) = self.mutator.random_arbitrary(max_cplx);
+        (self.build(&items), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut Tree,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (token, cplx) = self.mutator.ordered_mutate(
+            &mut cache.items,
+            &mut cache.items_cache,
+            step,
+            subvalue_provider,
+            max_cplx,
+        )?;
+        let tree = std::mem::replace(value, self.build(&cache.items));
+        Some((UnmutateToken::Items { tree, token }, cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut Tree, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        if self.rng.usize(..4) == 0 {
+            if let Some((path, rotate_right)) = self.balanced_rotation(value, cache.items.len()) {
+                let cplx = self.mutator.complexity(&cache.items, &cache.items_cache);
+                return (UnmutateToken::Rotation { path, rotate_right }, cplx);
+            }
+        }
+        let (token, cplx) = self
+            .mutator
+            .random_mutate(&mut cache.items, &mut cache.items_cache, max_cplx);
+        let tree = std::mem::replace(value, self.build(&cache.items));
+        (UnmutateToken::Items { tree, token }, cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut Tree, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateToken::Items { tree, token } => {
+                self.mutator.unmutate(&mut cache.items, &mut cache.items_cache, token);
+                *value = tree;
+            }
+            UnmutateToken::Rotation { path, rotate_right } => {
+                let (unrotated, _) = Self::rotate_at(std::mem::replace(value, Tree::empty()), &path, !rotate_right);
+                *value = unrotated;
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, _value: &'a Tree, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(&cache.items, &cache.items_cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &Tree, b: &Tree) -> Option<f64> {
+        let (mut a_items, mut b_items) = (vec![], vec![]);
+        in_order_items(a, &mut a_items);
+        in_order_items(b, &mut b_items);
+        self.mutator.distance(&a_items, &b_items)
+    }
+}
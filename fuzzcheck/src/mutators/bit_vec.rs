@@ -0,0 +1,290 @@
+//! A mutator for vectors of booleans, stored compactly in a [`FixedBitSet`].
+//!
+//! ```
+//! use fuzzcheck::mutators::bit_vec::BitVecMutator;
+//! use fuzzcheck::{FixedBitSet, MutatorExt};
+//!
+//! // impl Mutator<FixedBitSet>
+//! let m = BitVecMutator::new(0..=256);
+//!
+//! // impl Mutator<Vec<bool>>
+//! let m = BitVecMutator::new(0..=256).map(
+//!     |bits: &FixedBitSet| (0..bits.len()).map(|i| bits.contains(i)).collect::<Vec<bool>>(),
+//!     |bools: &Vec<bool>| {
+//!         let mut bits = FixedBitSet::with_capacity(bools.len());
+//!         for (i, _) in bools.iter().enumerate().filter(|(_, b)| **b) {
+//!             bits.insert(i);
+//!         }
+//!         Some(bits)
+//!     },
+//! );
+//! ```
+use std::any::Any;
+use std::ops::RangeInclusive;
+
+use crate::{FixedBitSet, Mutator};
+
+/// The maximum number of bits added to the bitset by a single mutation
+const MAX_NBR_ADDED_BITS: usize = 64;
+
+/// A mutator for values of type [`FixedBitSet`], whose length is within a given range.
+///
+/// The mutations toggle single bits, enable or disable ranges of bits, and change
+/// the length of the bitset. The complexity of a bitset is `1.0 + len`, which is
+/// the same as the complexity of the equivalent `Vec<bool>`.
+pub struct BitVecMutator {
+    rng: fastrand::Rng,
+    len_range: RangeInclusive<usize>,
+}
+
+impl BitVecMutator {
+    #[coverage(off)]
+    pub fn new(len_range: RangeInclusive<usize>) -> Self {
+        Self {
+            rng: fastrand::Rng::new(),
+            len_range,
+        }
+    }
+
+    /// The maximum length of a bitset whose complexity is at most `max_cplx`
+    #[coverage(off)]
+    fn max_len(&self, max_cplx: f64) -> usize {
+        let max_len_for_cplx = if max_cplx.is_finite() {
+            (max_cplx - 1.0).max(0.0) as usize
+        } else {
+            usize::MAX
+        };
+        std::cmp::max(
+            *self.len_range.start(),
+            std::cmp::min(*self.len_range.end(), max_len_for_cplx),
+        )
+    }
+
+    #[coverage(off)]
+    fn complexity_of_len(len: usize) -> f64 {
+        1.0 + len as f64
+    }
+
+    /// Append `nbr_bits` random bits to the bitset
+    #[coverage(off)]
+    fn append_random_bits(&self, value: &mut FixedBitSet, nbr_bits: usize) {
+        let start = value.len();
+        value.grow(start + nbr_bits);
+        for bit in start..value.len() {
+            if self.rng.bool() {
+                value.insert(bit);
+            }
+        }
+    }
+}
+
+impl Default for BitVecMutator {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new(0..=usize::MAX)
+    }
+}
+
+#[doc(hidden)]
+pub enum UnmutateToken {
+    Toggle(usize),
+    Replace(FixedBitSet),
+}
+
+impl Mutator<FixedBitSet> for BitVecMutator {
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = usize;
+    #[doc(hidden)]
+    type ArbitraryStep = bool;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        false
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &FixedBitSet) -> bool {
+        self.len_range.contains(&value.len())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &FixedBitSet) -> Option<Self::Cache> {
+        if self.is_valid(value) {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &FixedBitSet, _cache: &Self::Cache) -> Self::MutationStep {
+        0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        Self::complexity_of_len(*self.len_range.end())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        Self::complexity_of_len(*self.len_range.start())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &FixedBitSet, _cache: &Self::Cache) -> f64 {
+        Self::complexity_of_len(value.len())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(FixedBitSet, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if !*step {
+            // the first generated value is the shortest bitset, with all bits disabled
+            *step = true;
+            let len = *self.len_range.start();
+            Some((FixedBitSet::with_capacity(len), Self::complexity_of_len(len)))
+        } else {
+            Some(self.random_arbitrary(max_cplx))
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (FixedBitSet, f64) {
+        let min_len = *self.len_range.start();
+        let max_len = self.max_len(max_cplx);
+        let len = self.rng.usize(min_len..=max_len);
+        let mut value = FixedBitSet::with_capacity(min_len);
+        for bit in 0..min_len {
+            if self.rng.bool() {
+                value.insert(bit);
+            }
+        }
+        self.append_random_bits(&mut value, len - min_len);
+        (value, Self::complexity_of_len(len))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut FixedBitSet,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        _subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if *step < value.len() {
+            // toggle each bit one after the other
+            let bit = *step;
+            value.toggle(bit);
+            *step += 1;
+            Some((UnmutateToken::Toggle(bit), Self::complexity_of_len(value.len())))
+        } else {
+            Some(self.random_mutate(value, cache, max_cplx))
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(
+        &self,
+        value: &mut FixedBitSet,
+        _cache: &mut Self::Cache,
+        max_cplx: f64,
+    ) -> (Self::UnmutateToken, f64) {
+        let len = value.len();
+        let can_grow = len < self.max_len(max_cplx);
+        let can_shrink = len > *self.len_range.start();
+        match self.rng.u8(..4) {
+            2 if can_grow => {
+                let old_value = value.clone();
+                let max_added_bits = std::cmp::min(MAX_NBR_ADDED_BITS, self.max_len(max_cplx) - len);
+                self.append_random_bits(value, self.rng.usize(1..=max_added_bits));
+                (UnmutateToken::Replace(old_value), Self::complexity_of_len(value.len()))
+            }
+            3 if can_shrink => {
+                let old_value = value.clone();
+                value.truncate(self.rng.usize(*self.len_range.start()..len));
+                (UnmutateToken::Replace(old_value), Self::complexity_of_len(value.len()))
+            }
+            1 if len > 0 => {
+                let old_value = value.clone();
+                let start = self.rng.usize(..len);
+                let end = self.rng.usize(start + 1..=len);
+                value.set_range(start..end, self.rng.bool());
+                (UnmutateToken::Replace(old_value), Self::complexity_of_len(len))
+            }
+            _ if len > 0 => {
+                let bit = self.rng.usize(..len);
+                value.toggle(bit);
+                (UnmutateToken::Toggle(bit), Self::complexity_of_len(len))
+            }
+            _ if can_grow => {
+                let old_value = value.clone();
+                self.append_random_bits(value, 1);
+                (UnmutateToken::Replace(old_value), Self::complexity_of_len(value.len()))
+            }
+            _ => (UnmutateToken::Replace(value.clone()), Self::complexity_of_len(len)),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut FixedBitSet, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateToken::Toggle(bit) => value.toggle(bit),
+            UnmutateToken::Replace(old_value) => *value = old_value,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        _value: &'a FixedBitSet,
+        _cache: &'a Self::Cache,
+        _visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
+}
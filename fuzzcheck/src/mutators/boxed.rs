@@ -17,6 +17,18 @@ impl<M> BoxMutator<M> {
             rng: fastrand::Rng::new(),
         }
     }
+
+    /// The mutator of the boxed value
+    #[coverage(off)]
+    pub fn inner(&self) -> &M {
+        &self.mutator
+    }
+
+    /// The mutator of the boxed value
+    #[coverage(off)]
+    pub fn inner_mut(&mut self) -> &mut M {
+        &mut self.mutator
+    }
 }
 #[derive(Clone)]
 pub struct MutationStep<T, MS> {
@@ -110,6 +122,13 @@ impl<T: Clone + 'static, M: Mutator<T>> Mutator<Box<T>> for BoxMutator<M> {
         (Box::new(value), cache)
     }
 
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(Box<T>, f64)> {
+        let (value, cplx) = self.mutator.arbitrary_at_complexity(target_cplx)?;
+        Some((Box::new(value), cplx))
+    }
+
     #[doc(hidden)]
     #[coverage(off)]
     fn ordered_mutate(
@@ -159,6 +178,37 @@ impl<T: Clone + 'static, M: Mutator<T>> Mutator<Box<T>> for BoxMutator<M> {
     fn visit_subvalues<'a>(&self, value: &'a Box<T>, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
         self.mutator.visit_subvalues(value, cache, visit)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &Box<T>, b: &Box<T>) -> Option<f64> {
+        self.mutator.distance(a, b)
+    }
 }
 
 impl<T> DefaultMutator for Box<T>
@@ -0,0 +1,44 @@
+//! A mutator for [`BTreeMap`], which inserts, removes, and mutates its entries.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::btree_map::BTreeMapMutator;
+//!
+//! // impl Mutator<BTreeMap<u8, String>>
+//! let m = <BTreeMap<u8, String>>::default_mutator();
+//! // impl Mutator<BTreeMap<u8, bool>> for maps of at most 16 entries
+//! let m = BTreeMapMutator::new(u8::default_mutator(), bool::default_mutator(), 0..=16);
+//! ```
+//!
+//! The entries of a `BTreeMap` are always sorted by key. When a key is mutated, its entry is
+//! removed from the map and inserted again with the new key, and unmutating it moves the entry
+//! back to its original key, so that the map is restored exactly.
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use crate::mutators::map_collection::impl_map_mutator;
+use crate::{DefaultMutator, Mutator};
+
+impl<K, V> DefaultMutator for BTreeMap<K, V>
+where
+    K: DefaultMutator + Ord + 'static,
+    V: DefaultMutator + 'static,
+{
+    type Mutator = BTreeMapMutator<K, V, K::Mutator, V::Mutator>;
+    #[coverage(off)]
+    fn default_mutator() -> Self::Mutator {
+        BTreeMapMutator::new(K::default_mutator(), V::default_mutator(), 0..=usize::MAX)
+    }
+}
+
+impl_map_mutator!(
+    BTreeMap,
+    BTreeMapMutator,
+    BTreeMapMutatorCache,
+    BTreeMapMutationStep,
+    UnmutateBTreeMapToken,
+    Ord
+);
@@ -0,0 +1,245 @@
+//! A wrapper that applies several mutations of a mutator at once.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::burst::BurstMutator;
+//!
+//! // each mutation of the vector is made of 5 mutations of the inner mutator
+//! let m = BurstMutator::new(<Vec<u8>>::default_mutator(), 5);
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::Mutator;
+
+/// A [`BurstMutator`] makes each mutation of the wrapped mutator bigger, by applying
+/// `k` of its mutations in a single call to `ordered_mutate` or `random_mutate`.
+///
+/// In `ordered_mutate`, the first mutation is the next ordered mutation of the wrapped
+/// mutator, and the `k - 1` others are random mutations of the result. It returns `None`
+/// when the wrapped mutator has no ordered mutation left.
+///
+/// The unmutate token holds the tokens of all the mutations that were applied, and
+/// [`unmutate`](Mutator::unmutate) undoes them in reverse order. The complexity of the
+/// mutated value is the one returned by the last mutation. Since the cache of the value
+/// is recomputed before each mutation but the first, `BurstMutator` is best suited to
+/// mutators whose [`validate_value`](Mutator::validate_value) is fast.
+///
+/// This is useful when the mutations of the wrapped mutator are too small to reach
+/// interesting values quickly, for example on large vectors. The values that can be
+/// generated are unchanged.
+pub struct BurstMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    mutator: M,
+    k: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M> BurstMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    /// Create a `BurstMutator` applying `k` mutations of `mutator` at once.
+    ///
+    /// **Panics** if `k` is `0`.
+    #[coverage(off)]
+    pub fn new(mutator: M, k: usize) -> Self {
+        assert!(k > 0, "a BurstMutator must apply at least one mutation at once");
+        Self {
+            mutator,
+            k,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Applies random mutations on top of those described by `tokens`, until there are `k` of them.
+    ///
+    /// A mutator may only update the cache of a value when it is unmutated, so the cache is
+    /// recomputed before each new mutation. The replaced cache is stored along with the
+    /// mutation's token, to be restored after the mutation is undone.
+    #[coverage(off)]
+    fn mutate_again(
+        &self,
+        value: &mut T,
+        cache: &mut M::Cache,
+        max_cplx: f64,
+        mut tokens: Vec<(M::UnmutateToken, Option<M::Cache>)>,
+        mut cplx: f64,
+    ) -> (Vec<(M::UnmutateToken, Option<M::Cache>)>, f64) {
+        while tokens.len() < self.k {
+            let new_cache = self.mutator.validate_value(value).unwrap();
+            let previous_cache = std::mem::replace(cache, new_cache);
+            let (token, new_cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+            tokens.push((token, Some(previous_cache)));
+            cplx = new_cplx;
+        }
+        (tokens, cplx)
+    }
+}
+
+impl<T, M> Mutator<T> for BurstMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = Vec<(M::UnmutateToken, Option<M::Cache>)>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.ordered_arbitrary(step, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        self.mutator.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.arbitrary_at_complexity(target_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        // the mutation step describes the next mutations of the original value, so
+        // the mutations applied on top of the first one are random
+        let (token, cplx) = self
+            .mutator
+            .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)?;
+        let mut tokens = Vec::with_capacity(self.k);
+        tokens.push((token, None));
+        Some(self.mutate_again(value, cache, max_cplx, tokens, cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let (token, cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+        let mut tokens = Vec::with_capacity(self.k);
+        tokens.push((token, None));
+        self.mutate_again(value, cache, max_cplx, tokens, cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        for (t, previous_cache) in t.into_iter().rev() {
+            self.mutator.unmutate(value, cache, t);
+            if let Some(previous_cache) = previous_cache {
+                *cache = previous_cache;
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &T, b: &T) -> Option<f64> {
+        self.mutator.distance(a, b)
+    }
+}
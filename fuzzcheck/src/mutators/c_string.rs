@@ -0,0 +1,139 @@
+//! Mutators for C strings, which cannot contain any NUL byte except their implicit terminator.
+//!
+//! ```
+//! use std::ffi::CString;
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::c_string::CStringBytesMutator;
+//!
+//! // impl Mutator<CString>
+//! let m = CString::default_mutator();
+//!
+//! // impl Mutator<Vec<u8>>, for the argument of `CString::new`, which
+//! // sometimes contains interior NUL bytes
+//! let m = CStringBytesMutator::new(true);
+//! ```
+use std::ffi::CString;
+
+use crate::mutators::alternation::AlternationMutator;
+use crate::mutators::integer_within_range::U8WithinRangeMutator;
+use crate::mutators::map::MapMutator;
+use crate::mutators::vector::VecMutator;
+use crate::mutators::Wrapper;
+use crate::DefaultMutator;
+
+/// A mutator for byte vectors, whose bytes are mutated by one of the given byte mutators
+type BytesMutator = VecMutator<u8, AlternationMutator<u8, U8WithinRangeMutator>>;
+
+/// A mutator of byte vectors that do not contain any NUL byte
+#[coverage(off)]
+fn nul_free_bytes_mutator() -> BytesMutator {
+    VecMutator::new(
+        AlternationMutator::new(vec![U8WithinRangeMutator::new(1..=u8::MAX)], 0.0),
+        0..=usize::MAX,
+    )
+}
+
+#[coverage(off)]
+fn parse_c_string(value: &CString) -> Option<Vec<u8>> {
+    Some(value.as_bytes().to_vec())
+}
+
+/// The bytes produced by [`nul_free_bytes_mutator`] are never NUL
+#[coverage(off)]
+#[allow(clippy::ptr_arg)]
+fn map_c_string(bytes: &Vec<u8>) -> CString {
+    CString::new(bytes.clone()).unwrap()
+}
+
+#[coverage(off)]
+fn c_string_cplx(_value: &CString, cplx: f64) -> f64 {
+    cplx
+}
+
+/// A mutator for [`CString`], the owned C strings of the standard library.
+///
+/// It generates the bytes of the strings, excluding their NUL terminator, in the same
+/// way as a mutator of `Vec<u8>`, except that they are never NUL. Every `CString` is
+/// therefore accepted by [`validate_value`](crate::Mutator::validate_value). It is the
+/// default mutator of `CString`.
+///
+/// Since a `CString` cannot contain interior NUL bytes, testing how the code under test
+/// handles them requires a mutator of byte vectors, such as [`CStringBytesMutator`].
+pub type CStringMutator = Wrapper<
+    MapMutator<
+        Vec<u8>,
+        CString,
+        BytesMutator,
+        fn(&CString) -> Option<Vec<u8>>,
+        fn(&Vec<u8>) -> CString,
+        fn(&CString, f64) -> f64,
+    >,
+>;
+
+impl CStringMutator {
+    #[coverage(off)]
+    pub fn new() -> Self {
+        Wrapper(MapMutator::new(
+            nul_free_bytes_mutator(),
+            parse_c_string,
+            map_c_string,
+            c_string_cplx,
+        ))
+    }
+}
+
+impl Default for CStringMutator {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DefaultMutator for CString {
+    type Mutator = CStringMutator;
+
+    #[coverage(off)]
+    fn default_mutator() -> Self::Mutator {
+        CStringMutator::new()
+    }
+}
+
+/// A mutator for the byte vectors passed to [`CString::new`], which mostly generates
+/// vectors that do not contain any NUL byte.
+///
+/// If `interior_nul` is `true`, a quarter of the generated vectors are instead made of
+/// arbitrary bytes, about half of which are NUL. Passing them to `CString::new` returns
+/// an error, which tests the code handling that error. Otherwise, the generated vectors
+/// never contain a NUL byte.
+///
+/// Every byte vector is accepted by [`validate_value`](crate::Mutator::validate_value).
+/// If `interior_nul` is `false`, the vectors containing NUL bytes, for example read
+/// from a corpus, are not mutated in place but replaced by vectors without NUL bytes.
+pub type CStringBytesMutator = Wrapper<AlternationMutator<Vec<u8>, BytesMutator>>;
+
+impl CStringBytesMutator {
+    #[coverage(off)]
+    pub fn new(interior_nul: bool) -> Self {
+        let with_nul = VecMutator::new(
+            AlternationMutator::new(
+                vec![U8WithinRangeMutator::new(1..=u8::MAX), U8WithinRangeMutator::new(0..=0)],
+                0.0,
+            ),
+            0..=usize::MAX,
+        );
+        let mut mutator = AlternationMutator::new(
+            vec![
+                nul_free_bytes_mutator(),
+                nul_free_bytes_mutator(),
+                nul_free_bytes_mutator(),
+                with_nul,
+            ],
+            0.0,
+        );
+        if !interior_nul {
+            // the last mutator is only used to accept the vectors containing NUL bytes
+            mutator.generate_only_with(vec![0]);
+        }
+        Wrapper(mutator)
+    }
+}
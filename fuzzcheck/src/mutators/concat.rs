@@ -0,0 +1,136 @@
+//! A mutator for byte buffers made of independent sections, each with its own mutator.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::concat::ConcatMutator;
+//! use fuzzcheck::mutators::either::Either;
+//! use fuzzcheck::mutators::fixed_len_vector::FixedLenVecMutator;
+//! use fuzzcheck::mutators::integer::U8Mutator;
+//!
+//! // impl Mutator<Vec<u8>>, generating a 4-byte header followed by a payload of any length
+//! let m = ConcatMutator::with_fixed_sizes(
+//!     vec![
+//!         Either::Left(FixedLenVecMutator::new_with_repeated_mutator(U8Mutator::default(), 4)),
+//!         Either::Right(<Vec<u8>>::default_mutator()),
+//!     ],
+//!     vec![4],
+//! );
+//! ```
+use crate::mutators::fixed_len_vector::FixedLenVecMutator;
+use crate::mutators::map::MapMutator;
+use crate::mutators::MutatorWrapper;
+use crate::Mutator;
+
+type ConcatMap<M> = MapMutator<
+    Vec<Vec<u8>>,
+    Vec<u8>,
+    FixedLenVecMutator<Vec<u8>, M>,
+    Box<dyn Fn(&Vec<u8>) -> Option<Vec<Vec<u8>>>>,
+    fn(&Vec<Vec<u8>>) -> Vec<u8>,
+    fn(&Vec<u8>, f64) -> f64,
+>;
+
+/// A mutator for `Vec<u8>` made of the concatenation of several sections, each
+/// generated by a different mutator.
+///
+/// Each section is mutated independently by its mutator, and the complexity of a value
+/// is the sum of the complexities of its sections.
+///
+/// To validate a value, it is first split into sections, either by a user-provided
+/// function or at fixed offsets, and then each section is validated by its mutator.
+/// The split must recover the sections generated by the mutators: for example, every
+/// section except the last one can have a fixed size, or the sections can be self-delimiting.
+///
+/// All mutators must have the same type. To use mutators of different types, wrap them in
+/// [`Either`](crate::mutators::either::Either) or [`Either3`](crate::mutators::either::Either3).
+pub struct ConcatMutator<M>
+where
+    M: Mutator<Vec<u8>>,
+{
+    mutator: ConcatMap<M>,
+}
+
+impl<M> ConcatMutator<M>
+where
+    M: Mutator<Vec<u8>>,
+{
+    /// Create a `ConcatMutator` from the mutators of each section and a function
+    /// returning the lengths of the sections of a value.
+    ///
+    /// The function should return `None` if the value cannot be split into as many
+    /// sections as there are mutators.
+    #[coverage(off)]
+    pub fn new(mutators: Vec<M>, split: impl Fn(&[u8]) -> Option<Vec<usize>> + 'static) -> Self {
+        let nbr_sections = mutators.len();
+        Self {
+            mutator: MapMutator::new(
+                FixedLenVecMutator::new_without_inherent_complexity(mutators),
+                Box::new(
+                    #[coverage(off)]
+                    move |value: &Vec<u8>| {
+                        let lengths = split(value)?;
+                        if lengths.len() != nbr_sections || lengths.iter().sum::<usize>() != value.len() {
+                            return None;
+                        }
+                        let mut sections = Vec::with_capacity(nbr_sections);
+                        let mut start = 0;
+                        for len in lengths {
+                            sections.push(value[start..start + len].to_vec());
+                            start += len;
+                        }
+                        Some(sections)
+                    },
+                ),
+                concat_sections,
+                concat_cplx,
+            ),
+        }
+    }
+
+    /// Create a `ConcatMutator` whose sections have the given sizes, except for the
+    /// last one, which contains all the remaining bytes.
+    ///
+    /// There must be exactly one fewer size than there are mutators.
+    #[coverage(off)]
+    pub fn with_fixed_sizes(mutators: Vec<M>, sizes: Vec<usize>) -> Self {
+        assert_eq!(
+            sizes.len() + 1,
+            mutators.len(),
+            "a ConcatMutator needs a size for every section except the last one"
+        );
+        let prefix_len = sizes.iter().sum::<usize>();
+        Self::new(
+            mutators,
+            #[coverage(off)]
+            move |value| {
+                let rest = value.len().checked_sub(prefix_len)?;
+                let mut lengths = sizes.clone();
+                lengths.push(rest);
+                Some(lengths)
+            },
+        )
+    }
+}
+
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn concat_sections(sections: &Vec<Vec<u8>>) -> Vec<u8> {
+    sections.concat()
+}
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn concat_cplx(_value: &Vec<u8>, cplx: f64) -> f64 {
+    cplx
+}
+
+impl<M> MutatorWrapper for ConcatMutator<M>
+where
+    M: Mutator<Vec<u8>>,
+{
+    type Wrapped = ConcatMap<M>;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
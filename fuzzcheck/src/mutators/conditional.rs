@@ -0,0 +1,296 @@
+//! A wrapper that sometimes uses a second mutator on the values matching a predicate.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::conditional::ConditionalMutator;
+//! use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+//! use fuzzcheck::mutators::vector::VecMutator;
+//!
+//! // when the byte buffer only contains ASCII digits, use a mutator that
+//! // keeps it numeric for half of the mutations
+//! let m = ConditionalMutator::new(
+//!     <Vec<u8>>::default_mutator(),
+//!     VecMutator::new(U8WithinRangeMutator::new(b'0'..=b'9'), 0..=usize::MAX),
+//!     |x: &Vec<u8>| x.iter().all(u8::is_ascii_digit),
+//!     0.5,
+//! );
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::Mutator;
+
+/// A [`ConditionalMutator`] delegates to a base mutator, but when the current value
+/// matches a predicate, it sometimes mutates it with an extra mutator instead.
+///
+/// When `predicate(value)` is `true`, a mutation is performed by the extra mutator with
+/// probability `probability`. The extra mutator is only used if the value is valid for it,
+/// and the mutated value is only kept if it is also valid for the base mutator. Otherwise,
+/// the base mutator is used.
+///
+/// The generated values, their complexity, and the arbitrary values always come from the
+/// base mutator. This is different from [`Either`](crate::mutators::either::Either) and
+/// [`AlternationMutator`](crate::mutators::alternation::AlternationMutator), which choose
+/// the mutator without looking at the current value.
+pub struct ConditionalMutator<T, M, Extra, Pred>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Extra: Mutator<T>,
+    Pred: Fn(&T) -> bool,
+{
+    mutator: M,
+    extra: Extra,
+    predicate: Pred,
+    probability: f64,
+    rng: fastrand::Rng,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M, Extra, Pred> ConditionalMutator<T, M, Extra, Pred>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Extra: Mutator<T>,
+    Pred: Fn(&T) -> bool,
+{
+    /// Create a new `ConditionalMutator`.
+    ///
+    /// `probability` is the chance, between 0.0 and 1.0, that a value matching the
+    /// predicate is mutated by `extra` instead of `mutator`.
+    #[coverage(off)]
+    pub fn new(mutator: M, extra: Extra, predicate: Pred, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "the probability of a ConditionalMutator must be between 0.0 and 1.0"
+        );
+        Self {
+            mutator,
+            extra,
+            predicate,
+            probability,
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Whether the next mutation of `value` should be performed by the extra mutator
+    #[coverage(off)]
+    fn use_extra(&self, value: &T) -> bool {
+        self.rng.f64() < self.probability && (self.predicate)(value)
+    }
+
+    /// Mutate the value with the extra mutator, if the value is valid for it and the
+    /// mutated value is valid for the base mutator.
+    #[coverage(off)]
+    fn extra_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut M::Cache,
+        max_cplx: f64,
+    ) -> Option<(
+        UnmutateToken<M::UnmutateToken, Extra::UnmutateToken, M::Cache, Extra::Cache>,
+        f64,
+    )> {
+        let mut extra_cache = self.extra.validate_value(value)?;
+        let (token, _) = self.extra.random_mutate(value, &mut extra_cache, max_cplx);
+        if let Some(new_cache) = self.mutator.validate_value(value) {
+            let cplx = self.mutator.complexity(value, &new_cache);
+            if cplx <= max_cplx {
+                let cache = std::mem::replace(cache, new_cache);
+                return Some((
+                    UnmutateToken::Extra {
+                        token,
+                        extra_cache,
+                        cache,
+                    },
+                    cplx,
+                ));
+            }
+        }
+        self.extra.unmutate(value, &mut extra_cache, token);
+        None
+    }
+}
+
+#[doc(hidden)]
+pub enum UnmutateToken<Base, Extra, BaseCache, ExtraCache> {
+    /// The value was mutated by the base mutator
+    Base(Base),
+    /// The value was mutated by the extra mutator, and the cache of the base mutator was replaced
+    Extra {
+        token: Extra,
+        extra_cache: ExtraCache,
+        cache: BaseCache,
+    },
+}
+
+impl<T, M, Extra, Pred> Mutator<T> for ConditionalMutator<T, M, Extra, Pred>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Extra: Mutator<T>,
+    Pred: Fn(&T) -> bool,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken<M::UnmutateToken, Extra::UnmutateToken, M::Cache, Extra::Cache>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+        self.extra.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.ordered_arbitrary(step, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        self.mutator.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if self.use_extra(value) {
+            if let Some(result) = self.extra_mutate(value, cache, max_cplx) {
+                return Some(result);
+            }
+        }
+        let (token, cplx) = self
+            .mutator
+            .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)?;
+        Some((UnmutateToken::Base(token), cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        if self.use_extra(value) {
+            if let Some(result) = self.extra_mutate(value, cache, max_cplx) {
+                return result;
+            }
+        }
+        let (token, cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+        (UnmutateToken::Base(token), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateToken::Base(t) => self.mutator.unmutate(value, cache, t),
+            UnmutateToken::Extra {
+                token,
+                mut extra_cache,
+                cache: old_cache,
+            } => {
+                self.extra.unmutate(value, &mut extra_cache, token);
+                *cache = old_cache;
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint);
+        self.extra.set_effort_hint(hint);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap() && self.extra.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_states(
+            &self.rng,
+            [self.mutator.rng_state(), self.extra.rng_state()],
+        ))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let mut states = crate::mutators::set_rng_states(&self.rng, state);
+        self.mutator.set_rng_state(states.next().unwrap_or_default());
+        self.extra.set_rng_state(states.next().unwrap_or_default());
+    }
+}
@@ -0,0 +1,229 @@
+//! A wrapper that counts how often a mutator produces recognized corner-case values.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::Mutator;
+//! use fuzzcheck::mutators::corner_case_counting::CornerCaseCountingMutator;
+//!
+//! let m = CornerCaseCountingMutator::new(
+//!     <Vec<u8>>::default_mutator(),
+//!     |v: &Vec<u8>| {
+//!         if v.is_empty() {
+//!             Some("empty")
+//!         } else if v.contains(&u8::MAX) {
+//!             Some("contains u8::MAX")
+//!         } else {
+//!             None
+//!         }
+//!     },
+//! );
+//! m.initialize();
+//! for _ in 0..100 {
+//!     let _ = m.random_arbitrary(100.0);
+//! }
+//! let counts = m.corner_case_counts();
+//! println!("{} empty vectors were generated", counts.get("empty").unwrap_or(&0));
+//! ```
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::Mutator;
+
+/// A [`CornerCaseCountingMutator`] forwards every method to the wrapped mutator,
+/// and counts the values that it produces which are recognized as corner cases
+/// by a classifier function.
+///
+/// The classifier returns a label, such as `"empty"` or `"i32::MIN"`, for the corner-case
+/// values, and `None` for the others. Each value returned by the `ordered_arbitrary`,
+/// `random_arbitrary`, `ordered_mutate`, and `random_mutate` methods is classified, and the
+/// number of values per label is available through [`corner_case_counts`](CornerCaseCountingMutator::corner_case_counts).
+///
+/// The wrapper does not change the behaviour of the mutator. It is meant to check
+/// that the mutator actually generates the edge cases that a test relies on.
+pub struct CornerCaseCountingMutator<T, M> {
+    mutator: M,
+    classifier: Box<dyn Fn(&T) -> Option<&'static str>>,
+    counts: RefCell<HashMap<&'static str, u64>>,
+}
+
+impl<T, M> CornerCaseCountingMutator<T, M> {
+    #[coverage(off)]
+    pub fn new(mutator: M, classifier: impl Fn(&T) -> Option<&'static str> + 'static) -> Self {
+        Self {
+            mutator,
+            classifier: Box::new(classifier),
+            counts: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// The number of corner-case values produced so far, for each label returned by the classifier
+    #[coverage(off)]
+    pub fn corner_case_counts(&self) -> HashMap<&'static str, u64> {
+        self.counts.borrow().clone()
+    }
+
+    #[coverage(off)]
+    fn record(&self, value: &T) {
+        if let Some(label) = (self.classifier)(value) {
+            *self.counts.borrow_mut().entry(label).or_default() += 1;
+        }
+    }
+}
+
+impl<T, M> Mutator<T> for CornerCaseCountingMutator<T, M>
+where
+    M: Mutator<T>,
+    T: Clone + 'static,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        let result = self.mutator.ordered_arbitrary(step, max_cplx);
+        if let Some((value, _)) = &result {
+            self.record(value);
+        }
+        result
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let (value, cplx) = self.mutator.random_arbitrary(max_cplx);
+        self.record(&value);
+        (value, cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let result = self
+            .mutator
+            .ordered_mutate(value, cache, step, subvalue_provider, max_cplx);
+        if result.is_some() {
+            self.record(value);
+        }
+        result
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let result = self.mutator.random_mutate(value, cache, max_cplx);
+        self.record(value);
+        result
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(value, cache, t)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &T, b: &T) -> Option<f64> {
+        self.mutator.distance(a, b)
+    }
+}
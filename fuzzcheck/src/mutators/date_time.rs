@@ -0,0 +1,266 @@
+//! Mutators for the dates and times of the [`chrono`] crate.
+//!
+//! Each component of a date or time is generated by a within-range integer mutator.
+//! Since those mutators favour the bounds of their range, the generated values often
+//! include boundary cases such as the first and last years of the allowed range,
+//! the last day of each month, and February 29 of leap years.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::date_time::{DateComponentsMutator, NaiveDateMutator};
+//!
+//! // impl Mutator<NaiveDate>, with years between 0 and 9999
+//! let m = chrono::NaiveDate::default_mutator();
+//! // impl Mutator<NaiveDate>, with years between 1970 and 2100
+//! let m = NaiveDateMutator::new(1970..=2100);
+//! // impl Mutator<(i32, u32, u32)> generating (year, month, day) triples that
+//! // are sometimes invalid dates, such as (2023, 2, 29) or (2023, 13, 0)
+//! let m = DateComponentsMutator::new(1970..=2100, true);
+//! ```
+use std::ops::RangeInclusive;
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::mutators::integer_within_range::{I32WithinRangeMutator, U32WithinRangeMutator};
+use crate::mutators::map::MapMutator;
+use crate::mutators::tuples::{
+    Tuple2, Tuple2Mutator, Tuple3, Tuple3Mutator, Tuple4, Tuple4Mutator, TupleMutatorWrapper,
+};
+use crate::mutators::Wrapper;
+use crate::DefaultMutator;
+
+/// The range of years used by the default mutators
+const DEFAULT_YEARS: RangeInclusive<i32> = 0..=9999;
+
+type ComponentsMutator = TupleMutatorWrapper<
+    Tuple3Mutator<I32WithinRangeMutator, U32WithinRangeMutator, U32WithinRangeMutator>,
+    Tuple3<i32, u32, u32>,
+>;
+
+/// Whether `year` is a leap year of the proleptic Gregorian calendar
+#[coverage(off)]
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// The number of days in the given month, or 0 if `month` is not between 1 and 12
+#[coverage(off)]
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// Change the day of a valid (year, month, day) triple to the last day of the
+/// month if it exceeds the length of the month
+#[coverage(off)]
+fn repair_date(&(year, month, day): &(i32, u32, u32)) -> (i32, u32, u32) {
+    (year, month, day.min(days_in_month(year, month)))
+}
+
+#[coverage(off)]
+fn is_valid_date(&(year, month, day): &(i32, u32, u32)) -> bool {
+    (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)
+}
+
+#[coverage(off)]
+fn same_components(components: &(i32, u32, u32)) -> (i32, u32, u32) {
+    *components
+}
+
+#[coverage(off)]
+fn parse_valid_components(components: &(i32, u32, u32)) -> Option<(i32, u32, u32)> {
+    if is_valid_date(components) {
+        Some(*components)
+    } else {
+        None
+    }
+}
+
+#[coverage(off)]
+fn parse_any_components(components: &(i32, u32, u32)) -> Option<(i32, u32, u32)> {
+    Some(*components)
+}
+
+#[coverage(off)]
+fn same_cplx<T>(_value: &T, cplx: f64) -> f64 {
+    cplx
+}
+
+#[coverage(off)]
+fn components_mutator(
+    years: RangeInclusive<i32>,
+    months: RangeInclusive<u32>,
+    days: RangeInclusive<u32>,
+) -> ComponentsMutator {
+    TupleMutatorWrapper::new(Tuple3Mutator::new(
+        I32WithinRangeMutator::new(years),
+        U32WithinRangeMutator::new(months),
+        U32WithinRangeMutator::new(days),
+    ))
+}
+
+/// Panics if dates of the given years cannot be represented by a [`NaiveDate`]
+#[coverage(off)]
+fn assert_years_are_supported(years: &RangeInclusive<i32>) {
+    assert!(
+        NaiveDate::from_ymd_opt(*years.start(), 1, 1).is_some()
+            && NaiveDate::from_ymd_opt(*years.end(), 12, 31).is_some(),
+        "the range of years {:?} is not supported by chrono::NaiveDate",
+        years
+    );
+}
+
+/// A mutator for `(year, month, day)` triples.
+///
+/// If `allow_invalid` is `false`, only valid dates of the proleptic Gregorian calendar
+/// are generated, and `validate_value` rejects the invalid ones. Otherwise, the month can
+/// also be 0 or 13, and the day can be 0 or any number up to 32 regardless of the month,
+/// which is useful to test how invalid dates are handled.
+pub type DateComponentsMutator = Wrapper<
+    MapMutator<
+        (i32, u32, u32),
+        (i32, u32, u32),
+        ComponentsMutator,
+        fn(&(i32, u32, u32)) -> Option<(i32, u32, u32)>,
+        fn(&(i32, u32, u32)) -> (i32, u32, u32),
+        fn(&(i32, u32, u32), f64) -> f64,
+    >,
+>;
+
+impl DateComponentsMutator {
+    #[coverage(off)]
+    pub fn new(years: RangeInclusive<i32>, allow_invalid: bool) -> Self {
+        if allow_invalid {
+            Wrapper(MapMutator::new(
+                components_mutator(years, 0..=13, 0..=32),
+                parse_any_components,
+                same_components,
+                same_cplx,
+            ))
+        } else {
+            Wrapper(MapMutator::new(
+                components_mutator(years, 1..=12, 1..=31),
+                parse_valid_components,
+                repair_date,
+                same_cplx,
+            ))
+        }
+    }
+}
+
+#[coverage(off)]
+fn date_from_components(components: &(i32, u32, u32)) -> NaiveDate {
+    let (year, month, day) = repair_date(components);
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+#[coverage(off)]
+fn components_from_date(date: &NaiveDate) -> Option<(i32, u32, u32)> {
+    Some((date.year(), date.month(), date.day()))
+}
+
+/// A mutator for [`NaiveDate`], whose year is within the given range.
+pub type NaiveDateMutator = Wrapper<
+    MapMutator<
+        (i32, u32, u32),
+        NaiveDate,
+        ComponentsMutator,
+        fn(&NaiveDate) -> Option<(i32, u32, u32)>,
+        fn(&(i32, u32, u32)) -> NaiveDate,
+        fn(&NaiveDate, f64) -> f64,
+    >,
+>;
+
+impl NaiveDateMutator {
+    /// Create a new `NaiveDateMutator`.
+    ///
+    /// **Panics** if some years of the range cannot be represented by a [`NaiveDate`].
+    #[coverage(off)]
+    pub fn new(years: RangeInclusive<i32>) -> Self {
+        assert_years_are_supported(&years);
+        Wrapper(MapMutator::new(
+            components_mutator(years, 1..=12, 1..=31),
+            components_from_date,
+            date_from_components,
+            same_cplx,
+        ))
+    }
+}
+
+impl DefaultMutator for NaiveDate {
+    type Mutator = NaiveDateMutator;
+    #[coverage(off)]
+    fn default_mutator() -> Self::Mutator {
+        NaiveDateMutator::new(DEFAULT_YEARS)
+    }
+}
+
+type TimeComponentsMutator = TupleMutatorWrapper<
+    Tuple4Mutator<U32WithinRangeMutator, U32WithinRangeMutator, U32WithinRangeMutator, U32WithinRangeMutator>,
+    Tuple4<u32, u32, u32, u32>,
+>;
+
+#[coverage(off)]
+fn date_time_from_components(&(date, (hour, min, sec, nano)): &(NaiveDate, (u32, u32, u32, u32))) -> NaiveDateTime {
+    date.and_time(NaiveTime::from_hms_nano_opt(hour, min, sec, nano).unwrap())
+}
+
+#[coverage(off)]
+fn components_from_date_time(date_time: &NaiveDateTime) -> Option<(NaiveDate, (u32, u32, u32, u32))> {
+    let time = date_time.time();
+    Some((
+        date_time.date(),
+        (time.hour(), time.minute(), time.second(), time.nanosecond()),
+    ))
+}
+
+/// A mutator for [`NaiveDateTime`], whose year is within the given range.
+///
+/// Leap seconds are not generated, and `validate_value` rejects them.
+pub type NaiveDateTimeMutator = Wrapper<
+    MapMutator<
+        (NaiveDate, (u32, u32, u32, u32)),
+        NaiveDateTime,
+        TupleMutatorWrapper<
+            Tuple2Mutator<NaiveDateMutator, TimeComponentsMutator>,
+            Tuple2<NaiveDate, (u32, u32, u32, u32)>,
+        >,
+        fn(&NaiveDateTime) -> Option<(NaiveDate, (u32, u32, u32, u32))>,
+        fn(&(NaiveDate, (u32, u32, u32, u32))) -> NaiveDateTime,
+        fn(&NaiveDateTime, f64) -> f64,
+    >,
+>;
+
+impl NaiveDateTimeMutator {
+    /// Create a new `NaiveDateTimeMutator`.
+    ///
+    /// **Panics** if some years of the range cannot be represented by a [`NaiveDate`].
+    #[coverage(off)]
+    pub fn new(years: RangeInclusive<i32>) -> Self {
+        let time_mutator = TupleMutatorWrapper::new(Tuple4Mutator::new(
+            U32WithinRangeMutator::new(0..=23),
+            U32WithinRangeMutator::new(0..=59),
+            U32WithinRangeMutator::new(0..=59),
+            U32WithinRangeMutator::new(0..=999_999_999),
+        ));
+        Wrapper(MapMutator::new(
+            TupleMutatorWrapper::new(Tuple2Mutator::new(NaiveDateMutator::new(years), time_mutator)),
+            components_from_date_time,
+            date_time_from_components,
+            same_cplx,
+        ))
+    }
+}
+
+impl DefaultMutator for NaiveDateTime {
+    type Mutator = NaiveDateTimeMutator;
+    #[coverage(off)]
+    fn default_mutator() -> Self::Mutator {
+        NaiveDateTimeMutator::new(DEFAULT_YEARS)
+    }
+}
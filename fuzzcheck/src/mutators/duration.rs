@@ -0,0 +1,318 @@
+//! A mutator for [`Duration`].
+//!
+//! ```
+//! use std::time::Duration;
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::duration::DurationMutator;
+//!
+//! let m: DurationMutator = Duration::default_mutator();
+//! ```
+//!
+//! The mutator produces, in this order:
+//! * zero, one nanosecond, microsecond, millisecond, and second, and the largest number of
+//!   nanoseconds below one second
+//! * the largest durations that can be converted to milliseconds, nanoseconds, or seconds
+//!   stored in an `i64` or a `u32`, and `Duration::MAX`
+//! * then durations whose seconds and nanoseconds are generated independently
+//!
+//! And the mutations of a duration:
+//! * replace it by one of the durations above
+//! * then change either its seconds or its nanoseconds, but never both
+//!
+//! The nanoseconds always stay below one billion. The complexity of a duration is the sum of
+//! the bit widths of its seconds and nanoseconds.
+use std::any::Any;
+use std::time::Duration;
+
+use crate::mutators::integer::U64Mutator;
+use crate::mutators::integer_within_range::U32WithinRangeMutator;
+use crate::mutators::interesting_values::{self, InterestingValues};
+use crate::{DefaultMutator, Mutator};
+
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+const COMPLEXITY: f64 = (u64::BITS + u32::BITS) as f64;
+
+impl InterestingValues for Duration {
+    const INTERESTING: &'static [Duration] = &[
+        Duration::ZERO,
+        Duration::from_nanos(1),
+        Duration::from_micros(1),
+        Duration::from_millis(1),
+        Duration::from_secs(1),
+        Duration::from_nanos(NANOS_PER_SEC as u64 - 1),
+        // the largest durations whose number of milliseconds and nanoseconds fit in a `u64`
+        Duration::from_millis(u64::MAX),
+        Duration::from_nanos(u64::MAX),
+        // the largest durations whose number of seconds fits in an `i64` and a `u32`
+        Duration::new(i64::MAX as u64, NANOS_PER_SEC - 1),
+        Duration::from_secs(u32::MAX as u64),
+        Duration::MAX,
+    ];
+
+    #[coverage(off)]
+    fn is_same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The default mutator of [`Duration`]. See the [module documentation](crate::mutators::duration)
+/// for a description of its mutations.
+pub struct DurationMutator {
+    secs: U64Mutator,
+    nanos: U32WithinRangeMutator,
+    rng: fastrand::Rng,
+}
+
+impl Default for DurationMutator {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self {
+            secs: U64Mutator::default(),
+            nanos: U32WithinRangeMutator::new(0..NANOS_PER_SEC),
+            rng: fastrand::Rng::default(),
+        }
+    }
+}
+
+impl DefaultMutator for Duration {
+    type Mutator = DurationMutator;
+    #[coverage(off)]
+    fn default_mutator() -> Self::Mutator {
+        DurationMutator::default()
+    }
+}
+
+#[derive(Clone)]
+pub struct DurationMutationStep {
+    step: u64,
+    secs: u64,
+    nanos: u64,
+}
+
+impl DurationMutator {
+    /// Mutate the seconds of the duration if `secs` is true, or its nanoseconds otherwise
+    #[coverage(off)]
+    fn mutate_part(
+        &self,
+        value: Duration,
+        secs: bool,
+        step: Option<&mut u64>,
+        subvalue_provider: &dyn crate::SubValueProvider,
+    ) -> Option<Duration> {
+        if secs {
+            let mut secs = value.as_secs();
+            match step {
+                Some(step) => {
+                    self.secs
+                        .ordered_mutate(&mut secs, &mut (), step, subvalue_provider, COMPLEXITY)?;
+                }
+                None => {
+                    self.secs.random_mutate(&mut secs, &mut (), COMPLEXITY);
+                }
+            }
+            Some(Duration::new(secs, value.subsec_nanos()))
+        } else {
+            let mut nanos = value.subsec_nanos();
+            match step {
+                Some(step) => {
+                    self.nanos
+                        .ordered_mutate(&mut nanos, &mut (), step, subvalue_provider, COMPLEXITY)?;
+                }
+                None => {
+                    self.nanos.random_mutate(&mut nanos, &mut (), COMPLEXITY);
+                }
+            }
+            Some(Duration::new(value.as_secs(), nanos))
+        }
+    }
+}
+
+impl Mutator<Duration> for DurationMutator {
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = DurationMutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = u64;
+    #[doc(hidden)]
+    type UnmutateToken = Duration; // old value
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.secs.initialize();
+        self.nanos.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        0
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, _value: &Duration) -> bool {
+        true
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, _value: &Duration) -> Option<Self::Cache> {
+        Some(())
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &Duration, _cache: &Self::Cache) -> Self::MutationStep {
+        DurationMutationStep {
+            step: 0,
+            secs: 0,
+            nanos: 0,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        COMPLEXITY
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        COMPLEXITY
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        COMPLEXITY
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, _value: &Duration, _cache: &Self::Cache) -> f64 {
+        COMPLEXITY
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(Duration, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        let value = interesting_values::ordered_arbitrary(
+            step,
+            #[coverage(off)]
+            |step| {
+                let (mut secs_step, mut nanos_step) = (step, step);
+                let (secs, _) = self.secs.ordered_arbitrary(&mut secs_step, COMPLEXITY)?;
+                let (nanos, _) = self.nanos.ordered_arbitrary(&mut nanos_step, COMPLEXITY)?;
+                Some(Duration::new(secs, nanos))
+            },
+        )?;
+        Some((value, COMPLEXITY))
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, _max_cplx: f64) -> (Duration, f64) {
+        let value = interesting_values::random_arbitrary(
+            &self.rng,
+            #[coverage(off)]
+            || {
+                let (secs, _) = self.secs.random_arbitrary(COMPLEXITY);
+                let (nanos, _) = self.nanos.random_arbitrary(COMPLEXITY);
+                Duration::new(secs, nanos)
+            },
+        );
+        (value, COMPLEXITY)
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut Duration,
+        _cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        let DurationMutationStep {
+            step,
+            secs: secs_step,
+            nanos: nanos_step,
+        } = step;
+        let token = interesting_values::ordered_mutate(
+            value,
+            step,
+            // there are no small changes: the interesting durations come first
+            0,
+            #[coverage(off)]
+            |value, _| value,
+            // then alternate between mutating the seconds and the nanoseconds, until both are exhausted
+            #[coverage(off)]
+            |value, idx| {
+                for secs in [idx % 2 == 0, idx % 2 != 0] {
+                    let part_step = if secs { &mut *secs_step } else { &mut *nanos_step };
+                    if let Some(new_value) = self.mutate_part(value, secs, Some(part_step), subvalue_provider) {
+                        return Some(new_value);
+                    }
+                }
+                None
+            },
+        )?;
+        Some((token, COMPLEXITY))
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(
+        &self,
+        value: &mut Duration,
+        _cache: &mut Self::Cache,
+        _max_cplx: f64,
+    ) -> (Self::UnmutateToken, f64) {
+        let new_value = if self.rng.usize(..4) == 0 {
+            interesting_values::random_interesting(&self.rng)
+        } else {
+            let secs = self.rng.bool();
+            self.mutate_part(*value, secs, None, &crate::subvalue_provider::EmptySubValueProvider)
+                .unwrap_or(*value)
+        };
+        (std::mem::replace(value, new_value), COMPLEXITY)
+    }
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut Duration, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = t;
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        _value: &'a Duration,
+        _cache: &'a Self::Cache,
+        _visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_states(
+            &self.rng,
+            [self.secs.rng_state(), self.nanos.rng_state()],
+        ))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let mut states = crate::mutators::set_rng_states(&self.rng, state);
+        self.secs.set_rng_state(states.next().unwrap_or_default());
+        self.nanos.set_rng_state(states.next().unwrap_or_default());
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &Duration, b: &Duration) -> Option<f64> {
+        Some(interesting_values::distance(a, b))
+    }
+}
@@ -210,6 +210,24 @@ where
             _ => unreachable!(),
         }
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        match self {
+            Either::Left(m) => m.set_effort_hint(hint),
+            Either::Right(m) => m.set_effort_hint(hint),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        match self {
+            Either::Left(m) => m.unmutate_is_cheap(),
+            Either::Right(m) => m.unmutate_is_cheap(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -450,4 +468,24 @@ where
             _ => unreachable!(),
         }
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        match self {
+            Either3::A(m) => m.set_effort_hint(hint),
+            Either3::B(m) => m.set_effort_hint(hint),
+            Either3::C(m) => m.set_effort_hint(hint),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        match self {
+            Either3::A(m) => m.unmutate_is_cheap(),
+            Either3::B(m) => m.unmutate_is_cheap(),
+            Either3::C(m) => m.unmutate_is_cheap(),
+        }
+    }
 }
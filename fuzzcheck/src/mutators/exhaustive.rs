@@ -0,0 +1,164 @@
+//! A wrapper that enumerates every value of a mutator whose domain is small, to test them exhaustively.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::exhaustive::ExhaustiveMutator;
+//!
+//! let m = ExhaustiveMutator::new(u8::default_mutator());
+//! assert_eq!(m.total_count(), Some(256));
+//! assert_eq!(m.values().count(), 256);
+//!
+//! // the domain of `u32` is too large to be enumerated
+//! let m = ExhaustiveMutator::new(u32::default_mutator());
+//! assert_eq!(m.total_count(), None);
+//! ```
+//!
+//! Such a mutator can be given to [`launch_exhaustive`](crate::launch_exhaustive), which runs
+//! the test function on every value instead of fuzzing it.
+use std::cell::OnceCell;
+use std::marker::PhantomData;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use super::MutatorWrapper;
+use crate::Mutator;
+
+/// The default maximum number of values of an [`ExhaustiveMutator`]
+pub const DEFAULT_MAX_EXHAUSTIVE_COUNT: u64 = 1 << 16;
+
+/// An [`ExhaustiveMutator`] wraps a mutator and enumerates all the values that it can
+/// generate, if there are at most `max_count` of them.
+///
+/// The values are enumerated by calling [`ordered_arbitrary`](crate::Mutator::ordered_arbitrary)
+/// on the wrapped mutator until it is exhausted. Therefore, each value is enumerated
+/// exactly once only if the wrapped mutator never generates the same value twice, which is
+/// the case for the mutators of `bool`, integers, integer ranges, and enums without fields.
+/// The mutators of tuples and structs generate random values from `ordered_arbitrary`,
+/// so they are never exhausted and their `total_count` is always `None`.
+///
+/// Counting the values takes as long as enumerating them, up to `max_count` values. It is
+/// done once, the first time [`total_count`](ExhaustiveMutator::total_count) is called.
+///
+/// Otherwise, it behaves exactly like the wrapped mutator.
+pub struct ExhaustiveMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    mutator: M,
+    max_count: u64,
+    total_count: OnceCell<Option<u64>>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M> ExhaustiveMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    /// Create a new `ExhaustiveMutator` enumerating at most [`DEFAULT_MAX_EXHAUSTIVE_COUNT`] values.
+    #[coverage(off)]
+    pub fn new(mutator: M) -> Self {
+        Self::with_max_count(mutator, DEFAULT_MAX_EXHAUSTIVE_COUNT)
+    }
+
+    /// Create a new `ExhaustiveMutator` enumerating at most `max_count` values.
+    #[coverage(off)]
+    pub fn with_max_count(mutator: M, max_count: u64) -> Self {
+        Self {
+            mutator,
+            max_count,
+            total_count: OnceCell::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The number of values of the wrapped mutator, or `None` if there are more than `max_count`.
+    #[coverage(off)]
+    pub fn total_count(&self) -> Option<u64> {
+        *self.total_count.get_or_init(
+            #[coverage(off)]
+            || {
+                let mut count = 0;
+                for _ in self.values() {
+                    count += 1;
+                    if count > self.max_count {
+                        return None;
+                    }
+                }
+                Some(count)
+            },
+        )
+    }
+
+    /// An iterator over all the values of the wrapped mutator.
+    ///
+    /// It does not stop after `max_count` values, so it may never end if the domain of the
+    /// mutator is infinite.
+    #[coverage(off)]
+    pub fn values(&self) -> ExhaustiveValues<'_, T, M> {
+        self.mutator.initialize();
+        ExhaustiveValues {
+            mutator: &self.mutator,
+            step: self.mutator.default_arbitrary_step(),
+            max_cplx: self.mutator.max_complexity(),
+        }
+    }
+
+    /// Run the test function on every value of the wrapped mutator, in order, and return the
+    /// first value on which it fails, i.e. returns `false` or panics.
+    ///
+    /// Like [`values`](ExhaustiveMutator::values), it does not stop after `max_count` values.
+    #[coverage(off)]
+    pub fn find_failure(&self, test_function: impl Fn(&T) -> bool) -> Option<T> {
+        self.values().find(
+            #[coverage(off)]
+            |value| {
+                !catch_unwind(AssertUnwindSafe(
+                    #[coverage(off)]
+                    || test_function(value),
+                ))
+                .unwrap_or(false)
+            },
+        )
+    }
+}
+
+/// An iterator over all the values of a mutator, returned by [`ExhaustiveMutator::values`]
+pub struct ExhaustiveValues<'a, T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    mutator: &'a M,
+    step: M::ArbitraryStep,
+    max_cplx: f64,
+}
+
+impl<T, M> Iterator for ExhaustiveValues<'_, T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type Item = T;
+
+    #[coverage(off)]
+    fn next(&mut self) -> Option<T> {
+        self.mutator.ordered_arbitrary(&mut self.step, self.max_cplx).map(
+            #[coverage(off)]
+            |(value, _)| value,
+        )
+    }
+}
+
+impl<T, M> MutatorWrapper for ExhaustiveMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    type Wrapped = M;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
@@ -0,0 +1,407 @@
+//! A wrapper that prevents a mutator from producing again the values that already made the test fail.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::failure_avoiding::{FailureAvoidingMutator, KnownFailures};
+//!
+//! // remember the hashes of at most 10_000 failing test cases
+//! let m = FailureAvoidingMutator::new(<Vec<u8>>::default_mutator(), 10_000);
+//!
+//! // values can also be marked as failing manually, e.g. those of a previous run
+//! let known_failures = m.known_failures();
+//! known_failures.insert(&vec![0xde, 0xad]);
+//! ```
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::Mutator;
+
+/// The maximum number of values that are generated by `random_arbitrary` and
+/// `random_mutate` while looking for a value that is not known to fail.
+const MAX_RANDOM_ATTEMPTS: usize = 20;
+
+#[coverage(off)]
+fn hash_value<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+struct KnownFailuresInner {
+    hashes: HashSet<u64>,
+    /// The hashes in the order they were inserted, to forget the oldest ones first
+    order: VecDeque<u64>,
+    capacity: usize,
+}
+
+/// A bounded set of the hashes of the values that are known to make the test fail.
+///
+/// It holds at most `capacity` hashes, each taking a few dozen bytes of memory. When
+/// it is full, inserting a new hash forgets the oldest one, so the values that failed
+/// a long time ago may be produced and tested again.
+///
+/// It is a cheap handle to a shared set: cloning it returns a handle to the same set,
+/// which can be given to several [`FailureAvoidingMutator`]s.
+#[derive(Clone)]
+pub struct KnownFailures {
+    inner: Rc<RefCell<KnownFailuresInner>>,
+}
+
+impl KnownFailures {
+    #[coverage(off)]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(KnownFailuresInner {
+                hashes: HashSet::new(),
+                order: VecDeque::new(),
+                capacity,
+            })),
+        }
+    }
+
+    /// Mark the value as failing
+    #[coverage(off)]
+    pub fn insert<T: Hash>(&self, value: &T) {
+        self.insert_hash(hash_value(value));
+    }
+
+    #[coverage(off)]
+    fn insert_hash(&self, hash: u64) {
+        let inner = &mut *self.inner.borrow_mut();
+        if inner.capacity == 0 || !inner.hashes.insert(hash) {
+            return;
+        }
+        inner.order.push_back(hash);
+        if inner.order.len() > inner.capacity {
+            let oldest = inner.order.pop_front().unwrap();
+            inner.hashes.remove(&oldest);
+        }
+    }
+
+    /// Whether the value, or another value with the same hash, is known to fail
+    #[coverage(off)]
+    pub fn contains<T: Hash>(&self, value: &T) -> bool {
+        self.inner.borrow().hashes.contains(&hash_value(value))
+    }
+
+    /// The number of failing values that are remembered
+    #[coverage(off)]
+    pub fn len(&self) -> usize {
+        self.inner.borrow().hashes.len()
+    }
+
+    #[coverage(off)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Forget all the failing values
+    #[coverage(off)]
+    pub fn clear(&self) {
+        let inner = &mut *self.inner.borrow_mut();
+        inner.hashes.clear();
+        inner.order.clear();
+    }
+}
+
+/// A [`FailureAvoidingMutator`] wraps a mutator such that it does not produce the
+/// values that are known to make the test function fail.
+///
+/// Whenever the test function fails, the fuzzer passes the failing value to
+/// [`Mutator::record_test_failure`], which adds its hash to the [`KnownFailures`].
+/// The mutations that produce a value whose hash is already in the set are undone: `ordered_mutate` and `ordered_arbitrary`
+/// advance the mutation step and try the next value instead, while `random_mutate` and
+/// `random_arbitrary` try a few other random values before giving up.
+///
+/// This keeps the fuzzer exploring new test cases after finding a failure, instead of
+/// finding the same one again. It is mostly useful when the fuzzer does not stop after
+/// the first failure. The set of known failures is bounded, see [`KnownFailures`].
+///
+/// **Important:** this mutator cannot be used as a submutator, since the fuzzer only
+/// reports the test failures to the mutator it was given.
+pub struct FailureAvoidingMutator<T, M>
+where
+    T: Clone + Hash + 'static,
+    M: Mutator<T>,
+{
+    mutator: M,
+    known_failures: KnownFailures,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M> FailureAvoidingMutator<T, M>
+where
+    T: Clone + Hash + 'static,
+    M: Mutator<T>,
+{
+    /// Create a new `FailureAvoidingMutator` remembering at most `capacity` failing values.
+    #[coverage(off)]
+    pub fn new(mutator: M, capacity: usize) -> Self {
+        Self::with_known_failures(mutator, KnownFailures::new(capacity))
+    }
+
+    /// Create a new `FailureAvoidingMutator` that avoids the values of an existing set
+    /// and adds the new failing values to it.
+    #[coverage(off)]
+    pub fn with_known_failures(mutator: M, known_failures: KnownFailures) -> Self {
+        Self {
+            mutator,
+            known_failures,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// A handle to the set of values known to fail
+    #[coverage(off)]
+    pub fn known_failures(&self) -> KnownFailures {
+        self.known_failures.clone()
+    }
+
+    /// Returns `false` if the value is known to fail
+    #[coverage(off)]
+    fn accept(&self, value: &T) -> bool {
+        !self.known_failures.contains(value)
+    }
+}
+
+impl<T, M> Mutator<T> for FailureAvoidingMutator<T, M>
+where
+    T: Clone + Hash + 'static,
+    M: Mutator<T>,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        loop {
+            let (x, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+            if self.accept(&x) {
+                return Some((x, cplx));
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        for _ in 0..MAX_RANDOM_ATTEMPTS {
+            let (x, cplx) = self.mutator.random_arbitrary(max_cplx);
+            if self.accept(&x) {
+                return (x, cplx);
+            }
+        }
+        self.mutator.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        loop {
+            let (t, cplx) = self
+                .mutator
+                .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)?;
+            if self.accept(value) {
+                return Some((t, cplx));
+            }
+            self.mutator.unmutate(value, cache, t);
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        for _ in 0..MAX_RANDOM_ATTEMPTS {
+            let (t, cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+            if self.accept(value) {
+                return (t, cplx);
+            }
+            self.mutator.unmutate(value, cache, t);
+        }
+        self.mutator.random_mutate(value, cache, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(value, cache, t)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &T, b: &T) -> Option<f64> {
+        self.mutator.distance(a, b)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn record_test_failure(&self, value: &T, _cache: &Self::Cache) {
+        self.known_failures.insert(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subvalue_provider::EmptySubValueProvider;
+    use crate::DefaultMutator;
+
+    #[test]
+    #[coverage(off)]
+    fn test_known_failures_is_bounded() {
+        let known_failures = KnownFailures::new(2);
+        assert!(known_failures.is_empty());
+        known_failures.insert(&1u8);
+        known_failures.insert(&2u8);
+        known_failures.insert(&2u8);
+        assert_eq!(known_failures.len(), 2);
+        known_failures.insert(&3u8);
+        assert_eq!(known_failures.len(), 2);
+        // the oldest value is forgotten first
+        assert!(!known_failures.contains(&1u8));
+        assert!(known_failures.contains(&2u8));
+        assert!(known_failures.contains(&3u8));
+        known_failures.clear();
+        assert!(known_failures.is_empty());
+    }
+
+    #[test]
+    #[coverage(off)]
+    #[allow(clippy::let_unit_value)]
+    fn test_failure_avoiding_mutator() {
+        let m = FailureAvoidingMutator::new(u8::default_mutator(), 100);
+        m.initialize();
+        m.known_failures().insert(&3u8);
+
+        let mut value = 0u8;
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        let mut produced = vec![];
+        while let Some((token, _)) = m.ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 8.0) {
+            produced.push(value);
+            if value == 10 {
+                // the test fails with the value that was produced last
+                m.record_test_failure(&value, &cache);
+            }
+            m.unmutate(&mut value, &mut cache, token);
+        }
+        assert!(!produced.contains(&3));
+        assert!((0..=u8::MAX).all(
+            #[coverage(off)]
+            |x| x == 3 || x == value || produced.contains(&x)
+        ));
+        assert!(m.known_failures().contains(&10u8));
+        assert_eq!(m.known_failures().len(), 2);
+
+        // the failing values are not produced again
+        let mut step = m.default_mutation_step(&value, &cache);
+        while let Some((token, _)) = m.ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 8.0) {
+            assert!(value != 3 && value != 10);
+            m.unmutate(&mut value, &mut cache, token);
+        }
+    }
+}
@@ -0,0 +1,374 @@
+//! A mutator for fixed-point decimal numbers, such as the amounts handled by financial software.
+//!
+//! ```
+//! use fuzzcheck::mutators::fixed_point::FixedPointMutator;
+//!
+//! /// An amount of money with two decimal places
+//! #[derive(Clone, Debug)]
+//! struct Amount {
+//!     cents: i64,
+//! }
+//!
+//! // impl Mutator<Amount>
+//! // amounts between -1,000,000.00 and 1,000,000.00
+//! let m = FixedPointMutator::new(
+//!     2,
+//!     -100_000_000..=100_000_000,
+//!     |cents| Amount { cents },
+//!     |amount: &Amount| Some(amount.cents),
+//! );
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds, RangeInclusive};
+
+use crate::mutators::integer_within_range::I64WithinRangeMutator;
+use crate::Mutator;
+
+/// The complexity of a fixed-point value whose mantissa is `mantissa`
+#[coverage(off)]
+fn mantissa_cplx(mantissa: i64) -> f64 {
+    1.0 + (i64::BITS - mantissa.unsigned_abs().leading_zeros()) as f64
+}
+
+/// Truncate the bits of `mantissa` so that its complexity is at most `max_cplx`
+#[coverage(off)]
+fn fit_mantissa(mantissa: i64, max_cplx: f64) -> i64 {
+    let bits = (max_cplx - 1.0).floor();
+    if bits >= 63.0 {
+        mantissa
+    } else if bits < 1.0 {
+        0
+    } else {
+        mantissa % (1 << bits as u32)
+    }
+}
+
+/// The mantissas within `range` that are most likely to expose rounding bugs, given the
+/// mantissa of the value `1` and the current mantissa, if there is one
+#[coverage(off)]
+fn interesting_mantissas(unit: i64, range: &RangeInclusive<i64>, current: Option<i64>) -> Vec<i64> {
+    let (start, end) = (*range.start(), *range.end());
+    let half = unit / 2;
+    let mut candidates = vec![
+        0,
+        1,
+        -1,
+        unit,
+        -unit,
+        unit - 1,
+        unit + 1,
+        half,
+        half - 1,
+        half + 1,
+        -half,
+        start,
+        end,
+        end - end % unit,
+        start - start % unit,
+    ];
+    if let Some(current) = current {
+        let rounded = current - current % unit;
+        candidates.extend([
+            rounded,
+            rounded.saturating_add(half),
+            rounded.saturating_add(unit),
+            rounded.saturating_sub(unit),
+            current.saturating_add(1),
+            current.saturating_sub(1),
+            current.saturating_neg(),
+        ]);
+    }
+    let mut mantissas = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if Some(candidate) != current && range.contains(&candidate) && !mantissas.contains(&candidate) {
+            mantissas.push(candidate);
+        }
+    }
+    mantissas
+}
+
+/// A mutator for fixed-point decimal numbers, represented by an integer mantissa and a fixed
+/// number of decimal places.
+///
+/// The value is `mantissa / 10^scale`, where the mantissa is within a given range. The mantissa
+/// is mutated by an [`I64WithinRangeMutator`], and converted to and from the decimal type by two
+/// user-provided functions:
+/// * `to_decimal` creates the decimal value from its mantissa
+/// * `from_decimal` returns the mantissa of a decimal value, or `None` if the value cannot
+///   be represented with this scale. Such values, as well as those whose mantissa is outside
+///   the range, are rejected by `validate_value`.
+///
+/// Before mutating the mantissa like any other integer, the mutator tries the values that are
+/// most likely to trip up rounding code: `0`, the smallest representable unit, `±1`, the values
+/// around `0.5` and `1`, the closest integral values, and the bounds of the range.
+///
+/// The complexity of a value is the number of bits of its mantissa, plus one.
+pub struct FixedPointMutator<D, ToDecimal, FromDecimal>
+where
+    D: Clone + 'static,
+    ToDecimal: Fn(i64) -> D,
+    FromDecimal: Fn(&D) -> Option<i64>,
+{
+    mutator: I64WithinRangeMutator,
+    range: RangeInclusive<i64>,
+    scale: u32,
+    unit: i64,
+    to_decimal: ToDecimal,
+    from_decimal: FromDecimal,
+    rng: fastrand::Rng,
+    _phantom: PhantomData<D>,
+}
+
+impl<D, ToDecimal, FromDecimal> FixedPointMutator<D, ToDecimal, FromDecimal>
+where
+    D: Clone + 'static,
+    ToDecimal: Fn(i64) -> D,
+    FromDecimal: Fn(&D) -> Option<i64>,
+{
+    /// Create a mutator for decimal numbers with `scale` decimal places, whose mantissa is
+    /// within `mantissa_range`.
+    ///
+    /// The scale must be at most 18, so that `10^scale` fits in an `i64`.
+    #[coverage(off)]
+    pub fn new(
+        scale: u32,
+        mantissa_range: impl RangeBounds<i64>,
+        to_decimal: ToDecimal,
+        from_decimal: FromDecimal,
+    ) -> Self {
+        let unit = 10i64
+            .checked_pow(scale)
+            .expect("the scale of a FixedPointMutator must be at most 18");
+        let start = match mantissa_range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => i64::MIN,
+        };
+        let end = match mantissa_range.end_bound() {
+            Bound::Included(&end) => end,
+            Bound::Excluded(&end) => end - 1,
+            Bound::Unbounded => i64::MAX,
+        };
+        Self {
+            mutator: I64WithinRangeMutator::new(start..=end),
+            range: start..=end,
+            scale,
+            unit,
+            to_decimal,
+            from_decimal,
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The number of decimal places of the generated values
+    #[coverage(off)]
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Truncate the bits of `mantissa` so that its complexity is at most `max_cplx`, while
+    /// keeping it within the range
+    #[coverage(off)]
+    fn fit_mantissa(&self, mantissa: i64, max_cplx: f64) -> i64 {
+        fit_mantissa(mantissa, max_cplx).clamp(*self.range.start(), *self.range.end())
+    }
+
+    /// Replace the mantissa of `value`, returning the old one
+    #[coverage(off)]
+    fn set_mantissa(&self, value: &mut D, cache: &mut i64, mantissa: i64) -> (i64, f64) {
+        *value = (self.to_decimal)(mantissa);
+        (std::mem::replace(cache, mantissa), mantissa_cplx(mantissa))
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct FixedPointStep {
+    interesting: usize,
+    inner: u64,
+}
+
+impl<D, ToDecimal, FromDecimal> Mutator<D> for FixedPointMutator<D, ToDecimal, FromDecimal>
+where
+    D: Clone + 'static,
+    ToDecimal: Fn(i64) -> D,
+    FromDecimal: Fn(&D) -> Option<i64>,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = i64;
+    #[doc(hidden)]
+    type MutationStep = FixedPointStep;
+    #[doc(hidden)]
+    type ArbitraryStep = FixedPointStep;
+    #[doc(hidden)]
+    type UnmutateToken = i64;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        FixedPointStep::default()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &D) -> bool {
+        self.validate_value(value).is_some()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &D) -> Option<Self::Cache> {
+        (self.from_decimal)(value).filter(
+            #[coverage(off)]
+            |mantissa| self.range.contains(mantissa),
+        )
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &D, _cache: &Self::Cache) -> Self::MutationStep {
+        FixedPointStep::default()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        1.0 + i64::BITS as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        mantissa_cplx(0.clamp(*self.range.start(), *self.range.end()))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, _value: &D, cache: &Self::Cache) -> f64 {
+        mantissa_cplx(*cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(D, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        let interesting = interesting_mantissas(self.unit, &self.range, None);
+        while let Some(&mantissa) = interesting.get(step.interesting) {
+            step.interesting += 1;
+            let cplx = mantissa_cplx(mantissa);
+            if cplx <= max_cplx {
+                return Some(((self.to_decimal)(mantissa), cplx));
+            }
+        }
+        let (mantissa, _) = self.mutator.ordered_arbitrary(&mut step.inner, f64::INFINITY)?;
+        let mantissa = self.fit_mantissa(mantissa, max_cplx);
+        Some(((self.to_decimal)(mantissa), mantissa_cplx(mantissa)))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (D, f64) {
+        let mantissa = if self.rng.usize(..4) == 0 {
+            let interesting = interesting_mantissas(self.unit, &self.range, None);
+            interesting[self.rng.usize(..interesting.len())]
+        } else {
+            self.mutator.random_arbitrary(f64::INFINITY).0
+        };
+        let mantissa = self.fit_mantissa(mantissa, max_cplx);
+        ((self.to_decimal)(mantissa), mantissa_cplx(mantissa))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut D,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        let interesting = interesting_mantissas(self.unit, &self.range, Some(*cache));
+        while let Some(&mantissa) = interesting.get(step.interesting) {
+            step.interesting += 1;
+            if mantissa_cplx(mantissa) <= max_cplx {
+                return Some(self.set_mantissa(value, cache, mantissa));
+            }
+        }
+        let mut mantissa = *cache;
+        self.mutator.ordered_mutate(
+            &mut mantissa,
+            &mut (),
+            &mut step.inner,
+            subvalue_provider,
+            f64::INFINITY,
+        )?;
+        let mantissa = self.fit_mantissa(mantissa, max_cplx);
+        Some(self.set_mantissa(value, cache, mantissa))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut D, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let interesting = interesting_mantissas(self.unit, &self.range, Some(*cache));
+        let mantissa = match self.rng.usize(..4) {
+            0 if !interesting.is_empty() => interesting[self.rng.usize(..interesting.len())],
+            1 => cache.saturating_add(self.rng.i64(-self.unit..=self.unit)),
+            _ => {
+                let mut mantissa = *cache;
+                self.mutator.random_mutate(&mut mantissa, &mut (), f64::INFINITY);
+                mantissa
+            }
+        };
+        let mantissa = self.fit_mantissa(mantissa, max_cplx);
+        self.set_mantissa(value, cache, mantissa)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut D, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = (self.to_decimal)(t);
+        *cache = t;
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, _value: &'a D, _cache: &'a Self::Cache, _visit: &mut dyn FnMut(&'a dyn Any, f64)) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &D, b: &D) -> Option<f64> {
+        let (a, b) = ((self.from_decimal)(a)?, (self.from_decimal)(b)?);
+        Some(if a == b { 0.0 } else { 1.0 })
+    }
+}
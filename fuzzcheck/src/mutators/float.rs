@@ -0,0 +1,310 @@
+//! Mutators for the floating point numbers `f32` and `f64`.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::float::F64Mutator;
+//!
+//! let m: F64Mutator = f64::default_mutator();
+//! ```
+//!
+//! The mutators produce, in this order:
+//! * the special values: `±0.0`, `±1.0`, `NaN`, the infinities, the extreme subnormal and
+//!   finite values, and the machine epsilon
+//! * then arbitrary bit patterns
+//!
+//! And the mutations of a value:
+//! * nudge it by a few [ULPs](https://en.wikipedia.org/wiki/Unit_in_the_last_place)
+//! * flip each bit of its sign, exponent, and mantissa
+//! * replace it by one of the special values, and then by arbitrary bit patterns
+//!
+//! The complexity of a float is its bit width.
+use std::any::Any;
+
+use crate::mutators::integer::{U32Mutator, U64Mutator};
+use crate::mutators::interesting_values::{self, InterestingValues};
+use crate::{DefaultMutator, Mutator};
+
+/// The number of mutations that nudge the value by ±1, ±2, ±3, or ±4 ULPs
+const NBR_NUDGES: u64 = 8;
+
+macro_rules! impl_ordered_bits {
+    ($name:ident, $bits:ident, $to_ordered:ident, $from_ordered:ident) => {
+        /// Map the bits of a float to an integer that is ordered like the float,
+        /// such that adding one to it gives the next float
+        #[coverage(off)]
+        pub(crate) fn $to_ordered(value: $name) -> $bits {
+            const SIGN: $bits = 1 << (<$bits>::BITS - 1);
+            let bits = value.to_bits();
+            if bits & SIGN == 0 {
+                bits | SIGN
+            } else {
+                !bits
+            }
+        }
+        /// The inverse of
+        #[doc = concat!("[`", stringify!($to_ordered), "`]")]
+        #[coverage(off)]
+        pub(crate) fn $from_ordered(ordered: $bits) -> $name {
+            const SIGN: $bits = 1 << (<$bits>::BITS - 1);
+            if ordered & SIGN == 0 {
+                <$name>::from_bits(!ordered)
+            } else {
+                <$name>::from_bits(ordered & !SIGN)
+            }
+        }
+    };
+}
+impl_ordered_bits!(f32, u32, f32_to_ordered, f32_from_ordered);
+impl_ordered_bits!(f64, u64, f64_to_ordered, f64_from_ordered);
+
+macro_rules! impl_float_mutator {
+    (
+        $name:ident,
+        $bits:ident,
+        $bits_mutator:ident,
+        $name_mutator:ident,
+        $to_ordered:ident,
+        $from_ordered:ident
+    ) => {
+        impl InterestingValues for $name {
+            const INTERESTING: &'static [$name] = &[
+                0.0,
+                -0.0,
+                1.0,
+                -1.0,
+                <$name>::NAN,
+                <$name>::INFINITY,
+                <$name>::NEG_INFINITY,
+                // the smallest positive and negative subnormal numbers
+                <$name>::from_bits(1),
+                -<$name>::from_bits(1),
+                // the largest subnormal number
+                <$name>::from_bits(<$name>::MIN_POSITIVE.to_bits() - 1),
+                <$name>::MIN_POSITIVE,
+                <$name>::MAX,
+                <$name>::MIN,
+                <$name>::EPSILON,
+            ];
+
+            #[coverage(off)]
+            fn is_same(&self, other: &Self) -> bool {
+                self.to_bits() == other.to_bits()
+            }
+        }
+
+        /// The default mutator of
+        #[doc = concat!("`", stringify!($name), "`")]
+        /// values. See the [module documentation](crate::mutators::float) for a description of its mutations.
+        #[derive(Clone)]
+        pub struct $name_mutator {
+            bits: $bits_mutator,
+            rng: fastrand::Rng,
+        }
+
+        impl Default for $name_mutator {
+            #[coverage(off)]
+            fn default() -> Self {
+                Self {
+                    bits: <$bits_mutator>::default(),
+                    rng: fastrand::Rng::default(),
+                }
+            }
+        }
+
+        impl $name_mutator {
+            #[coverage(off)]
+            fn nudge(value: $name, nudge: u64) -> $name {
+                let ulps = (nudge / 2 + 1) as $bits;
+                let ordered = $to_ordered(value);
+                let ordered = if nudge % 2 == 0 {
+                    ordered.wrapping_add(ulps)
+                } else {
+                    ordered.wrapping_sub(ulps)
+                };
+                $from_ordered(ordered)
+            }
+            #[coverage(off)]
+            fn flip_bit(value: $name, bit: u32) -> $name {
+                <$name>::from_bits(value.to_bits() ^ (1 << (<$bits>::BITS - 1 - bit)))
+            }
+            #[coverage(off)]
+            fn arbitrary_bits(&self, step: u64) -> Option<$name> {
+                let mut step = step;
+                self.bits.ordered_arbitrary(&mut step, f64::INFINITY).map(
+                    #[coverage(off)]
+                    |(bits, _)| <$name>::from_bits(bits),
+                )
+            }
+        }
+
+        impl Mutator<$name> for $name_mutator {
+            #[doc(hidden)]
+            type Cache = ();
+            #[doc(hidden)]
+            type MutationStep = u64;
+            #[doc(hidden)]
+            type ArbitraryStep = u64;
+            #[doc(hidden)]
+            type UnmutateToken = $name; // old value
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn initialize(&self) {}
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+                0
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn is_valid(&self, _value: &$name) -> bool {
+                true
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn validate_value(&self, _value: &$name) -> Option<Self::Cache> {
+                Some(())
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_mutation_step(&self, _value: &$name, _cache: &Self::Cache) -> Self::MutationStep {
+                0
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn global_search_space_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn max_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn min_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn complexity(&self, _value: &$name, _cache: &Self::Cache) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<($name, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let value = interesting_values::ordered_arbitrary(
+                    step,
+                    #[coverage(off)]
+                    |step| self.arbitrary_bits(step),
+                )?;
+                Some((value, <$bits>::BITS as f64))
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_arbitrary(&self, _max_cplx: f64) -> ($name, f64) {
+                let value = interesting_values::random_arbitrary(
+                    &self.rng,
+                    #[coverage(off)]
+                    || <$name>::from_bits(self.rng.$bits(..)),
+                );
+                (value, <$bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                step: &mut Self::MutationStep,
+                _subvalue_provider: &dyn crate::SubValueProvider,
+                max_cplx: f64,
+            ) -> Option<(Self::UnmutateToken, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let token = interesting_values::ordered_mutate(
+                    value,
+                    step,
+                    NBR_NUDGES + <$bits>::BITS as u64,
+                    #[coverage(off)]
+                    |value, idx| {
+                        if idx < NBR_NUDGES {
+                            Self::nudge(value, idx)
+                        } else {
+                            Self::flip_bit(value, (idx - NBR_NUDGES) as u32)
+                        }
+                    },
+                    #[coverage(off)]
+                    |_, idx| self.arbitrary_bits(idx),
+                )?;
+                Some((token, <$bits>::BITS as f64))
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                _max_cplx: f64,
+            ) -> (Self::UnmutateToken, f64) {
+                let new_value = match self.rng.usize(..4) {
+                    0 => Self::nudge(*value, self.rng.u64(..NBR_NUDGES)),
+                    1 => Self::flip_bit(*value, self.rng.u32(..<$bits>::BITS)),
+                    2 => interesting_values::random_interesting(&self.rng),
+                    _ => <$name>::from_bits(self.rng.$bits(..)),
+                };
+                (std::mem::replace(value, new_value), <$bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn unmutate(&self, value: &mut $name, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+                *value = t;
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn visit_subvalues<'a>(
+                &self,
+                _value: &'a $name,
+                _cache: &'a Self::Cache,
+                _visit: &mut dyn FnMut(&'a dyn Any, f64),
+            ) {
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn rng_state(&self) -> Option<Vec<u8>> {
+                Some(crate::mutators::rng_state(&self.rng))
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_rng_state(&self, state: &[u8]) {
+                crate::mutators::set_rng_state(&self.rng, state);
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn distance(&self, a: &$name, b: &$name) -> Option<f64> {
+                Some(interesting_values::distance(a, b))
+            }
+        }
+
+        impl DefaultMutator for $name {
+            type Mutator = $name_mutator;
+            #[coverage(off)]
+            fn default_mutator() -> Self::Mutator {
+                <$name_mutator>::default()
+            }
+        }
+    };
+}
+
+impl_float_mutator!(f32, u32, U32Mutator, F32Mutator, f32_to_ordered, f32_from_ordered);
+impl_float_mutator!(f64, u64, U64Mutator, F64Mutator, f64_to_ordered, f64_from_ordered);
@@ -0,0 +1,348 @@
+//! Mutators for the floating point numbers `f32` and `f64` that are within a given range.
+//!
+//! ```
+//! use fuzzcheck::mutators::float_within_range::F64WithinRangeMutator;
+//!
+//! // impl Mutator<f64>, generating values between -1.0 and 1.0, both included
+//! let m = F64WithinRangeMutator::new(-1.0..=1.0);
+//!
+//! // generating any value that is not NaN: an unbounded range contains the infinities
+//! let m = F64WithinRangeMutator::new(..);
+//! ```
+//!
+//! The values are never `NaN`, and `validate_value` rejects `NaN` and the values outside of the
+//! range, where `-0.0` is considered smaller than `0.0`. `ordered_arbitrary` first produces the
+//! bounds of the range, then its midpoint, and then the midpoints of the intervals between the
+//! values produced so far. If the range contains few floats, it enumerates all of them instead,
+//! starting from the midpoint. `random_arbitrary` samples the values uniformly within the range,
+//! or uniformly among the representable floats if the width of the range is infinite.
+use std::any::Any;
+use std::ops::{Bound, RangeBounds};
+
+use crate::mutators::float::{f32_from_ordered, f32_to_ordered, f64_from_ordered, f64_to_ordered};
+use crate::mutators::integer::{binary_search_arbitrary_u32, binary_search_arbitrary_u64};
+use crate::Mutator;
+
+/// The number of mutations that nudge the value by ±1, ±2, ±3, or ±4 ULPs
+const NBR_NUDGES: u64 = 8;
+/// The ranges containing at most this number of floats are enumerated entirely by `ordered_arbitrary`
+const MAX_ENUMERATED_LEN: u64 = 1 << 16;
+
+macro_rules! impl_float_mutator_constrained {
+    (
+        $name:ident,
+        $bits:ident,
+        $name_mutator:ident,
+        $to_ordered:ident,
+        $from_ordered:ident,
+        $name_binary_arbitrary_function:ident
+    ) => {
+        pub struct $name_mutator {
+            start: $name,
+            end: $name,
+            start_ordered: $bits,
+            /// the number of floats in the range, minus one
+            len_ordered: $bits,
+            rng: fastrand::Rng,
+        }
+
+        impl $name_mutator {
+            /// Create a mutator generating the values within the given range.
+            ///
+            /// The bounds are compared by their bits, such that `-0.0` is smaller than `0.0`, but
+            /// an excluded bound that is zero excludes both `-0.0` and `0.0`.
+            ///
+            /// It panics if a bound of the range is `NaN`, or if the range is empty.
+            #[coverage(off)]
+            pub fn new<RB: RangeBounds<$name>>(range: RB) -> Self {
+                let start = match range.start_bound() {
+                    Bound::Included(b) => *b,
+                    Bound::Excluded(b) => {
+                        assert_ne!(*b, <$name>::INFINITY);
+                        let b = if *b == 0.0 { 0.0 } else { *b };
+                        $from_ordered($to_ordered(b).wrapping_add(1))
+                    }
+                    Bound::Unbounded => <$name>::NEG_INFINITY,
+                };
+                let end = match range.end_bound() {
+                    Bound::Included(b) => *b,
+                    Bound::Excluded(b) => {
+                        assert_ne!(*b, <$name>::NEG_INFINITY);
+                        let b = if *b == 0.0 { -0.0 } else { *b };
+                        $from_ordered($to_ordered(b).wrapping_sub(1))
+                    }
+                    Bound::Unbounded => <$name>::INFINITY,
+                };
+                assert!(
+                    !start.is_nan() && !end.is_nan(),
+                    "The bounds of a float range cannot be NaN"
+                );
+                let (start_ordered, end_ordered) = ($to_ordered(start), $to_ordered(end));
+                if start_ordered > end_ordered {
+                    panic!(
+                        "You have provided a float range where the value of the start of the range \
+                        is larger than the end of the range!\nRange start: {:#?}\nRange end: {:#?}",
+                        range.start_bound(),
+                        range.end_bound()
+                    )
+                }
+                Self {
+                    start,
+                    end,
+                    start_ordered,
+                    len_ordered: end_ordered - start_ordered,
+                    rng: fastrand::Rng::default(),
+                }
+            }
+
+            #[coverage(off)]
+            fn contains(&self, value: $name) -> bool {
+                !value.is_nan()
+                    && $to_ordered(value).checked_sub(self.start_ordered).is_some_and(
+                        #[coverage(off)]
+                        |offset| offset <= self.len_ordered,
+                    )
+            }
+
+            /// The value at the fraction `numerator / 2^log2_denominator` of the range, which is at most 1
+            #[coverage(off)]
+            fn interpolate(&self, numerator: u64, log2_denominator: u32) -> $name {
+                let width = self.end - self.start;
+                if width.is_finite() {
+                    let fraction = numerator as $name / (log2_denominator as $name).exp2();
+                    (self.start + width * fraction).clamp(self.start, self.end)
+                } else {
+                    let offset = (self.len_ordered as u128 * numerator as u128) >> log2_denominator;
+                    $from_ordered(self.start_ordered + offset as $bits)
+                }
+            }
+
+            /// The `step`-th value produced by `ordered_arbitrary`
+            #[coverage(off)]
+            fn nth_value(&self, step: u64) -> Option<$name> {
+                if (self.len_ordered as u64) < MAX_ENUMERATED_LEN {
+                    if step > self.len_ordered as u64 {
+                        return None;
+                    }
+                    let offset = $name_binary_arbitrary_function(0, self.len_ordered, step);
+                    return Some($from_ordered(self.start_ordered + offset));
+                }
+                match step {
+                    0 => Some(self.start),
+                    1 => Some(self.end),
+                    _ => {
+                        // the midpoints of the intervals between the values produced so far:
+                        // 1/2, then 1/4 and 3/4, then 1/8, 3/8, 5/8, 7/8, etc.
+                        let t = step - 1;
+                        let level = u64::BITS - t.leading_zeros();
+                        if level > <$name>::MANTISSA_DIGITS {
+                            return None;
+                        }
+                        let i = t - (1 << (level - 1));
+                        Some(self.interpolate(2 * i + 1, level))
+                    }
+                }
+            }
+
+            /// The value nudged by a number of ULPs, if it is still within the range
+            #[coverage(off)]
+            fn nudge(&self, value: $name, nudge: u64) -> Option<$name> {
+                let ulps = (nudge / 2 + 1) as $bits;
+                let offset = $to_ordered(value).checked_sub(self.start_ordered)?;
+                let offset = if nudge % 2 == 0 {
+                    offset.checked_add(ulps)?
+                } else {
+                    offset.checked_sub(ulps)?
+                };
+                if offset > self.len_ordered {
+                    return None;
+                }
+                Some($from_ordered(self.start_ordered + offset))
+            }
+
+            #[coverage(off)]
+            fn random_value(&self) -> $name {
+                let width = self.end - self.start;
+                if width.is_finite() {
+                    (self.start + width * self.rng.$name()).clamp(self.start, self.end)
+                } else {
+                    $from_ordered(self.start_ordered + self.rng.$bits(0..=self.len_ordered))
+                }
+            }
+        }
+
+        impl Mutator<$name> for $name_mutator {
+            #[doc(hidden)]
+            type Cache = ();
+            #[doc(hidden)]
+            type MutationStep = u64;
+            #[doc(hidden)]
+            type ArbitraryStep = u64;
+            #[doc(hidden)]
+            type UnmutateToken = $name; // old value
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn initialize(&self) {}
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+                0
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn is_valid(&self, value: &$name) -> bool {
+                self.contains(*value)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn validate_value(&self, value: &$name) -> Option<Self::Cache> {
+                if self.contains(*value) {
+                    Some(())
+                } else {
+                    None
+                }
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_mutation_step(&self, _value: &$name, _cache: &Self::Cache) -> Self::MutationStep {
+                0
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn global_search_space_complexity(&self) -> f64 {
+                super::size_to_cplxity(self.len_ordered as usize)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn max_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn min_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn complexity(&self, _value: &$name, _cache: &Self::Cache) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<($name, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let value = self.nth_value(*step)?;
+                *step += 1;
+                Some((value, <$bits>::BITS as f64))
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_arbitrary(&self, _max_cplx: f64) -> ($name, f64) {
+                (self.random_value(), <$bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                step: &mut Self::MutationStep,
+                _subvalue_provider: &dyn crate::SubValueProvider,
+                max_cplx: f64,
+            ) -> Option<(Self::UnmutateToken, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let new_value = loop {
+                    if *step < NBR_NUDGES {
+                        let nudged = self.nudge(*value, *step);
+                        *step += 1;
+                        if let Some(nudged) = nudged {
+                            break nudged;
+                        }
+                    } else {
+                        let new_value = self.nth_value(*step - NBR_NUDGES)?;
+                        *step += 1;
+                        if new_value.to_bits() != value.to_bits() {
+                            break new_value;
+                        }
+                    }
+                };
+                Some((std::mem::replace(value, new_value), <$bits>::BITS as f64))
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_mutate(
+                &self,
+                value: &mut $name,
+                _cache: &mut Self::Cache,
+                _max_cplx: f64,
+            ) -> (Self::UnmutateToken, f64) {
+                let new_value = if self.rng.bool() {
+                    self.nudge(*value, self.rng.u64(..NBR_NUDGES)).unwrap_or_else(
+                        #[coverage(off)]
+                        || self.random_value(),
+                    )
+                } else {
+                    self.random_value()
+                };
+                (std::mem::replace(value, new_value), <$bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn unmutate(&self, value: &mut $name, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+                *value = t;
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn visit_subvalues<'a>(
+                &self,
+                _value: &'a $name,
+                _cache: &'a Self::Cache,
+                _visit: &mut dyn FnMut(&'a dyn Any, f64),
+            ) {
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn rng_state(&self) -> Option<Vec<u8>> {
+                Some(crate::mutators::rng_state(&self.rng))
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_rng_state(&self, state: &[u8]) {
+                crate::mutators::set_rng_state(&self.rng, state);
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn distance(&self, a: &$name, b: &$name) -> Option<f64> {
+                Some(if a.to_bits() == b.to_bits() { 0.0 } else { 1.0 })
+            }
+        }
+    };
+}
+
+impl_float_mutator_constrained!(
+    f32,
+    u32,
+    F32WithinRangeMutator,
+    f32_to_ordered,
+    f32_from_ordered,
+    binary_search_arbitrary_u32
+);
+impl_float_mutator_constrained!(
+    f64,
+    u64,
+    F64WithinRangeMutator,
+    f64_to_ordered,
+    f64_from_ordered,
+    binary_search_arbitrary_u64
+);
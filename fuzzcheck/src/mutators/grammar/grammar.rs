@@ -3,6 +3,7 @@ use std::rc::{Rc, Weak};
 
 #[cfg(feature = "regex_grammar")]
 use crate::mutators::grammar::regex::grammar_from_regex;
+use crate::mutators::vector::ComplexityDistribution;
 
 #[derive(Clone, Debug)]
 /// A grammar which can be used for fuzzing.
@@ -13,6 +14,9 @@ pub enum Grammar {
     Alternation(Vec<Rc<Grammar>>),
     Concatenation(Vec<Rc<Grammar>>),
     Repetition(Rc<Grammar>, Range<usize>),
+    /// A [`Repetition`](Grammar::Repetition) that shares the complexity of newly generated
+    /// values between the repeats according to the given distribution
+    RepetitionWithDistribution(Rc<Grammar>, Range<usize>, ComplexityDistribution),
     Recurse(Weak<Grammar>),
     Recursive(Rc<Grammar>),
 }
@@ -96,7 +100,44 @@ pub fn concatenation(gs: impl IntoIterator<Item = Rc<Grammar>>) -> Rc<Grammar> {
 
 #[coverage(off)]
 /// Repeats the provided grammar some number of times in the given range.
+///
+/// When generating a new value, the complexity is shared evenly between the repeats,
+/// so that they have similar sizes. Use [`repetition_with_distribution`] to change that.
 pub fn repetition<R>(gs: Rc<Grammar>, range: R) -> Rc<Grammar>
+where
+    R: RangeBounds<usize>,
+{
+    Rc::new(Grammar::Repetition(gs, repetition_range(range)))
+}
+
+#[coverage(off)]
+/// Repeats the provided grammar some number of times in the given range, sharing the
+/// complexity of newly generated values between the repeats according to `distribution`.
+///
+/// For example, the following grammar generates lists of numbers where one number is
+/// usually much longer than the others:
+/// ```
+/// # use fuzzcheck::mutators::grammar::{literal_range, repetition, repetition_with_distribution};
+/// use fuzzcheck::mutators::vector::ComplexityDistribution;
+///
+/// let number = repetition(literal_range('0'..='9'), 1..);
+/// let list = repetition_with_distribution(number, 1..10, ComplexityDistribution::OneLarge(0.8));
+/// ```
+///
+/// The distribution does not change which values match the grammar.
+pub fn repetition_with_distribution<R>(gs: Rc<Grammar>, range: R, distribution: ComplexityDistribution) -> Rc<Grammar>
+where
+    R: RangeBounds<usize>,
+{
+    Rc::new(Grammar::RepetitionWithDistribution(
+        gs,
+        repetition_range(range),
+        distribution,
+    ))
+}
+
+#[coverage(off)]
+fn repetition_range<R>(range: R) -> Range<usize>
 where
     R: RangeBounds<usize>,
 {
@@ -110,7 +151,7 @@ where
         std::ops::Bound::Excluded(x) => *x,
         std::ops::Bound::Unbounded => usize::MAX,
     };
-    Rc::new(Grammar::Repetition(gs, start..end))
+    start..end
 }
 
 #[coverage(off)]
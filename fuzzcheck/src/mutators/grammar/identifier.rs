@@ -0,0 +1,174 @@
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use ahash::AHashSet;
+
+use super::grammar::{concatenation, literal_ranges, repetition, Grammar};
+use super::{grammar_based_ast_mutator, write_string, ASTMutator, AST};
+use crate::mutators::filter::FilterMutator;
+use crate::mutators::map::AndMapMutator;
+use crate::mutators::MutatorWrapper;
+
+/// The strict and reserved keywords of Rust, as well as `_`, which is not a valid identifier
+pub const RUST_KEYWORDS: &[&str] = &[
+    "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop", "macro", "match",
+    "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "self", "Self", "static", "struct", "super",
+    "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The keywords of C11
+pub const C_KEYWORDS: &[&str] = &[
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Alignas",
+    "_Alignof",
+    "_Atomic",
+    "_Bool",
+    "_Complex",
+    "_Generic",
+    "_Imaginary",
+    "_Noreturn",
+    "_Static_assert",
+    "_Thread_local",
+];
+
+type IdentifierFilter = Box<dyn Fn(&(String, AST)) -> bool>;
+
+/// A mutator for strings that are valid identifiers in languages such as Rust or C.
+///
+/// An identifier starts with a letter or an underscore, followed by any number of
+/// letters, digits, and underscores. When `unicode` is `true`, “letter” and “digit”
+/// are understood as the `XID_Start` and `XID_Continue` Unicode properties. Otherwise,
+/// only ASCII characters are used.
+///
+/// Identifiers that are part of the keyword blocklist are never generated, and
+/// `validate_value` rejects them.
+///
+/// Like other grammar-based mutators, it generates values of type `(String, AST)`.
+/// ```
+/// use fuzzcheck::mutators::grammar::{IdentifierMutator, RUST_KEYWORDS};
+///
+/// let m = IdentifierMutator::new(RUST_KEYWORDS.iter().copied(), false);
+/// ```
+pub struct IdentifierMutator {
+    mutator: FilterMutator<AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>, IdentifierFilter>,
+}
+
+impl IdentifierMutator {
+    #[coverage(off)]
+    pub fn new<K>(keywords: impl IntoIterator<Item = K>, unicode: bool) -> Self
+    where
+        K: Into<String>,
+    {
+        let keywords = keywords.into_iter().map(Into::into).collect::<AHashSet<String>>();
+        let mutator = AndMapMutator::new(
+            grammar_based_ast_mutator(identifier_grammar(unicode)),
+            write_string as fn(&AST, &mut String),
+            String::with_capacity(16),
+        );
+        let filter: IdentifierFilter = Box::new(
+            #[coverage(off)]
+            move |(string, _)| is_identifier(string, unicode) && !keywords.contains(string),
+        );
+        Self {
+            mutator: FilterMutator::new(mutator, filter),
+        }
+    }
+}
+
+impl MutatorWrapper for IdentifierMutator {
+    type Wrapped = FilterMutator<AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>, IdentifierFilter>;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+/// Returns a grammar matching identifiers, without taking keywords into account
+#[coverage(off)]
+pub fn identifier_grammar(unicode: bool) -> Rc<Grammar> {
+    let (start, rest) = if unicode {
+        (
+            char_ranges(
+                #[coverage(off)]
+                |c| c == '_' || unicode_ident::is_xid_start(c),
+            ),
+            char_ranges(unicode_ident::is_xid_continue),
+        )
+    } else {
+        (
+            vec!['A'..='Z', '_'..='_', 'a'..='z'],
+            vec!['0'..='9', 'A'..='Z', '_'..='_', 'a'..='z'],
+        )
+    };
+    concatenation([literal_ranges(start), repetition(literal_ranges(rest), 0..)])
+}
+
+#[coverage(off)]
+fn is_identifier(string: &str, unicode: bool) -> bool {
+    let mut chars = string.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    if unicode {
+        (first == '_' || unicode_ident::is_xid_start(first)) && chars.all(unicode_ident::is_xid_continue)
+    } else {
+        (first == '_' || first.is_ascii_alphabetic())
+            && chars.all(
+                #[coverage(off)]
+                |c| c == '_' || c.is_ascii_alphanumeric(),
+            )
+    }
+}
+
+/// The contiguous ranges of characters for which `predicate` returns `true`
+#[coverage(off)]
+fn char_ranges(predicate: impl Fn(char) -> bool) -> Vec<RangeInclusive<char>> {
+    let mut ranges: Vec<RangeInclusive<char>> = vec![];
+    for c in (char::MIN..=char::MAX).filter(
+        #[coverage(off)]
+        |&c| predicate(c),
+    ) {
+        match ranges.last_mut() {
+            Some(range) if *range.end() as u32 + 1 == c as u32 => {
+                *range = *range.start()..=c;
+            }
+            _ => ranges.push(c..=c),
+        }
+    }
+    ranges
+}
@@ -3,7 +3,8 @@
 //! This module provides a grammar-based `impl Mutator<AST>` which generates an abstract syntax
 //! tree satisfying a grammar, created through [`grammar_based_ast_mutator`]. The resulting mutator can be
 //! transformed into a `Mutator<(AST, String)>`, where the second element of the tuple is the string corresponding
-//! to the abstract syntax tree, by calling [`.with_string()`](ASTMutator::with_string).
+//! to the abstract syntax tree, by calling [`.with_string()`](ASTMutator::with_string). To generate values of
+//! type `String` only, use [`grammar_based_string_mutator`] instead.
 //!
 //! To specify a grammar, you should use the following functions:
 #![cfg_attr(
@@ -15,8 +16,18 @@
 //! * [`literal_ranges`] for a grammar matching a single character within any of multiple ranges
 //! * [`alternation`] for a grammar matching any of a list of grammar rules
 //! * [`concatenation`] matching multiple grammar rules one after the other
-//! * [`repetition`] matching a grammar rule multiple times
+//! * [`repetition`] matching a grammar rule multiple times, and [`repetition_with_distribution`] to control the size of each repeat
 //! * [`recursive`] and [`recurse`] to create recursive grammar rules
+//! * [`matched_tags`] for XML/HTML-like elements whose opening and closing tags match
+//!
+#![cfg_attr(
+    feature = "identifier_grammar",
+    doc = "For the common case of generating identifiers of a programming language, [`IdentifierMutator`] uses [`identifier_grammar`] and rejects the values belonging to a list of keywords **(only supported on crate feature `identifier_grammar`)**."
+)]
+//! [`UrlMutator`] uses [`url_grammar`] to generate well-formed URLs, and [`XmlLikeMutator`] uses
+//! [`matched_tags`] to generate well-nested XML/HTML-like markup. [`NumericStringMutator`] generates
+//! numbers that are hard to parse, such as `00001e-400`, and optionally some invalid ones.
+//! [`RegexPatternMutator`] does the same for regular expression patterns such as `(a+)*b{0,1000}`.
 #![cfg_attr(
     feature = "regex_grammar",
     doc = r###"
@@ -62,9 +73,18 @@ let rule = recursive(|rule| {
 #![allow(clippy::module_inception)]
 #![allow(clippy::nonstandard_macro_braces)]
 
+use std::rc::Rc;
+
 mod ast;
 mod grammar;
+#[cfg(feature = "identifier_grammar")]
+mod identifier;
 mod mutators;
+mod numeric;
+mod regex_pattern;
+mod string;
+mod url;
+mod xml;
 
 #[cfg(feature = "regex_grammar")]
 mod regex;
@@ -78,8 +98,47 @@ pub use grammar::regex;
 #[doc(inline)]
 pub use grammar::Grammar;
 #[doc(inline)]
-pub use grammar::{alternation, concatenation, literal, literal_range, literal_ranges, recurse, recursive, repetition};
+pub use grammar::{
+    alternation, concatenation, literal, literal_range, literal_ranges, recurse, recursive, repetition,
+    repetition_with_distribution,
+};
+#[cfg(feature = "identifier_grammar")]
+#[doc(inline)]
+#[doc(cfg(feature = "identifier_grammar"))]
+pub use identifier::{identifier_grammar, IdentifierMutator, C_KEYWORDS, RUST_KEYWORDS};
 #[doc(inline)]
 pub use mutators::grammar_based_ast_mutator;
 #[doc(inline)]
 pub use mutators::ASTMutator;
+#[doc(inline)]
+pub use numeric::{numeric_string_grammar, NumericStringMutator};
+#[doc(inline)]
+pub use regex_pattern::{regex_pattern_grammar, RegexPatternMutator};
+#[doc(inline)]
+pub use string::{grammar_based_string_mutator, GrammarStringMutator};
+#[doc(inline)]
+pub use url::{url_grammar, UrlMutator};
+#[doc(inline)]
+pub use xml::{matched_tags, XmlLikeMutator};
+
+/// A grammar matching exactly the given string
+#[coverage(off)]
+fn string_literal(s: &str) -> Rc<Grammar> {
+    concatenation(s.chars().map(literal))
+}
+
+/// A grammar matching any of the characters of `chars`
+#[coverage(off)]
+fn any_char_of(chars: &str) -> Rc<Grammar> {
+    alternation(chars.chars().map(literal))
+}
+
+/// Writes the string generated by the abstract syntax tree into `string`, replacing its content.
+///
+/// It is the function used by the grammar-based mutators of this module to map their
+/// abstract syntax trees to strings.
+#[coverage(off)]
+fn write_string(ast: &AST, string: &mut String) {
+    string.clear();
+    ast.generate_string_in(string);
+}
@@ -2,6 +2,7 @@ extern crate self as fuzzcheck;
 
 use std::any::Any;
 use std::collections::HashMap;
+use std::ops::Range;
 use std::rc::{Rc, Weak};
 
 use fuzzcheck_mutators_derive::make_single_variant_mutator;
@@ -15,7 +16,7 @@ use crate::mutators::grammar::ast::AST;
 use crate::mutators::map::AndMapMutator;
 use crate::mutators::recursive::{RecurToMutator, RecursiveMutator};
 use crate::mutators::tuples::Tuple1Mutator;
-use crate::mutators::vector::VecMutator;
+use crate::mutators::vector::{ComplexityDistribution, VecMutator};
 use crate::Mutator;
 
 // NOTE: the complexity of the vectors in the AST is the complexity of their
@@ -61,15 +62,38 @@ type InnerASTMutator = Either3<
     RecursiveMutator<ASTMutator>,
 >;
 
+/// The maximum number of ordered mutations that a [minimizing](ASTMutator::minimizing) mutator
+/// tries on a value while looking for one that reduces its complexity.
+const MAX_SHRINK_ATTEMPTS: usize = 100;
+
 /// A mutator created by [`grammar_based_ast_mutator`](crate::mutators::grammar::grammar_based_ast_mutator)
 ///
 /// It only generates syntax trees whose [`to_string()`](crate::mutators::grammar::AST::to_string)
 /// value matches the given grammar.
 pub struct ASTMutator {
     inner: Box<InnerASTMutator>,
+    minimizing: bool,
 }
 
 impl ASTMutator {
+    /// Make `ordered_mutate` try the mutations that reduce the complexity of the syntax tree first,
+    /// such as shortening repetitions or choosing simpler alternatives, before any other mutation.
+    ///
+    /// This makes the generated strings shrink faster when minimizing a failing test case.
+    /// The values that can be generated are unchanged.
+    /// ```
+    /// use fuzzcheck::mutators::grammar::{grammar_based_ast_mutator, literal, repetition};
+    ///
+    /// let mutator = grammar_based_ast_mutator(repetition(literal('a'), 1..10))
+    ///     .minimizing()
+    ///     .with_string();
+    /// ```
+    #[coverage(off)]
+    pub fn minimizing(mut self) -> Self {
+        self.minimizing = true;
+        self
+    }
+
     #[coverage(off)]
     pub fn with_string(self) -> impl Mutator<(String, AST)> {
         AndMapMutator::new(
@@ -96,11 +120,17 @@ impl ASTMutatorCache {
 #[derive(Clone)]
 pub struct ASTMutatorMutationStep {
     inner: Box<<InnerASTMutator as Mutator<AST>>::MutationStep>,
+    /// The step used to look for mutations reducing the complexity of the value,
+    /// along with the number of remaining attempts
+    shrink: Option<(Box<<InnerASTMutator as Mutator<AST>>::MutationStep>, usize)>,
 }
 impl ASTMutatorMutationStep {
     #[coverage(off)]
     fn new(inner: <InnerASTMutator as Mutator<AST>>::MutationStep) -> Self {
-        Self { inner: Box::new(inner) }
+        Self {
+            inner: Box::new(inner),
+            shrink: None,
+        }
     }
 }
 #[derive(Clone)]
@@ -157,7 +187,12 @@ impl Mutator<AST> for ASTMutator {
     #[doc(hidden)]
     #[coverage(off)]
     fn default_mutation_step(&self, value: &AST, cache: &Self::Cache) -> Self::MutationStep {
-        Self::MutationStep::new(self.inner.default_mutation_step(value, &cache.inner))
+        let mut step = Self::MutationStep::new(self.inner.default_mutation_step(value, &cache.inner));
+        if self.minimizing {
+            let shrink_step = self.inner.default_mutation_step(value, &cache.inner);
+            step.shrink = Some((Box::new(shrink_step), MAX_SHRINK_ATTEMPTS));
+        }
+        step
     }
 
     #[doc(hidden)]
@@ -206,6 +241,24 @@ impl Mutator<AST> for ASTMutator {
         subvalue_provider: &dyn crate::SubValueProvider,
         max_cplx: f64,
     ) -> Option<(Self::UnmutateToken, f64)> {
+        if let Some((shrink_step, attempts_left)) = &mut step.shrink {
+            // first look for mutations reducing the complexity of the value
+            let cplx = self.inner.complexity(value, &cache.inner);
+            while *attempts_left > 0 {
+                *attempts_left -= 1;
+                match self
+                    .inner
+                    .ordered_mutate(value, &mut cache.inner, shrink_step, subvalue_provider, cplx)
+                {
+                    Some((token, new_cplx)) if new_cplx < cplx => {
+                        return Some((Self::UnmutateToken::new(token), new_cplx));
+                    }
+                    Some((token, _)) => self.inner.unmutate(value, &mut cache.inner, token),
+                    None => break,
+                }
+            }
+            step.shrink = None;
+        }
         let (token, cplx) =
             self.inner
                 .ordered_mutate(value, &mut cache.inner, &mut step.inner, subvalue_provider, max_cplx)?;
@@ -242,6 +295,7 @@ impl ASTMutator {
     fn token(m: CharacterMutator) -> Self {
         Self {
             inner: Box::new(Either3::B(ASTSingleVariant::Token(Tuple1Mutator::new(m)))),
+            minimizing: false,
         }
     }
     #[coverage(off)]
@@ -250,6 +304,7 @@ impl ASTMutator {
             inner: Box::new(Either3::B(ASTSingleVariant::Sequence(Tuple1Mutator::new(Either3::B(
                 m,
             ))))),
+            minimizing: false,
         }
     }
     #[coverage(off)]
@@ -258,12 +313,14 @@ impl ASTMutator {
             inner: Box::new(Either3::B(ASTSingleVariant::Sequence(Tuple1Mutator::new(Either3::C(
                 m,
             ))))),
+            minimizing: false,
         }
     }
     #[coverage(off)]
     fn alternation(m: AlternationMutator<AST, ASTMutator>) -> Self {
         Self {
             inner: Box::new(Either3::A(m)),
+            minimizing: false,
         }
     }
     #[coverage(off)]
@@ -272,12 +329,14 @@ impl ASTMutator {
             inner: Box::new(Either3::B(ASTSingleVariant::Sequence(Tuple1Mutator::new(Either3::A(
                 FixedLenVecMutator::new_without_inherent_complexity(vec![m]),
             ))))),
+            minimizing: false,
         }
     }
     #[coverage(off)]
     fn recursive(m: impl FnMut(&Weak<Self>) -> Self) -> Self {
         Self {
             inner: Box::new(Either3::C(RecursiveMutator::new(m))),
+            minimizing: false,
         }
     }
 
@@ -311,10 +370,10 @@ impl ASTMutator {
                 }
                 Self::concatenation(FixedLenVecMutator::new_without_inherent_complexity(ms))
             }
-            Grammar::Repetition(g, range) => Self::repetition(VecMutator::new_without_inherent_complexity(
-                Self::from_grammar_rec(g.clone(), others),
-                range.start..=range.end - 1,
-            )),
+            Grammar::Repetition(g, range) => Self::from_repetition(g, range, ComplexityDistribution::Even, others),
+            Grammar::RepetitionWithDistribution(g, range, distribution) => {
+                Self::from_repetition(g, range, *distribution, others)
+            }
             Grammar::Recurse(g) => {
                 if let Some(m) = others.get(&g.as_ptr()) {
                     Self::recur(RecurToMutator::from(m))
@@ -332,4 +391,20 @@ impl ASTMutator {
             ),
         }
     }
+
+    #[coverage(off)]
+    fn from_repetition(
+        grammar: &Rc<Grammar>,
+        range: &Range<usize>,
+        distribution: ComplexityDistribution,
+        others: &mut HashMap<*const Grammar, Weak<ASTMutator>>,
+    ) -> Self {
+        Self::repetition(
+            VecMutator::new_without_inherent_complexity(
+                Self::from_grammar_rec(grammar.clone(), others),
+                range.start..=range.end - 1,
+            )
+            .with_complexity_distribution(distribution),
+        )
+    }
 }
@@ -0,0 +1,120 @@
+use std::rc::Rc;
+
+use super::grammar::{alternation, concatenation, literal, literal_ranges, repetition, Grammar};
+use super::{any_char_of, grammar_based_ast_mutator, string_literal, write_string, ASTMutator, AST};
+use crate::mutators::map::AndMapMutator;
+use crate::mutators::MutatorWrapper;
+
+/// A mutator for strings representing numbers, biased towards the shapes that are
+/// most likely to trip up a number parser.
+///
+/// The generated numbers often have many leading zeros, many digits, a `+` or `-` sign,
+/// a missing integer or fractional part (`.5`, `5.`), huge or tiny exponents (`1e1000`,
+/// `0.000…01`), or are one of the special values `inf`, `infinity`, and `nan`. When
+/// `allow_invalid` is `false`, every generated string is accepted by `str::parse::<f64>`.
+///
+/// When `allow_invalid` is `true`, the mutator also generates almost-numbers that are
+/// rejected by `str::parse::<f64>`, such as numbers surrounded by whitespace, `1e+`,
+/// `+-1`, `1.2.3`, `0x1F`, `1_000`, `.`, or the empty string, in order to test the
+/// error paths of the parser.
+///
+/// Like other grammar-based mutators, it generates values of type `(String, AST)`.
+/// ```
+/// use fuzzcheck::mutators::grammar::NumericStringMutator;
+///
+/// let m = NumericStringMutator::new(false);
+/// ```
+pub struct NumericStringMutator {
+    mutator: AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>,
+}
+
+impl NumericStringMutator {
+    #[coverage(off)]
+    pub fn new(allow_invalid: bool) -> Self {
+        Self {
+            mutator: AndMapMutator::new(
+                grammar_based_ast_mutator(numeric_string_grammar(allow_invalid)),
+                write_string as fn(&AST, &mut String),
+                String::with_capacity(32),
+            ),
+        }
+    }
+}
+
+impl MutatorWrapper for NumericStringMutator {
+    type Wrapped = AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+/// Returns a grammar matching the numbers generated by [`NumericStringMutator`]
+#[coverage(off)]
+pub fn numeric_string_grammar(allow_invalid: bool) -> Rc<Grammar> {
+    let digit = literal_ranges(vec!['0'..='9']);
+    let digits = repetition(digit.clone(), 1..);
+    let sign = repetition(any_char_of("+-"), 0..=1);
+    let zeros = repetition(literal('0'), 1..);
+
+    let mantissa = alternation([
+        digits.clone(),
+        concatenation([digits.clone(), literal('.'), repetition(digit.clone(), 0..)]),
+        concatenation([literal('.'), digits.clone()]),
+        // leading zeros
+        concatenation([zeros.clone(), digits.clone()]),
+        // very small numbers
+        concatenation([
+            string_literal("0."),
+            repetition(literal('0'), 0..=400),
+            literal_ranges(vec!['1'..='9']),
+        ]),
+        // more digits than fit in any integer or float type
+        concatenation([literal_ranges(vec!['1'..='9']), repetition(digit.clone(), 16..=400)]),
+    ]);
+    let exponent = concatenation([
+        any_char_of("eE"),
+        sign.clone(),
+        alternation([
+            digits.clone(),
+            concatenation([zeros, digits.clone()]),
+            string_literal("308"),
+            string_literal("309"),
+            string_literal("324"),
+            string_literal("1000"),
+            string_literal("4294967296"),
+            string_literal("99999999999999999999"),
+        ]),
+    ]);
+    let special = alternation(["inf", "infinity", "nan", "Infinity", "NaN", "INF"].map(string_literal));
+    let number = concatenation([
+        sign.clone(),
+        alternation([concatenation([mantissa.clone(), repetition(exponent, 0..=1)]), special]),
+    ]);
+    if !allow_invalid {
+        return number;
+    }
+
+    let whitespace = repetition(any_char_of(" \t\n\r"), 1..=3);
+    alternation([
+        number.clone(),
+        number.clone(),
+        concatenation([whitespace.clone(), number.clone()]),
+        concatenation([number.clone(), whitespace]),
+        // an exponent without digits
+        concatenation([mantissa.clone(), any_char_of("eE"), sign]),
+        // two signs
+        concatenation([any_char_of("+-"), any_char_of("+-"), mantissa.clone()]),
+        // two decimal points
+        concatenation([mantissa, literal('.'), digits.clone()]),
+        concatenation([
+            string_literal("0x"),
+            repetition(literal_ranges(vec!['0'..='9', 'A'..='F', 'a'..='f']), 1..=16),
+        ]),
+        // digit separators
+        concatenation([digits.clone(), any_char_of("_,"), digits]),
+        literal('.'),
+        repetition(literal('-'), 0..=1),
+    ])
+}
@@ -0,0 +1,229 @@
+use std::rc::Rc;
+
+use super::grammar::{alternation, concatenation, literal, literal_ranges, recurse, recursive, repetition, Grammar};
+use super::string::{grammar_based_string_mutator, GrammarStringMutator};
+use super::{any_char_of, string_literal};
+use crate::mutators::alternation::AlternationMutator;
+use crate::mutators::either::Either;
+use crate::mutators::MutatorWrapper;
+use crate::DefaultMutator;
+
+type RegexPatternAlternation =
+    AlternationMutator<String, Either<GrammarStringMutator, <String as DefaultMutator>::Mutator>>;
+
+/// A mutator for strings that are regular expression patterns, biased towards the
+/// constructs that are most likely to stress a regex engine.
+///
+/// The generated patterns contain literal and escaped characters, character classes,
+/// the `\d`, `\w`, `\s` classes and their negations, capturing and non-capturing groups,
+/// alternations, anchors (`^`, `$`, `\b`, `\B`), greedy and lazy quantifiers, and
+/// backreferences. Nested quantifiers such as `(a+)*` and large bounded repetitions
+/// such as `a{0,1000}` are generated often. When `allow_invalid` is `false`, every
+/// generated pattern is syntactically valid in the common syntax of engines such as
+/// PCRE and JavaScript. The patterns without backreferences are also accepted by the
+/// `regex` crate.
+///
+/// When `allow_invalid` is `true`, the mutator also generates almost-valid patterns,
+/// such as patterns with unbalanced parentheses, an unterminated character class,
+/// a quantifier without operand, a trailing backslash, or a reversed range, in order
+/// to test the error paths of the engine.
+///
+/// Unlike the other grammar-based mutators, it generates values of type `String`, and
+/// [`validate_value`](crate::Mutator::validate_value) accepts any string. The strings that
+/// do not match [`regex_pattern_grammar`], such as those of a corpus written by hand, are
+/// never mutated in place but replaced by newly generated patterns.
+/// ```
+/// use fuzzcheck::mutators::grammar::RegexPatternMutator;
+///
+/// // impl Mutator<String>
+/// let m = RegexPatternMutator::new(false);
+/// ```
+pub struct RegexPatternMutator {
+    mutator: RegexPatternAlternation,
+}
+
+impl RegexPatternMutator {
+    #[coverage(off)]
+    pub fn new(allow_invalid: bool) -> Self {
+        let mut mutator = AlternationMutator::new(
+            vec![
+                Either::Left(grammar_based_string_mutator(regex_pattern_grammar(allow_invalid))),
+                Either::Right(String::default_mutator()),
+            ],
+            0.0,
+        );
+        // the string mutator is only used to accept the strings that are not patterns
+        mutator.generate_only_with(vec![0]);
+        Self { mutator }
+    }
+}
+
+impl MutatorWrapper for RegexPatternMutator {
+    type Wrapped = RegexPatternAlternation;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+/// Returns a grammar matching the patterns generated by [`RegexPatternMutator`]
+#[coverage(off)]
+pub fn regex_pattern_grammar(allow_invalid: bool) -> Rc<Grammar> {
+    let digit = literal_ranges(vec!['0'..='9']);
+    let count = alternation([
+        literal('0'),
+        concatenation([literal_ranges(vec!['1'..='9']), repetition(digit, 0..=2)]),
+    ]);
+    let quantifier = concatenation([
+        alternation([
+            any_char_of("*+?"),
+            any_char_of("*+?"),
+            concatenation([literal('{'), count.clone(), literal('}')]),
+            concatenation([literal('{'), count.clone(), string_literal(",}")]),
+            concatenation([string_literal("{0,"), count, literal('}')]),
+            // large bounded repetitions
+            alternation(["{1000}", "{0,1000}", "{1,65535}", "{256,}"].map(string_literal)),
+        ]),
+        // lazy quantifier
+        repetition(literal('?'), 0..=1),
+    ]);
+    // a few characters are used much more often than the others, such that the
+    // backreferences and alternations have a chance to match the same text
+    let character = alternation([
+        literal_ranges(vec!['a'..='c']),
+        literal_ranges(vec!['a'..='z', 'A'..='Z', '0'..='9']),
+        any_char_of(" _-"),
+        concatenation([literal('\\'), any_char_of(".*+?()[]{}|^$\\")]),
+    ]);
+    let perl_class = concatenation([literal('\\'), any_char_of("dwsDWS")]);
+    let class_item = alternation([
+        literal_ranges(vec!['a'..='c']),
+        alternation(["a-z", "A-Z", "0-9"].map(string_literal)),
+        concatenation([literal('\\'), any_char_of("[]\\^.")]),
+        perl_class.clone(),
+    ]);
+    let class = concatenation([
+        literal('['),
+        repetition(literal('^'), 0..=1),
+        repetition(class_item.clone(), 1..=4),
+        literal(']'),
+    ]);
+    let anchor = alternation(["^", "$", "\\b", "\\B"].map(string_literal));
+
+    let quantified_atom = concatenation([
+        alternation([character.clone(), character, literal('.'), perl_class.clone(), class]),
+        repetition(quantifier.clone(), 0..=1),
+    ]);
+
+    // each kind of group is a single alternative, such that parsing a pattern with
+    // nested groups, in `validate_value`, does not take exponential time
+    let pattern = recursive(
+        #[coverage(off)]
+        |pattern| {
+            // quantified groups are nested quantifiers when the group contains a quantifier,
+            // which cause catastrophic backtracking in some engines
+            let capturing_group = concatenation([
+                literal('('),
+                recurse(pattern),
+                literal(')'),
+                repetition(
+                    alternation([
+                        quantifier.clone(),
+                        // a backreference to the first group, which always precedes it; it is
+                        // wrapped in a group so that a following digit is not part of the reference
+                        concatenation([string_literal("(?:\\1)"), repetition(quantifier.clone(), 0..=1)]),
+                    ]),
+                    0..=1,
+                ),
+            ]);
+            let non_capturing_group = concatenation([
+                alternation([string_literal("(?:"), string_literal("(?i:")]),
+                recurse(pattern),
+                literal(')'),
+                repetition(quantifier.clone(), 0..=1),
+            ]);
+            let piece = alternation([
+                quantified_atom.clone(),
+                quantified_atom.clone(),
+                quantified_atom.clone(),
+                capturing_group,
+                non_capturing_group,
+                anchor.clone(),
+            ]);
+            let branch = repetition(piece, 1..);
+            concatenation([branch.clone(), repetition(concatenation([literal('|'), branch]), 0..=3)])
+        },
+    );
+    if !allow_invalid {
+        return pattern;
+    }
+
+    let invalid = alternation([
+        // unbalanced parentheses
+        concatenation([literal('('), pattern.clone()]),
+        concatenation([pattern.clone(), literal(')')]),
+        // an unterminated character class
+        concatenation([pattern.clone(), literal('['), repetition(class_item, 0..=3)]),
+        // a quantifier without operand
+        concatenation([quantifier.clone(), pattern.clone()]),
+        concatenation([pattern.clone(), literal('|'), quantifier]),
+        // a trailing backslash
+        concatenation([pattern.clone(), literal('\\')]),
+        // reversed ranges
+        concatenation([
+            pattern.clone(),
+            literal_ranges(vec!['a'..='c']),
+            string_literal("{9,1}"),
+        ]),
+        string_literal("[z-a]"),
+    ]);
+    // a quarter of the generated patterns are invalid
+    alternation([pattern.clone(), pattern.clone(), pattern, invalid])
+}
+
+#[cfg(all(test, feature = "regex_grammar"))]
+mod tests {
+    use super::RegexPatternMutator;
+    use crate::Mutator;
+
+    #[coverage(off)]
+    fn is_valid_regex(pattern: &str) -> bool {
+        regex_syntax::Parser::new().parse(pattern).is_ok()
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_regex_pattern_mutator_validity() {
+        let m = RegexPatternMutator::new(false);
+        m.initialize();
+        for _ in 0..1000 {
+            let (pattern, _) = m.random_arbitrary(100.0);
+            // the regex crate does not support backreferences
+            if !pattern.contains("\\1") {
+                assert!(is_valid_regex(&pattern), "{pattern}");
+            }
+        }
+
+        let m = RegexPatternMutator::new(true);
+        m.initialize();
+        let mut nbr_invalid = 0;
+        for _ in 0..1000 {
+            let (pattern, _) = m.random_arbitrary(100.0);
+            if !pattern.contains("\\1") && !is_valid_regex(&pattern) {
+                nbr_invalid += 1;
+            }
+        }
+        assert!(nbr_invalid > 100, "{nbr_invalid}");
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_regex_pattern_mutator_accepts_any_string() {
+        let m = RegexPatternMutator::new(false);
+        m.initialize();
+        for string in ["a(b|c)*", "", "(", "\u{0}\u{ffff}"] {
+            assert!(m.validate_value(&string.to_owned()).is_some(), "{string}");
+        }
+    }
+}
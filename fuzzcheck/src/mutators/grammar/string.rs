@@ -0,0 +1,219 @@
+use std::rc::Rc;
+
+use super::grammar::Grammar;
+use super::{grammar_based_ast_mutator, ASTMutator, AST};
+use crate::mutators::map::MapMutator;
+use crate::mutators::MutatorWrapper;
+
+type GrammarStringMap = MapMutator<
+    AST,
+    String,
+    ASTMutator,
+    Box<dyn Fn(&String) -> Option<AST>>,
+    fn(&AST) -> String,
+    fn(&String, f64) -> f64,
+>;
+
+/// A mutator for strings matching a grammar, created by [`grammar_based_string_mutator`].
+///
+/// Unlike the mutator returned by [`ASTMutator::with_string`], it generates values of type
+/// `String` instead of `(String, AST)`. The syntax tree of each string is recovered by
+/// parsing it with the grammar, which means that [`validate_value`](crate::Mutator::validate_value)
+/// only accepts the strings matching the grammar. However, parsing a string is slower than
+/// reading a syntax tree, so this mutator is best suited to short strings and simple grammars.
+pub struct GrammarStringMutator {
+    mutator: GrammarStringMap,
+}
+
+impl MutatorWrapper for GrammarStringMutator {
+    type Wrapped = GrammarStringMap;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+/// Create a mutator for strings matching the given grammar.
+#[cfg_attr(
+    feature = "regex_grammar",
+    doc = r###"
+```
+use fuzzcheck::mutators::grammar::{grammar_based_string_mutator, regex};
+use fuzzcheck::Mutator;
+
+// impl Mutator<String>
+let m = grammar_based_string_mutator(regex("[a-z]{1,8}@[a-z]{1,8}\\.com"));
+assert!(m.validate_value(&"abc@def.com".to_owned()).is_some());
+assert!(m.validate_value(&"abc@def.org".to_owned()).is_none());
+```
+"###
+)]
+#[coverage(off)]
+pub fn grammar_based_string_mutator(grammar: Rc<Grammar>) -> GrammarStringMutator {
+    let parse_grammar = grammar.clone();
+    GrammarStringMutator {
+        mutator: MapMutator::new(
+            grammar_based_ast_mutator(grammar),
+            Box::new(
+                #[coverage(off)]
+                move |string: &String| parse_string(&parse_grammar, string),
+            ),
+            AST::to_string as fn(&AST) -> String,
+            grammar_string_cplx,
+        ),
+    }
+}
+
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn grammar_string_cplx(_value: &String, cplx: f64) -> f64 {
+    cplx
+}
+
+/// Returns the syntax tree, with the shape expected by [`ASTMutator`], of a string matching the grammar
+#[coverage(off)]
+pub(crate) fn parse_string(grammar: &Rc<Grammar>, string: &str) -> Option<AST> {
+    let chars = string.chars().collect::<Vec<_>>();
+    let mut parser = Parser {
+        chars: &chars,
+        active_recursions: vec![],
+    };
+    parser
+        .parse(grammar, 0)
+        .into_iter()
+        .find(
+            #[coverage(off)]
+            |(_, end)| *end == chars.len(),
+        )
+        .map(
+            #[coverage(off)]
+            |(ast, _)| ast,
+        )
+}
+
+struct Parser<'a> {
+    chars: &'a [char],
+    /// the recursive rules being parsed and their start position, to avoid looping forever on left-recursive rules
+    active_recursions: Vec<(*const Grammar, usize)>,
+}
+
+/// Adds the parse result to the list, unless another result already ends at the same position
+#[coverage(off)]
+fn push_result<T>(results: &mut Vec<(T, usize)>, value: T, end: usize) {
+    if !results.iter().any(
+        #[coverage(off)]
+        |(_, e)| *e == end,
+    ) {
+        results.push((value, end));
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Returns the syntax trees of the prefixes of `self.chars[pos..]` matching the grammar,
+    /// along with the position where each of them ends. There is at most one tree per end position.
+    #[coverage(off)]
+    fn parse(&mut self, grammar: &Rc<Grammar>, pos: usize) -> Vec<(AST, usize)> {
+        match grammar.as_ref() {
+            Grammar::Literal(ranges) => match self.chars.get(pos) {
+                Some(c)
+                    if ranges.iter().any(
+                        #[coverage(off)]
+                        |r| r.contains(c),
+                    ) =>
+                {
+                    vec![(AST::Token(*c), pos + 1)]
+                }
+                _ => vec![],
+            },
+            Grammar::Alternation(gs) => {
+                let mut results = vec![];
+                for g in gs {
+                    for (ast, end) in self.parse(g, pos) {
+                        push_result(&mut results, ast, end);
+                    }
+                }
+                results
+            }
+            Grammar::Concatenation(gs) => {
+                let mut states = vec![(Vec::with_capacity(gs.len()), pos)];
+                for g in gs {
+                    let mut next_states = vec![];
+                    for (asts, p) in states {
+                        for (ast, end) in self.parse(g, p) {
+                            let mut asts = asts.clone();
+                            asts.push(ast);
+                            push_result(&mut next_states, asts, end);
+                        }
+                    }
+                    states = next_states;
+                }
+                states
+                    .into_iter()
+                    .map(
+                        #[coverage(off)]
+                        |(asts, end)| (AST::Sequence(asts), end),
+                    )
+                    .collect()
+            }
+            Grammar::Repetition(g, range) | Grammar::RepetitionWithDistribution(g, range, _) => {
+                let mut results = vec![];
+                let mut states = vec![(vec![], pos)];
+                let mut count = 0;
+                while !states.is_empty() {
+                    if count >= range.start {
+                        // a state ending at the same position as a previous result, with more
+                        // repetitions, cannot lead to new results
+                        states.retain(
+                            #[coverage(off)]
+                            |(_, end)| {
+                                !results.iter().any(
+                                    #[coverage(off)]
+                                    |(_, e)| e == end,
+                                )
+                            },
+                        );
+                        for (asts, end) in &states {
+                            push_result(&mut results, AST::Sequence(asts.clone()), *end);
+                        }
+                    }
+                    count += 1;
+                    if count >= range.end {
+                        break;
+                    }
+                    let mut next_states = vec![];
+                    for (asts, p) in states {
+                        for (ast, end) in self.parse(g, p) {
+                            let mut asts = asts.clone();
+                            asts.push(ast);
+                            push_result(&mut next_states, asts, end);
+                        }
+                    }
+                    states = next_states;
+                }
+                results
+            }
+            Grammar::Recurse(g) => {
+                let key = (g.as_ptr(), pos);
+                if self.active_recursions.contains(&key) {
+                    return vec![];
+                }
+                let Some(g) = g.upgrade() else {
+                    return vec![];
+                };
+                self.active_recursions.push(key);
+                let results = self
+                    .parse(&g, pos)
+                    .into_iter()
+                    .map(
+                        #[coverage(off)]
+                        |(ast, end)| (AST::Sequence(vec![ast]), end),
+                    )
+                    .collect();
+                self.active_recursions.pop();
+                results
+            }
+            Grammar::Recursive(g) => self.parse(g, pos),
+        }
+    }
+}
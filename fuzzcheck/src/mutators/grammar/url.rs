@@ -0,0 +1,241 @@
+use std::rc::Rc;
+
+use super::grammar::{alternation, concatenation, literal, literal_ranges, repetition, Grammar};
+use super::{any_char_of, grammar_based_ast_mutator, string_literal, write_string, ASTMutator, AST};
+use crate::mutators::filter::FilterMutator;
+use crate::mutators::map::AndMapMutator;
+use crate::mutators::MutatorWrapper;
+
+/// The characters that can appear unencoded in any component of a URL
+const SUB_DELIMS: &str = "!$&'()*+,;=";
+/// The characters, other than those of `SUB_DELIMS`, that can appear unencoded in a URL
+const UNRESERVED_PUNCTUATION: &str = "-._~";
+
+type UrlFilter = Box<dyn Fn(&(String, AST)) -> bool>;
+
+/// A mutator for strings that are well-formed URLs, as specified by RFC 3986.
+///
+/// The generated URLs are made of a scheme, an optional authority (user information,
+/// host, and port), a path, and an optional query and fragment. Characters that are not
+/// allowed in a component are always percent-encoded, and any character, including the
+/// reserved ones, may also appear percent-encoded. The path, query, and fragment can
+/// be empty, and the authority can be missing, as in `mailto:someone@example.com`.
+///
+/// When the list of schemes is empty, any syntactically valid scheme is generated.
+/// Otherwise, only the given schemes are used, and `validate_value` rejects the URLs
+/// with a different scheme.
+///
+/// Like other grammar-based mutators, it generates values of type `(String, AST)`.
+/// ```
+/// use fuzzcheck::mutators::grammar::UrlMutator;
+///
+/// let m = UrlMutator::new(["http", "https"]);
+/// ```
+pub struct UrlMutator {
+    mutator: FilterMutator<AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>, UrlFilter>,
+}
+
+impl UrlMutator {
+    #[coverage(off)]
+    pub fn new<S>(schemes: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        let schemes = schemes.into_iter().map(Into::into).collect::<Vec<String>>();
+        let mutator = AndMapMutator::new(
+            grammar_based_ast_mutator(url_grammar(&schemes)),
+            write_string as fn(&AST, &mut String),
+            String::with_capacity(64),
+        );
+        let filter: UrlFilter = Box::new(
+            #[coverage(off)]
+            move |(string, _)| is_url(string, &schemes),
+        );
+        Self {
+            mutator: FilterMutator::new(mutator, filter),
+        }
+    }
+}
+
+impl MutatorWrapper for UrlMutator {
+    type Wrapped = FilterMutator<AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>, UrlFilter>;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+/// Returns a grammar matching URLs whose scheme is one of `schemes`, or any
+/// valid scheme if `schemes` is empty.
+#[coverage(off)]
+pub fn url_grammar<S>(schemes: &[S]) -> Rc<Grammar>
+where
+    S: AsRef<str>,
+{
+    let scheme = if schemes.is_empty() {
+        concatenation([
+            literal_ranges(vec!['A'..='Z', 'a'..='z']),
+            repetition(
+                alternation([
+                    literal_ranges(vec!['0'..='9', 'A'..='Z', 'a'..='z']),
+                    any_char_of("+-."),
+                ]),
+                0..,
+            ),
+        ])
+    } else {
+        alternation(schemes.iter().map(
+            #[coverage(off)]
+            |scheme| string_literal(scheme.as_ref()),
+        ))
+    };
+    let userinfo = concatenation([repetition(component_char(":"), 0..), literal('@')]);
+    let host = repetition(component_char(""), 0..);
+    let port = concatenation([literal(':'), repetition(literal_ranges(vec!['0'..='9']), 0..=5)]);
+    let authority = concatenation([
+        string_literal("//"),
+        repetition(userinfo, 0..=1),
+        host,
+        repetition(port, 0..=1),
+    ]);
+    let segment = repetition(component_char(":@"), 0..);
+    let non_empty_segment = repetition(component_char(":@"), 1..);
+    let path_abempty = repetition(concatenation([literal('/'), segment.clone()]), 0..);
+    let path_rootless = concatenation([non_empty_segment, path_abempty.clone()]);
+    let path_absolute = concatenation([literal('/'), repetition(path_rootless.clone(), 0..=1)]);
+    let hier_part = alternation([
+        concatenation([authority, path_abempty]),
+        // an absolute, rootless, or empty path
+        repetition(alternation([path_absolute, path_rootless]), 0..=1),
+    ]);
+    let query = concatenation([literal('?'), repetition(component_char(":@/?"), 0..)]);
+    let fragment = concatenation([literal('#'), repetition(component_char(":@/?"), 0..)]);
+    concatenation([
+        scheme,
+        literal(':'),
+        hier_part,
+        repetition(query, 0..=1),
+        repetition(fragment, 0..=1),
+    ])
+}
+
+/// A grammar matching a single character of a URL component, which is either
+/// an unreserved character, a sub-delimiter, a percent-encoded byte, or one of `extra`.
+#[coverage(off)]
+fn component_char(extra: &str) -> Rc<Grammar> {
+    let hex_digit = literal_ranges(vec!['0'..='9', 'A'..='F', 'a'..='f']);
+    let percent_encoded = concatenation([literal('%'), hex_digit.clone(), hex_digit]);
+    let mut chars = UNRESERVED_PUNCTUATION.to_owned();
+    chars.push_str(SUB_DELIMS);
+    chars.push_str(extra);
+    alternation([
+        literal_ranges(vec!['0'..='9', 'A'..='Z', 'a'..='z']),
+        any_char_of(&chars),
+        percent_encoded,
+    ])
+}
+
+/// Whether `component` only contains valid percent-encoded bytes, unreserved
+/// characters, sub-delimiters, and characters of `extra`.
+#[coverage(off)]
+fn is_component(component: &str, extra: &str) -> bool {
+    let mut bytes = component.bytes();
+    while let Some(b) = bytes.next() {
+        let is_valid = match b {
+            b'%' => {
+                let is_hex_digit = #[coverage(off)]
+                |b: u8| b.is_ascii_hexdigit();
+                bytes.next().is_some_and(is_hex_digit) && bytes.next().is_some_and(is_hex_digit)
+            }
+            b if b.is_ascii_alphanumeric() => true,
+            b => {
+                let c = b as char;
+                UNRESERVED_PUNCTUATION.contains(c) || SUB_DELIMS.contains(c) || extra.contains(c)
+            }
+        };
+        if !is_valid {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether `url` is a well-formed URL whose scheme is one of `schemes`, or any
+/// valid scheme if `schemes` is empty.
+#[coverage(off)]
+fn is_url(url: &str, schemes: &[String]) -> bool {
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return false;
+    };
+    let is_valid_scheme = if schemes.is_empty() {
+        scheme.starts_with(
+            #[coverage(off)]
+            |c: char| c.is_ascii_alphabetic(),
+        ) && scheme.chars().all(
+            #[coverage(off)]
+            |c| c.is_ascii_alphanumeric() || "+-.".contains(c),
+        )
+    } else {
+        schemes.iter().any(
+            #[coverage(off)]
+            |s| s == scheme,
+        )
+    };
+    if !is_valid_scheme {
+        return false;
+    }
+    let (rest, fragment) = rest.split_once('#').unwrap_or((rest, ""));
+    let (hier_part, query) = rest.split_once('?').unwrap_or((rest, ""));
+    if !is_component(query, ":@/?") || !is_component(fragment, ":@/?") {
+        return false;
+    }
+    let path = if let Some(rest) = hier_part.strip_prefix("//") {
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => rest.split_at(idx),
+            None => (rest, ""),
+        };
+        let (userinfo, host_and_port) = authority.rsplit_once('@').unwrap_or(("", authority));
+        let (host, port) = host_and_port.split_once(':').unwrap_or((host_and_port, ""));
+        if !is_component(userinfo, ":")
+            || !is_component(host, "")
+            || port.len() > 5
+            || !port.bytes().all(
+                #[coverage(off)]
+                |b| b.is_ascii_digit(),
+            )
+        {
+            return false;
+        }
+        path
+    } else {
+        hier_part
+    };
+    is_component(path, ":@/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_url;
+
+    #[test]
+    #[coverage(off)]
+    fn test_is_url() {
+        let http = ["http".to_owned(), "https".to_owned()];
+        assert!(is_url("http://example.com", &http));
+        assert!(is_url("https://user:pw@example.com:8080/a/b%2F?q=1&r=%3F#frag", &http));
+        assert!(is_url("http:", &http));
+        assert!(is_url("http:/a", &http));
+        assert!(is_url("http://", &http));
+        assert!(!is_url("ftp://example.com", &http));
+        assert!(!is_url("http://example.com/a b", &http));
+        assert!(!is_url("http://example.com/%2", &http));
+        assert!(!is_url("http://example.com:80a", &http));
+        assert!(!is_url("http//example.com", &http));
+
+        assert!(is_url("mailto:someone@example.com", &[]));
+        assert!(is_url("a+b.c-d:", &[]));
+        assert!(!is_url("1a:", &[]));
+        assert!(!is_url(":", &[]));
+    }
+}
@@ -0,0 +1,256 @@
+use std::rc::Rc;
+
+use super::grammar::{alternation, concatenation, literal, literal_ranges, recurse, recursive, repetition, Grammar};
+use super::{grammar_based_ast_mutator, string_literal, write_string, ASTMutator, AST};
+use crate::mutators::filter::FilterMutator;
+use crate::mutators::map::AndMapMutator;
+use crate::mutators::MutatorWrapper;
+
+type XmlLikeFilter = Box<dyn Fn(&(String, AST)) -> bool>;
+
+/// A mutator for strings made of well-nested XML/HTML-like elements.
+///
+/// Each element is either a pair of matching opening and closing tags, such as
+/// `<b class="x">…</b>`, or a self-closing tag, such as `<br/>`. The names of the
+/// elements are chosen from the given list, and each opening tag may have any
+/// number of attributes. Between the elements, the generated string contains
+/// text without the characters `<`, `>`, `&`, and `"`.
+///
+/// The `validate_value` method rejects strings with unknown element names,
+/// mismatched closing tags, or unclosed elements.
+///
+/// Like other grammar-based mutators, it generates values of type `(String, AST)`.
+/// ```
+/// use fuzzcheck::mutators::grammar::XmlLikeMutator;
+///
+/// let m = XmlLikeMutator::new(["p", "b", "br", "a"]);
+/// ```
+pub struct XmlLikeMutator {
+    mutator: FilterMutator<AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>, XmlLikeFilter>,
+}
+
+impl XmlLikeMutator {
+    /// Create a new `XmlLikeMutator`.
+    ///
+    /// **Panics** if the list of names is empty or if a name is not made of
+    /// ASCII alphanumeric characters, dashes, and underscores.
+    #[coverage(off)]
+    pub fn new<S>(names: impl IntoIterator<Item = S>) -> Self
+    where
+        S: Into<String>,
+    {
+        let names = names.into_iter().map(Into::into).collect::<Vec<String>>();
+        let grammar = recursive(
+            #[coverage(off)]
+            |content| repetition(alternation([text(), matched_tags(&names, recurse(content))]), 0..),
+        );
+        let mutator = AndMapMutator::new(
+            grammar_based_ast_mutator(grammar),
+            write_string as fn(&AST, &mut String),
+            String::with_capacity(64),
+        );
+        let filter: XmlLikeFilter = Box::new(
+            #[coverage(off)]
+            move |(string, _)| is_well_nested(string, &names),
+        );
+        Self {
+            mutator: FilterMutator::new(mutator, filter),
+        }
+    }
+}
+
+impl MutatorWrapper for XmlLikeMutator {
+    type Wrapped = FilterMutator<AndMapMutator<AST, String, ASTMutator, fn(&AST, &mut String)>, XmlLikeFilter>;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
+
+/// Returns a grammar matching an element whose name is one of `names` and whose
+/// content matches `inner`.
+///
+/// The element is either a pair of matching tags, such as `<name attr="value">inner</name>`,
+/// or a self-closing tag, such as `<name attr="value"/>`. Since each name has its own
+/// grammar rule, the closing tag always matches the opening tag. To generate nested
+/// elements, `inner` can be a recursive grammar rule.
+///
+/// **Panics** if the list of names is empty or if a name is not made of ASCII
+/// alphanumeric characters, dashes, and underscores.
+/// ```
+/// use fuzzcheck::mutators::grammar::{alternation, literal_range, matched_tags, recurse, recursive, repetition};
+///
+/// // matches `<b>a</b>`, `<i/>`, `<i>b<b>c</b>d</i>`, etc.
+/// let grammar = recursive(|content| {
+///     repetition(alternation([literal_range('a'..='z'), matched_tags(&["b", "i"], recurse(content))]), 0..)
+/// });
+/// ```
+#[coverage(off)]
+pub fn matched_tags<S>(names: &[S], inner: Rc<Grammar>) -> Rc<Grammar>
+where
+    S: AsRef<str>,
+{
+    assert!(!names.is_empty(), "the list of tag names must not be empty");
+    let attribute = concatenation([
+        literal(' '),
+        repetition(literal_ranges(vec!['a'..='z']), 1..),
+        string_literal("=\""),
+        repetition(text_char(), 0..),
+        literal('"'),
+    ]);
+    let attributes = repetition(attribute, 0..);
+    alternation(names.iter().map(
+        #[coverage(off)]
+        |name| {
+            let name = name.as_ref();
+            assert!(is_name(name), "{name:?} is not a valid tag name");
+            alternation([
+                concatenation([
+                    literal('<'),
+                    string_literal(name),
+                    attributes.clone(),
+                    literal('>'),
+                    inner.clone(),
+                    string_literal("</"),
+                    string_literal(name),
+                    literal('>'),
+                ]),
+                concatenation([
+                    literal('<'),
+                    string_literal(name),
+                    attributes.clone(),
+                    string_literal("/>"),
+                ]),
+            ])
+        },
+    ))
+}
+
+/// A grammar matching any character that can appear in text and attribute values
+#[coverage(off)]
+fn text_char() -> Rc<Grammar> {
+    literal_ranges(vec![' '..='!', '#'..='%', '\''..=';', '='..='=', '?'..='~'])
+}
+
+/// A grammar matching non-empty text between elements
+#[coverage(off)]
+fn text() -> Rc<Grammar> {
+    repetition(text_char(), 1..)
+}
+
+#[coverage(off)]
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.bytes().all(
+            #[coverage(off)]
+            |b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_',
+        )
+}
+
+#[coverage(off)]
+fn is_text_char(c: char) -> bool {
+    !matches!(c, '<' | '>' | '&' | '"')
+}
+
+/// Whether `string` is a sequence of text and well-nested elements whose names
+/// are among `names`.
+#[coverage(off)]
+fn is_well_nested(string: &str, names: &[String]) -> bool {
+    let mut open_elements: Vec<&str> = vec![];
+    let mut rest = string;
+    while let Some(c) = rest.chars().next() {
+        if is_text_char(c) {
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        if c != '<' {
+            return false;
+        }
+        if let Some(closing) = rest.strip_prefix("</") {
+            let Some((name, after)) = closing.split_once('>') else {
+                return false;
+            };
+            if open_elements.pop() != Some(name) {
+                return false;
+            }
+            rest = after;
+            continue;
+        }
+        let tag = &rest[1..];
+        let name_len = tag
+            .find(
+                #[coverage(off)]
+                |c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+            )
+            .unwrap_or(tag.len());
+        let name = &tag[..name_len];
+        if !names.iter().any(
+            #[coverage(off)]
+            |n| n == name,
+        ) {
+            return false;
+        }
+        let mut attributes = &tag[name_len..];
+        loop {
+            if let Some(after) = attributes.strip_prefix("/>") {
+                rest = after;
+                break;
+            } else if let Some(after) = attributes.strip_prefix('>') {
+                open_elements.push(name);
+                rest = after;
+                break;
+            }
+            // an attribute, such as ` name="value"`
+            let Some(attribute) = attributes.strip_prefix(' ') else {
+                return false;
+            };
+            let Some((attribute_name, after_name)) = attribute.split_once("=\"") else {
+                return false;
+            };
+            let Some((value, after_value)) = after_name.split_once('"') else {
+                return false;
+            };
+            if attribute_name.is_empty()
+                || !attribute_name.bytes().all(
+                    #[coverage(off)]
+                    |b| b.is_ascii_lowercase(),
+                )
+                || !value.chars().all(is_text_char)
+            {
+                return false;
+            }
+            attributes = after_value;
+        }
+    }
+    open_elements.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_well_nested;
+
+    #[test]
+    #[coverage(off)]
+    fn test_is_well_nested() {
+        let names = ["a".to_owned(), "br".to_owned(), "div".to_owned()];
+        assert!(is_well_nested("", &names));
+        assert!(is_well_nested("text", &names));
+        assert!(is_well_nested("<a>x</a><br/>", &names));
+        assert!(is_well_nested(
+            "<div id=\"main\" x=\"\"><a href=\"/\">link</a>text<br/></div>",
+            &names
+        ));
+        assert!(is_well_nested("<div><div></div></div>", &names));
+
+        assert!(!is_well_nested("<a>", &names));
+        assert!(!is_well_nested("</a>", &names));
+        assert!(!is_well_nested("<a></div>", &names));
+        assert!(!is_well_nested("<a><div></a></div>", &names));
+        assert!(!is_well_nested("<p></p>", &names));
+        assert!(!is_well_nested("<a x=\"1\" y>", &names));
+        assert!(!is_well_nested("<a x=\"<\"></a>", &names));
+        assert!(!is_well_nested("a > b", &names));
+        assert!(!is_well_nested("<a", &names));
+    }
+}
@@ -0,0 +1,41 @@
+//! A mutator for [`HashMap`], which inserts, removes, and mutates its entries.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::hash_map::HashMapMutator;
+//!
+//! // impl Mutator<HashMap<u8, String>>
+//! let m = <HashMap<u8, String>>::default_mutator();
+//! // impl Mutator<HashMap<u8, bool>> for maps of at most 16 entries
+//! let m = HashMapMutator::new(u8::default_mutator(), bool::default_mutator(), 0..=16);
+//! ```
+use std::any::Any;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use crate::mutators::map_collection::impl_map_mutator;
+use crate::{DefaultMutator, Mutator};
+
+impl<K, V> DefaultMutator for HashMap<K, V>
+where
+    K: DefaultMutator + Hash + Eq + 'static,
+    V: DefaultMutator + 'static,
+{
+    type Mutator = HashMapMutator<K, V, K::Mutator, V::Mutator>;
+    #[coverage(off)]
+    fn default_mutator() -> Self::Mutator {
+        HashMapMutator::new(K::default_mutator(), V::default_mutator(), 0..=usize::MAX)
+    }
+}
+
+impl_map_mutator!(
+    HashMap,
+    HashMapMutator,
+    HashMapMutatorCache,
+    HashMapMutationStep,
+    UnmutateHashMapToken,
+    Hash + Eq
+);
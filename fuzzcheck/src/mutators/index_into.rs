@@ -0,0 +1,436 @@
+//! Mutators for indices that must point into an enclosing collection.
+//!
+//! Some data structures refer to their own elements by index. For example, a
+//! graph can be represented as a `Vec<Node>` where each node has a list of
+//! edges of type `Vec<usize>`, each of which must be a valid index into the
+//! vector of nodes. Mutating the edges independently from the vector of nodes
+//! produces out-of-range indices most of the time.
+//!
+//! The solution is to share a [`CollectionLength`] between two mutators:
+//! 1. an [`IndexIntoMutator`], which only produces indices smaller than the shared length
+//! 2. an [`IndexedCollectionMutator`], which wraps the mutator of the enclosing vector and
+//!    keeps the shared length up to date. It also rejects mutations of the enclosing vector
+//!    that would leave some indices out of range (for example, by removing an element).
+//!
+//! ```
+//! use fuzzcheck::mutators::index_into::{CollectionLength, IndexIntoMutator, IndexedCollectionMutator};
+//! use fuzzcheck::mutators::vector::VecMutator;
+//!
+//! let length = CollectionLength::default();
+//! // each node is a list of edges
+//! let edges_mutator = VecMutator::new(IndexIntoMutator::new(&length), 0..=4);
+//! // impl Mutator<Vec<Vec<usize>>>
+//! let graph_mutator = IndexedCollectionMutator::new(VecMutator::new(edges_mutator, 0..=16), &length);
+//! ```
+use std::any::Any;
+use std::cell::Cell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use crate::Mutator;
+
+/// The number of mutations of the enclosing vector tried by [`IndexedCollectionMutator`] before
+/// giving up, when they all leave some indices out of range
+const MAX_MUTATION_ATTEMPTS: usize = 100;
+
+/// The length of a collection, shared between an [`IndexedCollectionMutator`]
+/// and the [`IndexIntoMutator`]s producing indices into it.
+#[derive(Clone, Default)]
+pub struct CollectionLength(Rc<Cell<usize>>);
+
+impl CollectionLength {
+    /// The current length of the collection
+    #[coverage(off)]
+    pub fn get(&self) -> usize {
+        self.0.get()
+    }
+
+    #[coverage(off)]
+    fn set(&self, len: usize) {
+        self.0.set(len)
+    }
+}
+
+/// A mutator for `usize` values that are valid indices into a collection whose
+/// length is given by a [`CollectionLength`].
+///
+/// It must be used as a submutator of an [`IndexedCollectionMutator`] sharing the same `CollectionLength`.
+/// If the collection is empty, no index is valid and the mutator will produce `0`.
+pub struct IndexIntoMutator {
+    length: CollectionLength,
+    rng: fastrand::Rng,
+}
+
+impl IndexIntoMutator {
+    #[coverage(off)]
+    pub fn new(length: &CollectionLength) -> Self {
+        Self {
+            length: length.clone(),
+            rng: fastrand::Rng::new(),
+        }
+    }
+
+    #[coverage(off)]
+    fn random_index(&self) -> usize {
+        let len = self.length.get();
+        if len == 0 {
+            0
+        } else {
+            self.rng.usize(..len)
+        }
+    }
+}
+
+impl Mutator<usize> for IndexIntoMutator {
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = usize;
+    #[doc(hidden)]
+    type ArbitraryStep = usize;
+    #[doc(hidden)]
+    type UnmutateToken = usize;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &usize) -> bool {
+        *value < self.length.get()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &usize) -> Option<Self::Cache> {
+        if self.is_valid(value) {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &usize, _cache: &Self::Cache) -> Self::MutationStep {
+        0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        super::size_to_cplxity(self.length.get())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        usize::BITS as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        usize::BITS as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, _value: &usize, _cache: &Self::Cache) -> f64 {
+        usize::BITS as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(usize, f64)> {
+        if max_cplx < self.min_complexity() || *step >= self.length.get() {
+            return None;
+        }
+        let value = *step;
+        *step += 1;
+        Some((value, usize::BITS as f64))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, _max_cplx: f64) -> (usize, f64) {
+        (self.random_index(), usize::BITS as f64)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut usize,
+        _cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        _subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if *step == *value {
+            *step += 1;
+        }
+        if *step >= self.length.get() {
+            return None;
+        }
+        let token = std::mem::replace(value, *step);
+        *step += 1;
+        Some((token, usize::BITS as f64))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut usize, _cache: &mut Self::Cache, _max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        (std::mem::replace(value, self.random_index()), usize::BITS as f64)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut usize, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = t;
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        _value: &'a usize,
+        _cache: &'a Self::Cache,
+        _visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
+}
+
+/// A wrapper around the mutator of a vector whose elements contain indices into the vector itself.
+///
+/// Before delegating to the wrapped mutator, it updates the shared [`CollectionLength`] so
+/// that the [`IndexIntoMutator`]s inside the elements produce valid indices. Mutations which
+/// leave some indices out of range are reverted and replaced by a different mutation. After too
+/// many failed attempts, `ordered_mutate` gives up and `random_mutate` replaces the vector by a new
+/// arbitrary value.
+///
+/// New values are generated as if the vector had a single element, such that all indices
+/// are initially `0`. They are diversified later on by mutations.
+pub struct IndexedCollectionMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<Vec<T>>,
+{
+    mutator: M,
+    length: CollectionLength,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M> IndexedCollectionMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<Vec<T>>,
+{
+    #[coverage(off)]
+    pub fn new(mutator: M, length: &CollectionLength) -> Self {
+        Self {
+            mutator,
+            length: length.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+pub enum IndexedCollectionUnmutateToken<T, Cache, UnmutateToken> {
+    Replace(Vec<T>, Cache),
+    Token(UnmutateToken),
+}
+
+impl<T, M> Mutator<Vec<T>> for IndexedCollectionMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<Vec<T>>,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = IndexedCollectionUnmutateToken<T, M::Cache, M::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &Vec<T>) -> bool {
+        self.length.set(value.len());
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &Vec<T>) -> Option<Self::Cache> {
+        self.length.set(value.len());
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &Vec<T>, cache: &Self::Cache) -> Self::MutationStep {
+        self.length.set(value.len());
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &Vec<T>, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(Vec<T>, f64)> {
+        self.length.set(1);
+        self.mutator.ordered_arbitrary(step, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (Vec<T>, f64) {
+        self.length.set(1);
+        self.mutator.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut Vec<T>,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        for _ in 0..MAX_MUTATION_ATTEMPTS {
+            self.length.set(value.len());
+            let (t, cplx) = self
+                .mutator
+                .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)?;
+            if self.is_valid(value) {
+                return Some((IndexedCollectionUnmutateToken::Token(t), cplx));
+            } else {
+                self.mutator.unmutate(value, cache, t);
+            }
+        }
+        self.length.set(value.len());
+        None
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut Vec<T>, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        for _ in 0..MAX_MUTATION_ATTEMPTS {
+            self.length.set(value.len());
+            let (t, cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+            if self.is_valid(value) {
+                return (IndexedCollectionUnmutateToken::Token(t), cplx);
+            } else {
+                self.mutator.unmutate(value, cache, t);
+            }
+        }
+        let (mut new_value, cplx) = self.random_arbitrary(max_cplx);
+        std::mem::swap(value, &mut new_value);
+        let old_cache = std::mem::replace(cache, self.validate_value(value).unwrap());
+        (IndexedCollectionUnmutateToken::Replace(new_value, old_cache), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut Vec<T>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            IndexedCollectionUnmutateToken::Replace(old_value, old_cache) => {
+                *value = old_value;
+                *cache = old_cache;
+            }
+            IndexedCollectionUnmutateToken::Token(t) => self.mutator.unmutate(value, cache, t),
+        }
+        self.length.set(value.len());
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a Vec<T>, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+}
@@ -287,6 +287,24 @@ macro_rules! impl_int_mutator {
                 _visit: &mut dyn FnMut(&'a dyn Any, f64),
             ) {
             }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn rng_state(&self) -> Option<Vec<u8>> {
+                Some(crate::mutators::rng_state(&self.rng))
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_rng_state(&self, state: &[u8]) {
+                crate::mutators::set_rng_state(&self.rng, state);
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn distance(&self, a: &$name, b: &$name) -> Option<f64> {
+                Some(if a == b { 0.0 } else { 1.0 })
+            }
         }
 
         impl DefaultMutator for $name {
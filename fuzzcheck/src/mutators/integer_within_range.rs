@@ -13,6 +13,8 @@ macro_rules! impl_int_mutator_constrained {
             start_range: $name,
             len_range: $name_unsigned,
             search_space_complexity: f64,
+            /// the offsets from the start of the range that are generated first, on a logarithmic scale
+            log_scale_boundaries: Option<Vec<$name_unsigned>>,
             rng: fastrand::Rng,
         }
         impl $name_mutator {
@@ -47,9 +49,70 @@ macro_rules! impl_int_mutator_constrained {
                     start_range: start,
                     len_range: end.wrapping_sub(start) as $name_unsigned,
                     search_space_complexity: super::size_to_cplxity(length as usize),
+                    log_scale_boundaries: None,
                     rng: fastrand::Rng::default(),
                 }
             }
+
+            /// Create a mutator that generates values on a logarithmic scale, measured from
+            /// the start of the range.
+            ///
+            /// Each order of magnitude of the distance to the start of the range is roughly
+            /// equally likely to be produced by `random_arbitrary` and `random_mutate`, so
+            /// small values are generated about as often as large ones. This is useful for
+            /// parameters such as sizes or durations, for which a uniform distribution
+            /// almost never produces small values. `ordered_arbitrary` first produces the
+            /// bounds of the range and the values whose distance to its start is a power of
+            /// two, or a power of two minus one, before enumerating the rest of the range.
+            ///
+            /// The values that are accepted by `validate_value` are the same as with
+            /// [`new`](Self::new).
+            #[coverage(off)]
+            pub fn log_scale<RB: RangeBounds<$name>>(range: RB) -> Self {
+                let mut m = Self::new(range);
+                let mut boundaries = vec![0];
+                if m.len_range != 0 {
+                    boundaries.push(m.len_range);
+                }
+                let mut power: $name_unsigned = 1;
+                loop {
+                    for offset in [power - 1, power] {
+                        if offset <= m.len_range && !boundaries.contains(&offset) {
+                            boundaries.push(offset);
+                        }
+                    }
+                    if power > m.len_range / 2 {
+                        break;
+                    }
+                    power *= 2;
+                }
+                m.log_scale_boundaries = Some(boundaries);
+                m
+            }
+
+            /// A random offset from the start of the range, whose number of bits is uniformly distributed
+            #[coverage(off)]
+            fn log_scale_offset(&self) -> $name_unsigned {
+                let bits = <$name_unsigned>::BITS - self.len_range.leading_zeros();
+                let nbr_bits = self.rng.u32(0..=bits);
+                if nbr_bits == 0 {
+                    return 0;
+                }
+                let low = 1 << (nbr_bits - 1);
+                let high = (<$name_unsigned>::MAX >> (<$name_unsigned>::BITS - nbr_bits)).min(self.len_range);
+                self.rng.$name_unsigned(low..=high)
+            }
+
+            /// A random value within the range, following the distribution chosen when creating the mutator
+            #[coverage(off)]
+            fn random_value(&self) -> $name {
+                if self.log_scale_boundaries.is_some() {
+                    self.start_range.wrapping_add(self.log_scale_offset() as $name)
+                } else {
+                    self.rng
+                        .$name(self.start_range..=self.start_range.wrapping_add(self.len_range as $name))
+                }
+            }
         }
 
         impl Mutator<$name> for $name_mutator {
@@ -122,25 +185,35 @@ macro_rules! impl_int_mutator_constrained {
                 if max_cplx < self.min_complexity() {
                     return None;
                 }
-                if *step > self.len_range as u64 {
-                    None
-                } else {
-                    let result = $name_binary_arbitrary_function(0, self.len_range, *step);
+                let boundaries = self.log_scale_boundaries.as_deref().unwrap_or_default();
+                loop {
+                    if *step > (self.len_range as u64).saturating_add(boundaries.len() as u64) {
+                        return None;
+                    }
+                    let offset = if let Some(offset) = boundaries.get(*step as usize) {
+                        *offset
+                    } else {
+                        let search_step = *step - boundaries.len() as u64;
+                        let offset = $name_binary_arbitrary_function(0, self.len_range, search_step);
+                        if boundaries.contains(&offset) {
+                            // already generated
+                            *step = step.wrapping_add(1);
+                            continue;
+                        }
+                        offset
+                    };
                     *step = step.wrapping_add(1);
-                    Some((
-                        self.start_range.wrapping_add(result as $name),
+                    return Some((
+                        self.start_range.wrapping_add(offset as $name),
                         <$name>::BITS as f64,
-                    ))
+                    ));
                 }
             }
 
             #[doc(hidden)]
             #[coverage(off)]
             fn random_arbitrary(&self, _max_cplx: f64) -> ($name, f64) {
-                let value = self
-                    .rng
-                    .$name(self.start_range..=self.start_range.wrapping_add(self.len_range as $name));
-                (value, <$name>::BITS as f64)
+                (self.random_value(), <$name>::BITS as f64)
             }
 
             #[doc(hidden)]
@@ -177,11 +250,7 @@ macro_rules! impl_int_mutator_constrained {
                 _max_cplx: f64,
             ) -> (Self::UnmutateToken, f64) {
                 (
-                    std::mem::replace(
-                        value,
-                        self.rng
-                            .$name(self.start_range..=self.start_range.wrapping_add(self.len_range as $name)),
-                    ),
+                    std::mem::replace(value, self.random_value()),
                     <$name>::BITS as f64,
                 )
             }
@@ -201,6 +270,24 @@ macro_rules! impl_int_mutator_constrained {
                 _visit: &mut dyn FnMut(&'a dyn Any, f64),
             ) {
             }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn rng_state(&self) -> Option<Vec<u8>> {
+                Some(crate::mutators::rng_state(&self.rng))
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_rng_state(&self, state: &[u8]) {
+                crate::mutators::set_rng_state(&self.rng, state);
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn distance(&self, a: &$name, b: &$name) -> Option<f64> {
+                Some(if a == b { 0.0 } else { 1.0 })
+            }
         }
     };
 }
@@ -225,4 +312,14 @@ mod tests {
         assert!(m.is_valid(&1));
         assert!(!m.is_valid(&2));
     }
+
+    #[test]
+    fn test_int_constrained_rng_state() {
+        let m = U8WithinRangeMutator::new(..);
+        let state = m.rng_state().unwrap();
+        let first = (0..10).map(|_| m.random_arbitrary(8.0).0).collect::<Vec<_>>();
+        m.set_rng_state(&state);
+        let second = (0..10).map(|_| m.random_arbitrary(8.0).0).collect::<Vec<_>>();
+        assert_eq!(first, second);
+    }
 }
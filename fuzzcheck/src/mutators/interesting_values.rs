@@ -0,0 +1,94 @@
+//! The implementation shared by the mutators of values of constant complexity that start
+//! from a table of interesting values, such as floats, durations, and IP addresses.
+//!
+//! Their arbitrary values are the interesting values first, and then values given by the
+//! mutator. Their ordered mutations go through three phases:
+//! 1. small changes of the value, such as flipping one of its bits
+//! 2. replacing it by each of the interesting values
+//! 3. replacing it by values given by the mutator, until they are exhausted
+//!
+//! The mutations are undone by restoring the old value, which is the unmutate token.
+
+/// A value of constant complexity whose mutators start from a table of interesting values
+pub(crate) trait InterestingValues: Copy + 'static {
+    /// The values that are generated first, in this order
+    const INTERESTING: &'static [Self];
+
+    /// Whether the two values are the same. A mutation that gives the same value is skipped.
+    fn is_same(&self, other: &Self) -> bool;
+}
+
+/// Returns the arbitrary value at `step` and increments it.
+///
+/// The first values are the interesting ones, and the following ones are given by `other`,
+/// which is called with the number of steps since the last interesting value.
+#[coverage(off)]
+pub(crate) fn ordered_arbitrary<T: InterestingValues>(
+    step: &mut u64,
+    other: impl FnOnce(u64) -> Option<T>,
+) -> Option<T> {
+    let nbr_interesting = T::INTERESTING.len() as u64;
+    let value = if *step < nbr_interesting {
+        T::INTERESTING[*step as usize]
+    } else {
+        other(*step - nbr_interesting)?
+    };
+    *step += 1;
+    Some(value)
+}
+
+/// Returns a random interesting value
+#[coverage(off)]
+pub(crate) fn random_interesting<T: InterestingValues>(rng: &fastrand::Rng) -> T {
+    T::INTERESTING[rng.usize(..T::INTERESTING.len())]
+}
+
+/// Returns an interesting value once every four times, and the value given by `other` otherwise
+#[coverage(off)]
+pub(crate) fn random_arbitrary<T: InterestingValues>(rng: &fastrand::Rng, other: impl FnOnce() -> T) -> T {
+    if rng.usize(..4) == 0 {
+        random_interesting(rng)
+    } else {
+        other()
+    }
+}
+
+/// Replaces `value` by its ordered mutation at `step`, and returns the old value, or `None`
+/// if the mutations are exhausted.
+///
+/// The first `nbr_small_changes` mutations are given by `small_change`, followed by the
+/// interesting values, and then by `other` until it returns `None`. Both functions are called
+/// with the value and the index of the mutation within their phase.
+#[coverage(off)]
+pub(crate) fn ordered_mutate<T: InterestingValues>(
+    value: &mut T,
+    step: &mut u64,
+    nbr_small_changes: u64,
+    small_change: impl Fn(T, u64) -> T,
+    mut other: impl FnMut(T, u64) -> Option<T>,
+) -> Option<T> {
+    let nbr_interesting = T::INTERESTING.len() as u64;
+    loop {
+        let new_value = if *step < nbr_small_changes {
+            small_change(*value, *step)
+        } else if *step < nbr_small_changes + nbr_interesting {
+            T::INTERESTING[(*step - nbr_small_changes) as usize]
+        } else {
+            other(*value, *step - nbr_small_changes - nbr_interesting)?
+        };
+        *step += 1;
+        if !new_value.is_same(value) {
+            return Some(std::mem::replace(value, new_value));
+        }
+    }
+}
+
+/// The distance between two values, which is 0 if they are the same and 1 otherwise
+#[coverage(off)]
+pub(crate) fn distance<T: InterestingValues>(a: &T, b: &T) -> f64 {
+    if a.is_same(b) {
+        0.0
+    } else {
+        1.0
+    }
+}
@@ -0,0 +1,308 @@
+//! Mutators for [`IpAddr`], [`Ipv4Addr`], and [`Ipv6Addr`].
+//!
+//! ```
+//! use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::ip_addr::{IpAddrMutator, Ipv4AddrMutator, Ipv6AddrMutator};
+//!
+//! let m: Ipv4AddrMutator = Ipv4Addr::default_mutator();
+//! let m: Ipv6AddrMutator = Ipv6Addr::default_mutator();
+//! let m: IpAddrMutator = IpAddr::default_mutator();
+//! ```
+//!
+//! The mutators produce, in this order:
+//! * the unspecified, loopback, and broadcast addresses, the addresses of private, link-local,
+//!   multicast, and documentation networks, and the IPv6 addresses that embed an IPv4 address
+//! * then random addresses
+//!
+//! And the mutations of an address:
+//! * flip all the bits of one of its octets (IPv4) or hextets (IPv6), set it to zero or to its
+//!   maximum value, or increment or decrement it
+//! * replace it by one of the addresses above, and then by random addresses
+//!
+//! The complexity of an address is its bit width. [`IpAddrMutator`] is the mutator of an enum
+//! whose variants are mutated by [`Ipv4AddrMutator`] and [`Ipv6AddrMutator`].
+extern crate self as fuzzcheck;
+
+use std::any::Any;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use fuzzcheck_mutators_derive::make_mutator;
+
+use crate::mutators::interesting_values::{self, InterestingValues};
+use crate::{DefaultMutator, Mutator};
+
+impl InterestingValues for Ipv4Addr {
+    const INTERESTING: &'static [Ipv4Addr] = &[
+        Ipv4Addr::UNSPECIFIED,
+        Ipv4Addr::BROADCAST,
+        Ipv4Addr::LOCALHOST,
+        Ipv4Addr::new(127, 255, 255, 255),
+        // private networks
+        Ipv4Addr::new(10, 0, 0, 1),
+        Ipv4Addr::new(172, 16, 0, 1),
+        Ipv4Addr::new(192, 168, 0, 1),
+        // shared address space, link-local, multicast, documentation, and reserved networks
+        Ipv4Addr::new(100, 64, 0, 1),
+        Ipv4Addr::new(169, 254, 0, 1),
+        Ipv4Addr::new(224, 0, 0, 1),
+        Ipv4Addr::new(192, 0, 2, 1),
+        Ipv4Addr::new(240, 0, 0, 1),
+    ];
+
+    #[coverage(off)]
+    fn is_same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl InterestingValues for Ipv6Addr {
+    const INTERESTING: &'static [Ipv6Addr] = &[
+        Ipv6Addr::UNSPECIFIED,
+        Ipv6Addr::LOCALHOST,
+        Ipv6Addr::new(0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff),
+        // IPv4-mapped and IPv4-compatible addresses
+        Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x7f00, 1),
+        Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0, 0),
+        Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0x7f00, 1),
+        // link-local, multicast, unique local, and documentation networks
+        Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1),
+        Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1),
+        Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 1),
+        Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+        // NAT64 and 6to4 addresses
+        Ipv6Addr::new(0x64, 0xff9b, 0, 0, 0, 0, 0x7f00, 1),
+        Ipv6Addr::new(0x2002, 0x7f00, 1, 0, 0, 0, 0, 1),
+    ];
+
+    #[coverage(off)]
+    fn is_same(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The number of ways in which `ordered_mutate` changes each segment of an address
+const NBR_SEGMENT_MUTATIONS: u64 = 5;
+
+macro_rules! impl_ip_addr_mutator {
+    ($addr:ident, $bits:ident, $segment:ident, $name_mutator:ident) => {
+        #[doc = concat!("The default mutator of [`", stringify!($addr), "`].")]
+        /// See the [module documentation](crate::mutators::ip_addr) for a description of its mutations.
+        pub struct $name_mutator {
+            rng: fastrand::Rng,
+        }
+
+        impl Default for $name_mutator {
+            #[coverage(off)]
+            fn default() -> Self {
+                Self {
+                    rng: fastrand::Rng::default(),
+                }
+            }
+        }
+
+        impl DefaultMutator for $addr {
+            type Mutator = $name_mutator;
+            #[coverage(off)]
+            fn default_mutator() -> Self::Mutator {
+                <$name_mutator>::default()
+            }
+        }
+
+        impl $name_mutator {
+            const NBR_SEGMENTS: u32 = <$bits>::BITS / <$segment>::BITS;
+
+            /// Apply the given mutation to the segment at index `idx`, starting from the most significant one
+            #[coverage(off)]
+            fn mutate_segment(value: $addr, idx: u32, mutation: u64) -> $addr {
+                let shift = <$bits>::BITS - (idx + 1) * <$segment>::BITS;
+                let bits = <$bits>::from(value);
+                let segment = (bits >> shift) as $segment;
+                let new_segment = match mutation {
+                    0 => !segment,
+                    1 => 0,
+                    2 => <$segment>::MAX,
+                    3 => segment.wrapping_add(1),
+                    _ => segment.wrapping_sub(1),
+                };
+                let mask = (<$segment>::MAX as $bits) << shift;
+                <$addr>::from((bits & !mask) | ((new_segment as $bits) << shift))
+            }
+            #[coverage(off)]
+            fn random_addr(&self) -> $addr {
+                <$addr>::from(self.rng.$bits(..))
+            }
+        }
+
+        impl Mutator<$addr> for $name_mutator {
+            #[doc(hidden)]
+            type Cache = ();
+            #[doc(hidden)]
+            type MutationStep = u64;
+            #[doc(hidden)]
+            type ArbitraryStep = u64;
+            #[doc(hidden)]
+            type UnmutateToken = $addr; // old value
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn initialize(&self) {}
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+                0
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn is_valid(&self, _value: &$addr) -> bool {
+                true
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn validate_value(&self, _value: &$addr) -> Option<Self::Cache> {
+                Some(())
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_mutation_step(&self, _value: &$addr, _cache: &Self::Cache) -> Self::MutationStep {
+                0
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn global_search_space_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn max_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn min_complexity(&self) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn complexity(&self, _value: &$addr, _cache: &Self::Cache) -> f64 {
+                <$bits>::BITS as f64
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<($addr, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let value = interesting_values::ordered_arbitrary(
+                    step,
+                    #[coverage(off)]
+                    |_| Some(self.random_addr()),
+                )?;
+                Some((value, <$bits>::BITS as f64))
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_arbitrary(&self, _max_cplx: f64) -> ($addr, f64) {
+                let value = interesting_values::random_arbitrary(
+                    &self.rng,
+                    #[coverage(off)]
+                    || self.random_addr(),
+                );
+                (value, <$bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_mutate(
+                &self,
+                value: &mut $addr,
+                _cache: &mut Self::Cache,
+                step: &mut Self::MutationStep,
+                _subvalue_provider: &dyn crate::SubValueProvider,
+                max_cplx: f64,
+            ) -> Option<(Self::UnmutateToken, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                let token = interesting_values::ordered_mutate(
+                    value,
+                    step,
+                    Self::NBR_SEGMENTS as u64 * NBR_SEGMENT_MUTATIONS,
+                    #[coverage(off)]
+                    |value, idx| {
+                        let segment = (idx / NBR_SEGMENT_MUTATIONS) as u32;
+                        Self::mutate_segment(value, segment, idx % NBR_SEGMENT_MUTATIONS)
+                    },
+                    #[coverage(off)]
+                    |_, _| Some(self.random_addr()),
+                )?;
+                Some((token, <$bits>::BITS as f64))
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_mutate(
+                &self,
+                value: &mut $addr,
+                _cache: &mut Self::Cache,
+                _max_cplx: f64,
+            ) -> (Self::UnmutateToken, f64) {
+                let new_value = match self.rng.usize(..4) {
+                    0 => interesting_values::random_interesting(&self.rng),
+                    1 => self.random_addr(),
+                    _ => Self::mutate_segment(
+                        *value,
+                        self.rng.u32(..Self::NBR_SEGMENTS),
+                        self.rng.u64(..NBR_SEGMENT_MUTATIONS),
+                    ),
+                };
+                (std::mem::replace(value, new_value), <$bits>::BITS as f64)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn unmutate(&self, value: &mut $addr, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+                *value = t;
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn visit_subvalues<'a>(
+                &self,
+                _value: &'a $addr,
+                _cache: &'a Self::Cache,
+                _visit: &mut dyn FnMut(&'a dyn Any, f64),
+            ) {
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn rng_state(&self) -> Option<Vec<u8>> {
+                Some(crate::mutators::rng_state(&self.rng))
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_rng_state(&self, state: &[u8]) {
+                crate::mutators::set_rng_state(&self.rng, state);
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn distance(&self, a: &$addr, b: &$addr) -> Option<f64> {
+                Some(interesting_values::distance(a, b))
+            }
+        }
+    };
+}
+
+impl_ip_addr_mutator!(Ipv4Addr, u32, u8, Ipv4AddrMutator);
+impl_ip_addr_mutator!(Ipv6Addr, u128, u16, Ipv6AddrMutator);
+
+make_mutator! {
+    name: IpAddrMutator,
+    default: true,
+    type: pub enum IpAddr {
+        V4(#[field_mutator(Ipv4AddrMutator)] Ipv4Addr),
+        V6(#[field_mutator(Ipv6AddrMutator)] Ipv6Addr),
+    }
+}
@@ -0,0 +1,355 @@
+//! A mutator for framed messages made of a length field followed by a payload.
+//!
+//! The length field and the payload are mutated independently, but the
+//! [`LengthPrefixMode`] decides whether the length field is allowed to
+//! disagree with the actual length of the payload. This makes it possible to
+//! use the same mutator type for a campaign testing the “happy path” of a
+//! parser (where lengths are always correct) and for a campaign testing its
+//! length-validation logic.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::length_prefixed::{LengthPrefixMode, LengthPrefixedMutator};
+//!
+//! // impl Mutator<(usize, Vec<u8>)>
+//! let m = LengthPrefixedMutator::new(<Vec<u8>>::default_mutator(), LengthPrefixMode::Adversarial);
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::Mutator;
+
+/// Once in `INCONSISTENT_RATE` mutations, a [`LengthPrefixMode::SometimesInconsistent`] mutator
+/// changes the length field only.
+const INCONSISTENT_RATE: usize = 10;
+/// The maximum number of length-only mutations that `ordered_mutate` tries for a single value.
+const MAX_ORDERED_LENGTH_MUTATIONS: usize = 8;
+
+/// Describes how the length field of a [`LengthPrefixedMutator`] relates to its payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthPrefixMode {
+    /// The length field always matches the length of the payload.
+    ///
+    /// Values whose length field is wrong are rejected by `validate_value`.
+    Consistent,
+    /// The length field occasionally takes a random value that does not match
+    /// the length of the payload.
+    SometimesInconsistent,
+    /// The length field is often set to a wrong value, with a bias towards
+    /// off-by-one errors, zero, and very large lengths.
+    Adversarial,
+}
+
+/// A mutator for values of type `(usize, Vec<T>)` where the first element is the
+/// length field of a framed message and the second element is its payload.
+///
+/// Mutations of the payload are delegated to the inner mutator and always
+/// update the length field to match the new payload. Depending on the
+/// [`LengthPrefixMode`], some mutations change only the length field.
+pub struct LengthPrefixedMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<Vec<T>>,
+{
+    mutator: M,
+    mode: LengthPrefixMode,
+    rng: fastrand::Rng,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M> LengthPrefixedMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<Vec<T>>,
+{
+    #[coverage(off)]
+    pub fn new(mutator: M, mode: LengthPrefixMode) -> Self {
+        Self {
+            mutator,
+            mode,
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The mode that was given to the mutator when it was created
+    #[coverage(off)]
+    pub fn mode(&self) -> LengthPrefixMode {
+        self.mode
+    }
+
+    /// Whether the next mutation should change the length field only
+    #[coverage(off)]
+    fn should_mutate_length(&self) -> bool {
+        match self.mode {
+            LengthPrefixMode::Consistent => false,
+            LengthPrefixMode::SometimesInconsistent => self.rng.usize(..INCONSISTENT_RATE) == 0,
+            LengthPrefixMode::Adversarial => self.rng.bool(),
+        }
+    }
+
+    /// Returns the `idx`-th wrong length for a payload of length `len`,
+    /// or `None` if no such length exists.
+    #[coverage(off)]
+    fn wrong_length(&self, len: usize, idx: usize) -> Option<usize> {
+        match self.mode {
+            LengthPrefixMode::Consistent => None,
+            LengthPrefixMode::SometimesInconsistent => {
+                if idx >= MAX_ORDERED_LENGTH_MUTATIONS {
+                    return None;
+                }
+                let wrong = self.rng.usize(..=len.saturating_mul(2).saturating_add(1));
+                if wrong == len {
+                    Some(len.wrapping_add(1))
+                } else {
+                    Some(wrong)
+                }
+            }
+            LengthPrefixMode::Adversarial => adversarial_lengths(len).get(idx).copied(),
+        }
+    }
+}
+
+/// The wrong lengths that are tried by an adversarial [`LengthPrefixedMutator`], in order.
+#[coverage(off)]
+fn adversarial_lengths(len: usize) -> Vec<usize> {
+    let candidates = [
+        len.wrapping_add(1),
+        len.wrapping_sub(1),
+        0,
+        len.saturating_mul(2),
+        u16::MAX as usize,
+        u32::MAX as usize,
+        usize::MAX,
+    ];
+    let mut lengths = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        if candidate != len && !lengths.contains(&candidate) {
+            lengths.push(candidate);
+        }
+    }
+    lengths
+}
+
+#[derive(Clone)]
+pub struct LengthPrefixedMutationStep<MS> {
+    payload_step: MS,
+    payload_exhausted: bool,
+    length_step: usize,
+}
+
+pub enum LengthPrefixedUnmutateToken<UT> {
+    Payload { token: UT, old_length: usize },
+    Length(usize),
+}
+
+impl<T, M> Mutator<(usize, Vec<T>)> for LengthPrefixedMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<Vec<T>>,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = LengthPrefixedMutationStep<M::MutationStep>;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = LengthPrefixedUnmutateToken<M::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &(usize, Vec<T>)) -> bool {
+        let (length, payload) = value;
+        if self.mode == LengthPrefixMode::Consistent && *length != payload.len() {
+            return false;
+        }
+        self.mutator.is_valid(payload)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &(usize, Vec<T>)) -> Option<Self::Cache> {
+        let (length, payload) = value;
+        if self.mode == LengthPrefixMode::Consistent && *length != payload.len() {
+            return None;
+        }
+        self.mutator.validate_value(payload)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &(usize, Vec<T>), cache: &Self::Cache) -> Self::MutationStep {
+        LengthPrefixedMutationStep {
+            payload_step: self.mutator.default_mutation_step(&value.1, cache),
+            payload_exhausted: false,
+            length_step: 0,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        match self.mode {
+            LengthPrefixMode::Consistent => self.mutator.global_search_space_complexity(),
+            LengthPrefixMode::SometimesInconsistent | LengthPrefixMode::Adversarial => {
+                self.mutator.global_search_space_complexity() + usize::BITS as f64
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &(usize, Vec<T>), cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(&value.1, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<((usize, Vec<T>), f64)> {
+        let (payload, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+        Some(((payload.len(), payload), cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> ((usize, Vec<T>), f64) {
+        let (payload, cplx) = self.mutator.random_arbitrary(max_cplx);
+        ((payload.len(), payload), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut (usize, Vec<T>),
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let (length, payload) = value;
+        if step.payload_exhausted || self.should_mutate_length() {
+            if let Some(wrong_length) = self.wrong_length(payload.len(), step.length_step) {
+                step.length_step += 1;
+                let old_length = std::mem::replace(length, wrong_length);
+                let cplx = self.mutator.complexity(payload, cache);
+                return Some((LengthPrefixedUnmutateToken::Length(old_length), cplx));
+            } else if step.payload_exhausted {
+                return None;
+            }
+        }
+        if let Some((token, cplx)) =
+            self.mutator
+                .ordered_mutate(payload, cache, &mut step.payload_step, subvalue_provider, max_cplx)
+        {
+            let old_length = std::mem::replace(length, payload.len());
+            Some((LengthPrefixedUnmutateToken::Payload { token, old_length }, cplx))
+        } else {
+            step.payload_exhausted = true;
+            self.ordered_mutate(value, cache, step, subvalue_provider, max_cplx)
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(
+        &self,
+        value: &mut (usize, Vec<T>),
+        cache: &mut Self::Cache,
+        max_cplx: f64,
+    ) -> (Self::UnmutateToken, f64) {
+        let (length, payload) = value;
+        if self.should_mutate_length() {
+            let idx = match self.mode {
+                LengthPrefixMode::Adversarial => self.rng.usize(..adversarial_lengths(payload.len()).len()),
+                _ => 0,
+            };
+            if let Some(wrong_length) = self.wrong_length(payload.len(), idx) {
+                let old_length = std::mem::replace(length, wrong_length);
+                let cplx = self.mutator.complexity(payload, cache);
+                return (LengthPrefixedUnmutateToken::Length(old_length), cplx);
+            }
+        }
+        let (token, cplx) = self.mutator.random_mutate(payload, cache, max_cplx);
+        let old_length = std::mem::replace(length, payload.len());
+        (LengthPrefixedUnmutateToken::Payload { token, old_length }, cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut (usize, Vec<T>), cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        let (length, payload) = value;
+        match t {
+            LengthPrefixedUnmutateToken::Payload { token, old_length } => {
+                self.mutator.unmutate(payload, cache, token);
+                *length = old_length;
+            }
+            LengthPrefixedUnmutateToken::Length(old_length) => {
+                *length = old_length;
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        value: &'a (usize, Vec<T>),
+        cache: &'a Self::Cache,
+        visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+        let payload = &value.1;
+        visit(payload, self.mutator.complexity(payload, cache));
+        self.mutator.visit_subvalues(payload, cache, visit);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+}
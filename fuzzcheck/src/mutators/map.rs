@@ -45,6 +45,18 @@ where
             _phantom: PhantomData,
         }
     }
+
+    /// The mutator of the `From` values
+    #[coverage(off)]
+    pub fn inner(&self) -> &M {
+        &self.mutator
+    }
+
+    /// The mutator of the `From` values
+    #[coverage(off)]
+    pub fn inner_mut(&mut self) -> &mut M {
+        &mut self.mutator
+    }
 }
 
 pub struct Cache<From, M>
@@ -211,6 +223,277 @@ where
         self.mutator
             .visit_subvalues(&cache.from_value, &cache.from_cache, visit)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        false
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &To, b: &To) -> Option<f64> {
+        self.mutator.distance(&(self.parse)(a)?, &(self.parse)(b)?)
+    }
+}
+
+/// [`TryMapMutator`] is like [`MapMutator`], but the `map` function is allowed to
+/// fail for some values of type `From`.
+///
+/// Values of type `From` that cannot be mapped are never shown to the fuzzer:
+/// mutations leading to such values are reverted and the next mutation is tried
+/// instead, and arbitrary values that cannot be mapped are skipped.
+///
+/// The `map` function should be deterministic: a `From` value that could be
+/// mapped once must always be mappable.
+pub struct TryMapMutator<From, To, M, Parse, Map, Cplx>
+where
+    From: Clone + 'static,
+    To: Clone + 'static,
+    M: Mutator<From>,
+    Parse: Fn(&To) -> Option<From>,
+    Map: Fn(&From) -> Option<To>,
+    Cplx: Fn(&To, f64) -> f64,
+{
+    pub mutator: M,
+    pub parse: Parse,
+    pub map: Map,
+    pub cplx: Cplx,
+    _phantom: PhantomData<(To, From)>,
+}
+impl<From, To, M, Parse, Map, Cplx> TryMapMutator<From, To, M, Parse, Map, Cplx>
+where
+    From: Clone + 'static,
+    To: Clone + 'static,
+    M: Mutator<From>,
+    Parse: Fn(&To) -> Option<From>,
+    Map: Fn(&From) -> Option<To>,
+    Cplx: Fn(&To, f64) -> f64,
+{
+    #[coverage(off)]
+    pub fn new(mutator: M, parse: Parse, map: Map, cplx: Cplx) -> Self {
+        Self {
+            mutator,
+            parse,
+            map,
+            cplx,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<From, To, M, Parse, Map, Cplx> Mutator<To> for TryMapMutator<From, To, M, Parse, Map, Cplx>
+where
+    From: Clone + 'static,
+    To: Clone + 'static,
+    M: Mutator<From>,
+    Parse: Fn(&To) -> Option<From>,
+    Map: Fn(&From) -> Option<To>,
+    Cplx: Fn(&To, f64) -> f64,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = Cache<From, M>;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &To) -> bool {
+        if let Some(from_value) = (self.parse)(value) {
+            self.mutator.is_valid(&from_value)
+        } else {
+            false
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, to_value: &To) -> Option<Self::Cache> {
+        let from_value = (self.parse)(to_value)?;
+        let from_cache = self.mutator.validate_value(&from_value)?;
+        Some(Cache { from_value, from_cache })
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &To, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(&cache.from_value, &cache.from_cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &To, cache: &Self::Cache) -> f64 {
+        let orig_cplx = self.mutator.complexity(&cache.from_value, &cache.from_cache);
+        (self.cplx)(value, orig_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(To, f64)> {
+        loop {
+            let (from_value, orig_cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+            if let Some(to_value) = (self.map)(&from_value) {
+                let cplx = (self.cplx)(&to_value, orig_cplx);
+                return Some((to_value, cplx));
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (To, f64) {
+        loop {
+            let (from_value, orig_cplx) = self.mutator.random_arbitrary(max_cplx);
+            if let Some(to_value) = (self.map)(&from_value) {
+                let cplx = (self.cplx)(&to_value, orig_cplx);
+                return (to_value, cplx);
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut To,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        loop {
+            let (token, orig_cplx) = self.mutator.ordered_mutate(
+                &mut cache.from_value,
+                &mut cache.from_cache,
+                step,
+                subvalue_provider,
+                max_cplx,
+            )?;
+            if let Some(to_value) = (self.map)(&cache.from_value) {
+                *value = to_value;
+                return Some((token, (self.cplx)(value, orig_cplx)));
+            } else {
+                self.mutator
+                    .unmutate(&mut cache.from_value, &mut cache.from_cache, token);
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut To, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        loop {
+            let (token, orig_cplx) = self
+                .mutator
+                .random_mutate(&mut cache.from_value, &mut cache.from_cache, max_cplx);
+            if let Some(to_value) = (self.map)(&cache.from_value) {
+                *value = to_value;
+                return (token, (self.cplx)(value, orig_cplx));
+            } else {
+                self.mutator
+                    .unmutate(&mut cache.from_value, &mut cache.from_cache, token);
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut To, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(&mut cache.from_value, &mut cache.from_cache, t);
+        if let Some(to_value) = (self.map)(&cache.from_value) {
+            *value = to_value;
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, _value: &'a To, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator
+            .visit_subvalues(&cache.from_value, &cache.from_cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        false
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &To, b: &To) -> Option<f64> {
+        self.mutator.distance(&(self.parse)(a)?, &(self.parse)(b)?)
+    }
 }
 
 pub struct AndMapMutator<From, To, M, Map>
@@ -387,4 +670,28 @@ where
         let (_, from_value) = value;
         self.mutator.visit_subvalues(from_value, cache, visit)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        false
+    }
 }
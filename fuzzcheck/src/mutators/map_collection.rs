@@ -0,0 +1,605 @@
+//! The implementation shared by the mutators of [`HashMap`](std::collections::HashMap)
+//! and [`BTreeMap`](std::collections::BTreeMap).
+
+/// Implement a mutator for a map type, whose keys satisfy the given bounds.
+///
+/// The map type must have the methods `new`, `len`, `is_empty`, `iter`, `keys`,
+/// `contains_key`, `get_mut`, `insert`, and `remove` of the maps of the standard library.
+macro_rules! impl_map_mutator {
+    (
+        $map:ident,
+        $mutator:ident,
+        $cache:ident,
+        $step:ident,
+        $token:ident,
+        $($key_bound:tt)+
+    ) => {
+        /// The maximum number of keys generated, or mutated, to find one that is not in the map yet
+        const MAX_NEW_KEY_ATTEMPTS: usize = 10;
+
+        /// The operations performed by the mutations of the map mutator, in the
+        /// order in which they are tried by `ordered_mutate`
+        #[derive(Clone, Copy)]
+        enum Operation {
+            Insert,
+            Remove,
+            MutateValue,
+            MutateKey,
+        }
+
+        const OPERATIONS: [Operation; 4] = [
+            Operation::Insert,
+            Operation::Remove,
+            Operation::MutateValue,
+            Operation::MutateKey,
+        ];
+
+        pub struct $cache<K, V, MK, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MK: Mutator<K>,
+            MV: Mutator<V>,
+        {
+            inner: $map<K, (MK::Cache, MV::Cache)>,
+            sum_cplx: f64,
+        }
+        impl<K, V, MK, MV> Clone for $cache<K, V, MK, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MK: Mutator<K>,
+            MV: Mutator<V>,
+        {
+            #[coverage(off)]
+            fn clone(&self) -> Self {
+                Self {
+                    inner: self.inner.clone(),
+                    sum_cplx: self.sum_cplx,
+                }
+            }
+        }
+
+        pub struct $step<K, V, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MV: Mutator<V>,
+        {
+            operation: usize,
+            inner: $map<K, MV::MutationStep>,
+        }
+        impl<K, V, MV> Clone for $step<K, V, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MV: Mutator<V>,
+        {
+            #[coverage(off)]
+            fn clone(&self) -> Self {
+                Self {
+                    operation: self.operation,
+                    inner: self.inner.clone(),
+                }
+            }
+        }
+
+        pub enum $token<K, V, MK, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MK: Mutator<K>,
+            MV: Mutator<V>,
+        {
+            Remove(K),
+            Insert(K, V, MK::Cache, MV::Cache),
+            Value(K, MV::UnmutateToken, f64),
+            Key(K, MK::UnmutateToken, f64),
+            Replace($map<K, V>, $cache<K, V, MK, MV>),
+        }
+
+        #[doc = concat!("A mutator for [`", stringify!($map), "`], whose number of entries is within the given range.")]
+        ///
+        /// The complexity of a map is the sum of the complexities of its keys and values plus one,
+        /// and plus the number of entries if the entries can have a complexity of zero, like the
+        /// complexity of a [`VecMutator`](crate::mutators::vector::VecMutator).
+        ///
+        /// Its mutations insert a new entry generated by the key and value mutators, remove an
+        /// entry, and mutate the value or the key of an entry. Mutating a key moves its entry
+        /// within the map. If the mutated key is unchanged or already in the map, it is mutated again,
+        /// and another mutation is performed after a few unsuccessful attempts.
+        ///
+        /// The key mutator may not be able to generate enough distinct keys to reach the minimum
+        /// number of entries, in which case the maps it generates are smaller and invalid.
+        pub struct $mutator<K, V, MK, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MK: Mutator<K>,
+            MV: Mutator<V>,
+        {
+            mk: MK,
+            mv: MV,
+            len_range: RangeInclusive<usize>,
+            rng: fastrand::Rng,
+            _phantom: PhantomData<(K, V)>,
+        }
+
+        impl<K, V, MK, MV> $mutator<K, V, MK, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MK: Mutator<K>,
+            MV: Mutator<V>,
+        {
+            #[coverage(off)]
+            pub fn new(mk: MK, mv: MV, len_range: RangeInclusive<usize>) -> Self {
+                Self {
+                    mk,
+                    mv,
+                    len_range,
+                    rng: fastrand::Rng::new(),
+                    _phantom: PhantomData,
+                }
+            }
+
+            #[coverage(off)]
+            fn min_entry_complexity(&self) -> f64 {
+                self.mk.min_complexity() + self.mv.min_complexity()
+            }
+
+            #[coverage(off)]
+            fn complexity_from_inner(&self, cplx: f64, len: usize) -> f64 {
+                1.0 + if len == 0 || self.min_entry_complexity() > 0.0 {
+                    cplx
+                } else {
+                    len as f64 + cplx
+                }
+            }
+
+            #[coverage(off)]
+            fn entry_complexity(&self, key: &K, value: &V, cache: &(MK::Cache, MV::Cache)) -> f64 {
+                self.mk.complexity(key, &cache.0) + self.mv.complexity(value, &cache.1)
+            }
+
+            /// Generate a key that is not in the map yet, and a value, whose complexity is at most `max_cplx`
+            #[coverage(off)]
+            fn new_entry(&self, map: &$map<K, V>, max_cplx: f64) -> Option<(K, V, f64)> {
+                let max_key_cplx = max_cplx - self.mv.min_complexity();
+                for _ in 0..MAX_NEW_KEY_ATTEMPTS {
+                    let (key, key_cplx) = self.mk.random_arbitrary(max_key_cplx);
+                    if !map.contains_key(&key) {
+                        let (value, value_cplx) = self.mv.random_arbitrary(max_cplx - key_cplx);
+                        return Some((key, value, key_cplx + value_cplx));
+                    }
+                }
+                None
+            }
+
+            #[coverage(off)]
+            fn random_key(&self, value: &$map<K, V>) -> K {
+                value.keys().nth(self.rng.usize(..value.len())).unwrap().clone()
+            }
+
+            /// Apply the given operation, or return `None` if it cannot be applied to the map
+            #[coverage(off)]
+            fn apply(
+                &self,
+                operation: Operation,
+                value: &mut $map<K, V>,
+                cache: &mut $cache<K, V, MK, MV>,
+                step: Option<&mut $step<K, V, MV>>,
+                subvalue_provider: &dyn crate::SubValueProvider,
+                max_cplx: f64,
+            ) -> Option<($token<K, V, MK, MV>, f64)> {
+                match operation {
+                    Operation::Insert => {
+                        if value.len() >= *self.len_range.end() {
+                            return None;
+                        }
+                        let current_cplx = self.complexity_from_inner(cache.sum_cplx, value.len());
+                        let len_cplx = if self.min_entry_complexity() > 0.0 { 0.0 } else { 1.0 };
+                        let max_entry_cplx = max_cplx - current_cplx - len_cplx;
+                        if max_entry_cplx < self.min_entry_complexity() {
+                            return None;
+                        }
+                        let (key, el, _) = self.new_entry(value, max_entry_cplx)?;
+                        let entry_cache = (
+                            self.mk.validate_value(&key).unwrap(),
+                            self.mv.validate_value(&el).unwrap(),
+                        );
+                        cache.sum_cplx += self.entry_complexity(&key, &el, &entry_cache);
+                        cache.inner.insert(key.clone(), entry_cache);
+                        value.insert(key.clone(), el);
+                        Some((
+                            $token::Remove(key),
+                            self.complexity_from_inner(cache.sum_cplx, value.len()),
+                        ))
+                    }
+                    Operation::Remove => {
+                        if value.len() <= *self.len_range.start() || value.is_empty() {
+                            return None;
+                        }
+                        let key = self.random_key(value);
+                        let el = value.remove(&key).unwrap();
+                        let (key_cache, el_cache) = cache.inner.remove(&key).unwrap();
+                        cache.sum_cplx -= self.mk.complexity(&key, &key_cache) + self.mv.complexity(&el, &el_cache);
+                        Some((
+                            $token::Insert(key, el, key_cache, el_cache),
+                            self.complexity_from_inner(cache.sum_cplx, value.len()),
+                        ))
+                    }
+                    Operation::MutateValue => {
+                        if value.is_empty() {
+                            return None;
+                        }
+                        let key = self.random_key(value);
+                        let current_cplx = self.complexity_from_inner(cache.sum_cplx, value.len());
+                        let el = value.get_mut(&key).unwrap();
+                        let (_, el_cache) = cache.inner.get_mut(&key).unwrap();
+                        let old_el_cplx = self.mv.complexity(el, el_cache);
+                        let max_el_cplx = max_cplx - current_cplx + old_el_cplx;
+                        let ordered_result = step.and_then(
+                            #[coverage(off)]
+                            |step| {
+                                // entries inserted after the step was created do not have a mutation step yet
+                                let el_step = step.inner.entry(key.clone()).or_insert_with(
+                                    #[coverage(off)]
+                                    || self.mv.default_mutation_step(el, el_cache),
+                                );
+                                self.mv
+                                    .ordered_mutate(el, el_cache, el_step, subvalue_provider, max_el_cplx)
+                            },
+                        );
+                        let (token, new_el_cplx) = ordered_result.unwrap_or_else(
+                            #[coverage(off)]
+                            || self.mv.random_mutate(el, el_cache, max_el_cplx),
+                        );
+                        let old_sum_cplx = cache.sum_cplx;
+                        cache.sum_cplx += new_el_cplx - old_el_cplx;
+                        Some((
+                            $token::Value(key, token, old_sum_cplx),
+                            self.complexity_from_inner(cache.sum_cplx, value.len()),
+                        ))
+                    }
+                    Operation::MutateKey => {
+                        if value.is_empty() {
+                            return None;
+                        }
+                        let mut key = self.random_key(value);
+                        let current_cplx = self.complexity_from_inner(cache.sum_cplx, value.len());
+                        let el = value.remove(&key).unwrap();
+                        let (mut key_cache, el_cache) = cache.inner.remove(&key).unwrap();
+                        let old_key_cplx = self.mk.complexity(&key, &key_cache);
+                        let max_key_cplx = max_cplx - current_cplx + old_key_cplx;
+                        let original_key = key.clone();
+                        for _ in 0..MAX_NEW_KEY_ATTEMPTS {
+                            let (token, new_key_cplx) = self.mk.random_mutate(&mut key, &mut key_cache, max_key_cplx);
+                            if key == original_key || value.contains_key(&key) {
+                                // the key is unchanged or already in the map, so it is mutated again from the original key
+                                self.mk.unmutate(&mut key, &mut key_cache, token);
+                                continue;
+                            }
+                            let old_sum_cplx = cache.sum_cplx;
+                            cache.sum_cplx += new_key_cplx - old_key_cplx;
+                            cache.inner.insert(key.clone(), (key_cache, el_cache));
+                            value.insert(key.clone(), el);
+                            return Some((
+                                $token::Key(key, token, old_sum_cplx),
+                                self.complexity_from_inner(cache.sum_cplx, value.len()),
+                            ));
+                        }
+                        // the entry is put back where it was
+                        cache.inner.insert(key.clone(), (key_cache, el_cache));
+                        value.insert(key, el);
+                        None
+                    }
+                }
+            }
+        }
+
+        impl<K, V, MK, MV> Mutator<$map<K, V>> for $mutator<K, V, MK, MV>
+        where
+            K: $($key_bound)+ + Clone + 'static,
+            V: Clone + 'static,
+            MK: Mutator<K>,
+            MV: Mutator<V>,
+        {
+            #[doc(hidden)]
+            type Cache = $cache<K, V, MK, MV>;
+            #[doc(hidden)]
+            type MutationStep = $step<K, V, MV>;
+            #[doc(hidden)]
+            type ArbitraryStep = bool;
+            #[doc(hidden)]
+            type UnmutateToken = $token<K, V, MK, MV>;
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn initialize(&self) {
+                self.mk.initialize();
+                self.mv.initialize();
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+                false
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn is_valid(&self, value: &$map<K, V>) -> bool {
+                self.len_range.contains(&value.len())
+                    && value.iter().all(
+                        #[coverage(off)]
+                        |(k, v)| self.mk.is_valid(k) && self.mv.is_valid(v),
+                    )
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn validate_value(&self, value: &$map<K, V>) -> Option<Self::Cache> {
+                if !self.len_range.contains(&value.len()) {
+                    return None;
+                }
+                let mut inner = <$map<K, _>>::new();
+                let mut sum_cplx = 0.0;
+                for (k, v) in value.iter() {
+                    let entry_cache = (self.mk.validate_value(k)?, self.mv.validate_value(v)?);
+                    sum_cplx += self.entry_complexity(k, v, &entry_cache);
+                    inner.insert(k.clone(), entry_cache);
+                }
+                Some($cache { inner, sum_cplx })
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn default_mutation_step(&self, value: &$map<K, V>, cache: &Self::Cache) -> Self::MutationStep {
+                $step {
+                    operation: 0,
+                    inner: value
+                        .iter()
+                        .map(
+                            #[coverage(off)]
+                            |(k, v)| (k.clone(), self.mv.default_mutation_step(v, &cache.inner[k].1)),
+                        )
+                        .collect(),
+                }
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn global_search_space_complexity(&self) -> f64 {
+                let entry_cplx = self.mk.global_search_space_complexity() + self.mv.global_search_space_complexity();
+                if entry_cplx == 0.0 {
+                    super::size_to_cplxity(self.len_range.end() - self.len_range.start() + 1)
+                } else {
+                    entry_cplx * ((self.len_range.end() - self.len_range.start()) as f64)
+                }
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn max_complexity(&self) -> f64 {
+                let max_len = *self.len_range.end();
+                let max_entry_cplx = self.mk.max_complexity() + self.mv.max_complexity();
+                self.complexity_from_inner((max_len as f64) * max_entry_cplx, max_len)
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn min_complexity(&self) -> f64 {
+                let min_len = *self.len_range.start();
+                let min_sum_cplx = if min_len == 0 {
+                    0.0
+                } else {
+                    (min_len as f64) * self.min_entry_complexity()
+                };
+                self.complexity_from_inner(min_sum_cplx, min_len)
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn complexity(&self, value: &$map<K, V>, cache: &Self::Cache) -> f64 {
+                self.complexity_from_inner(cache.sum_cplx, value.len())
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<($map<K, V>, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                if !*step {
+                    // the first value is the least complex one
+                    *step = true;
+                    let mut value = <$map<K, V>>::new();
+                    for _ in 0..*self.len_range.start() {
+                        if let Some((k, v, _)) = self.new_entry(&value, self.min_entry_complexity()) {
+                            value.insert(k, v);
+                        }
+                    }
+                    let cplx = match self.validate_value(&value) {
+                        Some(cache) => self.complexity(&value, &cache),
+                        None => self.min_complexity(),
+                    };
+                    Some((value, cplx))
+                } else {
+                    Some(self.random_arbitrary(max_cplx))
+                }
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_arbitrary(&self, max_cplx: f64) -> ($map<K, V>, f64) {
+                let min_cplx = self.min_complexity();
+                let target_cplx = if max_cplx <= min_cplx {
+                    min_cplx
+                } else {
+                    crate::mutators::gen_f64(&self.rng, min_cplx..max_cplx)
+                };
+                // each entry costs at least its minimum complexity, or 1.0 for the length if that is 0
+                let min_entry_cplx = self.min_entry_complexity().max(1.0);
+                let max_len = ((target_cplx - 1.0).max(0.0) / min_entry_cplx) as usize;
+                let max_len = max_len.clamp(*self.len_range.start(), *self.len_range.end());
+                let target_len = self.rng.usize(*self.len_range.start()..=max_len);
+
+                let mut value = <$map<K, V>>::new();
+                let mut sum_cplx = 0.0;
+                for i in 0..target_len {
+                    let remaining = (target_len - i) as f64;
+                    let len_cplx = if self.min_entry_complexity() > 0.0 { 0.0 } else { 1.0 };
+                    let max_entry_cplx = ((target_cplx - 1.0 - sum_cplx) / remaining - len_cplx).max(0.0);
+                    if let Some((k, v, entry_cplx)) = self.new_entry(&value, max_entry_cplx) {
+                        sum_cplx += entry_cplx;
+                        value.insert(k, v);
+                    }
+                }
+                let cplx = self.complexity_from_inner(sum_cplx, value.len());
+                (value, cplx)
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn ordered_mutate(
+                &self,
+                value: &mut $map<K, V>,
+                cache: &mut Self::Cache,
+                step: &mut Self::MutationStep,
+                subvalue_provider: &dyn crate::SubValueProvider,
+                max_cplx: f64,
+            ) -> Option<(Self::UnmutateToken, f64)> {
+                if max_cplx < self.min_complexity() {
+                    return None;
+                }
+                for _ in 0..OPERATIONS.len() {
+                    let operation = OPERATIONS[step.operation % OPERATIONS.len()];
+                    step.operation += 1;
+                    if let Some(result) = self.apply(operation, value, cache, Some(step), subvalue_provider, max_cplx) {
+                        return Some(result);
+                    }
+                }
+                None
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn random_mutate(
+                &self,
+                value: &mut $map<K, V>,
+                cache: &mut Self::Cache,
+                max_cplx: f64,
+            ) -> (Self::UnmutateToken, f64) {
+                let start = self.rng.usize(..OPERATIONS.len());
+                for i in 0..OPERATIONS.len() {
+                    let operation = OPERATIONS[(start + i) % OPERATIONS.len()];
+                    if let Some(result) = self.apply(
+                        operation,
+                        value,
+                        cache,
+                        None,
+                        &crate::subvalue_provider::EmptySubValueProvider,
+                        max_cplx,
+                    ) {
+                        return result;
+                    }
+                }
+                // no operation can be applied, so the value is replaced by a new one
+                let (mut new_value, cplx) = self.random_arbitrary(max_cplx);
+                let mut new_cache = self.validate_value(&new_value).unwrap();
+                std::mem::swap(value, &mut new_value);
+                std::mem::swap(cache, &mut new_cache);
+                ($token::Replace(new_value, new_cache), cplx)
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn unmutate(&self, value: &mut $map<K, V>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+                match t {
+                    $token::Remove(key) => {
+                        let el = value.remove(&key).unwrap();
+                        let entry_cache = cache.inner.remove(&key).unwrap();
+                        cache.sum_cplx -= self.entry_complexity(&key, &el, &entry_cache);
+                    }
+                    $token::Insert(key, el, key_cache, el_cache) => {
+                        let entry_cache = (key_cache, el_cache);
+                        cache.sum_cplx += self.entry_complexity(&key, &el, &entry_cache);
+                        cache.inner.insert(key.clone(), entry_cache);
+                        value.insert(key, el);
+                    }
+                    $token::Value(key, token, old_sum_cplx) => {
+                        let el = value.get_mut(&key).unwrap();
+                        let (_, el_cache) = cache.inner.get_mut(&key).unwrap();
+                        self.mv.unmutate(el, el_cache, token);
+                        cache.sum_cplx = old_sum_cplx;
+                    }
+                    $token::Key(key, token, old_sum_cplx) => {
+                        let el = value.remove(&key).unwrap();
+                        let (mut key_cache, el_cache) = cache.inner.remove(&key).unwrap();
+                        let mut key = key;
+                        self.mk.unmutate(&mut key, &mut key_cache, token);
+                        cache.inner.insert(key.clone(), (key_cache, el_cache));
+                        value.insert(key, el);
+                        cache.sum_cplx = old_sum_cplx;
+                    }
+                    $token::Replace(old_value, old_cache) => {
+                        *value = old_value;
+                        *cache = old_cache;
+                    }
+                }
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn visit_subvalues<'a>(
+                &self,
+                value: &'a $map<K, V>,
+                cache: &'a Self::Cache,
+                visit: &mut dyn FnMut(&'a dyn Any, f64),
+            ) {
+                for (k, v) in value.iter() {
+                    let (key_cache, el_cache) = &cache.inner[k];
+                    visit(k, self.mk.complexity(k, key_cache));
+                    visit(v, self.mv.complexity(v, el_cache));
+                }
+                for (k, v) in value.iter() {
+                    let (key_cache, el_cache) = &cache.inner[k];
+                    self.mk.visit_subvalues(k, key_cache, visit);
+                    self.mv.visit_subvalues(v, el_cache, visit);
+                }
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn rng_state(&self) -> Option<Vec<u8>> {
+                Some(crate::mutators::rng_states(
+                    &self.rng,
+                    [self.mk.rng_state(), self.mv.rng_state()],
+                ))
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_rng_state(&self, state: &[u8]) {
+                let mut states = crate::mutators::set_rng_states(&self.rng, state);
+                self.mk.set_rng_state(states.next().unwrap_or_default());
+                self.mv.set_rng_state(states.next().unwrap_or_default());
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_effort_hint(&self, hint: crate::EffortHint) {
+                self.mk.set_effort_hint(hint);
+                self.mv.set_effort_hint(hint);
+            }
+
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn unmutate_is_cheap(&self) -> bool {
+                self.mk.unmutate_is_cheap() && self.mv.unmutate_is_cheap()
+            }
+        }
+    };
+}
+pub(crate) use impl_map_mutator;
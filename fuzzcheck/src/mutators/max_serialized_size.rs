@@ -0,0 +1,345 @@
+//! A wrapper that prevents a mutator from producing values whose serialized size exceeds a limit.
+//!
+//! ```
+//! use fuzzcheck::{ByteSerializer, DefaultMutator};
+//! use fuzzcheck::mutators::max_serialized_size::MaxSerializedSizeMutator;
+//!
+//! // never produce byte buffers that are larger than 64 KiB once serialized
+//! let m = MaxSerializedSizeMutator::new(<Vec<u8>>::default_mutator(), ByteSerializer::new("bin"), 64 * 1024);
+//!
+//! // the same, but without serializing each value
+//! let m = MaxSerializedSizeMutator::with_size_hint(<Vec<u8>>::default_mutator(), |x: &Vec<u8>| x.len(), 64 * 1024);
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::{Mutator, Serializer};
+
+/// The maximum number of values that are generated, or mutations that are tried,
+/// while looking for a value that is small enough.
+const MAX_ATTEMPTS: usize = 100;
+
+/// A way to compute the size of a value, in bytes.
+///
+/// It is implemented by every [`Serializer`], which computes the length of the
+/// serialized value, and by [`SizeHint`], which wraps a closure.
+pub trait ValueSize<T> {
+    fn size(&self, value: &T) -> usize;
+}
+
+impl<T, S> ValueSize<T> for S
+where
+    S: Serializer<Value = T>,
+{
+    #[coverage(off)]
+    fn size(&self, value: &T) -> usize {
+        self.to_data(value).len()
+    }
+}
+
+/// A [`ValueSize`] that computes the size of a value with a closure.
+///
+/// It is useful when the size of a serialized value can be computed more cheaply
+/// than by serializing it.
+pub struct SizeHint<F>(pub F);
+
+impl<T, F> ValueSize<T> for SizeHint<F>
+where
+    F: Fn(&T) -> usize,
+{
+    #[coverage(off)]
+    fn size(&self, value: &T) -> usize {
+        (self.0)(value)
+    }
+}
+
+/// A [`MaxSerializedSizeMutator`] wraps a mutator such that it never produces
+/// values whose size, as computed by a [`ValueSize`], is greater than `max_size`.
+///
+/// When a mutation produces a value that is too large, it is unmutated and a
+/// smaller mutation is tried instead, by lowering the maximum complexity given to
+/// the wrapped mutator. The mutations give up after a fixed number of attempts,
+/// in which case `ordered_mutate` returns `None` and `random_mutate` leaves the
+/// value unchanged. Similarly, the `random_arbitrary` and `ordered_arbitrary`
+/// methods eventually fall back to the simplest value of the wrapped mutator. If
+/// that value is too large as well, `ordered_arbitrary` returns `None` and
+/// `random_arbitrary` panics.
+///
+/// Values from the corpus that are too large are rejected by `validate_value`.
+pub struct MaxSerializedSizeMutator<T, M, S>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    S: ValueSize<T>,
+{
+    mutator: M,
+    size: S,
+    max_size: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M, S> MaxSerializedSizeMutator<T, M, S>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    S: ValueSize<T>,
+{
+    /// Create a new `MaxSerializedSizeMutator` producing values whose serialized size,
+    /// computed by `serializer`, is at most `max_size` bytes.
+    #[coverage(off)]
+    pub fn new(mutator: M, serializer: S, max_size: usize) -> Self {
+        Self {
+            mutator,
+            size: serializer,
+            max_size,
+            _phantom: PhantomData,
+        }
+    }
+
+    #[coverage(off)]
+    fn is_small_enough(&self, value: &T) -> bool {
+        self.size.size(value) <= self.max_size
+    }
+
+    /// The simplest value of the wrapped mutator, if it is small enough
+    #[coverage(off)]
+    fn simplest_arbitrary(&self) -> Option<(T, f64)> {
+        let min_cplx = self.mutator.min_complexity();
+        let mut step = self.mutator.default_arbitrary_step();
+        let (x, cplx) = self.mutator.ordered_arbitrary(&mut step, min_cplx).unwrap_or_else(
+            #[coverage(off)]
+            || self.mutator.random_arbitrary(min_cplx),
+        );
+        if self.is_small_enough(&x) {
+            Some((x, cplx))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, M, F> MaxSerializedSizeMutator<T, M, SizeHint<F>>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    F: Fn(&T) -> usize,
+{
+    /// Create a new `MaxSerializedSizeMutator` producing values whose size, computed
+    /// by the closure `size_hint`, is at most `max_size` bytes.
+    #[coverage(off)]
+    pub fn with_size_hint(mutator: M, size_hint: F, max_size: usize) -> Self {
+        Self::new(mutator, SizeHint(size_hint), max_size)
+    }
+}
+
+/// The maximum complexity to give to the next mutation after one that produced a
+/// value of complexity `rejected_cplx` that was too large.
+///
+/// It is halfway between `lower_cplx` and the rejected complexity.
+#[coverage(off)]
+fn smaller_max_cplx(lower_cplx: f64, rejected_cplx: f64, max_cplx: f64) -> f64 {
+    let upper_cplx = rejected_cplx.min(max_cplx);
+    if upper_cplx <= lower_cplx {
+        lower_cplx
+    } else {
+        lower_cplx + (upper_cplx - lower_cplx) / 2.0
+    }
+}
+
+impl<T, M, S> Mutator<T> for MaxSerializedSizeMutator<T, M, S>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    S: ValueSize<T>,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = Option<M::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value) && self.is_small_enough(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        if self.is_small_enough(value) {
+            self.mutator.validate_value(value)
+        } else {
+            None
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        let min_cplx = self.mutator.min_complexity();
+        let mut max_cplx = max_cplx;
+        for _ in 0..MAX_ATTEMPTS {
+            let (x, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+            if self.is_small_enough(&x) {
+                return Some((x, cplx));
+            }
+            max_cplx = smaller_max_cplx(min_cplx, cplx, max_cplx);
+        }
+        self.simplest_arbitrary()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let min_cplx = self.mutator.min_complexity();
+        let mut max_cplx = max_cplx;
+        for _ in 0..MAX_ATTEMPTS {
+            let (x, cplx) = self.mutator.random_arbitrary(max_cplx);
+            if self.is_small_enough(&x) {
+                return (x, cplx);
+            }
+            max_cplx = smaller_max_cplx(min_cplx, cplx, max_cplx);
+        }
+        self.simplest_arbitrary().unwrap_or_else(
+            #[coverage(off)]
+            || {
+                panic!(
+                    "MaxSerializedSizeMutator: the simplest value of the wrapped mutator is larger than {} bytes",
+                    self.max_size
+                )
+            },
+        )
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        let current_cplx = self.mutator.complexity(value, cache);
+        let mut max_cplx = max_cplx;
+        for _ in 0..MAX_ATTEMPTS {
+            let (t, cplx) = self
+                .mutator
+                .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)?;
+            if self.is_small_enough(value) {
+                return Some((Some(t), cplx));
+            }
+            self.mutator.unmutate(value, cache, t);
+            max_cplx = smaller_max_cplx(current_cplx, cplx, max_cplx);
+        }
+        None
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let current_cplx = self.mutator.complexity(value, cache);
+        let mut max_cplx = max_cplx;
+        for _ in 0..MAX_ATTEMPTS {
+            let (t, cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+            if self.is_small_enough(value) {
+                return (Some(t), cplx);
+            }
+            self.mutator.unmutate(value, cache, t);
+            max_cplx = smaller_max_cplx(current_cplx, cplx, max_cplx);
+        }
+        (None, current_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        if let Some(t) = t {
+            self.mutator.unmutate(value, cache, t)
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &T, b: &T) -> Option<f64> {
+        self.mutator.distance(a, b)
+    }
+}
@@ -0,0 +1,293 @@
+//! A wrapper that remembers the results of `validate_value` for the values it has already seen.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::memoizing::MemoizingMutator;
+//!
+//! // remembers the caches of the last 10,000 validated values
+//! let m = MemoizingMutator::new(<Vec<u8>>::default_mutator(), 10_000);
+//! ```
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::hash_map::{Entry, RandomState};
+use std::collections::{HashMap, VecDeque};
+use std::hash::{BuildHasher, Hash};
+
+use crate::Mutator;
+
+/// A [`MemoizingMutator`] forwards every method to the wrapped mutator, but
+/// remembers the result of [`validate_value`](Mutator::validate_value) for the
+/// most recently validated values.
+///
+/// When a value that was already validated is validated again, a clone of the
+/// remembered cache is returned instead of running the validation of the wrapped
+/// mutator. This speeds up harnesses that load the same corpus several times, for
+/// example in differential testing, when the validation is expensive. The values
+/// that are rejected are remembered too.
+///
+/// Each remembered value is stored along with its cache, which is cloned on every
+/// hit (the [`Cache`](Mutator::Cache) of a mutator is always `Clone`). At most
+/// `capacity` values are remembered: when the limit is reached, the value that was
+/// validated first is forgotten.
+///
+/// The values that can be generated, and their caches, are unchanged. The wrapped
+/// mutator must return the same result each time a value is validated.
+pub struct MemoizingMutator<T, M>
+where
+    T: Clone + Hash + Eq + 'static,
+    M: Mutator<T>,
+{
+    mutator: M,
+    capacity: usize,
+    memo: RefCell<Memo<T, M::Cache>>,
+}
+
+/// The remembered results of `validate_value`, along with the order in which they were inserted
+///
+/// Each value is stored once, in the bucket of its hash. The eviction queue only stores the hashes:
+/// the oldest value of a bucket is always the first one.
+struct Memo<T, C> {
+    hasher: RandomState,
+    caches: HashMap<u64, Vec<(T, Option<C>)>>,
+    order: VecDeque<u64>,
+}
+
+impl<T, C> Memo<T, C>
+where
+    T: Clone + Hash + Eq,
+    C: Clone,
+{
+    #[coverage(off)]
+    fn new() -> Self {
+        Self {
+            hasher: RandomState::new(),
+            caches: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    #[coverage(off)]
+    fn get(&self, value: &T) -> Option<&Option<C>> {
+        let bucket = self.caches.get(&self.hasher.hash_one(value))?;
+        bucket.iter().find_map(
+            #[coverage(off)]
+            |(x, cache)| (x == value).then_some(cache),
+        )
+    }
+
+    #[coverage(off)]
+    fn insert(&mut self, value: &T, cache: &Option<C>, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        while self.order.len() >= capacity {
+            let Some(oldest) = self.order.pop_front() else { break };
+            if let Entry::Occupied(mut bucket) = self.caches.entry(oldest) {
+                bucket.get_mut().remove(0);
+                if bucket.get().is_empty() {
+                    bucket.remove();
+                }
+            }
+        }
+        let hash = self.hasher.hash_one(value);
+        self.caches
+            .entry(hash)
+            .or_default()
+            .push((value.clone(), cache.clone()));
+        self.order.push_back(hash);
+    }
+
+    #[coverage(off)]
+    fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    #[coverage(off)]
+    fn clear(&mut self) {
+        self.caches.clear();
+        self.order.clear();
+    }
+}
+
+impl<T, M> MemoizingMutator<T, M>
+where
+    T: Clone + Hash + Eq + 'static,
+    M: Mutator<T>,
+{
+    /// Create a `MemoizingMutator` remembering the results of `validate_value` for
+    /// at most `capacity` values.
+    #[coverage(off)]
+    pub fn new(mutator: M, capacity: usize) -> Self {
+        Self {
+            mutator,
+            capacity,
+            memo: RefCell::new(Memo::new()),
+        }
+    }
+
+    /// The number of values whose result of `validate_value` is currently remembered
+    #[coverage(off)]
+    pub fn memoized_len(&self) -> usize {
+        self.memo.borrow().len()
+    }
+
+    /// Forget all the remembered results of `validate_value`
+    #[coverage(off)]
+    pub fn clear(&self) {
+        self.memo.borrow_mut().clear();
+    }
+}
+
+impl<T, M> Mutator<T> for MemoizingMutator<T, M>
+where
+    T: Clone + Hash + Eq + 'static,
+    M: Mutator<T>,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        if let Some(cache) = self.memo.borrow().get(value) {
+            return cache.clone();
+        }
+        let cache = self.mutator.validate_value(value);
+        self.memo.borrow_mut().insert(value, &cache, self.capacity);
+        cache
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.ordered_arbitrary(step, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        self.mutator.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.arbitrary_at_complexity(target_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        self.mutator
+            .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        self.mutator.random_mutate(value, cache, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(value, cache, t)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &T, b: &T) -> Option<f64> {
+        self.mutator.distance(a, b)
+    }
+}
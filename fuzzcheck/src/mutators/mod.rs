@@ -7,10 +7,23 @@ This module provides the following mutators:
     * `bool` ([here](crate::mutators::bool::BoolMutator))
     * `char` ([here](crate::mutators::char::CharWithinRangeMutator) and [here](crate::mutators::character_classes::CharacterMutator))
     * integers ([here](crate::mutators::integer) and [here](crate::mutators::integer_within_range))
+    * floating point numbers `f32` and `f64` ([here](crate::mutators::float) and [here](crate::mutators::float_within_range))
+    * fixed-point decimal numbers ([here](crate::mutators::fixed_point::FixedPointMutator))
+    * `Duration` ([here](crate::mutators::duration::DurationMutator))
+    * `IpAddr`, `Ipv4Addr`, and `Ipv6Addr` ([here](crate::mutators::ip_addr))
+    * pairs of integers whose sum, difference, or product overflows ([here](crate::mutators::overflow_seeking::OverflowSeekingMutator))
+    * `Saturating` integers ([here](crate::mutators::saturating::SaturatingMutator))
     * `Vec` ([here](crate::mutators::vector::VecMutator) and [here](crate::mutators::fixed_len_vector::FixedLenVecMutator))
+    * `VecDeque` ([here](crate::mutators::vec_deque::VecDequeMutator))
+    * `HashMap` and `BTreeMap` ([here](crate::mutators::hash_map::HashMapMutator) and [here](crate::mutators::btree_map::BTreeMapMutator))
+    * `CString`, and byte strings that sometimes contain interior NUL bytes ([here](crate::mutators::c_string))
+    * byte strings mixing valid and invalid UTF-8 ([here](crate::mutators::utf8::Utf8FuzzMutator))
+    * vectors of booleans stored in a [`FixedBitSet`](crate::FixedBitSet) ([here](crate::mutators::bit_vec::BitVecMutator))
+    * approximately balanced binary trees ([here](crate::mutators::balanced_tree::BalancedTreeMutator))
     * `Option` ([here](crate::mutators::option::OptionMutator))
     * `Result` ([here](crate::mutators::result::ResultMutator))
     * `Box` ([here](crate::mutators::boxed))
+    * `Option<Box<T>>`, handling both layers at once ([here](crate::mutators::option_box::OptionBoxMutator))
     * tuples of up to 10 elements ([here](crate::mutators::tuples))
 
 * procedural macros to generate mutators for custom types:
@@ -25,16 +38,88 @@ This module provides the following mutators:
     not(feature = "grammar_mutator"),
     doc = "* ~~grammar-based string and syntax tree mutators~~ (note: you are viewing the documentation of fuzzcheck without the `grammar_mutator` feature. Therefore, grammar-based mutators are not available)"
 )]
+#![cfg_attr(
+    feature = "chrono",
+    doc = "* dates and times of the `chrono` crate ([here](crate::mutators::date_time)) __(supported on crate feature `chrono` only)__"
+)]
+#![cfg_attr(
+    feature = "bytemuck",
+    doc = "* a mutator for plain-old-data types that mutates their raw bytes ([here](crate::mutators::pod)) __(supported on crate feature `bytemuck` only)__"
+)]
 /*!
 - basic blocks to build more complex mutators:
     * [`AlternationMutator<_, M>`](crate::mutators::alternation::AlternationMutator) to use multiple different mutators acting on the same test case type
     * [`Either<M1, M2>`](crate::mutators::either::Either) is the regular `Either` type, which also implements `Mutator<T>` if both `M1` and `M2` implement it too
     * [`RecursiveMutator` and `RecurToMutator`](crate::mutators::recursive) are wrappers allowing mutators to call themselves recursively, which is necessary to mutate recursive types.
+    * [`ParallelMutator<..>`](crate::mutators::parallel::ParallelMutator) creates independent copies of a mutator that can be sent to worker threads.
     * [`MapMutator<..>`](crate::mutators::map::MapMutator) wraps a mutator and transforms the generated value using a user-provided function.
+    * [`TryMapMutator<..>`](crate::mutators::map::TryMapMutator) is like `MapMutator`, but the transformation may fail, in which case the value is skipped.
+    * [`IndexIntoMutator` and `IndexedCollectionMutator`](crate::mutators::index_into) produce indices that stay within the bounds of an enclosing vector.
+    * [`OracleBiasedMutator<..>`](crate::mutators::oracle_biased::OracleBiasedMutator) keeps most generated values within the subset accepted by an oracle function, while letting some others through.
+    * [`ShrinkTowardMutator<..>`](crate::mutators::shrink_toward::ShrinkTowardMutator) simplifies values toward a user-specified target value instead of toward the least complex value.
+    * [`SingleFieldMutator<..>`](crate::mutators::single_field::SingleFieldMutator) only mutates a single field of a value, keeping the others equal to those of a base value.
+    * [`LengthPrefixedMutator<..>`](crate::mutators::length_prefixed::LengthPrefixedMutator) mutates framed messages made of a length field and a payload, optionally producing wrong lengths.
+    * [`ConcatMutator<..>`](crate::mutators::concat::ConcatMutator) mutates byte buffers made of independent sections, each with its own mutator.
+    * [`PascalStringMutator<..>`](crate::mutators::pascal_string::PascalStringMutator) mutates byte strings made of a `u8`, `u16`, or `u32` length prefix followed by that many bytes.
+    * [`RecordAlignedByteMutator`](crate::mutators::record_aligned::RecordAlignedByteMutator) mutates byte buffers made of fixed-size records, inserting, removing, and changing whole records.
+    * [`TaggedUnionMutator<..>`](crate::mutators::tagged_union::TaggedUnionMutator) mutates byte buffers made of a tag byte followed by a payload whose layout depends on the tag.
+    * [`MaxSerializedSizeMutator<..>`](crate::mutators::max_serialized_size::MaxSerializedSizeMutator) rejects the generated values whose serialized size exceeds a limit.
+    * [`BurstMutator<..>`](crate::mutators::burst::BurstMutator) applies several mutations of a mutator at once, to take bigger steps through the search space.
+    * [`NoCrossoverMutator<..>`](crate::mutators::no_crossover::NoCrossoverMutator) prevents a mutator from using parts of other test cases, to measure the effect of crossover.
+    * [`CornerCaseCountingMutator<..>`](crate::mutators::corner_case_counting::CornerCaseCountingMutator) counts how often a mutator produces values recognized as corner cases.
+    * [`ConditionalMutator<..>`](crate::mutators::conditional::ConditionalMutator) sometimes uses an extra mutator on the values matching a predicate.
+    * [`MemoizingMutator<..>`](crate::mutators::memoizing::MemoizingMutator) remembers the results of `validate_value`, to avoid validating the same values again when a corpus is loaded several times.
+    * [`ExhaustiveMutator<..>`](crate::mutators::exhaustive::ExhaustiveMutator) enumerates every value of a mutator with a small domain, to test them all with [`launch_exhaustive`](crate::launch_exhaustive).
+    * [`FailureAvoidingMutator<..>`](crate::mutators::failure_avoiding::FailureAvoidingMutator) does not produce again the values that already made the test fail, to keep exploring after finding a failure.
+    * [`SequenceMutator<..>`](crate::mutators::sequence::SequenceMutator) mutates sequences of messages that must satisfy a sequence-level invariant, for stateful fuzzing.
+    * [`WeightedConstructorMutator<..>`](crate::mutators::weighted_constructor::WeightedConstructorMutator) generates values by calling user-provided constructor functions, each chosen with a given weight.
 */
 
 pub const CROSSOVER_RATE: u8 = 10;
 
+/// The number of values that [`Mutator::arbitrary_at_complexity`] generates by default
+/// before giving up on reaching the target complexity
+pub const ARBITRARY_AT_COMPLEXITY_ATTEMPTS: usize = 100;
+
+/// The budget with which [`Mutator::ordered_mutate_with_status`] checks whether a value that
+/// could not be mutated within `max_cplx` can be mutated at all: twice `max_cplx`, plus `64.0`
+#[coverage(off)]
+pub fn larger_mutation_budget(max_cplx: f64) -> f64 {
+    2.0 * max_cplx + 64.0
+}
+
+/// The maximum difference between the complexity of a value returned by
+/// [`Mutator::arbitrary_at_complexity`] and the target complexity: 10% of the target,
+/// but at least `1.0`
+#[coverage(off)]
+pub fn arbitrary_at_complexity_tolerance(target_cplx: f64) -> f64 {
+    f64::max(1.0, 0.1 * target_cplx.abs())
+}
+
+/// The default implementation of [`Mutator::arbitrary_at_complexity`] and
+/// [`TupleMutator::arbitrary_at_complexity`](crate::mutators::tuples::TupleMutator::arbitrary_at_complexity),
+/// which calls `random_arbitrary(target_cplx)` a few times, until it returns a value that is
+/// close enough to the target
+#[coverage(off)]
+pub(crate) fn arbitrary_at_complexity_by_sampling<T>(
+    target_cplx: f64,
+    min_cplx: f64,
+    max_cplx: f64,
+    mut random_arbitrary: impl FnMut(f64) -> (T, f64),
+) -> Option<(T, f64)> {
+    let tolerance = arbitrary_at_complexity_tolerance(target_cplx);
+    if target_cplx + tolerance < min_cplx || target_cplx - tolerance > max_cplx {
+        return None;
+    }
+    for _ in 0..ARBITRARY_AT_COMPLEXITY_ATTEMPTS {
+        let (value, cplx) = random_arbitrary(target_cplx);
+        if (cplx - target_cplx).abs() <= tolerance {
+            return Some((value, cplx));
+        }
+    }
+    None
+}
+
 use std::any::{Any, TypeId};
 use std::marker::PhantomData;
 use std::ops::Range;
@@ -42,41 +127,84 @@ use std::ops::Range;
 use ahash::AHashMap;
 
 use self::filter::FilterMutator;
-use self::map::MapMutator;
+use self::map::{MapMutator, TryMapMutator};
 use crate::subvalue_provider::Generation;
 use crate::{Mutator, SubValueProvider};
 
 pub mod alternation;
 pub mod arc;
 pub mod array;
+pub mod balanced_tree;
+pub mod bit_vec;
 pub mod bool;
 pub mod boxed;
+pub mod btree_map;
+pub mod burst;
+pub mod c_string;
 pub mod char;
 pub mod character_classes;
+pub mod concat;
+pub mod conditional;
+pub mod corner_case_counting;
 pub mod cow;
+#[cfg(feature = "chrono")]
+#[doc(cfg(feature = "chrono"))]
+pub mod date_time;
+pub mod duration;
 pub mod either;
 pub mod enums;
+pub mod exhaustive;
+pub mod failure_avoiding;
 pub mod filter;
 pub mod fixed_len_vector;
+pub mod fixed_point;
+pub mod float;
+pub mod float_within_range;
 #[cfg(feature = "grammar_mutator")]
 #[doc(cfg(feature = "grammar_mutator"))]
 pub mod grammar;
+pub mod hash_map;
+pub mod index_into;
 pub mod integer;
 pub mod integer_within_range;
+mod interesting_values;
+pub mod ip_addr;
+pub mod length_prefixed;
 pub mod map;
+mod map_collection;
+pub mod max_serialized_size;
+pub mod memoizing;
 pub mod mutations;
 pub mod never;
+pub mod no_crossover;
 pub mod option;
+pub mod option_box;
+pub mod oracle_biased;
+pub mod overflow_seeking;
+pub mod parallel;
+pub mod pascal_string;
+#[cfg(feature = "bytemuck")]
+#[doc(cfg(feature = "bytemuck"))]
+pub mod pod;
 pub mod range;
 pub mod rc;
+pub mod record_aligned;
 pub mod recursive;
 pub mod result;
+pub mod saturating;
+pub mod sequence;
+pub mod shrink_toward;
+pub mod single_field;
 pub mod string;
+pub mod tagged_union;
 pub mod tuples;
 pub mod unique;
 pub mod unit;
+pub mod utf8;
+pub mod vec_deque;
 pub mod vector;
 pub mod vose_alias;
+pub mod weighted_constructor;
 
 /// A trait for giving a type a default [Mutator]
 pub trait DefaultMutator: Clone + 'static {
@@ -156,6 +284,58 @@ where
     {
         MapMutator::new(self, parse, map, keep_orig_cplx)
     }
+    /// Create a mutator which wraps `self` and transforms the values generated by `self`
+    /// using the `map` closure, skipping the values for which `map` returns `None`.
+    /// The second closure, `parse`, should apply the opposite transformation.
+    #[coverage(off)]
+    fn try_map<To, Map, Parse>(
+        self,
+        map: Map,
+        parse: Parse,
+    ) -> TryMapMutator<T, To, Self, Parse, Map, fn(&To, f64) -> f64>
+    where
+        To: Clone + 'static,
+        Map: Fn(&T) -> Option<To>,
+        Parse: Fn(&To) -> Option<T>,
+    {
+        TryMapMutator::new(self, parse, map, keep_orig_cplx)
+    }
+    /// Calls [`ordered_mutate`](Mutator::ordered_mutate) and returns, along with its unmutate
+    /// token, the complexity of the value before and after the mutation.
+    #[coverage(off)]
+    fn ordered_mutate_with_outcome(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<MutationOutcome<Self::UnmutateToken>> {
+        let old_cplx = self.complexity(value, cache);
+        let (token, new_cplx) = self.ordered_mutate(value, cache, step, subvalue_provider, max_cplx)?;
+        Some(MutationOutcome {
+            old_cplx,
+            new_cplx,
+            token,
+        })
+    }
+    /// Calls [`random_mutate`](Mutator::random_mutate) and returns, along with its unmutate
+    /// token, the complexity of the value before and after the mutation.
+    #[coverage(off)]
+    fn random_mutate_with_outcome(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        max_cplx: f64,
+    ) -> MutationOutcome<Self::UnmutateToken> {
+        let old_cplx = self.complexity(value, cache);
+        let (token, new_cplx) = self.random_mutate(value, cache, max_cplx);
+        MutationOutcome {
+            old_cplx,
+            new_cplx,
+            token,
+        }
+    }
 }
 impl<T, M> MutatorExt<T> for M
 where
@@ -164,6 +344,29 @@ where
 {
 }
 
+/// A mutation performed by [`MutatorExt::ordered_mutate_with_outcome`] or
+/// [`MutatorExt::random_mutate_with_outcome`], with its effect on the complexity of the value.
+///
+/// It is useful to drivers that learn which mutations change the complexity the most.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MutationOutcome<UnmutateToken> {
+    /// The complexity of the value before the mutation
+    pub old_cplx: f64,
+    /// The complexity of the mutated value
+    pub new_cplx: f64,
+    /// The token describing how to undo the mutation
+    pub token: UnmutateToken,
+}
+
+impl<UnmutateToken> MutationOutcome<UnmutateToken> {
+    /// How much the mutation increased the complexity of the value. It is negative if
+    /// the mutation made the value simpler.
+    #[coverage(off)]
+    pub fn cplx_delta(&self) -> f64 {
+        self.new_cplx - self.old_cplx
+    }
+}
+
 /**
  A trait for types that are basic wrappers over a mutator, such as `Box<M>`.
 
@@ -256,6 +459,12 @@ where
         self.wrapped_mutator().random_arbitrary(max_cplx)
     }
 
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        self.wrapped_mutator().arbitrary_at_complexity(target_cplx)
+    }
+
     #[doc(hidden)]
     #[coverage(off)]
     fn ordered_mutate(
@@ -270,6 +479,20 @@ where
             .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)
     }
 
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate_with_status(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> crate::MutateResult<Self::UnmutateToken> {
+        self.wrapped_mutator()
+            .ordered_mutate_with_status(value, cache, step, subvalue_provider, max_cplx)
+    }
+
     #[doc(hidden)]
     #[coverage(off)]
     fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
@@ -287,6 +510,42 @@ where
     fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
         self.wrapped_mutator().visit_subvalues(value, cache, visit)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.wrapped_mutator().rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.wrapped_mutator().set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.wrapped_mutator().set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.wrapped_mutator().unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn record_test_failure(&self, value: &T, cache: &Self::Cache) {
+        self.wrapped_mutator().record_test_failure(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &T, b: &T) -> Option<f64> {
+        self.wrapped_mutator().distance(a, b)
+    }
 }
 
 impl<M> MutatorWrapper for Box<M> {
@@ -315,6 +574,69 @@ pub(crate) fn gen_f64(rng: &fastrand::Rng, range: Range<f64>) -> f64 {
     range.start + rng.f64() * (range.end - range.start)
 }
 
+/// Serializes the state of a random number generator, for [`Mutator::rng_state`]
+#[coverage(off)]
+pub(crate) fn rng_state(rng: &fastrand::Rng) -> Vec<u8> {
+    rng.get_seed().to_le_bytes().to_vec()
+}
+
+/// Serializes the state of a random number generator followed by the state of a submutator
+#[coverage(off)]
+pub(crate) fn rng_state_with_submutator<T: Clone + 'static, M: Mutator<T>>(rng: &fastrand::Rng, m: &M) -> Vec<u8> {
+    let mut state = rng_state(rng);
+    if let Some(inner_state) = m.rng_state() {
+        state.extend(inner_state);
+    }
+    state
+}
+
+/// Restores the state of a random number generator serialized by [`rng_state`]
+/// and returns the remaining bytes of `state`
+#[coverage(off)]
+pub(crate) fn set_rng_state<'a>(rng: &fastrand::Rng, state: &'a [u8]) -> &'a [u8] {
+    if let Some((seed, rest)) = state.split_first_chunk::<8>() {
+        rng.seed(u64::from_le_bytes(*seed));
+        rest
+    } else {
+        &[]
+    }
+}
+
+/// Serializes the state of a random number generator followed by the states of several submutators,
+/// each preceded by its length.
+///
+/// It is used by the mutators that own more than one submutator, including the ones generated by the
+/// [`DefaultMutator`](derive@crate::DefaultMutator) derive macro. The state is restored by [`set_rng_states`].
+#[doc(hidden)]
+#[coverage(off)]
+pub fn rng_states(rng: &fastrand::Rng, submutator_states: impl IntoIterator<Item = Option<Vec<u8>>>) -> Vec<u8> {
+    let mut state = rng_state(rng);
+    for submutator_state in submutator_states {
+        let submutator_state = submutator_state.unwrap_or_default();
+        state.extend((submutator_state.len() as u32).to_le_bytes());
+        state.extend(submutator_state);
+    }
+    state
+}
+
+/// Restores the state of a random number generator serialized by [`rng_states`] and returns an iterator
+/// over the states of the submutators, in the order in which they were serialized.
+#[doc(hidden)]
+#[coverage(off)]
+pub fn set_rng_states<'a>(rng: &fastrand::Rng, state: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+    let mut rest = set_rng_state(rng, state);
+    std::iter::from_fn(
+        #[coverage(off)]
+        move || {
+            let (len, tail) = rest.split_first_chunk::<4>()?;
+            let len = (u32::from_le_bytes(*len) as usize).min(tail.len());
+            let (submutator_state, tail) = tail.split_at(len);
+            rest = tail;
+            Some(submutator_state)
+        },
+    )
+}
+
 #[must_use]
 #[coverage(off)]
 fn size_to_cplxity(size: usize) -> f64 {
@@ -457,6 +779,45 @@ pub mod testing_utilities {
         //     }
         // }
     }
+
+    /// Generate `samples` values with `random_arbitrary` and check that their
+    /// complexities are consistent.
+    ///
+    /// For each generated value, the complexity returned by `random_arbitrary` must
+    /// be equal to the one computed by `m.complexity(..)`, and it must be between
+    /// `m.min_complexity()` and `m.max_complexity()`.
+    ///
+    /// Unlike [`test_mutator`], it does not require the values to be hashable and
+    /// focuses only on the complexities, which are the most common source of bugs
+    /// in custom mutators.
+    ///
+    /// # Panics
+    /// Panics with a message showing the faulty value on the first inconsistency.
+    #[coverage(off)]
+    pub fn check_complexity_consistency<T, M>(m: &M, samples: usize, max_cplx: f64)
+    where
+        M: Mutator<T>,
+        T: Clone + Debug + 'static,
+    {
+        m.initialize();
+        let min = m.min_complexity();
+        let max = m.max_complexity();
+        for i in 0..samples {
+            let (x, cplx) = m.random_arbitrary(max_cplx);
+            let cache = m
+                .validate_value(&x)
+                .unwrap_or_else(|| panic!("sample {i}: the generated value {x:?} is not valid for the mutator"));
+            let other_cplx = m.complexity(&x, &cache);
+            assert!(
+                (cplx - other_cplx).abs() < 0.01,
+                "sample {i}: random_arbitrary returned a complexity of {cplx:.3}, but the complexity of {x:?} is {other_cplx:.3}"
+            );
+            assert!(
+                cplx >= min - 0.01 && cplx <= max + 0.01,
+                "sample {i}: the complexity of {x:?} is {cplx:.3}, which is outside of the mutator's bounds [{min:.3}, {max:.3}]"
+            );
+        }
+    }
     // #[coverage(off)]
     // pub fn bench_mutator<T, M>(
     //     m: M,
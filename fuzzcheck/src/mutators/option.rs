@@ -1,6 +1,8 @@
 use fuzzcheck_mutators_derive::make_mutator;
 extern crate self as fuzzcheck;
 
+use crate::Mutator;
+
 make_mutator! {
     name: OptionMutator,
     default: true,
@@ -9,3 +11,41 @@ make_mutator! {
         None,
     }
 }
+
+impl<T, M> OptionMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    /// Create an `OptionMutator` that never generates `None`.
+    ///
+    /// It is meant for values that are typed `Option<T>` but are always `Some` in practice.
+    /// A `None` value, for example read from a corpus, is still valid and is the simplest
+    /// possible value, but it is only ever mutated into a `Some` value.
+    #[coverage(off)]
+    pub fn always_some(inner: M) -> Self {
+        let mut m = Self::new(inner);
+        m.mutator.generate_only_with(vec![0]);
+        m
+    }
+
+    /// The mutator of the value inside `Some`
+    #[coverage(off)]
+    pub fn inner(&self) -> &M {
+        match &self.mutator.mutators()[0] {
+            OptionSingleVariant::Some(m) => m.inner(),
+            OptionSingleVariant::None(_) => unreachable!(),
+        }
+    }
+
+    /// The mutator of the value inside `Some`.
+    ///
+    /// It should only be modified before the mutator is initialized.
+    #[coverage(off)]
+    pub fn inner_mut(&mut self) -> &mut M {
+        match &mut self.mutator.mutators_mut()[0] {
+            OptionSingleVariant::Some(m) => m.inner_mut(),
+            OptionSingleVariant::None(_) => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,365 @@
+//! A mutator for `Option<Box<T>>` that handles both layers at once.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::option_box::OptionBoxMutator;
+//!
+//! // impl Mutator<Option<Box<Vec<u8>>>>
+//! let m = OptionBoxMutator::new(<Vec<u8>>::default_mutator());
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use super::CrossoverStep;
+use crate::{Mutator, CROSSOVER_RATE};
+
+/// The number of ordered mutations of a value that first try to switch between `None` and `Some`
+const NBR_VARIANT_SWITCHES: usize = 20;
+
+/// A mutator for `Option<Box<T>>` that behaves like
+/// [`OptionMutator::new(BoxMutator::new(mutator))`](crate::mutators::option::OptionMutator),
+/// but handles both layers at once.
+///
+/// It generates the same values, with the same complexities, but its cache, mutation
+/// step, and unmutate token are a single level deep instead of two. This makes it
+/// cheaper to use in recursive types, where `Option<Box<Self>>` fields are common and
+/// the nested layers are repeated at each level of recursion.
+///
+/// `#[derive(DefaultMutator)]` uses it for the fields of type `Option<Box<T>>`.
+pub struct OptionBoxMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    mutator: M,
+    rng: fastrand::Rng,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M> OptionBoxMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[coverage(off)]
+    pub fn new(mutator: M) -> Self {
+        Self {
+            mutator,
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The mutator of the boxed value
+    #[coverage(off)]
+    pub fn inner(&self) -> &M {
+        &self.mutator
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ArbitraryStep<AS> {
+    inner: AS,
+    /// whether `None` can still be generated
+    none: bool,
+    /// whether `Some` values can still be generated
+    some: bool,
+    /// the number of values generated so far
+    count: usize,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct MutationStep<T, MS, AS> {
+    crossover_step: CrossoverStep<T>,
+    inner: Option<MS>,
+    /// the step used to switch to the other variant
+    arbitrary: ArbitraryStep<AS>,
+    count: usize,
+}
+
+#[doc(hidden)]
+pub enum UnmutateToken<T, C, U> {
+    Replace(Option<Box<T>>, Option<C>),
+    ReplaceInner(T),
+    Inner(U),
+}
+
+impl<T, M> OptionBoxMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[coverage(off)]
+    fn arbitrary_step(&self, none: bool, some: bool) -> ArbitraryStep<M::ArbitraryStep> {
+        ArbitraryStep {
+            inner: self.mutator.default_arbitrary_step(),
+            none,
+            some,
+            count: 0,
+        }
+    }
+
+    /// Replaces the value and its cache with a new one, returning the token to restore them
+    #[coverage(off)]
+    fn replace(
+        &self,
+        value: &mut Option<Box<T>>,
+        cache: &mut Option<M::Cache>,
+        new_value: Option<Box<T>>,
+    ) -> UnmutateToken<T, M::Cache, M::UnmutateToken> {
+        let new_cache = new_value.as_ref().and_then(
+            #[coverage(off)]
+            |v| self.mutator.validate_value(v),
+        );
+        UnmutateToken::Replace(std::mem::replace(value, new_value), std::mem::replace(cache, new_cache))
+    }
+}
+
+impl<T, M> Mutator<Option<Box<T>>> for OptionBoxMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[doc(hidden)]
+    type Cache = Option<M::Cache>;
+    #[doc(hidden)]
+    type MutationStep = MutationStep<T, M::MutationStep, M::ArbitraryStep>;
+    #[doc(hidden)]
+    type ArbitraryStep = ArbitraryStep<M::ArbitraryStep>;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken<T, M::Cache, M::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.arbitrary_step(true, true)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &Option<Box<T>>) -> bool {
+        match value {
+            Some(x) => self.mutator.is_valid(x),
+            None => true,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &Option<Box<T>>) -> Option<Self::Cache> {
+        match value {
+            Some(x) => self.mutator.validate_value(x).map(Some),
+            None => Some(None),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &Option<Box<T>>, cache: &Self::Cache) -> Self::MutationStep {
+        let inner = match (value, cache) {
+            (Some(x), Some(c)) => Some(self.mutator.default_mutation_step(x, c)),
+            _ => None,
+        };
+        MutationStep {
+            crossover_step: CrossoverStep::default(),
+            inner,
+            arbitrary: self.arbitrary_step(value.is_some(), value.is_none()),
+            count: 0,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        // the `None` variant adds one bit to a choice between two variants
+        f64::max(self.mutator.global_search_space_complexity(), 2.0)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        1.0 + self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        1.0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &Option<Box<T>>, cache: &Self::Cache) -> f64 {
+        match (value, cache) {
+            (Some(x), Some(c)) => 1.0 + self.mutator.complexity(x, c),
+            _ => 1.0,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(Option<Box<T>>, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        // alternate between the variants, starting with `Some`, like an `OptionMutator` does
+        loop {
+            let try_none = step.none && (step.count % 2 == 1 || !step.some);
+            if try_none {
+                step.none = false;
+                step.count += 1;
+                return Some((None, 1.0));
+            }
+            if !step.some {
+                return None;
+            }
+            if let Some((x, cplx)) = self.mutator.ordered_arbitrary(&mut step.inner, max_cplx) {
+                step.count += 1;
+                return Some((Some(Box::new(x)), 1.0 + cplx));
+            }
+            step.some = false;
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (Option<Box<T>>, f64) {
+        if max_cplx < self.min_complexity() || self.rng.bool() {
+            (None, 1.0)
+        } else {
+            let (x, cplx) = self.mutator.random_arbitrary(max_cplx);
+            (Some(Box::new(x)), 1.0 + cplx)
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut Option<Box<T>>,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if self.rng.usize(..100) == 0 {
+            let (new_value, cplx) = self.random_arbitrary(max_cplx);
+            return Some((self.replace(value, cache, new_value), cplx));
+        }
+        step.count += 1;
+        if step.count < NBR_VARIANT_SWITCHES
+            && let Some((new_value, cplx)) = self.ordered_arbitrary(&mut step.arbitrary, max_cplx)
+        {
+            return Some((self.replace(value, cache, new_value), cplx));
+        }
+        if let (Some(x), Some(c), Some(inner_step)) = (value.as_mut(), cache.as_mut(), step.inner.as_mut()) {
+            if self.rng.u8(..CROSSOVER_RATE) == 0
+                && let Some((subvalue, subcplx)) = step.crossover_step.get_next_subvalue(subvalue_provider, max_cplx)
+                && self.mutator.is_valid(subvalue)
+            {
+                let mut replacer = subvalue.clone();
+                std::mem::swap(x.as_mut(), &mut replacer);
+                return Some((UnmutateToken::ReplaceInner(replacer), 1.0 + subcplx));
+            }
+            if let Some((t, cplx)) = self
+                .mutator
+                .ordered_mutate(x, c, inner_step, subvalue_provider, max_cplx)
+            {
+                return Some((UnmutateToken::Inner(t), 1.0 + cplx));
+            }
+        }
+        let (new_value, cplx) = self.ordered_arbitrary(&mut step.arbitrary, max_cplx)?;
+        Some((self.replace(value, cache, new_value), cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(
+        &self,
+        value: &mut Option<Box<T>>,
+        cache: &mut Self::Cache,
+        max_cplx: f64,
+    ) -> (Self::UnmutateToken, f64) {
+        match (value.as_mut(), cache.as_mut()) {
+            (Some(x), Some(c)) if self.rng.usize(..100) != 0 => {
+                let (t, cplx) = self.mutator.random_mutate(x, c, max_cplx);
+                (UnmutateToken::Inner(t), 1.0 + cplx)
+            }
+            _ => {
+                let (new_value, cplx) = self.random_arbitrary(max_cplx);
+                (self.replace(value, cache, new_value), cplx)
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut Option<Box<T>>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateToken::Replace(v, c) => {
+                *value = v;
+                *cache = c;
+            }
+            UnmutateToken::ReplaceInner(x) => {
+                if let Some(v) = value {
+                    **v = x;
+                }
+            }
+            UnmutateToken::Inner(t) => {
+                if let (Some(x), Some(c)) = (value.as_mut(), cache.as_mut()) {
+                    self.mutator.unmutate(x, c, t);
+                }
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        value: &'a Option<Box<T>>,
+        cache: &'a Self::Cache,
+        visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+        if let (Some(x), Some(c)) = (value, cache) {
+            visit(x, self.mutator.complexity(x, c));
+            self.mutator.visit_subvalues(x, c, visit);
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+}
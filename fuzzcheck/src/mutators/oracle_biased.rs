@@ -0,0 +1,261 @@
+//! A wrapper that keeps most, but not all, generated values within the subset accepted by an oracle.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::oracle_biased::OracleBiasedMutator;
+//!
+//! // spend 90% of the mutations on byte buffers starting with a valid magic number
+//! let m = OracleBiasedMutator::new(<Vec<u8>>::default_mutator(), |x: &Vec<u8>| x.starts_with(b"FR"), 0.9);
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::Mutator;
+
+/// The maximum number of values that are generated by each method of the mutator
+/// while looking for a value accepted by the oracle.
+const MAX_RANDOM_ATTEMPTS: usize = 100;
+
+/// An [`OracleBiasedMutator`] biases the values generated by a mutator towards
+/// those accepted by an oracle function.
+///
+/// With probability `probability`, a generated value or mutation is only accepted
+/// if the oracle returns `true` for it. Rejected mutations are unmutated, and the
+/// next mutation is tried instead. Otherwise, with probability `1 - probability`,
+/// values rejected by the oracle are let through.
+///
+/// Unlike [`FilterMutator`](crate::mutators::filter::FilterMutator), values rejected
+/// by the oracle are still valid for the mutator, so they can be part of the corpus
+/// and be mutated further.
+///
+/// The mutator gives up looking for a value accepted by the oracle after a fixed
+/// number of attempts, so that an oracle rejecting almost every value cannot make
+/// the fuzzer stall. `random_arbitrary`, `random_mutate`, and `ordered_mutate` then
+/// return the next value regardless of the oracle, and `ordered_arbitrary` returns `None`.
+pub struct OracleBiasedMutator<T, M, Oracle>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Oracle: Fn(&T) -> bool,
+{
+    mutator: M,
+    oracle: Oracle,
+    probability: f64,
+    rng: fastrand::Rng,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M, Oracle> OracleBiasedMutator<T, M, Oracle>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Oracle: Fn(&T) -> bool,
+{
+    /// Create a new `OracleBiasedMutator`.
+    ///
+    /// `probability` is the chance, between 0.0 and 1.0, that a generated value
+    /// must be accepted by the oracle.
+    #[coverage(off)]
+    pub fn new(mutator: M, oracle: Oracle, probability: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&probability),
+            "the probability of an OracleBiasedMutator must be between 0.0 and 1.0"
+        );
+        Self {
+            mutator,
+            oracle,
+            probability,
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Whether the next generated value must be accepted by the oracle
+    #[coverage(off)]
+    fn must_satisfy_oracle(&self) -> bool {
+        self.rng.f64() < self.probability
+    }
+}
+
+impl<T, M, Oracle> Mutator<T> for OracleBiasedMutator<T, M, Oracle>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+    Oracle: Fn(&T) -> bool,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        if !self.must_satisfy_oracle() {
+            return self.mutator.ordered_arbitrary(step, max_cplx);
+        }
+        for _ in 0..MAX_RANDOM_ATTEMPTS {
+            let x = self.mutator.ordered_arbitrary(step, max_cplx)?;
+            if (self.oracle)(&x.0) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let mut x = self.mutator.random_arbitrary(max_cplx);
+        if self.must_satisfy_oracle() {
+            for _ in 1..MAX_RANDOM_ATTEMPTS {
+                if (self.oracle)(&x.0) {
+                    break;
+                }
+                x = self.mutator.random_arbitrary(max_cplx);
+            }
+        }
+        x
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if !self.must_satisfy_oracle() {
+            return self
+                .mutator
+                .ordered_mutate(value, cache, step, subvalue_provider, max_cplx);
+        }
+        for _ in 1..MAX_RANDOM_ATTEMPTS {
+            let (t, cplx) = self
+                .mutator
+                .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)?;
+            if (self.oracle)(value) {
+                return Some((t, cplx));
+            } else {
+                self.mutator.unmutate(value, cache, t);
+            }
+        }
+        self.mutator
+            .ordered_mutate(value, cache, step, subvalue_provider, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        if self.must_satisfy_oracle() {
+            for _ in 1..MAX_RANDOM_ATTEMPTS {
+                let (t, cplx) = self.mutator.random_mutate(value, cache, max_cplx);
+                if (self.oracle)(value) {
+                    return (t, cplx);
+                } else {
+                    self.mutator.unmutate(value, cache, t);
+                }
+            }
+        }
+        self.mutator.random_mutate(value, cache, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(value, cache, t)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+}
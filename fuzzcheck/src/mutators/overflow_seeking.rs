@@ -0,0 +1,476 @@
+//! A mutator for pairs of integers whose sum, difference, or product overflows.
+//!
+//! ```
+//! use fuzzcheck::mutators::overflow_seeking::OverflowSeekingMutator;
+//!
+//! // impl Mutator<(u32, u32)>, generating operands such as (u32::MAX, 1) or (65536, 65536) first
+//! let m = OverflowSeekingMutator::<u32>::new();
+//! ```
+use std::any::Any;
+
+use crate::{DefaultMutator, Mutator};
+
+/// An integer type whose arithmetic overflows can be sought by an [`OverflowSeekingMutator`].
+///
+/// The computations are done on `i128`, which can represent every value of the
+/// implementing types, as well as the results of adding or subtracting two of them.
+pub trait OverflowInteger: Copy + PartialEq + DefaultMutator + 'static {
+    /// The smallest value of the type
+    const MIN_I128: i128;
+    /// The largest value of the type
+    const MAX_I128: i128;
+
+    #[doc(hidden)]
+    fn to_i128(self) -> i128;
+    #[doc(hidden)]
+    fn from_i128(x: i128) -> Option<Self>;
+}
+
+macro_rules! impl_overflow_integer {
+    ($name:ident) => {
+        impl OverflowInteger for $name {
+            const MIN_I128: i128 = <$name>::MIN as i128;
+            const MAX_I128: i128 = <$name>::MAX as i128;
+
+            #[coverage(off)]
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+            #[coverage(off)]
+            fn from_i128(x: i128) -> Option<Self> {
+                <$name>::try_from(x).ok()
+            }
+        }
+    };
+}
+impl_overflow_integer!(u8);
+impl_overflow_integer!(u16);
+impl_overflow_integer!(u32);
+impl_overflow_integer!(u64);
+impl_overflow_integer!(usize);
+impl_overflow_integer!(i8);
+impl_overflow_integer!(i16);
+impl_overflow_integer!(i32);
+impl_overflow_integer!(i64);
+impl_overflow_integer!(isize);
+
+/// Returns whether `x` is a value of the type `T`
+#[coverage(off)]
+fn fits<T: OverflowInteger>(x: i128) -> bool {
+    (T::MIN_I128..=T::MAX_I128).contains(&x)
+}
+
+/// Returns whether `a + b`, `a - b`, or `a * b` overflows the type `T`
+#[coverage(off)]
+pub fn overflows<T: OverflowInteger>(a: T, b: T) -> bool {
+    let (a, b) = (a.to_i128(), b.to_i128());
+    !fits::<T>(a + b) || !fits::<T>(a - b) || !a.checked_mul(b).is_some_and(fits::<T>)
+}
+
+/// Converts the candidate pairs to `T`, skipping those that do not fit and the duplicates
+#[coverage(off)]
+fn collect_pairs<T: OverflowInteger>(candidates: impl IntoIterator<Item = (i128, i128)>) -> Vec<(T, T)> {
+    let mut pairs = vec![];
+    for (a, b) in candidates {
+        if let (Some(a), Some(b)) = (T::from_i128(a), T::from_i128(b))
+            && !pairs.contains(&(a, b))
+        {
+            pairs.push((a, b));
+        }
+    }
+    pairs
+}
+
+/// The pairs of operands near the bounds of `T` that make the common operations overflow,
+/// each followed by the closest pair that does not overflow
+#[coverage(off)]
+fn boundary_pairs<T: OverflowInteger>() -> Vec<(T, T)> {
+    let (min, max) = (T::MIN_I128, T::MAX_I128);
+    let sqrt = max.isqrt();
+    collect_pairs([
+        // addition
+        (max, 1),
+        (max, 0),
+        (1, max),
+        (max, max),
+        (max / 2 + 1, max / 2 + 1),
+        (max / 2, max / 2 + 1),
+        (min, -1),
+        (min, 0),
+        (min, min),
+        // subtraction
+        (min, 1),
+        (min, 0),
+        (min, max),
+        (max, -1),
+        (0, min),
+        (-2, max),
+        (-1, max),
+        // multiplication and division
+        (sqrt + 1, sqrt + 1),
+        (sqrt, sqrt),
+        (max, 2),
+        (2, max),
+        (max / 2 + 1, 2),
+        (max / 2, 2),
+        (min, -1),
+        (-1, min),
+        (min, 2),
+        (min / 2, 2),
+        (-(sqrt + 1), sqrt + 1),
+    ])
+}
+
+/// The values of `b` around which `a + b`, `a - b`, or `a * b` starts overflowing
+#[coverage(off)]
+fn boundaries_for(a: i128, min: i128, max: i128) -> Vec<i128> {
+    let mut bs = vec![
+        max - a + 1,
+        max - a,
+        min - a - 1,
+        min - a,
+        a - min + 1,
+        a - min,
+        a - max - 1,
+        a - max,
+    ];
+    if a != 0 {
+        for bound in [max, min] {
+            let q = bound / a;
+            bs.extend([q - 1, q, q + 1]);
+        }
+    }
+    bs
+}
+
+/// The pairs obtained by moving one of the operands of `(a, b)` to an overflow boundary
+#[coverage(off)]
+fn nudged_pairs<T: OverflowInteger>(a: T, b: T) -> Vec<(T, T)> {
+    let (a, b) = (a.to_i128(), b.to_i128());
+    let (min, max) = (T::MIN_I128, T::MAX_I128);
+    let mut candidates = vec![];
+    for new_b in boundaries_for(a, min, max) {
+        candidates.push((a, new_b));
+    }
+    for new_a in boundaries_for(b, min, max) {
+        candidates.push((new_a, b));
+    }
+    candidates.extend([(b, a), (a + 1, b), (a - 1, b), (a, b + 1), (a, b - 1)]);
+    let mut pairs = collect_pairs::<T>(candidates);
+    if let (Some(a), Some(b)) = (T::from_i128(a), T::from_i128(b)) {
+        pairs.retain(
+            #[coverage(off)]
+            |p| *p != (a, b),
+        );
+    }
+    pairs
+}
+
+/// A mutator for pairs of integers `(a, b)` that seeks the operands for which `a + b`,
+/// `a - b`, or `a * b` overflows the type `T`.
+///
+/// It is meant to test arithmetic code, where overflow bugs are hidden behind operands
+/// that a uniform integer mutator is very unlikely to produce together.
+///
+/// * `ordered_arbitrary` first enumerates the pairs near `T::MIN` and `T::MAX` that make
+///   the common operations overflow, such as `(T::MAX, 1)`, `(T::MIN, -1)`, or two
+///   operands just above the square root of `T::MAX`, each followed by the closest pair
+///   that does not overflow.
+/// * `ordered_mutate` first moves one of the operands to the values where an operation
+///   starts overflowing, given the other operand.
+///
+/// After that, the operands are generated and mutated by the default mutator of `T`.
+/// Every pair of integers is valid.
+pub struct OverflowSeekingMutator<T>
+where
+    T: OverflowInteger,
+{
+    mutator: T::Mutator,
+    pairs: Vec<(T, T)>,
+    rng: fastrand::Rng,
+}
+
+impl<T> OverflowSeekingMutator<T>
+where
+    T: OverflowInteger,
+{
+    #[coverage(off)]
+    pub fn new() -> Self {
+        Self {
+            mutator: T::default_mutator(),
+            pairs: boundary_pairs(),
+            rng: fastrand::Rng::new(),
+        }
+    }
+}
+
+impl<T> Default for OverflowSeekingMutator<T>
+where
+    T: OverflowInteger,
+{
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ArbitraryStep<AS> {
+    /// the index of the next pair of `boundary_pairs` to generate
+    pair: usize,
+    inner: AS,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct MutationStep<T, MS> {
+    /// the pairs of the nudged value that remain to be tried
+    nudged: Vec<(T, T)>,
+    a: MS,
+    b: MS,
+    mutate_a: bool,
+}
+
+#[doc(hidden)]
+pub enum UnmutateToken<T, C, U> {
+    Replace((T, T), (C, C)),
+    A(U),
+    B(U),
+}
+
+impl<T> OverflowSeekingMutator<T>
+where
+    T: OverflowInteger,
+{
+    /// The maximum complexity of each operand, given the maximum complexity of the pair
+    #[coverage(off)]
+    fn operand_max_cplx(&self, max_cplx: f64) -> f64 {
+        max_cplx - self.mutator.min_complexity()
+    }
+
+    /// Replaces the pair and its cache with a new one, returning the token to restore them
+    #[coverage(off)]
+    fn replace(
+        &self,
+        value: &mut (T, T),
+        cache: &mut <Self as Mutator<(T, T)>>::Cache,
+        new_value: (T, T),
+    ) -> (<Self as Mutator<(T, T)>>::UnmutateToken, f64) {
+        let new_cache = self.validate_value(&new_value).unwrap();
+        let cplx = self.complexity(&new_value, &new_cache);
+        let old_value = std::mem::replace(value, new_value);
+        let old_cache = std::mem::replace(cache, new_cache);
+        (UnmutateToken::Replace(old_value, old_cache), cplx)
+    }
+}
+
+impl<T> Mutator<(T, T)> for OverflowSeekingMutator<T>
+where
+    T: OverflowInteger,
+{
+    #[doc(hidden)]
+    type Cache = (<T::Mutator as Mutator<T>>::Cache, <T::Mutator as Mutator<T>>::Cache);
+    #[doc(hidden)]
+    type MutationStep = MutationStep<T, <T::Mutator as Mutator<T>>::MutationStep>;
+    #[doc(hidden)]
+    type ArbitraryStep = ArbitraryStep<<T::Mutator as Mutator<T>>::ArbitraryStep>;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken<T, <T::Mutator as Mutator<T>>::Cache, <T::Mutator as Mutator<T>>::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        ArbitraryStep {
+            pair: 0,
+            inner: self.mutator.default_arbitrary_step(),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &(T, T)) -> bool {
+        self.mutator.is_valid(&value.0) && self.mutator.is_valid(&value.1)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &(T, T)) -> Option<Self::Cache> {
+        Some((
+            self.mutator.validate_value(&value.0)?,
+            self.mutator.validate_value(&value.1)?,
+        ))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &(T, T), cache: &Self::Cache) -> Self::MutationStep {
+        let mut nudged = nudged_pairs(value.0, value.1);
+        // the pairs are popped from the end
+        nudged.reverse();
+        MutationStep {
+            nudged,
+            a: self.mutator.default_mutation_step(&value.0, &cache.0),
+            b: self.mutator.default_mutation_step(&value.1, &cache.1),
+            mutate_a: true,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        2.0 * self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        2.0 * self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        2.0 * self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &(T, T), cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(&value.0, &cache.0) + self.mutator.complexity(&value.1, &cache.1)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<((T, T), f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if let Some(&pair) = self.pairs.get(step.pair) {
+            step.pair += 1;
+            let cache = self.validate_value(&pair).unwrap();
+            return Some((pair, self.complexity(&pair, &cache)));
+        }
+        let max_cplx = self.operand_max_cplx(max_cplx);
+        let (a, a_cplx) = self.mutator.ordered_arbitrary(&mut step.inner, max_cplx)?;
+        let (b, b_cplx) = self.mutator.random_arbitrary(max_cplx);
+        Some(((a, b), a_cplx + b_cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> ((T, T), f64) {
+        if self.rng.bool() {
+            let pair = self.pairs[self.rng.usize(..self.pairs.len())];
+            let cache = self.validate_value(&pair).unwrap();
+            return (pair, self.complexity(&pair, &cache));
+        }
+        let max_cplx = self.operand_max_cplx(max_cplx);
+        let (a, a_cplx) = self.mutator.random_arbitrary(max_cplx);
+        let (b, b_cplx) = self.mutator.random_arbitrary(max_cplx);
+        ((a, b), a_cplx + b_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut (T, T),
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if let Some(pair) = step.nudged.pop() {
+            return Some(self.replace(value, cache, pair));
+        }
+        let operand_max_cplx = self.operand_max_cplx(max_cplx);
+        // alternate between the operands, until neither can be mutated anymore
+        for _ in 0..2 {
+            let mutate_a = step.mutate_a;
+            step.mutate_a = !step.mutate_a;
+            if mutate_a {
+                if let Some((t, cplx)) = self.mutator.ordered_mutate(
+                    &mut value.0,
+                    &mut cache.0,
+                    &mut step.a,
+                    subvalue_provider,
+                    operand_max_cplx,
+                ) {
+                    return Some((UnmutateToken::A(t), cplx + self.mutator.complexity(&value.1, &cache.1)));
+                }
+            } else if let Some((t, cplx)) = self.mutator.ordered_mutate(
+                &mut value.1,
+                &mut cache.1,
+                &mut step.b,
+                subvalue_provider,
+                operand_max_cplx,
+            ) {
+                return Some((UnmutateToken::B(t), self.mutator.complexity(&value.0, &cache.0) + cplx));
+            }
+        }
+        None
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut (T, T), cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        if self.rng.bool() {
+            let nudged = nudged_pairs(value.0, value.1);
+            if !nudged.is_empty() {
+                let pair = nudged[self.rng.usize(..nudged.len())];
+                return self.replace(value, cache, pair);
+            }
+        }
+        let operand_max_cplx = self.operand_max_cplx(max_cplx);
+        if self.rng.bool() {
+            let (t, cplx) = self.mutator.random_mutate(&mut value.0, &mut cache.0, operand_max_cplx);
+            (UnmutateToken::A(t), cplx + self.mutator.complexity(&value.1, &cache.1))
+        } else {
+            let (t, cplx) = self.mutator.random_mutate(&mut value.1, &mut cache.1, operand_max_cplx);
+            (UnmutateToken::B(t), self.mutator.complexity(&value.0, &cache.0) + cplx)
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut (T, T), cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateToken::Replace(v, c) => {
+                *value = v;
+                *cache = c;
+            }
+            UnmutateToken::A(t) => self.mutator.unmutate(&mut value.0, &mut cache.0, t),
+            UnmutateToken::B(t) => self.mutator.unmutate(&mut value.1, &mut cache.1, t),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a (T, T), cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        visit(&value.0, self.mutator.complexity(&value.0, &cache.0));
+        visit(&value.1, self.mutator.complexity(&value.1, &cache.1));
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+}
@@ -0,0 +1,94 @@
+//! A wrapper that creates independent copies of a mutator for multi-threaded fuzzing.
+//!
+//! Most mutators hold a [`fastrand::Rng`], which is `Send` but not `Sync`. They
+//! can therefore be moved to another thread but not shared between threads.
+//! Instead of sharing a single mutator, [`ParallelMutator`] keeps the function
+//! that was used to build it and calls it again whenever a worker needs its own
+//! mutator. Each copy is built from scratch, which means that it gets its own
+//! random number generator, seeded independently from the others.
+//!
+//! ```
+//! use std::thread;
+//!
+//! use fuzzcheck::mutators::parallel::ParallelMutator;
+//! use fuzzcheck::{DefaultMutator, Mutator};
+//!
+//! let m = ParallelMutator::new(<Vec<u8>>::default_mutator);
+//! let workers = (0..4)
+//!     .map(|_| {
+//!         let worker = m.fork();
+//!         thread::spawn(move || worker.random_arbitrary(100.0))
+//!     })
+//!     .collect::<Vec<_>>();
+//! for worker in workers {
+//!     worker.join().unwrap();
+//! }
+//! ```
+//!
+//! Mutators that are not `Send`, such as [`RecursiveMutator`](crate::mutators::recursive::RecursiveMutator),
+//! cannot be forked on the main thread and then sent to a worker. Instead, send the
+//! [`constructor`](ParallelMutator::constructor) to the worker thread and build the mutator there with
+//! [`ParallelMutator::from_constructor`].
+use std::sync::Arc;
+
+use super::MutatorWrapper;
+
+/// A mutator that can [`fork`](ParallelMutator::fork) itself into independent copies
+/// that can be sent to worker threads.
+///
+/// It implements `Mutator<T>` whenever the mutator built by `F` does.
+pub struct ParallelMutator<M, F>
+where
+    F: Fn() -> M,
+{
+    mutator: M,
+    make_mutator: Arc<F>,
+}
+
+impl<M, F> ParallelMutator<M, F>
+where
+    F: Fn() -> M,
+{
+    /// Create a new `ParallelMutator` from a function that builds the wrapped mutator.
+    #[coverage(off)]
+    pub fn new(make_mutator: F) -> Self {
+        Self::from_constructor(Arc::new(make_mutator))
+    }
+
+    /// Create a new `ParallelMutator` from a function shared with other `ParallelMutator`s.
+    #[coverage(off)]
+    pub fn from_constructor(make_mutator: Arc<F>) -> Self {
+        Self {
+            mutator: make_mutator(),
+            make_mutator,
+        }
+    }
+
+    /// The function used to build the wrapped mutator.
+    ///
+    /// It is `Send` if `F` is `Send + Sync`, even when the mutator itself is not `Send`.
+    #[coverage(off)]
+    pub fn constructor(&self) -> Arc<F> {
+        self.make_mutator.clone()
+    }
+
+    /// Create a new copy of the mutator, with its own internal state and random number generator.
+    ///
+    /// The returned mutator is `Send` if `M` is `Send` and `F` is `Send + Sync`.
+    #[coverage(off)]
+    pub fn fork(&self) -> Self {
+        Self::from_constructor(self.constructor())
+    }
+}
+
+impl<M, F> MutatorWrapper for ParallelMutator<M, F>
+where
+    F: Fn() -> M,
+{
+    type Wrapped = M;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
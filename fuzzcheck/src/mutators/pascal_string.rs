@@ -0,0 +1,137 @@
+//! A mutator for length-prefixed byte strings, such as Pascal strings.
+//!
+//! ```
+//! use fuzzcheck::mutators::pascal_string::PascalStringMutator;
+//!
+//! // impl Mutator<Vec<u8>>, generating [len: u8][bytes...]
+//! let m = PascalStringMutator::new();
+//! // impl Mutator<Vec<u8>>, generating [len: u16 little-endian][bytes...]
+//! let m = PascalStringMutator::<u16>::with_length_prefix();
+//! ```
+use crate::mutators::integer::U8Mutator;
+use crate::mutators::map::MapMutator;
+use crate::mutators::vector::VecMutator;
+use crate::mutators::MutatorWrapper;
+
+/// The integer type used to encode the length of a [`PascalStringMutator`]’s strings.
+///
+/// It is implemented for `u8`, `u16`, and `u32`. The length is encoded in little-endian.
+pub trait PascalLength: 'static {
+    /// The number of bytes of the length prefix
+    const SIZE: usize;
+    /// The maximum number of bytes that can follow the length prefix
+    const MAX_LEN: usize;
+
+    /// Append the encoded length to `bytes`
+    fn write(len: usize, bytes: &mut Vec<u8>);
+    /// Decode the length at the beginning of `bytes`
+    fn read(bytes: &[u8]) -> Option<usize>;
+}
+
+macro_rules! impl_pascal_length {
+    ($ty:ty) => {
+        impl PascalLength for $ty {
+            const SIZE: usize = std::mem::size_of::<$ty>();
+            const MAX_LEN: usize = <$ty>::MAX as usize;
+
+            #[coverage(off)]
+            fn write(len: usize, bytes: &mut Vec<u8>) {
+                bytes.extend_from_slice(&(len as $ty).to_le_bytes());
+            }
+            #[coverage(off)]
+            fn read(bytes: &[u8]) -> Option<usize> {
+                let prefix = bytes.get(..Self::SIZE)?;
+                Some(<$ty>::from_le_bytes(prefix.try_into().ok()?) as usize)
+            }
+        }
+    };
+}
+impl_pascal_length!(u8);
+impl_pascal_length!(u16);
+impl_pascal_length!(u32);
+
+type PascalStringMap = MapMutator<
+    Vec<u8>,
+    Vec<u8>,
+    VecMutator<u8, U8Mutator>,
+    fn(&Vec<u8>) -> Option<Vec<u8>>,
+    fn(&Vec<u8>) -> Vec<u8>,
+    fn(&Vec<u8>, f64) -> f64,
+>;
+
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn parse_pascal_string<L: PascalLength>(value: &Vec<u8>) -> Option<Vec<u8>> {
+    let len = L::read(value)?;
+    let bytes = &value[L::SIZE..];
+    if len == bytes.len() && len <= L::MAX_LEN {
+        Some(bytes.to_vec())
+    } else {
+        None
+    }
+}
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn make_pascal_string<L: PascalLength>(bytes: &Vec<u8>) -> Vec<u8> {
+    let mut value = Vec::with_capacity(L::SIZE + bytes.len());
+    L::write(bytes.len(), &mut value);
+    value.extend_from_slice(bytes);
+    value
+}
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn pascal_string_cplx(_value: &Vec<u8>, cplx: f64) -> f64 {
+    cplx
+}
+
+/// A mutator for `Vec<u8>` made of a length prefix followed by that many bytes.
+///
+/// The prefix is an integer of type `L` (by default `u8`) and always equals the number of
+/// bytes that follow it, so that a `PascalStringMutator<u8>` generates strings of 0 to 255
+/// bytes. Values whose prefix does not match their length are rejected by `validate_value`.
+///
+/// Use [`LengthPrefixedMutator`](crate::mutators::length_prefixed::LengthPrefixedMutator)
+/// instead to generate framed messages whose length field may be wrong.
+pub struct PascalStringMutator<L: PascalLength = u8> {
+    mutator: PascalStringMap,
+    _phantom: std::marker::PhantomData<L>,
+}
+
+impl PascalStringMutator<u8> {
+    /// Create a mutator for strings prefixed by a single-byte length
+    #[coverage(off)]
+    pub fn new() -> Self {
+        Self::with_length_prefix()
+    }
+}
+impl Default for PascalStringMutator<u8> {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L: PascalLength> PascalStringMutator<L> {
+    /// Create a mutator for strings prefixed by a length of type `L`
+    #[coverage(off)]
+    pub fn with_length_prefix() -> Self {
+        Self {
+            mutator: MapMutator::new(
+                VecMutator::new(U8Mutator::default(), 0..=L::MAX_LEN),
+                parse_pascal_string::<L>,
+                make_pascal_string::<L>,
+                pascal_string_cplx,
+            ),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<L: PascalLength> MutatorWrapper for PascalStringMutator<L> {
+    type Wrapped = PascalStringMap;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
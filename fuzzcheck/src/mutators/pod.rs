@@ -0,0 +1,231 @@
+//! A mutator for plain-old-data types, which mutates their raw bytes.
+//!
+//! This is useful to fuzz `#[repr(C)]` structs that are passed over FFI or
+//! read directly from a byte buffer. Any byte pattern is a valid value of a
+//! [`Pod`](bytemuck::Pod) type, so the mutator can freely change the bytes of
+//! the value without knowing anything about its fields.
+//!
+//! ```
+//! use bytemuck::{Pod, Zeroable};
+//! use fuzzcheck::mutators::pod::PodMutator;
+//!
+//! #[repr(C)]
+//! #[derive(Clone, Copy, Pod, Zeroable)]
+//! struct Header {
+//!     magic: u32,
+//!     length: u16,
+//!     flags: u16,
+//! }
+//!
+//! // impl Mutator<Header>
+//! let m = PodMutator::<Header>::new();
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use bytemuck::Pod;
+
+use crate::Mutator;
+
+/// A mutator for values of any type implementing [`bytemuck::Pod`].
+///
+/// The mutations are performed on the bytes of the value, which are then
+/// reinterpreted as a value of type `T`. Its complexity is always the number
+/// of bits of `T`.
+pub struct PodMutator<T>
+where
+    T: Pod,
+{
+    rng: fastrand::Rng,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> PodMutator<T>
+where
+    T: Pod,
+{
+    #[coverage(off)]
+    pub fn new() -> Self {
+        Self {
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    #[coverage(off)]
+    fn nbr_bits() -> usize {
+        std::mem::size_of::<T>() * 8
+    }
+}
+
+impl<T> Default for PodMutator<T>
+where
+    T: Pod,
+{
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Mutator<T> for PodMutator<T>
+where
+    T: Pod,
+{
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = usize;
+    #[doc(hidden)]
+    type ArbitraryStep = bool;
+    #[doc(hidden)]
+    type UnmutateToken = T;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        false
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, _value: &T) -> bool {
+        true
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, _value: &T) -> Option<Self::Cache> {
+        Some(())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &T, _cache: &Self::Cache) -> Self::MutationStep {
+        0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        Self::nbr_bits() as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        Self::nbr_bits() as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        Self::nbr_bits() as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, _value: &T, _cache: &Self::Cache) -> f64 {
+        Self::nbr_bits() as f64
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if !*step {
+            // the first generated value is the one whose bytes are all zero
+            *step = true;
+            Some((T::zeroed(), self.min_complexity()))
+        } else {
+            Some(self.random_arbitrary(max_cplx))
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, _max_cplx: f64) -> (T, f64) {
+        let mut value = T::zeroed();
+        self.rng.fill(bytemuck::bytes_of_mut(&mut value));
+        (value, self.min_complexity())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        _subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() || Self::nbr_bits() == 0 {
+            return None;
+        }
+        if *step < Self::nbr_bits() {
+            // flip each bit one after the other
+            let old_value = *value;
+            let bytes = bytemuck::bytes_of_mut(value);
+            bytes[*step / 8] ^= 1 << (*step % 8);
+            *step += 1;
+            Some((old_value, self.min_complexity()))
+        } else {
+            Some(self.random_mutate(value, cache, max_cplx))
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, _cache: &mut Self::Cache, _max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let old_value = *value;
+        let bytes = bytemuck::bytes_of_mut(value);
+        if bytes.is_empty() {
+            return (old_value, self.min_complexity());
+        }
+        match self.rng.u8(..3) {
+            0 => {
+                let bit = self.rng.usize(..bytes.len() * 8);
+                bytes[bit / 8] ^= 1 << (bit % 8);
+            }
+            1 => {
+                let idx = self.rng.usize(..bytes.len());
+                bytes[idx] = self.rng.u8(..);
+            }
+            _ => {
+                let start = self.rng.usize(..bytes.len());
+                let end = self.rng.usize(start + 1..=bytes.len().min(start + 8));
+                self.rng.fill(&mut bytes[start..end]);
+            }
+        }
+        (old_value, self.min_complexity())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = t;
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, _value: &'a T, _cache: &'a Self::Cache, _visit: &mut dyn FnMut(&'a dyn Any, f64)) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
+}
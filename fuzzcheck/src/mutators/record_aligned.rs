@@ -0,0 +1,103 @@
+//! A mutator for byte buffers made of fixed-size records, such as arrays of binary structs.
+//!
+//! ```
+//! use fuzzcheck::mutators::record_aligned::RecordAlignedByteMutator;
+//!
+//! // impl Mutator<Vec<u8>>, generating arrays of 16-byte records
+//! let m = RecordAlignedByteMutator::new(16);
+//!
+//! // the same, but with 1 to 8 records
+//! let m = RecordAlignedByteMutator::with_nbr_records(16, 1..=8);
+//! ```
+use std::ops::RangeInclusive;
+
+use crate::mutators::fixed_len_vector::FixedLenVecMutator;
+use crate::mutators::integer::U8Mutator;
+use crate::mutators::map::MapMutator;
+use crate::mutators::vector::VecMutator;
+use crate::mutators::MutatorWrapper;
+
+type RecordAlignedMap = MapMutator<
+    Vec<Vec<u8>>,
+    Vec<u8>,
+    VecMutator<Vec<u8>, FixedLenVecMutator<u8, U8Mutator>>,
+    Box<dyn Fn(&Vec<u8>) -> Option<Vec<Vec<u8>>>>,
+    fn(&Vec<Vec<u8>>) -> Vec<u8>,
+    fn(&Vec<u8>, f64) -> f64,
+>;
+
+/// A mutator for `Vec<u8>` whose length is always a multiple of a record size.
+///
+/// The value is mutated as a vector of records rather than as a vector of bytes.
+/// So the mutations insert, remove, replace, or copy whole records, or change the
+/// bytes at some offsets within a record, but they never shift the bytes that follow by
+/// less than a record. This keeps the generated values structurally plausible for
+/// binary formats made of an array of fixed-size structs.
+///
+/// Values whose length is not a multiple of the record size are rejected by `validate_value`.
+pub struct RecordAlignedByteMutator {
+    mutator: RecordAlignedMap,
+    record_size: usize,
+}
+
+impl RecordAlignedByteMutator {
+    /// Create a mutator for arrays of any number of records of `record_size` bytes
+    #[coverage(off)]
+    pub fn new(record_size: usize) -> Self {
+        Self::with_nbr_records(record_size, 0..=usize::MAX)
+    }
+
+    /// Create a mutator for arrays of records of `record_size` bytes, whose number of
+    /// records is within `nbr_records`
+    #[coverage(off)]
+    pub fn with_nbr_records(record_size: usize, nbr_records: RangeInclusive<usize>) -> Self {
+        assert!(
+            record_size > 0,
+            "the records of a RecordAlignedByteMutator cannot be empty"
+        );
+        let record_mutator = FixedLenVecMutator::new_with_repeated_mutator(U8Mutator::default(), record_size);
+        Self {
+            mutator: MapMutator::new(
+                VecMutator::new(record_mutator, nbr_records),
+                Box::new(
+                    #[coverage(off)]
+                    move |value: &Vec<u8>| {
+                        if !value.len().is_multiple_of(record_size) {
+                            return None;
+                        }
+                        Some(value.chunks(record_size).map(<[u8]>::to_vec).collect())
+                    },
+                ),
+                concat_records,
+                record_aligned_cplx,
+            ),
+            record_size,
+        }
+    }
+
+    /// The number of bytes of each record
+    #[coverage(off)]
+    pub fn record_size(&self) -> usize {
+        self.record_size
+    }
+}
+
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn concat_records(records: &Vec<Vec<u8>>) -> Vec<u8> {
+    records.concat()
+}
+#[allow(clippy::ptr_arg)]
+#[coverage(off)]
+fn record_aligned_cplx(_value: &Vec<u8>, cplx: f64) -> f64 {
+    cplx
+}
+
+impl MutatorWrapper for RecordAlignedByteMutator {
+    type Wrapped = RecordAlignedMap;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
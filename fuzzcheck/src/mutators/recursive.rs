@@ -4,6 +4,10 @@
 //! 1. [`RecursiveMutator`] is the top-level mutator for the recursive type
 //! 2. [`RecurToMutator`] is the mutator used at points of recursion. It is essentially a weak reference to [`RecursiveMutator`]
 //!
+//! Both of them use [`Rc`] and [`Weak`](std::rc::Weak) internally and are therefore not `Send`. To fuzz a
+//! recursive type from multiple threads, build one mutator per thread with
+//! [`ParallelMutator::from_constructor`](crate::mutators::parallel::ParallelMutator::from_constructor).
+//!
 //! In practice, you will want to use the [`make_mutator!`](crate::make_mutator) procedural macro to create recursive mutators.
 //! For example:
 //! ```
@@ -47,11 +51,16 @@
 //! ```
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::rc::{Rc, Weak};
 
 use crate::Mutator;
 
+/// The number of attempts made by the arbitrary and mutation methods of a recursive mutator
+/// to find a value within its maximum depth, before falling back to a leaf value or giving up
+const MAX_DEPTH_ATTEMPTS: usize = 100;
+
 /// The ArbitraryStep that is used for recursive mutators
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum RecursingArbitraryStep<AS> {
@@ -112,6 +121,7 @@ let s_mutator = RecursiveMutator::new(|mutator| {
 pub struct RecursiveMutator<M> {
     pub mutator: Rc<M>,
     rng: fastrand::Rng,
+    max_depth: Option<usize>,
 }
 impl<M> RecursiveMutator<M> {
     /// Create a new `RecursiveMutator` using a weak reference to itself.
@@ -120,8 +130,18 @@ impl<M> RecursiveMutator<M> {
         Self {
             mutator: Rc::new_cyclic(data_fn),
             rng: fastrand::Rng::new(),
+            max_depth: None,
         }
     }
+
+    /// The mutator that is shared with the [`RecurToMutator`]s at the points of recursion.
+    ///
+    /// There is no `inner_mut` accessor, because the mutator is referenced by those
+    /// [`RecurToMutator`]s.
+    #[coverage(off)]
+    pub fn inner(&self) -> &M {
+        &self.mutator
+    }
 }
 
 /// A mutator that defers to a weak reference of a
@@ -210,18 +230,14 @@ where
     #[doc(hidden)]
     #[coverage(off)]
     fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
-        match step {
-            RecursingArbitraryStep::Default => {
-                let inner_step = self.reference.upgrade().unwrap().default_arbitrary_step();
-                *step = RecursingArbitraryStep::Initialized(inner_step);
-                self.ordered_arbitrary(step, max_cplx)
-            }
-            RecursingArbitraryStep::Initialized(inner_step) => self
-                .reference
-                .upgrade()
-                .unwrap()
-                .ordered_arbitrary(inner_step, max_cplx),
+        let mutator = self.reference.upgrade().unwrap();
+        if let RecursingArbitraryStep::Default = step {
+            *step = RecursingArbitraryStep::Initialized(mutator.default_arbitrary_step());
         }
+        let RecursingArbitraryStep::Initialized(inner_step) = step else {
+            unreachable!()
+        };
+        mutator.ordered_arbitrary(inner_step, max_cplx)
     }
 
     #[doc(hidden)]
@@ -230,6 +246,12 @@ where
         self.reference.upgrade().unwrap().random_arbitrary(max_cplx)
     }
 
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        self.reference.upgrade().unwrap().arbitrary_at_complexity(target_cplx)
+    }
+
     #[doc(hidden)]
     #[coverage(off)]
     fn ordered_mutate(
@@ -283,6 +305,78 @@ pub enum RecursiveMutatorUnmutateToken<T, UnmutateToken> {
     Token(UnmutateToken),
 }
 
+impl<M> RecursiveMutator<M> {
+    /// Limit the nesting depth of the generated values to `max_depth`.
+    ///
+    /// The depth of a value is the largest number of values of the same type nested in one
+    /// another that it contains. Values deeper than `max_depth` are rejected by `validate_value`.
+    /// The arbitrary values and mutations that would exceed it are retried, with a decreasing
+    /// complexity for the arbitrary values, before falling back to a leaf value or giving up.
+    ///
+    /// This is useful when the test function, or the code that consumes the values,
+    /// overflows its stack on deeply nested values. Note that checking the depth of each
+    /// generated value requires validating its nested values again, which slows down the
+    /// mutations.
+    #[coverage(off)]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// The maximum nesting depth of the generated values, if any
+    #[coverage(off)]
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns the nesting depth of the value, i.e. the largest number of values of type `T`
+    /// nested in one another that it contains.
+    ///
+    /// Visiting the subvalues of a value also visits the subvalues of its subvalues, so the
+    /// depth of each nested value is memoized in `depths`, keyed by its address.
+    #[coverage(off)]
+    fn depth<T>(&self, value: &T, depths: &mut HashMap<*const T, usize>) -> usize
+    where
+        M: Mutator<T>,
+        T: Clone + 'static,
+    {
+        if let Some(depth) = depths.get(&(value as *const T)) {
+            return *depth;
+        }
+        let mut depth = 0;
+        if let Some(cache) = self.mutator.validate_value(value) {
+            self.mutator.visit_subvalues(
+                value,
+                &cache,
+                &mut #[coverage(off)]
+                |subvalue, _| {
+                    if let Some(subvalue) = subvalue.downcast_ref::<T>() {
+                        depth = depth.max(self.depth(subvalue, depths) + 1);
+                    }
+                },
+            );
+        }
+        depths.insert(value as *const T, depth);
+        depth
+    }
+
+    /// Returns whether the value is within the maximum depth
+    ///
+    /// The cache of a mutated value may only be updated when it is unmutated, so
+    /// the value is validated again to visit its subvalues.
+    #[coverage(off)]
+    fn is_within_max_depth<T>(&self, value: &T) -> bool
+    where
+        M: Mutator<T>,
+        T: Clone + 'static,
+    {
+        self.max_depth.is_none_or(
+            #[coverage(off)]
+            |max_depth| self.depth(value, &mut HashMap::new()) <= max_depth,
+        )
+    }
+}
+
 impl<M, T: Clone + 'static> Mutator<T> for RecursiveMutator<M>
 where
     M: Mutator<T>,
@@ -334,6 +428,9 @@ where
 
             self.mutator
                 .visit_subvalues(&cloned_self.0, &cloned_self.1, &mut visit_subvalues);
+            if !self.is_within_max_depth(&cloned_self.0) {
+                return None;
+            }
             Some(RecursiveMutatorCache {
                 inner: cache,
                 _cloned_self: cloned_self,
@@ -381,13 +478,48 @@ where
     #[doc(hidden)]
     #[coverage(off)]
     fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
-        self.mutator.ordered_arbitrary(step, max_cplx)
+        if self.max_depth.is_none() {
+            return self.mutator.ordered_arbitrary(step, max_cplx);
+        }
+        for _ in 0..MAX_DEPTH_ATTEMPTS {
+            let (value, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+            if self.is_within_max_depth(&value) {
+                return Some((value, cplx));
+            }
+        }
+        // the least complex values are leaves
+        self.mutator.ordered_arbitrary(step, self.min_complexity()).filter(
+            #[coverage(off)]
+            |(value, _)| self.is_within_max_depth(value),
+        )
     }
 
     #[doc(hidden)]
     #[coverage(off)]
     fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
-        self.mutator.random_arbitrary(max_cplx)
+        if self.max_depth.is_none() {
+            return self.mutator.random_arbitrary(max_cplx);
+        }
+        let mut max_cplx = max_cplx;
+        for _ in 0..MAX_DEPTH_ATTEMPTS {
+            let (value, cplx) = self.mutator.random_arbitrary(max_cplx);
+            if self.is_within_max_depth(&value) {
+                return (value, cplx);
+            }
+            // less complex values are less deeply nested
+            max_cplx = self.min_complexity().max(max_cplx * 0.9);
+        }
+        // the least complex values are leaves
+        self.mutator.random_arbitrary(self.min_complexity())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.arbitrary_at_complexity(target_cplx).filter(
+            #[coverage(off)]
+            |(value, _)| self.is_within_max_depth(value),
+        )
     }
 
     #[doc(hidden)]
@@ -401,6 +533,7 @@ where
         max_cplx: f64,
     ) -> Option<(Self::UnmutateToken, f64)> {
         if step.idx_sub_self_values < cache.sub_self_values.len() {
+            // a subvalue is never deeper than the value containing it
             let (subself, cplx) = cache.sub_self_values[step.idx_sub_self_values];
             let subself = unsafe { subself.as_ref() }.unwrap();
             let mut tmp = subself.clone();
@@ -408,17 +541,20 @@ where
             std::mem::swap(value, &mut tmp);
             Some((RecursiveMutatorUnmutateToken::Replace(tmp), cplx))
         } else {
-            if let Some((token, cplx)) = self.mutator.ordered_mutate(
-                value,
-                &mut cache.inner,
-                &mut step.mutation_step,
-                subvalue_provider,
-                max_cplx,
-            ) {
-                Some((RecursiveMutatorUnmutateToken::Token(token), cplx))
-            } else {
-                None
+            for _ in 0..MAX_DEPTH_ATTEMPTS {
+                let (token, cplx) = self.mutator.ordered_mutate(
+                    value,
+                    &mut cache.inner,
+                    &mut step.mutation_step,
+                    subvalue_provider,
+                    max_cplx,
+                )?;
+                if self.is_within_max_depth(value) {
+                    return Some((RecursiveMutatorUnmutateToken::Token(token), cplx));
+                }
+                self.mutator.unmutate(value, &mut cache.inner, token);
             }
+            None
         }
     }
 
@@ -433,9 +569,16 @@ where
             std::mem::swap(value, &mut tmp);
             (RecursiveMutatorUnmutateToken::Replace(tmp), cplx)
         } else {
-            let (token, cplx) = self.mutator.random_mutate(value, &mut cache.inner, max_cplx);
-            let token = RecursiveMutatorUnmutateToken::Token(token);
-            (token, cplx)
+            for _ in 0..MAX_DEPTH_ATTEMPTS {
+                let (token, cplx) = self.mutator.random_mutate(value, &mut cache.inner, max_cplx);
+                if self.is_within_max_depth(value) {
+                    return (RecursiveMutatorUnmutateToken::Token(token), cplx);
+                }
+                self.mutator.unmutate(value, &mut cache.inner, token);
+            }
+            let (mut tmp, cplx) = self.random_arbitrary(max_cplx);
+            std::mem::swap(value, &mut tmp);
+            (RecursiveMutatorUnmutateToken::Replace(tmp), cplx)
         }
     }
 
@@ -455,4 +598,32 @@ where
     fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
         self.mutator.visit_subvalues(value, &cache.inner, visit)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(
+            &self.rng,
+            self.mutator.as_ref(),
+        ))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
 }
@@ -3,6 +3,9 @@ use std::result::Result;
 use fuzzcheck_mutators_derive::make_mutator;
 extern crate self as fuzzcheck;
 
+use crate::mutators::unit::UnitMutator;
+use crate::Mutator;
+
 make_mutator! {
     name: ResultMutator,
     default: true,
@@ -11,3 +14,41 @@ make_mutator! {
         Err(E)
     }
 }
+
+impl<T, E, M> ResultMutator<T, E, UnitMutator<T>, M>
+where
+    T: Clone + 'static,
+    E: Clone + 'static,
+    M: Mutator<E>,
+{
+    /// Create a `ResultMutator` that only fuzzes the `Err` variant, using `err_mutator`.
+    ///
+    /// The `Ok` variant is pinned to `ok_value` and is never generated. An `Ok` value, for
+    /// example read from a corpus, is still valid and is the simplest possible value, but it
+    /// is only ever mutated into an `Err` value.
+    #[coverage(off)]
+    pub fn fuzz_err_only(ok_value: T, err_mutator: M) -> Self {
+        let mut m = Self::new(UnitMutator::new(ok_value, 0.0), err_mutator);
+        m.mutator.generate_only_with(vec![1]);
+        m
+    }
+}
+
+impl<T, E, M> ResultMutator<T, E, M, UnitMutator<E>>
+where
+    T: Clone + 'static,
+    E: Clone + 'static,
+    M: Mutator<T>,
+{
+    /// Create a `ResultMutator` that only fuzzes the `Ok` variant, using `ok_mutator`.
+    ///
+    /// The `Err` variant is pinned to `err_value` and is never generated. An `Err` value, for
+    /// example read from a corpus, is still valid and is the simplest possible value, but it
+    /// is only ever mutated into an `Ok` value.
+    #[coverage(off)]
+    pub fn fuzz_ok_only(ok_mutator: M, err_value: E) -> Self {
+        let mut m = Self::new(ok_mutator, UnitMutator::new(err_value, 0.0));
+        m.mutator.generate_only_with(vec![0]);
+        m
+    }
+}
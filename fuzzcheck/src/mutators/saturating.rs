@@ -0,0 +1,330 @@
+//! A mutator for [`Saturating`] integers that generates the saturation bounds often.
+//!
+//! ```
+//! use std::num::Saturating;
+//! use fuzzcheck::DefaultMutator;
+//!
+//! // impl Mutator<Saturating<u32>>
+//! let m = <Saturating<u32>>::default_mutator();
+//! ```
+use std::any::Any;
+use std::num::Saturating;
+
+use crate::{DefaultMutator, Mutator};
+
+/// The probability, out of 100, that `random_arbitrary` and `random_mutate` produce one of the boundaries
+const BOUNDARY_PERCENT: usize = 25;
+
+/// A mutator for [`Saturating<T>`] values, built from a mutator of `T` and a list of
+/// boundaries, which are usually `T::MIN` and `T::MAX`.
+///
+/// The boundaries are the first values produced by `ordered_arbitrary` and the first
+/// replacements tried by `ordered_mutate`, and they are produced a quarter of the time
+/// by `random_arbitrary` and `random_mutate`. This exercises the code paths where the
+/// saturating arithmetic clamps its results. Otherwise, the values are generated by the
+/// wrapped mutator.
+///
+/// The complexity of a value is its complexity according to the wrapped mutator.
+pub struct SaturatingMutator<T, M>
+where
+    T: Clone + PartialEq + 'static,
+    M: Mutator<T>,
+{
+    mutator: M,
+    boundaries: Vec<T>,
+    rng: fastrand::Rng,
+}
+
+impl<T, M> SaturatingMutator<T, M>
+where
+    T: Clone + PartialEq + 'static,
+    M: Mutator<T>,
+{
+    /// Create a `SaturatingMutator` producing the given boundaries often.
+    ///
+    /// The boundaries that the wrapped mutator rejects are ignored.
+    #[coverage(off)]
+    pub fn new(mutator: M, boundaries: Vec<T>) -> Self {
+        let boundaries = boundaries
+            .into_iter()
+            .filter(
+                #[coverage(off)]
+                |b| mutator.is_valid(b),
+            )
+            .collect();
+        Self {
+            mutator,
+            boundaries,
+            rng: fastrand::Rng::new(),
+        }
+    }
+
+    /// Replaces the value and its cache with the given boundary, returning the token to restore them
+    #[coverage(off)]
+    fn replace_with_boundary(
+        &self,
+        value: &mut Saturating<T>,
+        cache: &mut M::Cache,
+        boundary: &T,
+    ) -> (UnmutateToken<T, M::Cache, M::UnmutateToken>, f64) {
+        let new_cache = self.mutator.validate_value(boundary).unwrap();
+        let cplx = self.mutator.complexity(boundary, &new_cache);
+        let old_value = std::mem::replace(&mut value.0, boundary.clone());
+        let old_cache = std::mem::replace(cache, new_cache);
+        (UnmutateToken::Replace(old_value, old_cache), cplx)
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ArbitraryStep<AS> {
+    /// the index of the next boundary to generate
+    boundary: usize,
+    inner: AS,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct MutationStep<MS> {
+    /// the index of the next boundary to try as a replacement
+    boundary: usize,
+    inner: MS,
+}
+
+#[doc(hidden)]
+pub enum UnmutateToken<T, C, U> {
+    Replace(T, C),
+    Inner(U),
+}
+
+impl<T, M> Mutator<Saturating<T>> for SaturatingMutator<T, M>
+where
+    T: Clone + PartialEq + 'static,
+    M: Mutator<T>,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = MutationStep<M::MutationStep>;
+    #[doc(hidden)]
+    type ArbitraryStep = ArbitraryStep<M::ArbitraryStep>;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateToken<T, M::Cache, M::UnmutateToken>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        ArbitraryStep {
+            boundary: 0,
+            inner: self.mutator.default_arbitrary_step(),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &Saturating<T>) -> bool {
+        self.mutator.is_valid(&value.0)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &Saturating<T>) -> Option<Self::Cache> {
+        self.mutator.validate_value(&value.0)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &Saturating<T>, cache: &Self::Cache) -> Self::MutationStep {
+        MutationStep {
+            boundary: 0,
+            inner: self.mutator.default_mutation_step(&value.0, cache),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &Saturating<T>, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(&value.0, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(Saturating<T>, f64)> {
+        while let Some(boundary) = self.boundaries.get(step.boundary) {
+            step.boundary += 1;
+            let cache = self.mutator.validate_value(boundary).unwrap();
+            let cplx = self.mutator.complexity(boundary, &cache);
+            if cplx <= max_cplx {
+                return Some((Saturating(boundary.clone()), cplx));
+            }
+        }
+        loop {
+            let (value, cplx) = self.mutator.ordered_arbitrary(&mut step.inner, max_cplx)?;
+            // the boundaries were already generated
+            if !self.boundaries.contains(&value) {
+                return Some((Saturating(value), cplx));
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (Saturating<T>, f64) {
+        if !self.boundaries.is_empty() && self.rng.usize(..100) < BOUNDARY_PERCENT {
+            let boundary = &self.boundaries[self.rng.usize(..self.boundaries.len())];
+            let cache = self.mutator.validate_value(boundary).unwrap();
+            let cplx = self.mutator.complexity(boundary, &cache);
+            if cplx <= max_cplx {
+                return (Saturating(boundary.clone()), cplx);
+            }
+        }
+        let (value, cplx) = self.mutator.random_arbitrary(max_cplx);
+        (Saturating(value), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut Saturating<T>,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        while let Some(boundary) = self.boundaries.get(step.boundary) {
+            step.boundary += 1;
+            if *boundary != value.0 {
+                let (token, cplx) = self.replace_with_boundary(value, cache, boundary);
+                if cplx <= max_cplx {
+                    return Some((token, cplx));
+                }
+                self.unmutate(value, cache, token);
+            }
+        }
+        let (token, cplx) =
+            self.mutator
+                .ordered_mutate(&mut value.0, cache, &mut step.inner, subvalue_provider, max_cplx)?;
+        Some((UnmutateToken::Inner(token), cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(
+        &self,
+        value: &mut Saturating<T>,
+        cache: &mut Self::Cache,
+        max_cplx: f64,
+    ) -> (Self::UnmutateToken, f64) {
+        if !self.boundaries.is_empty() && self.rng.usize(..100) < BOUNDARY_PERCENT {
+            let boundary = &self.boundaries[self.rng.usize(..self.boundaries.len())];
+            let (token, cplx) = self.replace_with_boundary(value, cache, boundary);
+            if cplx <= max_cplx {
+                return (token, cplx);
+            }
+            self.unmutate(value, cache, token);
+        }
+        let (token, cplx) = self.mutator.random_mutate(&mut value.0, cache, max_cplx);
+        (UnmutateToken::Inner(token), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut Saturating<T>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateToken::Replace(v, c) => {
+                value.0 = v;
+                *cache = c;
+            }
+            UnmutateToken::Inner(t) => self.mutator.unmutate(&mut value.0, cache, t),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        value: &'a Saturating<T>,
+        cache: &'a Self::Cache,
+        visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+        self.mutator.visit_subvalues(&value.0, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.mutator.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &Saturating<T>, b: &Saturating<T>) -> Option<f64> {
+        self.mutator.distance(&a.0, &b.0)
+    }
+}
+
+macro_rules! impl_default_mutator_for_saturating {
+    ($name:ident) => {
+        impl DefaultMutator for Saturating<$name> {
+            type Mutator = SaturatingMutator<$name, <$name as DefaultMutator>::Mutator>;
+            #[coverage(off)]
+            fn default_mutator() -> Self::Mutator {
+                SaturatingMutator::new(<$name>::default_mutator(), vec![<$name>::MAX, <$name>::MIN])
+            }
+        }
+    };
+}
+impl_default_mutator_for_saturating!(u8);
+impl_default_mutator_for_saturating!(u16);
+impl_default_mutator_for_saturating!(u32);
+impl_default_mutator_for_saturating!(u64);
+impl_default_mutator_for_saturating!(usize);
+impl_default_mutator_for_saturating!(i8);
+impl_default_mutator_for_saturating!(i16);
+impl_default_mutator_for_saturating!(i32);
+impl_default_mutator_for_saturating!(i64);
+impl_default_mutator_for_saturating!(isize);
@@ -0,0 +1,95 @@
+//! A mutator for sequences of messages that must satisfy a sequence-level invariant.
+//!
+//! It is meant to fuzz stateful systems, such as network protocols or state machines,
+//! whose behaviour depends on the order of the messages they receive.
+//!
+//! ```
+//! # #![feature(coverage_attribute)]
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::sequence::SequenceMutator;
+//!
+//! #[derive(Clone, DefaultMutator)]
+//! enum Message {
+//!     Login,
+//!     Send(u8),
+//!     Logout,
+//! }
+//!
+//! // impl Mutator<Vec<Message>>
+//! let m = SequenceMutator::new(Message::default_mutator(), 0..=20, |messages: &[Message]| {
+//!     // every message is sent while logged in
+//!     let mut logged_in = false;
+//!     messages.iter().all(|message| match message {
+//!         Message::Login => !std::mem::replace(&mut logged_in, true),
+//!         Message::Send(_) => logged_in,
+//!         Message::Logout => std::mem::replace(&mut logged_in, false),
+//!     })
+//! });
+//! ```
+use std::ops::RangeInclusive;
+
+use crate::mutators::filter::FilterMutator;
+use crate::mutators::vector::VecMutator;
+use crate::mutators::MutatorWrapper;
+use crate::Mutator;
+
+type SequenceFilter<Msg> = Box<dyn Fn(&Vec<Msg>) -> bool>;
+
+/// A mutator for `Vec<Msg>` that only generates the sequences accepted by a predicate.
+///
+/// The messages are generated by the given mutator, and the sequences are mutated like
+/// any vector: messages are inserted, removed, swapped, copied, and mutated. After each
+/// mutation, the predicate is called on the whole sequence. If it returns `false`, the
+/// mutation is reverted and another one is tried.
+///
+/// The predicate is typically a model of the system under test, which checks that each
+/// message is allowed given the messages that were sent before it.
+///
+/// Note that generating a new sequence is retried until the predicate accepts it, so the
+/// predicate should accept a reasonable proportion of arbitrary sequences, such as the
+/// empty sequence.
+pub struct SequenceMutator<Msg, M>
+where
+    Msg: Clone + 'static,
+    M: Mutator<Msg>,
+{
+    mutator: FilterMutator<VecMutator<Msg, M>, SequenceFilter<Msg>>,
+}
+
+impl<Msg, M> SequenceMutator<Msg, M>
+where
+    Msg: Clone + 'static,
+    M: Mutator<Msg>,
+{
+    /// Create a new `SequenceMutator`.
+    ///
+    /// The length of the generated sequences is within `len_range`, and each of them
+    /// is accepted by `is_valid_sequence`.
+    #[coverage(off)]
+    pub fn new(
+        mutator: M,
+        len_range: RangeInclusive<usize>,
+        is_valid_sequence: impl Fn(&[Msg]) -> bool + 'static,
+    ) -> Self {
+        let filter: SequenceFilter<Msg> = Box::new(
+            #[coverage(off)]
+            move |messages| is_valid_sequence(messages),
+        );
+        Self {
+            mutator: FilterMutator::new(VecMutator::new(mutator, len_range), filter),
+        }
+    }
+}
+
+impl<Msg, M> MutatorWrapper for SequenceMutator<Msg, M>
+where
+    Msg: Clone + 'static,
+    M: Mutator<Msg>,
+{
+    type Wrapped = FilterMutator<VecMutator<Msg, M>, SequenceFilter<Msg>>;
+
+    #[coverage(off)]
+    fn wrapped_mutator(&self) -> &Self::Wrapped {
+        &self.mutator
+    }
+}
@@ -0,0 +1,235 @@
+//! A wrapper that makes a mutator simplify values toward a user-specified target.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::shrink_toward::ShrinkTowardMutator;
+//!
+//! // the values are simplified toward a typical request instead of toward the empty vector
+//! let m = ShrinkTowardMutator::new(<Vec<u8>>::default_mutator(), b"GET / HTTP/1.1".to_vec());
+//! ```
+use std::any::Any;
+
+use crate::Mutator;
+
+/// The maximum number of ordered mutations that a [`ShrinkTowardMutator`] tries on a value
+/// while looking for one that brings it closer to the target.
+const MAX_SHRINK_ATTEMPTS: usize = 100;
+
+/// A [`ShrinkTowardMutator`] makes the wrapped mutator simplify values toward a
+/// target value instead of toward the least complex value.
+///
+/// When `ordered_mutate` is first called on a value, it tries the mutations of the
+/// wrapped mutator that bring the value closer to the target before any other mutation.
+/// This makes the failing test cases found during minimization resemble the target,
+/// for example a configuration where most fields have their default value.
+///
+/// The closeness to the target is measured by the [`distance`](Mutator::distance)
+/// method of the wrapped mutator. If it does not implement it, the mutations that make
+/// the value equal to the target are tried first.
+///
+/// The values that can be generated are unchanged.
+pub struct ShrinkTowardMutator<T, M>
+where
+    T: Clone + PartialEq + 'static,
+    M: Mutator<T>,
+{
+    mutator: M,
+    target: T,
+}
+
+impl<T, M> ShrinkTowardMutator<T, M>
+where
+    T: Clone + PartialEq + 'static,
+    M: Mutator<T>,
+{
+    #[coverage(off)]
+    pub fn new(mutator: M, target: T) -> Self {
+        Self { mutator, target }
+    }
+
+    #[coverage(off)]
+    fn distance_to_target(&self, value: &T) -> f64 {
+        self.mutator
+            .distance(value, &self.target)
+            .unwrap_or(if *value == self.target { 0.0 } else { 1.0 })
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct ShrinkTowardMutationStep<S> {
+    inner: S,
+    /// The step used to look for mutations bringing the value closer to the target,
+    /// along with the number of remaining attempts
+    shrink: Option<(S, usize)>,
+}
+
+impl<T, M> Mutator<T> for ShrinkTowardMutator<T, M>
+where
+    T: Clone + PartialEq + 'static,
+    M: Mutator<T>,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = ShrinkTowardMutationStep<M::MutationStep>;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value(value)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        ShrinkTowardMutationStep {
+            inner: self.mutator.default_mutation_step(value, cache),
+            shrink: Some((self.mutator.default_mutation_step(value, cache), MAX_SHRINK_ATTEMPTS)),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity(value, cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.ordered_arbitrary(step, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        self.mutator.random_arbitrary(max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.arbitrary_at_complexity(target_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if let Some((shrink_step, attempts_left)) = &mut step.shrink {
+            // first look for mutations bringing the value closer to the target
+            let distance = self.distance_to_target(value);
+            while distance > 0.0 && *attempts_left > 0 {
+                *attempts_left -= 1;
+                match self
+                    .mutator
+                    .ordered_mutate(value, cache, shrink_step, subvalue_provider, max_cplx)
+                {
+                    Some((token, cplx)) if self.distance_to_target(value) < distance => {
+                        return Some((token, cplx));
+                    }
+                    Some((token, _)) => self.mutator.unmutate(value, cache, token),
+                    None => break,
+                }
+            }
+            step.shrink = None;
+        }
+        self.mutator
+            .ordered_mutate(value, cache, &mut step.inner, subvalue_provider, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        self.mutator.random_mutate(value, cache, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate(value, cache, t)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues(value, cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &T, b: &T) -> Option<f64> {
+        self.mutator.distance(a, b)
+    }
+}
@@ -0,0 +1,230 @@
+//! A mutator that only ever changes a single field of a value, keeping all the
+//! other fields equal to those of a base value.
+//!
+//! This is useful to cheaply fuzz a specific part of a large structure, for
+//! example to regression-test the fix of a bug in the handling of one field.
+//!
+//! ```
+//! # #![feature(coverage_attribute)]
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::single_field::SingleFieldMutator;
+//!
+//! #[derive(Clone, Debug, DefaultMutator)]
+//! struct Config {
+//!     name: String,
+//!     retries: u8,
+//!     verbose: bool,
+//! }
+//!
+//! let base = Config { name: "server".to_owned(), retries: 3, verbose: false };
+//! // impl Mutator<Config>, which only mutates the `retries` field
+//! let m = SingleFieldMutator::new(
+//!     base,
+//!     u8::default_mutator(),
+//!     |c: &Config| &c.retries,
+//!     |c: &mut Config| &mut c.retries,
+//! );
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::Mutator;
+
+/// A mutator for values of type `T` which only mutates one of its fields, of type `F`.
+///
+/// The field is addressed by two accessor functions, `get` and `get_mut`, and is
+/// mutated by a [`Mutator<F>`]. The values generated by `ordered_arbitrary` and
+/// `random_arbitrary` are copies of the base value where only that field is
+/// changed.
+///
+/// The complexity of a value is the complexity of its field, since the rest of
+/// the value is considered constant.
+pub struct SingleFieldMutator<T, F, M, Get, GetMut>
+where
+    T: Clone + 'static,
+    F: Clone + 'static,
+    M: Mutator<F>,
+    Get: Fn(&T) -> &F,
+    GetMut: Fn(&mut T) -> &mut F,
+{
+    base: T,
+    mutator: M,
+    get: Get,
+    get_mut: GetMut,
+    _phantom: PhantomData<F>,
+}
+
+impl<T, F, M, Get, GetMut> SingleFieldMutator<T, F, M, Get, GetMut>
+where
+    T: Clone + 'static,
+    F: Clone + 'static,
+    M: Mutator<F>,
+    Get: Fn(&T) -> &F,
+    GetMut: Fn(&mut T) -> &mut F,
+{
+    /// Create a new `SingleFieldMutator`.
+    ///
+    /// * `base` is the value whose other fields are kept unchanged
+    /// * `mutator` is the mutator of the targeted field
+    /// * `get` and `get_mut` return a reference to the targeted field
+    #[coverage(off)]
+    pub fn new(base: T, mutator: M, get: Get, get_mut: GetMut) -> Self {
+        Self {
+            base,
+            mutator,
+            get,
+            get_mut,
+            _phantom: PhantomData,
+        }
+    }
+
+    #[coverage(off)]
+    fn with_field(&self, field: F) -> T {
+        let mut value = self.base.clone();
+        *(self.get_mut)(&mut value) = field;
+        value
+    }
+}
+
+impl<T, F, M, Get, GetMut> Mutator<T> for SingleFieldMutator<T, F, M, Get, GetMut>
+where
+    T: Clone + 'static,
+    F: Clone + 'static,
+    M: Mutator<F>,
+    Get: Fn(&T) -> &F,
+    GetMut: Fn(&mut T) -> &mut F,
+    Self: 'static,
+{
+    #[doc(hidden)]
+    type Cache = M::Cache;
+    #[doc(hidden)]
+    type MutationStep = M::MutationStep;
+    #[doc(hidden)]
+    type ArbitraryStep = M::ArbitraryStep;
+    #[doc(hidden)]
+    type UnmutateToken = M::UnmutateToken;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.mutator.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        self.mutator.default_arbitrary_step()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &T) -> bool {
+        self.mutator.is_valid((self.get)(value))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &T) -> Option<Self::Cache> {
+        self.mutator.validate_value((self.get)(value))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &T, cache: &Self::Cache) -> Self::MutationStep {
+        self.mutator.default_mutation_step((self.get)(value), cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        self.mutator.global_search_space_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        self.mutator.max_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        self.mutator.min_complexity()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &T, cache: &Self::Cache) -> f64 {
+        self.mutator.complexity((self.get)(value), cache)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        let (field, cplx) = self.mutator.ordered_arbitrary(step, max_cplx)?;
+        Some((self.with_field(field), cplx))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (T, f64) {
+        let (field, cplx) = self.mutator.random_arbitrary(max_cplx);
+        (self.with_field(field), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        self.mutator
+            .ordered_mutate((self.get_mut)(value), cache, step, subvalue_provider, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        self.mutator.random_mutate((self.get_mut)(value), cache, max_cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        self.mutator.unmutate((self.get_mut)(value), cache, t)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
+        self.mutator.visit_subvalues((self.get)(value), cache, visit)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+}
@@ -0,0 +1,404 @@
+//! A mutator for byte buffers made of a tag byte followed by a payload whose layout depends on the tag.
+//!
+//! ```
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::fixed_len_vector::FixedLenVecMutator;
+//! use fuzzcheck::mutators::tagged_union::TaggedUnionMutator;
+//!
+//! // a tag of 0 is followed by a 4-byte payload, and a tag of 1 by a 16-byte payload
+//! // impl Mutator<Vec<u8>>
+//! let m = TaggedUnionMutator::new(vec![
+//!     (0, FixedLenVecMutator::new_with_repeated_mutator(u8::default_mutator(), 4)),
+//!     (1, FixedLenVecMutator::new_with_repeated_mutator(u8::default_mutator(), 16)),
+//! ]);
+//! ```
+use std::any::Any;
+
+use crate::Mutator;
+
+/// The complexity of the tag byte
+const TAG_COMPLEXITY: f64 = 8.0;
+/// Once in `TAG_MUTATION_RATE` ordered mutations, the tag of the value is changed.
+const TAG_MUTATION_RATE: usize = 4;
+
+/// A mutator for values of type `Vec<u8>` whose first byte is a tag, and whose other
+/// bytes are a payload generated by the mutator associated with that tag.
+///
+/// Values starting with a tag that is not known to the mutator are rejected by
+/// `validate_value`. The mutations either change the tag, and then generate a new
+/// payload for it, or mutate the current payload with the mutator of its tag.
+///
+/// All payload mutators must have the same type. To use mutators of different types,
+/// wrap them in an [`Either`](crate::mutators::either::Either).
+pub struct TaggedUnionMutator<M>
+where
+    M: Mutator<Vec<u8>>,
+{
+    mutators: Vec<(u8, M)>,
+    rng: fastrand::Rng,
+}
+
+impl<M> TaggedUnionMutator<M>
+where
+    M: Mutator<Vec<u8>>,
+{
+    /// Create a new `TaggedUnionMutator` from a list of tags and their payload mutators.
+    ///
+    /// # Panics
+    /// Panics if the list is empty or if the same tag is given twice.
+    #[coverage(off)]
+    pub fn new(mutators: Vec<(u8, M)>) -> Self {
+        assert!(
+            !mutators.is_empty(),
+            "a TaggedUnionMutator must be created with at least one tag"
+        );
+        for (i, (tag, _)) in mutators.iter().enumerate() {
+            assert!(
+                mutators[..i].iter().all(
+                    #[coverage(off)]
+                    |(other_tag, _)| other_tag != tag
+                ),
+                "the tag {tag} is given twice to a TaggedUnionMutator"
+            );
+        }
+        Self {
+            mutators,
+            rng: fastrand::Rng::new(),
+        }
+    }
+
+    /// Replace the payload of `value`, which follows its tag byte, by `payload`
+    #[coverage(off)]
+    fn set_payload(value: &mut Vec<u8>, payload: &[u8]) {
+        value.truncate(1);
+        value.extend_from_slice(payload);
+    }
+
+    /// Replace the value with a random payload generated by the mutator at index `idx`
+    #[coverage(off)]
+    fn switch_to(
+        &self,
+        idx: usize,
+        value: &mut Vec<u8>,
+        cache: &mut <Self as Mutator<Vec<u8>>>::Cache,
+        max_cplx: f64,
+    ) -> (<Self as Mutator<Vec<u8>>>::UnmutateToken, f64) {
+        let (tag, m) = &self.mutators[idx];
+        let (payload, payload_cplx) = m.random_arbitrary(max_cplx - TAG_COMPLEXITY);
+        let inner_cache = m.validate_value(&payload).unwrap();
+        let cplx = TAG_COMPLEXITY + payload_cplx;
+        let old_value = std::mem::replace(value, tagged(*tag, &payload));
+        let old_cache = std::mem::replace(
+            cache,
+            TaggedUnionCache {
+                idx,
+                payload,
+                inner: inner_cache,
+                cplx,
+            },
+        );
+        (
+            TaggedUnionUnmutateToken::Replace {
+                value: old_value,
+                cache: old_cache,
+            },
+            cplx,
+        )
+    }
+}
+
+/// The value made of `tag` followed by `payload`
+#[coverage(off)]
+fn tagged(tag: u8, payload: &[u8]) -> Vec<u8> {
+    let mut value = Vec::with_capacity(payload.len() + 1);
+    value.push(tag);
+    value.extend_from_slice(payload);
+    value
+}
+
+/// The cache of a [`TaggedUnionMutator`].
+///
+/// It holds a copy of the payload, which is mutated by the payload mutators and then copied
+/// back into the value, so that the bytes of the value are not shifted to remove its tag.
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct TaggedUnionCache<C> {
+    idx: usize,
+    payload: Vec<u8>,
+    inner: C,
+    cplx: f64,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct TaggedUnionMutationStep<S> {
+    inner: S,
+    nbr_mutations: usize,
+    /// The offset of the index of the next tag to switch to, relative to the current one
+    next_tag_offset: usize,
+    inner_exhausted: bool,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct TaggedUnionArbitraryStep<S> {
+    steps: Vec<Option<S>>,
+    next: usize,
+}
+
+#[doc(hidden)]
+pub enum TaggedUnionUnmutateToken<T, C> {
+    Inner(T, f64),
+    Replace { value: Vec<u8>, cache: TaggedUnionCache<C> },
+}
+
+impl<M> Mutator<Vec<u8>> for TaggedUnionMutator<M>
+where
+    M: Mutator<Vec<u8>>,
+{
+    #[doc(hidden)]
+    type Cache = TaggedUnionCache<M::Cache>;
+    #[doc(hidden)]
+    type MutationStep = TaggedUnionMutationStep<M::MutationStep>;
+    #[doc(hidden)]
+    type ArbitraryStep = TaggedUnionArbitraryStep<M::ArbitraryStep>;
+    #[doc(hidden)]
+    type UnmutateToken = TaggedUnionUnmutateToken<M::UnmutateToken, M::Cache>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        for (_, m) in self.mutators.iter() {
+            m.initialize();
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        TaggedUnionArbitraryStep {
+            steps: self
+                .mutators
+                .iter()
+                .map(
+                    #[coverage(off)]
+                    |(_, m)| Some(m.default_arbitrary_step()),
+                )
+                .collect(),
+            next: 0,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &Vec<u8>) -> bool {
+        self.validate_value(value).is_some()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &Vec<u8>) -> Option<Self::Cache> {
+        let (tag, payload) = value.split_first()?;
+        let idx = self.mutators.iter().position(
+            #[coverage(off)]
+            |(t, _)| t == tag,
+        )?;
+        let m = &self.mutators[idx].1;
+        let payload = payload.to_vec();
+        let inner = m.validate_value(&payload)?;
+        let cplx = TAG_COMPLEXITY + m.complexity(&payload, &inner);
+        Some(TaggedUnionCache {
+            idx,
+            payload,
+            inner,
+            cplx,
+        })
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &Vec<u8>, cache: &Self::Cache) -> Self::MutationStep {
+        TaggedUnionMutationStep {
+            inner: self.mutators[cache.idx]
+                .1
+                .default_mutation_step(&cache.payload, &cache.inner),
+            nbr_mutations: 0,
+            next_tag_offset: 1,
+            inner_exhausted: false,
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        TAG_COMPLEXITY
+            + self
+                .mutators
+                .iter()
+                .map(
+                    #[coverage(off)]
+                    |(_, m)| m.global_search_space_complexity(),
+                )
+                .fold(0.0, f64::max)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        TAG_COMPLEXITY
+            + self
+                .mutators
+                .iter()
+                .map(
+                    #[coverage(off)]
+                    |(_, m)| m.max_complexity(),
+                )
+                .fold(0.0, f64::max)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        TAG_COMPLEXITY
+            + self
+                .mutators
+                .iter()
+                .map(
+                    #[coverage(off)]
+                    |(_, m)| m.min_complexity(),
+                )
+                .fold(f64::INFINITY, f64::min)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, _value: &Vec<u8>, cache: &Self::Cache) -> f64 {
+        cache.cplx
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(Vec<u8>, f64)> {
+        // generate the values of each tag in turn
+        for _ in 0..step.steps.len() {
+            let idx = step.next;
+            step.next = (step.next + 1) % step.steps.len();
+            let Some(inner_step) = &mut step.steps[idx] else {
+                continue;
+            };
+            let (tag, m) = &self.mutators[idx];
+            if let Some((payload, cplx)) = m.ordered_arbitrary(inner_step, max_cplx - TAG_COMPLEXITY) {
+                return Some((tagged(*tag, &payload), TAG_COMPLEXITY + cplx));
+            } else {
+                step.steps[idx] = None;
+            }
+        }
+        None
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (Vec<u8>, f64) {
+        let (tag, m) = &self.mutators[self.rng.usize(..self.mutators.len())];
+        let (payload, cplx) = m.random_arbitrary(max_cplx - TAG_COMPLEXITY);
+        (tagged(*tag, &payload), TAG_COMPLEXITY + cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut Vec<u8>,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        step.nbr_mutations += 1;
+        let can_switch_tag = step.next_tag_offset < self.mutators.len();
+        if can_switch_tag && (step.inner_exhausted || step.nbr_mutations % TAG_MUTATION_RATE == 0) {
+            let idx = (cache.idx + step.next_tag_offset) % self.mutators.len();
+            step.next_tag_offset += 1;
+            return Some(self.switch_to(idx, value, cache, max_cplx));
+        }
+        if step.inner_exhausted {
+            return None;
+        }
+        let m = &self.mutators[cache.idx].1;
+        let old_cplx = cache.cplx;
+        let result = m.ordered_mutate(
+            &mut cache.payload,
+            &mut cache.inner,
+            &mut step.inner,
+            subvalue_provider,
+            max_cplx - TAG_COMPLEXITY,
+        );
+        if let Some((token, payload_cplx)) = result {
+            Self::set_payload(value, &cache.payload);
+            cache.cplx = TAG_COMPLEXITY + payload_cplx;
+            Some((TaggedUnionUnmutateToken::Inner(token, old_cplx), cache.cplx))
+        } else {
+            step.inner_exhausted = true;
+            self.ordered_mutate(value, cache, step, subvalue_provider, max_cplx)
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut Vec<u8>, cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        if self.mutators.len() > 1 && self.rng.usize(..TAG_MUTATION_RATE) == 0 {
+            let idx = (cache.idx + self.rng.usize(1..self.mutators.len())) % self.mutators.len();
+            return self.switch_to(idx, value, cache, max_cplx);
+        }
+        let m = &self.mutators[cache.idx].1;
+        let old_cplx = cache.cplx;
+        let (token, payload_cplx) = m.random_mutate(&mut cache.payload, &mut cache.inner, max_cplx - TAG_COMPLEXITY);
+        Self::set_payload(value, &cache.payload);
+        cache.cplx = TAG_COMPLEXITY + payload_cplx;
+        (TaggedUnionUnmutateToken::Inner(token, old_cplx), cache.cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut Vec<u8>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            TaggedUnionUnmutateToken::Inner(t, old_cplx) => {
+                let m = &self.mutators[cache.idx].1;
+                m.unmutate(&mut cache.payload, &mut cache.inner, t);
+                Self::set_payload(value, &cache.payload);
+                cache.cplx = old_cplx;
+            }
+            TaggedUnionUnmutateToken::Replace {
+                value: old_value,
+                cache: old_cache,
+            } => {
+                *value = old_value;
+                *cache = old_cache;
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        _value: &'a Vec<u8>,
+        _cache: &'a Self::Cache,
+        _visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+        // the payload is not a part of the value, but a suffix of it, so it
+        // is not given as a subvalue
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
+}
@@ -154,6 +154,20 @@ where
 
     fn random_arbitrary(&self, max_cplx: f64) -> (T, f64);
 
+    /// See [`Mutator::arbitrary_at_complexity`]. The default implementation calls
+    /// `random_arbitrary(target_cplx)` a few times, until it returns a value that is
+    /// close enough to the target.
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        crate::mutators::arbitrary_at_complexity_by_sampling(
+            target_cplx,
+            self.min_complexity(),
+            self.max_complexity(),
+            #[coverage(off)]
+            |max_cplx| self.random_arbitrary(max_cplx),
+        )
+    }
+
     fn ordered_mutate<'a>(
         &self,
         value: TupleKind::Mut<'a>,
@@ -178,6 +192,26 @@ where
         cache: &'a Self::Cache,
         visit: &mut dyn FnMut(&'a dyn Any, f64),
     );
+
+    /// See [`Mutator::set_effort_hint`]. The default implementation does nothing.
+    #[coverage(off)]
+    fn set_effort_hint(&self, _hint: crate::EffortHint) {}
+
+    /// See [`Mutator::unmutate_is_cheap`]. The default implementation returns `true`.
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        true
+    }
+
+    /// See [`Mutator::rng_state`]. The default implementation returns `None`.
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// See [`Mutator::set_rng_state`]. The default implementation does nothing.
+    #[coverage(off)]
+    fn set_rng_state(&self, _state: &[u8]) {}
 }
 
 /// A wrapper that transforms a [`TupleMutator`] into a [`Mutator`] of values [with a tuple structure](TupleStructure).
@@ -295,6 +329,12 @@ where
         self.mutator.random_arbitrary(max_cplx)
     }
 
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+        self.mutator.arbitrary_at_complexity(target_cplx)
+    }
+
     #[doc(hidden)]
     #[coverage(off)]
     fn ordered_mutate(
@@ -326,6 +366,30 @@ where
     fn visit_subvalues<'a>(&self, value: &'a T, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64)) {
         self.mutator.visit_subvalues(value.get_ref(), cache, visit)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.mutator.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.mutator.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.mutator.rng_state()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.mutator.set_rng_state(state)
+    }
 }
 
 pub use tuple0::{Tuple0, Tuple0Mutator};
@@ -527,6 +591,18 @@ mod tuple1 {
                 rng: fastrand::Rng::new(),
             }
         }
+
+        /// The mutator of the single element of the tuple
+        #[coverage(off)]
+        pub fn inner(&self) -> &M0 {
+            &self.mutator_0
+        }
+
+        /// The mutator of the single element of the tuple
+        #[coverage(off)]
+        pub fn inner_mut(&mut self) -> &mut M0 {
+            &mut self.mutator_0
+        }
     }
 
     impl<T, T0, M0> TupleMutator<T, Tuple1<T0>> for Tuple1Mutator<M0>
@@ -619,6 +695,12 @@ mod tuple1 {
         }
         #[doc(hidden)]
         #[coverage(off)]
+        fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(T, f64)> {
+            let (value, cplx) = self.mutator_0.arbitrary_at_complexity(target_cplx)?;
+            Some((T::new((value,)), cplx))
+        }
+        #[doc(hidden)]
+        #[coverage(off)]
         fn ordered_mutate<'a>(
             &self,
             value: <Tuple1<T0> as RefTypes>::Mut<'a>,
@@ -684,6 +766,31 @@ mod tuple1 {
             visit(value.0, cplx);
             self.mutator_0.visit_subvalues(value.0, cache, visit);
         }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn set_effort_hint(&self, hint: crate::EffortHint) {
+            self.mutator_0.set_effort_hint(hint)
+        }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn unmutate_is_cheap(&self) -> bool {
+            self.mutator_0.unmutate_is_cheap()
+        }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn rng_state(&self) -> Option<Vec<u8>> {
+            Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.mutator_0))
+        }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn set_rng_state(&self, state: &[u8]) {
+            let rest = crate::mutators::set_rng_state(&self.rng, state);
+            self.mutator_0.set_rng_state(rest);
+        }
     }
     impl<T0> crate::mutators::DefaultMutator for (T0,)
     where
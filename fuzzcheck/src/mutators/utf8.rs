@@ -0,0 +1,216 @@
+//! A mutator for byte strings that are mostly valid UTF-8, but contain specific invalid sequences.
+//!
+//! It is meant to test UTF-8 decoders and validators, whose error paths are rarely reached
+//! by generating random bytes or valid strings.
+//!
+//! ```
+//! use fuzzcheck::mutators::utf8::Utf8FuzzMutator;
+//!
+//! // impl Mutator<Vec<u8>>
+//! let m = Utf8FuzzMutator::new();
+//! ```
+extern crate self as fuzzcheck;
+
+use fuzzcheck_mutators_derive::make_mutator;
+
+use crate::mutators::char::CharWithinRangeMutator;
+use crate::mutators::integer_within_range::{U16WithinRangeMutator, U8WithinRangeMutator};
+use crate::mutators::map::MapMutator;
+use crate::mutators::vector::VecMutator;
+use crate::mutators::Wrapper;
+use crate::DefaultMutator;
+
+/// A part of a byte string generated by [`Utf8FuzzMutator`].
+///
+/// It is either a valid character or a specific kind of invalid UTF-8 sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Utf8Segment {
+    /// A valid character
+    Char(char),
+    /// A continuation byte, between `0x80` and `0xBF`, that is not preceded by a leading byte
+    LoneContinuation(u8),
+    /// An ASCII character encoded with more bytes than necessary, such as `[0xC0, 0xAF]` for `/`.
+    ///
+    /// The length of the encoding is between 2 and 4 bytes.
+    Overlong { ascii: u8, len: u8 },
+    /// The first bytes of the encoding of a non-ASCII character, without its last byte(s).
+    ///
+    /// At most `len` bytes are kept, and always fewer than the length of the full encoding.
+    Truncated { c: char, len: u8 },
+    /// The three-byte encoding of a UTF-16 surrogate code point, between `0xD800` and `0xDFFF`
+    Surrogate(u16),
+    /// Any other byte
+    Byte(u8),
+}
+
+make_mutator! {
+    name: Utf8SegmentMutator,
+    default: true,
+    type: pub enum Utf8Segment {
+        Char(#[field_mutator(CharWithinRangeMutator = { CharWithinRangeMutator::new('\0'..=char::MAX) })] char),
+        LoneContinuation(#[field_mutator(U8WithinRangeMutator = { U8WithinRangeMutator::new(0x80..=0xBF) })] u8),
+        Overlong {
+            #[field_mutator(U8WithinRangeMutator = { U8WithinRangeMutator::new(0..=0x7F) })]
+            ascii: u8,
+            #[field_mutator(U8WithinRangeMutator = { U8WithinRangeMutator::new(2..=4) })]
+            len: u8,
+        },
+        Truncated {
+            #[field_mutator(CharWithinRangeMutator = { CharWithinRangeMutator::new('\u{80}'..=char::MAX) })]
+            c: char,
+            #[field_mutator(U8WithinRangeMutator = { U8WithinRangeMutator::new(1..=3) })]
+            len: u8,
+        },
+        Surrogate(#[field_mutator(U16WithinRangeMutator = { U16WithinRangeMutator::new(0xD800..=0xDFFF) })] u16),
+        Byte(#[field_mutator(U8WithinRangeMutator = { U8WithinRangeMutator::new(..) })] u8),
+    }
+}
+
+impl Utf8Segment {
+    /// Append the bytes of the segment to `bytes`
+    #[coverage(off)]
+    pub fn encode_into(&self, bytes: &mut Vec<u8>) {
+        match *self {
+            Utf8Segment::Char(c) => {
+                let mut buffer = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+            }
+            Utf8Segment::LoneContinuation(b) | Utf8Segment::Byte(b) => bytes.push(b),
+            Utf8Segment::Overlong { ascii, len } => {
+                let ascii = ascii & 0x7F;
+                match len {
+                    0..=2 => bytes.extend_from_slice(&[0xC0 | (ascii >> 6), 0x80 | (ascii & 0x3F)]),
+                    3 => bytes.extend_from_slice(&[0xE0, 0x80 | (ascii >> 6), 0x80 | (ascii & 0x3F)]),
+                    _ => bytes.extend_from_slice(&[0xF0, 0x80, 0x80 | (ascii >> 6), 0x80 | (ascii & 0x3F)]),
+                }
+            }
+            Utf8Segment::Truncated { c, len } => {
+                let mut buffer = [0; 4];
+                let encoded = c.encode_utf8(&mut buffer).as_bytes();
+                let len = (len as usize).clamp(1, encoded.len().saturating_sub(1).max(1));
+                bytes.extend_from_slice(&encoded[..len]);
+            }
+            Utf8Segment::Surrogate(code_point) => {
+                bytes.extend_from_slice(&[
+                    0xE0 | (code_point >> 12) as u8,
+                    0x80 | ((code_point >> 6) & 0x3F) as u8,
+                    0x80 | (code_point & 0x3F) as u8,
+                ]);
+            }
+        }
+    }
+}
+
+#[coverage(off)]
+#[allow(clippy::ptr_arg)]
+fn parse_segments(bytes: &Vec<u8>) -> Option<Vec<Utf8Segment>> {
+    let mut segments = vec![];
+    for chunk in bytes.utf8_chunks() {
+        segments.extend(chunk.valid().chars().map(Utf8Segment::Char));
+        segments.extend(chunk.invalid().iter().copied().map(Utf8Segment::Byte));
+    }
+    Some(segments)
+}
+
+#[coverage(off)]
+fn encode_segments(segments: &Vec<Utf8Segment>) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(segments.len() * 2);
+    for segment in segments {
+        segment.encode_into(&mut bytes);
+    }
+    bytes
+}
+
+/// The complexity of a byte string only depends on its length, since the same bytes
+/// can be generated from different lists of segments
+#[coverage(off)]
+#[allow(clippy::ptr_arg)]
+fn bytes_cplx(value: &Vec<u8>, _cplx: f64) -> f64 {
+    (value.len() * 8) as f64
+}
+
+/// A mutator for `Vec<u8>` that mixes valid UTF-8 characters with invalid sequences.
+///
+/// The generated byte strings are made of [segments](Utf8Segment), each of which is either
+/// a valid character or one of the following invalid sequences:
+/// - a lone continuation byte
+/// - an overlong encoding of an ASCII character
+/// - a multi-byte character whose last byte(s) are missing
+/// - the encoding of a UTF-16 surrogate
+/// - an arbitrary byte
+///
+/// The kind and position of each segment is chosen by the fuzzer, like the variant of any enum.
+/// Every byte string is accepted by `validate_value`: its valid characters and its invalid bytes
+/// are then mutated as individual segments.
+pub type Utf8FuzzMutator = Wrapper<
+    MapMutator<
+        Vec<Utf8Segment>,
+        Vec<u8>,
+        VecMutator<Utf8Segment, Utf8SegmentMutator>,
+        fn(&Vec<u8>) -> Option<Vec<Utf8Segment>>,
+        fn(&Vec<Utf8Segment>) -> Vec<u8>,
+        fn(&Vec<u8>, f64) -> f64,
+    >,
+>;
+
+impl Utf8FuzzMutator {
+    #[coverage(off)]
+    pub fn new() -> Self {
+        Wrapper(MapMutator::new(
+            VecMutator::new(Utf8Segment::default_mutator(), 0..=usize::MAX),
+            parse_segments,
+            encode_segments,
+            bytes_cplx,
+        ))
+    }
+}
+
+impl Default for Utf8FuzzMutator {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Utf8Segment;
+
+    #[coverage(off)]
+    fn encode(segment: Utf8Segment) -> Vec<u8> {
+        let mut bytes = vec![];
+        segment.encode_into(&mut bytes);
+        bytes
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_invalid_segments_are_invalid_utf8() {
+        assert_eq!(encode(Utf8Segment::Overlong { ascii: b'/', len: 2 }), [0xC0, 0xAF]);
+        assert_eq!(
+            encode(Utf8Segment::Overlong { ascii: b'/', len: 3 }),
+            [0xE0, 0x80, 0xAF]
+        );
+        assert_eq!(
+            encode(Utf8Segment::Overlong { ascii: 0, len: 4 }),
+            [0xF0, 0x80, 0x80, 0x80]
+        );
+        assert_eq!(encode(Utf8Segment::Surrogate(0xD800)), [0xED, 0xA0, 0x80]);
+        assert_eq!(encode(Utf8Segment::Truncated { c: '€', len: 3 }), [0xE2, 0x82]);
+        assert_eq!(encode(Utf8Segment::Truncated { c: '€', len: 1 }), [0xE2]);
+        for segment in [
+            Utf8Segment::LoneContinuation(0x80),
+            Utf8Segment::Overlong { ascii: 0x7F, len: 2 },
+            Utf8Segment::Overlong { ascii: 0x41, len: 3 },
+            Utf8Segment::Overlong { ascii: 0x41, len: 4 },
+            Utf8Segment::Truncated { c: 'é', len: 3 },
+            Utf8Segment::Truncated {
+                c: '\u{10FFFF}',
+                len: 3,
+            },
+            Utf8Segment::Surrogate(0xDFFF),
+        ] {
+            assert!(std::str::from_utf8(&encode(segment.clone())).is_err(), "{segment:?}");
+        }
+    }
+}
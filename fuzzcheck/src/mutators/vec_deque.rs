@@ -0,0 +1,548 @@
+//! A mutator for [`VecDeque`], which exercises both ends of the deque.
+//!
+//! ```
+//! use std::collections::VecDeque;
+//! use fuzzcheck::DefaultMutator;
+//! use fuzzcheck::mutators::vec_deque::VecDequeMutator;
+//!
+//! // impl Mutator<VecDeque<u8>>
+//! let m = <VecDeque<u8>>::default_mutator();
+//! // impl Mutator<VecDeque<u8>> for deques of at most 16 elements
+//! let m = VecDequeMutator::new(u8::default_mutator(), 0..=16);
+//! ```
+use std::any::Any;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
+
+use crate::{DefaultMutator, Mutator};
+
+impl<T> DefaultMutator for VecDeque<T>
+where
+    T: DefaultMutator + 'static,
+{
+    type Mutator = VecDequeMutator<T, T::Mutator>;
+    #[coverage(off)]
+    fn default_mutator() -> Self::Mutator {
+        VecDequeMutator::new(T::default_mutator(), 0..=usize::MAX)
+    }
+}
+
+/// The operations performed by the mutations of a [`VecDequeMutator`], in the
+/// order in which they are tried by `ordered_mutate`
+#[derive(Clone, Copy)]
+enum Operation {
+    PushFront,
+    PushBack,
+    PopFront,
+    PopBack,
+    MutateFront,
+    MutateBack,
+    MutateAny,
+}
+
+const OPERATIONS: [Operation; 7] = [
+    Operation::PushFront,
+    Operation::PushBack,
+    Operation::PopFront,
+    Operation::PopBack,
+    Operation::MutateFront,
+    Operation::MutateBack,
+    Operation::MutateAny,
+];
+
+pub struct VecDequeMutatorCache<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    inner: VecDeque<M::Cache>,
+    sum_cplx: f64,
+}
+impl<T, M> Clone for VecDequeMutatorCache<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[coverage(off)]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            sum_cplx: self.sum_cplx,
+        }
+    }
+}
+
+pub struct VecDequeMutationStep<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    operation: usize,
+    inner: VecDeque<M::MutationStep>,
+}
+impl<T, M> Clone for VecDequeMutationStep<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[coverage(off)]
+    fn clone(&self) -> Self {
+        Self {
+            operation: self.operation,
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+pub enum UnmutateVecDequeToken<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    PopFront,
+    PopBack,
+    PushFront(T, M::Cache),
+    PushBack(T, M::Cache),
+    /// The index of the mutated element, its unmutate token, and the sum of the complexities
+    /// of the elements before the mutation
+    Element(usize, M::UnmutateToken, f64),
+    Replace(VecDeque<T>, VecDequeMutatorCache<T, M>),
+}
+
+/// A mutator for [`VecDeque`], whose length is within the given range.
+///
+/// It uses the same complexity as [`VecMutator`](crate::mutators::vector::VecMutator): the sum of
+/// the complexities of the elements plus one, and plus the length of the deque if the elements
+/// can have a complexity of zero.
+///
+/// Its mutations push and pop elements at either end of the deque, and mutate its first, last,
+/// or any other element. They are applied to both ends equally often, so that bugs that are
+/// specific to the front or to the back of the deque can be found.
+pub struct VecDequeMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    m: M,
+    len_range: RangeInclusive<usize>,
+    rng: fastrand::Rng,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, M> VecDequeMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[coverage(off)]
+    pub fn new(m: M, len_range: RangeInclusive<usize>) -> Self {
+        Self {
+            m,
+            len_range,
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    #[coverage(off)]
+    fn complexity_from_inner(&self, cplx: f64, len: usize) -> f64 {
+        1.0 + if len == 0 || self.m.min_complexity() > 0.0 {
+            cplx
+        } else {
+            len as f64 + cplx
+        }
+    }
+
+    /// Apply the given operation, or return `None` if it cannot be applied to the deque
+    #[coverage(off)]
+    fn apply(
+        &self,
+        operation: Operation,
+        value: &mut VecDeque<T>,
+        cache: &mut VecDequeMutatorCache<T, M>,
+        step: Option<&mut VecDequeMutationStep<T, M>>,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(UnmutateVecDequeToken<T, M>, f64)> {
+        match operation {
+            Operation::PushFront | Operation::PushBack => {
+                if value.len() >= *self.len_range.end() {
+                    return None;
+                }
+                let current_cplx = self.complexity_from_inner(cache.sum_cplx, value.len());
+                let len_cplx = if self.m.min_complexity() > 0.0 { 0.0 } else { 1.0 };
+                let max_el_cplx = max_cplx - current_cplx - len_cplx;
+                if max_el_cplx < self.m.min_complexity() {
+                    return None;
+                }
+                let (el, el_cplx) = self.m.random_arbitrary(max_el_cplx);
+                let el_cache = self.m.validate_value(&el).unwrap();
+                cache.sum_cplx += el_cplx;
+                if let Operation::PushFront = operation {
+                    value.push_front(el);
+                    cache.inner.push_front(el_cache);
+                    Some((
+                        UnmutateVecDequeToken::PopFront,
+                        self.complexity_from_inner(cache.sum_cplx, value.len()),
+                    ))
+                } else {
+                    value.push_back(el);
+                    cache.inner.push_back(el_cache);
+                    Some((
+                        UnmutateVecDequeToken::PopBack,
+                        self.complexity_from_inner(cache.sum_cplx, value.len()),
+                    ))
+                }
+            }
+            Operation::PopFront | Operation::PopBack => {
+                if value.len() <= *self.len_range.start() || value.is_empty() {
+                    return None;
+                }
+                let token = if let Operation::PopFront = operation {
+                    let el = value.pop_front().unwrap();
+                    let el_cache = cache.inner.pop_front().unwrap();
+                    cache.sum_cplx -= self.m.complexity(&el, &el_cache);
+                    UnmutateVecDequeToken::PushFront(el, el_cache)
+                } else {
+                    let el = value.pop_back().unwrap();
+                    let el_cache = cache.inner.pop_back().unwrap();
+                    cache.sum_cplx -= self.m.complexity(&el, &el_cache);
+                    UnmutateVecDequeToken::PushBack(el, el_cache)
+                };
+                Some((token, self.complexity_from_inner(cache.sum_cplx, value.len())))
+            }
+            Operation::MutateFront | Operation::MutateBack | Operation::MutateAny => {
+                if value.is_empty() {
+                    return None;
+                }
+                let idx = match operation {
+                    Operation::MutateFront => 0,
+                    Operation::MutateBack => value.len() - 1,
+                    _ => self.rng.usize(..value.len()),
+                };
+                let current_cplx = self.complexity_from_inner(cache.sum_cplx, value.len());
+                let el = &mut value[idx];
+                let el_cache = &mut cache.inner[idx];
+                let old_el_cplx = self.m.complexity(el, el_cache);
+                let max_el_cplx = max_cplx - current_cplx + old_el_cplx;
+                let ordered_result = step.and_then(
+                    #[coverage(off)]
+                    |step| {
+                        self.m
+                            .ordered_mutate(el, el_cache, &mut step.inner[idx], subvalue_provider, max_el_cplx)
+                    },
+                );
+                let (token, new_el_cplx) = ordered_result.unwrap_or_else(
+                    #[coverage(off)]
+                    || self.m.random_mutate(el, el_cache, max_el_cplx),
+                );
+                let old_sum_cplx = cache.sum_cplx;
+                cache.sum_cplx += new_el_cplx - old_el_cplx;
+                Some((
+                    UnmutateVecDequeToken::Element(idx, token, old_sum_cplx),
+                    self.complexity_from_inner(cache.sum_cplx, value.len()),
+                ))
+            }
+        }
+    }
+}
+
+impl<T, M> Mutator<VecDeque<T>> for VecDequeMutator<T, M>
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    #[doc(hidden)]
+    type Cache = VecDequeMutatorCache<T, M>;
+    #[doc(hidden)]
+    type MutationStep = VecDequeMutationStep<T, M>;
+    #[doc(hidden)]
+    type ArbitraryStep = bool;
+    #[doc(hidden)]
+    type UnmutateToken = UnmutateVecDequeToken<T, M>;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {
+        self.m.initialize();
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        false
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, value: &VecDeque<T>) -> bool {
+        self.len_range.contains(&value.len())
+            && value.iter().all(
+                #[coverage(off)]
+                |x| self.m.is_valid(x),
+            )
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, value: &VecDeque<T>) -> Option<Self::Cache> {
+        if !self.len_range.contains(&value.len()) {
+            return None;
+        }
+        let inner = value
+            .iter()
+            .map(
+                #[coverage(off)]
+                |x| self.m.validate_value(x),
+            )
+            .collect::<Option<VecDeque<_>>>()?;
+        let sum_cplx = value
+            .iter()
+            .zip(inner.iter())
+            .map(
+                #[coverage(off)]
+                |(x, c)| self.m.complexity(x, c),
+            )
+            .sum();
+        Some(VecDequeMutatorCache { inner, sum_cplx })
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, value: &VecDeque<T>, cache: &Self::Cache) -> Self::MutationStep {
+        VecDequeMutationStep {
+            operation: 0,
+            inner: value
+                .iter()
+                .zip(cache.inner.iter())
+                .map(
+                    #[coverage(off)]
+                    |(x, c)| self.m.default_mutation_step(x, c),
+                )
+                .collect(),
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        if self.m.global_search_space_complexity() == 0.0 {
+            super::size_to_cplxity(self.len_range.end() - self.len_range.start() + 1)
+        } else {
+            self.m.global_search_space_complexity() * ((self.len_range.end() - self.len_range.start()) as f64)
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        let max_len = *self.len_range.end();
+        self.complexity_from_inner((max_len as f64) * self.m.max_complexity(), max_len)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        let min_len = *self.len_range.start();
+        let min_sum_cplx = if min_len == 0 {
+            0.0
+        } else {
+            (min_len as f64) * self.m.min_complexity()
+        };
+        self.complexity_from_inner(min_sum_cplx, min_len)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, value: &VecDeque<T>, cache: &Self::Cache) -> f64 {
+        self.complexity_from_inner(cache.sum_cplx, value.len())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(VecDeque<T>, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        if !*step {
+            // the first value is the least complex one
+            *step = true;
+            let value = (0..*self.len_range.start())
+                .map(
+                    #[coverage(off)]
+                    |_| self.m.random_arbitrary(0.0).0,
+                )
+                .collect::<VecDeque<_>>();
+            let cache = self.validate_value(&value).unwrap();
+            let cplx = self.complexity(&value, &cache);
+            Some((value, cplx))
+        } else {
+            Some(self.random_arbitrary(max_cplx))
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, max_cplx: f64) -> (VecDeque<T>, f64) {
+        let min_cplx = self.min_complexity();
+        let target_cplx = if max_cplx <= min_cplx {
+            min_cplx
+        } else {
+            crate::mutators::gen_f64(&self.rng, min_cplx..max_cplx)
+        };
+        // each element costs at least its minimum complexity, or 1.0 for the length if that is 0
+        let min_el_cplx = self.m.min_complexity().max(1.0);
+        let max_len = ((target_cplx - 1.0).max(0.0) / min_el_cplx) as usize;
+        let max_len = max_len.clamp(*self.len_range.start(), *self.len_range.end());
+        let target_len = self.rng.usize(*self.len_range.start()..=max_len);
+
+        let mut value = VecDeque::with_capacity(target_len);
+        let mut sum_cplx = 0.0;
+        for i in 0..target_len {
+            let remaining = (target_len - i) as f64;
+            let len_cplx = if self.m.min_complexity() > 0.0 { 0.0 } else { 1.0 };
+            let max_el_cplx = ((target_cplx - 1.0 - sum_cplx) / remaining - len_cplx).max(0.0);
+            let (el, el_cplx) = self.m.random_arbitrary(max_el_cplx);
+            sum_cplx += el_cplx;
+            if self.rng.bool() {
+                value.push_front(el);
+            } else {
+                value.push_back(el);
+            }
+        }
+        let cplx = self.complexity_from_inner(sum_cplx, value.len());
+        (value, cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut VecDeque<T>,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        for _ in 0..OPERATIONS.len() {
+            let operation = OPERATIONS[step.operation % OPERATIONS.len()];
+            step.operation += 1;
+            if let Some(result) = self.apply(operation, value, cache, Some(step), subvalue_provider, max_cplx) {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(
+        &self,
+        value: &mut VecDeque<T>,
+        cache: &mut Self::Cache,
+        max_cplx: f64,
+    ) -> (Self::UnmutateToken, f64) {
+        let start = self.rng.usize(..OPERATIONS.len());
+        for i in 0..OPERATIONS.len() {
+            let operation = OPERATIONS[(start + i) % OPERATIONS.len()];
+            if let Some(result) = self.apply(
+                operation,
+                value,
+                cache,
+                None,
+                &crate::subvalue_provider::EmptySubValueProvider,
+                max_cplx,
+            ) {
+                return result;
+            }
+        }
+        // no operation can be applied, so the value is replaced by a new one
+        let (mut new_value, cplx) = self.random_arbitrary(max_cplx);
+        let mut new_cache = self.validate_value(&new_value).unwrap();
+        std::mem::swap(value, &mut new_value);
+        std::mem::swap(cache, &mut new_cache);
+        (UnmutateVecDequeToken::Replace(new_value, new_cache), cplx)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut VecDeque<T>, cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        match t {
+            UnmutateVecDequeToken::PopFront => {
+                let el = value.pop_front().unwrap();
+                let el_cache = cache.inner.pop_front().unwrap();
+                cache.sum_cplx -= self.m.complexity(&el, &el_cache);
+            }
+            UnmutateVecDequeToken::PopBack => {
+                let el = value.pop_back().unwrap();
+                let el_cache = cache.inner.pop_back().unwrap();
+                cache.sum_cplx -= self.m.complexity(&el, &el_cache);
+            }
+            UnmutateVecDequeToken::PushFront(el, el_cache) => {
+                cache.sum_cplx += self.m.complexity(&el, &el_cache);
+                value.push_front(el);
+                cache.inner.push_front(el_cache);
+            }
+            UnmutateVecDequeToken::PushBack(el, el_cache) => {
+                cache.sum_cplx += self.m.complexity(&el, &el_cache);
+                value.push_back(el);
+                cache.inner.push_back(el_cache);
+            }
+            UnmutateVecDequeToken::Element(idx, token, old_sum_cplx) => {
+                // the cache of a mutated element does not always reflect its complexity,
+                // so the complexity is restored instead of being recomputed
+                self.m.unmutate(&mut value[idx], &mut cache.inner[idx], token);
+                cache.sum_cplx = old_sum_cplx;
+            }
+            UnmutateVecDequeToken::Replace(old_value, old_cache) => {
+                *value = old_value;
+                *cache = old_cache;
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(
+        &self,
+        value: &'a VecDeque<T>,
+        cache: &'a Self::Cache,
+        visit: &mut dyn FnMut(&'a dyn Any, f64),
+    ) {
+        for (el, el_cache) in value.iter().zip(cache.inner.iter()) {
+            visit(el, self.m.complexity(el, el_cache));
+        }
+        for (el, el_cache) in value.iter().zip(cache.inner.iter()) {
+            self.m.visit_subvalues(el, el_cache, visit);
+        }
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.m))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.m.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.m.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.m.unmutate_is_cheap()
+    }
+}
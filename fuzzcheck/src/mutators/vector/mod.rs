@@ -62,6 +62,18 @@ where
     }
 }
 
+/// How a [`VecMutator`] shares the complexity of a newly generated vector between its elements
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ComplexityDistribution {
+    /// Every element receives the same share, so that the elements have similar sizes
+    #[default]
+    Even,
+    /// A randomly chosen element receives the given fraction (between `0.0` and `1.0`)
+    /// of the complexity, and the other elements share the rest evenly. This produces
+    /// vectors made of one large element among many small ones.
+    OneLarge(f64),
+}
+
 pub struct VecMutator<T, M>
 where
     T: Clone + 'static,
@@ -72,6 +84,7 @@ where
     rng: fastrand::Rng,
     mutations: VectorMutation,
     inherent_complexity: bool,
+    distribution: ComplexityDistribution,
     _phantom: PhantomData<T>,
 }
 
@@ -88,6 +101,7 @@ where
             rng: fastrand::Rng::new(),
             mutations: VectorMutation::default(),
             inherent_complexity: false,
+            distribution: ComplexityDistribution::Even,
             _phantom: PhantomData,
         }
     }
@@ -100,10 +114,20 @@ where
             rng: fastrand::Rng::new(),
             mutations: VectorMutation::default(),
             inherent_complexity: true,
+            distribution: ComplexityDistribution::Even,
             _phantom: PhantomData,
         }
     }
 
+    /// Sets how the complexity of a newly generated vector is shared between its elements.
+    ///
+    /// By default, it is shared evenly.
+    #[coverage(off)]
+    pub fn with_complexity_distribution(mut self, distribution: ComplexityDistribution) -> Self {
+        self.distribution = distribution;
+        self
+    }
+
     #[coverage(off)]
     fn complexity_from_inner(&self, cplx: f64, len: usize) -> f64 {
         if self.inherent_complexity {
@@ -301,6 +325,42 @@ where
         let cplx = self.complexity_from_inner(inner_cplx, v.len());
         (v, cplx)
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(Vec<T>, f64)> {
+        let tolerance = crate::mutators::arbitrary_at_complexity_tolerance(target_cplx);
+        if target_cplx + tolerance < self.min_complexity() || target_cplx - tolerance > self.max_complexity() {
+            return None;
+        }
+        // the complexity of the elements, without the part that comes from the vector itself
+        let target_inner_cplx = if self.inherent_complexity {
+            f64::max(0.0, target_cplx - 1.0)
+        } else {
+            target_cplx
+        };
+        for _ in 0..crate::mutators::ARBITRARY_AT_COMPLEXITY_ATTEMPTS {
+            let len_range = self.choose_slice_length(target_inner_cplx);
+            let len = self.rng.usize(len_range);
+            // share the remaining complexity equally between the remaining elements
+            let mut v = Vec::with_capacity(len);
+            let mut inner_cplx = 0.0;
+            for i in 0..len {
+                let el_target_cplx = (target_inner_cplx - inner_cplx) / ((len - i) as f64);
+                let (el, el_cplx) = self.m.arbitrary_at_complexity(el_target_cplx).unwrap_or_else(
+                    #[coverage(off)]
+                    || self.m.random_arbitrary(el_target_cplx),
+                );
+                v.push(el);
+                inner_cplx += el_cplx;
+            }
+            let cplx = self.complexity_from_inner(inner_cplx, v.len());
+            if (cplx - target_cplx).abs() <= tolerance {
+                return Some((v, cplx));
+            }
+        }
+        None
+    }
     #[doc(hidden)]
     #[coverage(off)]
     fn ordered_mutate(
@@ -351,6 +411,60 @@ where
             }
         }
     }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state_with_submutator(&self.rng, &self.m))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let rest = crate::mutators::set_rng_state(&self.rng, state);
+        self.m.set_rng_state(rest);
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_effort_hint(&self, hint: crate::EffortHint) {
+        self.m.set_effort_hint(hint)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        self.m.unmutate_is_cheap()
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn distance(&self, a: &Vec<T>, b: &Vec<T>) -> Option<f64> {
+        // Levenshtein distance where the cost of substituting an element by another
+        // is the distance between them, normalized by the length of the longest vector
+        let max_len = std::cmp::max(a.len(), b.len());
+        if max_len == 0 {
+            return Some(0.0);
+        }
+        let mut previous_row = (0..=b.len())
+            .map(
+                #[coverage(off)]
+                |j| j as f64,
+            )
+            .collect::<Vec<_>>();
+        let mut current_row = vec![0.0; b.len() + 1];
+        for (i, x) in a.iter().enumerate() {
+            current_row[0] = (i + 1) as f64;
+            for (j, y) in b.iter().enumerate() {
+                let substitution = previous_row[j] + self.m.distance(x, y)?;
+                let deletion = previous_row[j + 1] + 1.0;
+                let insertion = current_row[j] + 1.0;
+                current_row[j + 1] = substitution.min(deletion).min(insertion);
+            }
+            std::mem::swap(&mut previous_row, &mut current_row);
+        }
+        Some(previous_row[b.len()] / max_len as f64)
+    }
 }
 
 impl<T, M> VecMutator<T, M>
@@ -397,18 +511,28 @@ where
         let mut v = Vec::with_capacity(target_len);
         let mut sum_cplx = 0.0;
 
-        let mut remaining_cplx = target_cplx;
-        for i in 0..target_len {
-            let max_cplx_element = remaining_cplx / ((target_len - i) as f64);
-            let min_cplx_el = self.m.min_complexity();
+        if let Some(budgets) = self.element_budgets(cmp::max(target_len, min_len), target_cplx) {
+            // the budget of each element is fixed in advance, so that the complexity left
+            // unused by an element is not passed on to the next ones
+            for budget in budgets {
+                let (x, x_cplx) = self.m.random_arbitrary(budget);
+                sum_cplx += x_cplx;
+                v.push(x);
+            }
+        } else {
+            let mut remaining_cplx = target_cplx;
+            for i in 0..target_len {
+                let max_cplx_element = remaining_cplx / ((target_len - i) as f64);
+                let min_cplx_el = self.m.min_complexity();
 
-            if min_cplx_el >= max_cplx_element {
-                break;
+                if min_cplx_el >= max_cplx_element {
+                    break;
+                }
+                let (x, x_cplx) = self.m.random_arbitrary(max_cplx_element);
+                sum_cplx += x_cplx;
+                v.push(x);
+                remaining_cplx -= x_cplx;
             }
-            let (x, x_cplx) = self.m.random_arbitrary(max_cplx_element);
-            sum_cplx += x_cplx;
-            v.push(x);
-            remaining_cplx -= x_cplx;
         }
         if v.len() < min_len {
             // at this point it is smaller than it must be, so we add new, minimal, elements
@@ -423,6 +547,28 @@ where
         // let cplx = self.complexity_from_inner(sum_cplx, v.len());
         (v, sum_cplx)
     }
+
+    /// The maximum complexity of each of the `len` elements of a new vector of complexity
+    /// `target_cplx`, or `None` if the complexity is shared evenly
+    #[coverage(off)]
+    fn element_budgets(&self, len: usize, target_cplx: f64) -> Option<Vec<f64>> {
+        let ComplexityDistribution::OneLarge(fraction) = self.distribution else {
+            return None;
+        };
+        if len == 0 {
+            return Some(vec![]);
+        }
+        // each element needs at least its minimum complexity, and the rest is shared between them
+        let min_cplx_el = self.m.min_complexity();
+        let extra_cplx = f64::max(0.0, target_cplx - min_cplx_el * len as f64);
+        if len == 1 {
+            return Some(vec![min_cplx_el + extra_cplx]);
+        }
+        let fraction = fraction.clamp(0.0, 1.0);
+        let mut budgets = vec![min_cplx_el + extra_cplx * (1.0 - fraction) / (len - 1) as f64; len];
+        budgets[self.rng.usize(..len)] = min_cplx_el + extra_cplx * fraction;
+        Some(budgets)
+    }
 }
 #[coverage(off)]
 fn clamp(range: &RangeInclusive<usize>, x: usize) -> usize {
@@ -191,7 +191,7 @@ macro_rules! impl_vec_mutation {
                 step: &Self::RandomStep,
                 max_cplx: f64,
             ) -> Self::Concrete<'a> {
-                let inner_step_idx = step.sampling.sample();
+                let inner_step_idx = step.sampling.sample_with(&mutator.rng);
                 let step = &step.inner_steps[inner_step_idx];
                 match step {
                     $(
@@ -254,7 +254,7 @@ macro_rules! impl_vec_mutation {
                 if step.inner_steps.is_empty() {
                     return None;
                 }
-                let inner_step_idx = step.sampling.sample();
+                let inner_step_idx = step.sampling.sample_with(&mutator.rng);
                 let step_raw = step as *mut Self::Step;
                 {
                     let inner_step = &mut step.inner_steps[inner_step_idx];
@@ -122,10 +122,19 @@ impl VoseAlias {
     /// It returns an index within `0` .. `original_probabilities.len()`.
     #[coverage(off)]
     pub fn sample(&self) -> usize {
+        self.sample_with(&self.rng)
+    }
+
+    /// Sample the Vose alias using the given random number generator instead of its own.
+    ///
+    /// It is used by mutators that store a Vose alias in their cache, so that the sampled
+    /// indices depend only on the state of the mutator’s random number generator.
+    #[coverage(off)]
+    pub fn sample_with(&self, rng: &Rng) -> usize {
         // Step 1
-        let i = self.rng.usize(..self.prob.len());
+        let i = rng.usize(..self.prob.len());
         // Step 2
-        if self.rng.f64() <= unsafe { *self.prob.get_unchecked(i) } {
+        if rng.f64() <= unsafe { *self.prob.get_unchecked(i) } {
             // Step 3
             i
         } else {
@@ -0,0 +1,244 @@
+//! A mutator that generates values by calling user-provided constructor functions.
+//!
+//! ```
+//! use fuzzcheck::mutators::weighted_constructor::WeightedConstructorMutator;
+//!
+//! // impl Mutator<Vec<u8>>
+//! let m = WeightedConstructorMutator::new(vec![
+//!     // 70% of the values are small random buffers
+//!     (0.7, Box::new(|rng: &fastrand::Rng| (0..rng.usize(..16)).map(|_| rng.u8(..)).collect()) as Box<dyn Fn(&fastrand::Rng) -> Vec<u8>>),
+//!     // 20% are large buffers of zeros
+//!     (0.2, Box::new(|rng: &fastrand::Rng| vec![0; rng.usize(1000..2000)])),
+//!     // 10% are empty
+//!     (0.1, Box::new(|_: &fastrand::Rng| vec![])),
+//! ]);
+//! ```
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::Mutator;
+
+/// The complexity of every value generated by a [`WeightedConstructorMutator`]
+const WEIGHTED_CONSTRUCTOR_COMPLEXITY: f64 = 1.0;
+
+/// A [`WeightedConstructorMutator`] generates values by calling one of several
+/// constructor functions, each chosen with a given weight.
+///
+/// It is useful for types that are too complex to be mutated structurally, but for
+/// which a few generation strategies can be written by hand.
+///
+/// * `random_arbitrary` and `random_mutate` call a constructor chosen according to the weights
+/// * `ordered_arbitrary` and `ordered_mutate` call each constructor in turn
+///
+/// Constructors with a weight of `0.0` are never called.
+///
+/// A mutation replaces the whole value with a newly constructed one. Because the mutator
+/// cannot know which constructor made a value, every value is considered valid and has
+/// the same complexity.
+pub struct WeightedConstructorMutator<T, F = Box<dyn Fn(&fastrand::Rng) -> T>>
+where
+    T: Clone + 'static,
+    F: Fn(&fastrand::Rng) -> T,
+{
+    constructors: Vec<F>,
+    cumulative_weights: Vec<f64>,
+    rng: fastrand::Rng,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, F> WeightedConstructorMutator<T, F>
+where
+    T: Clone + 'static,
+    F: Fn(&fastrand::Rng) -> T,
+{
+    /// Create a new `WeightedConstructorMutator` from a list of `(weight, constructor)` pairs.
+    ///
+    /// The weights do not need to sum up to 1.0, but they must be non-negative and
+    /// at least one of them must be positive.
+    #[coverage(off)]
+    pub fn new(constructors: Vec<(f64, F)>) -> Self {
+        assert!(
+            !constructors.is_empty(),
+            "a WeightedConstructorMutator needs at least one constructor"
+        );
+        let mut cumulative_weights = Vec::with_capacity(constructors.len());
+        let mut positive_constructors = Vec::with_capacity(constructors.len());
+        let mut sum = 0.0;
+        for (weight, constructor) in constructors {
+            assert!(
+                weight >= 0.0 && weight.is_finite(),
+                "the weights of a WeightedConstructorMutator must be non-negative"
+            );
+            // constructors with a weight of 0 are never used
+            if weight > 0.0 {
+                sum += weight;
+                cumulative_weights.push(sum);
+                positive_constructors.push(constructor);
+            }
+        }
+        assert!(
+            sum > 0.0,
+            "at least one weight of a WeightedConstructorMutator must be positive"
+        );
+        Self {
+            constructors: positive_constructors,
+            cumulative_weights,
+            rng: fastrand::Rng::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Choose the index of a constructor according to the weights
+    #[coverage(off)]
+    fn random_constructor_idx(&self) -> usize {
+        let total = *self.cumulative_weights.last().unwrap();
+        let x = self.rng.f64() * total;
+        let idx = self.cumulative_weights.partition_point(
+            #[coverage(off)]
+            |&w| w <= x,
+        );
+        std::cmp::min(idx, self.constructors.len() - 1)
+    }
+
+    #[coverage(off)]
+    fn construct(&self, idx: usize) -> T {
+        (self.constructors[idx])(&self.rng)
+    }
+}
+
+impl<T, F> Mutator<T> for WeightedConstructorMutator<T, F>
+where
+    T: Clone + 'static,
+    F: Fn(&fastrand::Rng) -> T + 'static,
+{
+    #[doc(hidden)]
+    type Cache = ();
+    #[doc(hidden)]
+    type MutationStep = usize;
+    #[doc(hidden)]
+    type ArbitraryStep = usize;
+    #[doc(hidden)]
+    type UnmutateToken = T;
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn initialize(&self) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_arbitrary_step(&self) -> Self::ArbitraryStep {
+        0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn is_valid(&self, _value: &T) -> bool {
+        true
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn validate_value(&self, _value: &T) -> Option<Self::Cache> {
+        Some(())
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn default_mutation_step(&self, _value: &T, _cache: &Self::Cache) -> Self::MutationStep {
+        0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn global_search_space_complexity(&self) -> f64 {
+        // the constructors are random, so the number of possible values is unknown
+        // and assumed to be large
+        64.0
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn max_complexity(&self) -> f64 {
+        WEIGHTED_CONSTRUCTOR_COMPLEXITY
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn min_complexity(&self) -> f64 {
+        WEIGHTED_CONSTRUCTOR_COMPLEXITY
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn complexity(&self, _value: &T, _cache: &Self::Cache) -> f64 {
+        WEIGHTED_CONSTRUCTOR_COMPLEXITY
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(T, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        let idx = *step % self.constructors.len();
+        *step = step.wrapping_add(1);
+        Some((self.construct(idx), WEIGHTED_CONSTRUCTOR_COMPLEXITY))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_arbitrary(&self, _max_cplx: f64) -> (T, f64) {
+        (
+            self.construct(self.random_constructor_idx()),
+            WEIGHTED_CONSTRUCTOR_COMPLEXITY,
+        )
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn ordered_mutate(
+        &self,
+        value: &mut T,
+        _cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        _subvalue_provider: &dyn crate::SubValueProvider,
+        max_cplx: f64,
+    ) -> Option<(Self::UnmutateToken, f64)> {
+        if max_cplx < self.min_complexity() {
+            return None;
+        }
+        let idx = *step % self.constructors.len();
+        *step = step.wrapping_add(1);
+        let old_value = std::mem::replace(value, self.construct(idx));
+        Some((old_value, WEIGHTED_CONSTRUCTOR_COMPLEXITY))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn random_mutate(&self, value: &mut T, _cache: &mut Self::Cache, _max_cplx: f64) -> (Self::UnmutateToken, f64) {
+        let old_value = std::mem::replace(value, self.construct(self.random_constructor_idx()));
+        (old_value, WEIGHTED_CONSTRUCTOR_COMPLEXITY)
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn unmutate(&self, value: &mut T, _cache: &mut Self::Cache, t: Self::UnmutateToken) {
+        *value = t;
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn visit_subvalues<'a>(&self, _value: &'a T, _cache: &'a Self::Cache, _visit: &mut dyn FnMut(&'a dyn Any, f64)) {}
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+
+    #[doc(hidden)]
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
+}
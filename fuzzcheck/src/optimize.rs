@@ -0,0 +1,81 @@
+//! Utilities to search for values maximizing a cost function, using a mutator but not the fuzzer.
+
+use crate::subvalue_provider::EmptySubValueProvider;
+use crate::Mutator;
+
+/// Search for a value of maximal cost by hill climbing, starting from a random value.
+///
+/// At each of the `iterations`, the current value is mutated, and the mutation is kept if
+/// it increases the value of `cost_fn`, otherwise it is undone. The mutations are the
+/// ordered mutations of the current value, followed by random mutations once they are
+/// exhausted. All the values have a complexity of at most `max_cplx`.
+///
+/// It returns the value of highest cost that was found, along with its cost. Unlike the
+/// fuzzer, it does not run any test function or collect code coverage, which makes it
+/// convenient to generate worst-case inputs for a benchmark.
+///
+/// ```
+/// use fuzzcheck::DefaultMutator;
+///
+/// // find a vector that takes many comparisons to sort with insertion sort
+/// fn nbr_comparisons(xs: &Vec<u8>) -> f64 {
+///     let mut xs = xs.clone();
+///     let mut count = 0;
+///     for i in 1..xs.len() {
+///         let mut j = i;
+///         while j > 0 {
+///             count += 1;
+///             if xs[j - 1] <= xs[j] {
+///                 break;
+///             }
+///             xs.swap(j - 1, j);
+///             j -= 1;
+///         }
+///     }
+///     count as f64
+/// }
+/// let (xs, cost) = fuzzcheck::optimize::hill_climb(<Vec<u8>>::default_mutator(), nbr_comparisons, 1000, 200.0);
+/// assert_eq!(cost, nbr_comparisons(&xs));
+/// ```
+#[coverage(off)]
+pub fn hill_climb<T, M>(mutator: M, cost_fn: impl Fn(&T) -> f64, iterations: usize, max_cplx: f64) -> (T, f64)
+where
+    T: Clone + 'static,
+    M: Mutator<T>,
+{
+    mutator.initialize();
+    let (mut value, _) = mutator.random_arbitrary(max_cplx);
+    let mut cost = cost_fn(&value);
+    let mut cache = mutator.validate_value(&value).unwrap();
+    let mut step = Some(mutator.default_mutation_step(&value, &cache));
+
+    for _ in 0..iterations {
+        let mutation = step.as_mut().and_then(
+            #[coverage(off)]
+            |step| mutator.ordered_mutate(&mut value, &mut cache, step, &EmptySubValueProvider, max_cplx),
+        );
+        let (token, cplx) = match mutation {
+            Some(mutation) => mutation,
+            None => {
+                // the ordered mutations of the current value are exhausted
+                step = None;
+                mutator.random_mutate(&mut value, &mut cache, max_cplx)
+            }
+        };
+        if cplx > max_cplx {
+            // random mutations may exceed the maximum complexity
+            mutator.unmutate(&mut value, &mut cache, token);
+            continue;
+        }
+        let new_cost = cost_fn(&value);
+        if new_cost > cost {
+            cost = new_cost;
+            // the cache is not always updated by a mutation
+            cache = mutator.validate_value(&value).unwrap();
+            step = Some(mutator.default_mutation_step(&value, &cache));
+        } else {
+            mutator.unmutate(&mut value, &mut cache, token);
+        }
+    }
+    (value, cost)
+}
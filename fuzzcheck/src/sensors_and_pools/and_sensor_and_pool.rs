@@ -150,6 +150,20 @@ where
     fn weight(&self) -> f64 {
         self.p1_weight() + self.p2_weight()
     }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_states(
+            &self.rng,
+            [self.p1.rng_state(), self.p2.rng_state()],
+        ))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let mut states = crate::mutators::set_rng_states(&self.rng, state);
+        self.p1.set_rng_state(states.next().unwrap_or_default());
+        self.p2.set_rng_state(states.next().unwrap_or_default());
+    }
 }
 
 impl<P1, P2, ObservationsMarker> SaveToStatsFolder for AndPool<P1, P2, ObservationsMarker>
@@ -258,6 +272,11 @@ where
         deltas.extend(deltas_2);
         deltas
     }
+
+    #[coverage(off)]
+    fn would_accept(&self, observations: &(O1, O2), complexity: f64) -> bool {
+        self.p1.would_accept(&observations.0, complexity) || self.p2.would_accept(&observations.1, complexity)
+    }
 }
 
 impl<P1, P2, O> CompatibleWithObservations<O> for AndPool<P1, P2, SameObservations>
@@ -286,6 +305,11 @@ where
         deltas.extend(deltas_2);
         deltas
     }
+
+    #[coverage(off)]
+    fn would_accept(&self, observations: &O, complexity: f64) -> bool {
+        self.p1.would_accept(observations, complexity) || self.p2.would_accept(observations, complexity)
+    }
 }
 
 impl<S1, S2> ToCSV for AndPoolStats<S1, S2>
@@ -408,4 +432,18 @@ impl SensorAndPool for AndSensorAndPool {
             self.sap1.get_random_index()
         }
     }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_states(
+            &self.rng,
+            [self.sap1.rng_state(), self.sap2.rng_state()],
+        ))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let mut states = crate::mutators::set_rng_states(&self.rng, state);
+        self.sap1.set_rng_state(states.next().unwrap_or_default());
+        self.sap2.set_rng_state(states.next().unwrap_or_default());
+    }
 }
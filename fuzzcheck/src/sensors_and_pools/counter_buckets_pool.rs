@@ -0,0 +1,143 @@
+use std::fmt::Debug;
+
+use super::unique_values_pool::UniqueValuesPoolStats;
+use super::UniqueValuesPool;
+use crate::traits::{CorpusDelta, Pool, SaveToStatsFolder};
+use crate::{CompatibleWithObservations, PoolStorageIndex};
+
+/// The number of different buckets returned by [`counter_bucket`]
+pub const NBR_COUNTER_BUCKETS: usize = 66;
+
+/// Returns the bucket of a counter's hit count.
+///
+/// The counts `0`, `1`, `2`, and `3` each have their own bucket. Larger counts are
+/// grouped by powers of two: `4..=7`, `8..=15`, `16..=31`, etc. The returned bucket
+/// is always smaller than [`NBR_COUNTER_BUCKETS`].
+#[coverage(off)]
+pub fn counter_bucket(count: u64) -> u8 {
+    if count < 4 {
+        count as u8
+    } else {
+        (u64::BITS - count.leading_zeros() + 1) as u8
+    }
+}
+
+/// A pool that stores an input for each bucket of hit counts of each sensor counter.
+///
+/// The hit count of a counter is sorted into a bucket using [`counter_bucket`].
+/// Unlike a pool that only tracks whether a counter was hit, this pool keeps test
+/// cases that execute a loop a different number of times, which helps to find bugs
+/// that only appear at higher iteration counts.
+///
+/// It is [compatible with](crate::CompatibleWithObservations) the following sensors:
+/// * [`CodeCoverageSensor`](crate::sensors_and_pools::CodeCoverageSensor)
+/// * any other sensor whose [observations](crate::Sensor::Observations) are given by an iterator of `(usize, u64)`
+pub struct CounterBucketsPool {
+    pool: UniqueValuesPool<u8>,
+}
+impl Debug for CounterBucketsPool {
+    #[coverage(off)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CounterBucketsPool").field("pool", &self.pool).finish()
+    }
+}
+
+impl CounterBucketsPool {
+    #[coverage(off)]
+    pub fn new(name: &str, size: usize) -> Self {
+        Self {
+            pool: UniqueValuesPool::new(name, size),
+        }
+    }
+}
+
+impl Pool for CounterBucketsPool {
+    type Stats = UniqueValuesPoolStats;
+
+    #[coverage(off)]
+    fn stats(&self) -> Self::Stats {
+        self.pool.stats()
+    }
+
+    #[coverage(off)]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        self.pool.get_random_index()
+    }
+}
+impl SaveToStatsFolder for CounterBucketsPool {
+    #[coverage(off)]
+    fn save_to_stats_folder(&self) -> Vec<(std::path::PathBuf, Vec<u8>)> {
+        self.pool.save_to_stats_folder()
+    }
+}
+
+impl<O> CompatibleWithObservations<O> for CounterBucketsPool
+where
+    for<'a> &'a O: IntoIterator<Item = &'a (usize, u64)>,
+{
+    #[coverage(off)]
+    fn process(&mut self, input_id: PoolStorageIndex, observations: &O, complexity: f64) -> Vec<CorpusDelta> {
+        let buckets = observations
+            .into_iter()
+            .map(
+                #[coverage(off)]
+                |&(index, count)| (index, counter_bucket(count)),
+            )
+            .collect::<Vec<_>>();
+        <UniqueValuesPool<u8> as CompatibleWithObservations<Vec<(usize, u8)>>>::process(
+            &mut self.pool,
+            input_id,
+            &buckets,
+            complexity,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[coverage(off)]
+    fn test_counter_bucket() {
+        assert_eq!(counter_bucket(0), 0);
+        assert_eq!(counter_bucket(1), 1);
+        assert_eq!(counter_bucket(2), 2);
+        assert_eq!(counter_bucket(3), 3);
+        for count in 4..=7 {
+            assert_eq!(counter_bucket(count), 4);
+        }
+        for count in 8..=15 {
+            assert_eq!(counter_bucket(count), 5);
+        }
+        assert_eq!(counter_bucket(16), 6);
+        assert_eq!(counter_bucket(u64::MAX), NBR_COUNTER_BUCKETS as u8 - 1);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_counter_bucket_is_monotonic() {
+        let mut previous = 0;
+        for count in 0..100_000 {
+            let bucket = counter_bucket(count);
+            assert!(bucket == previous || bucket == previous + 1);
+            previous = bucket;
+        }
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_counter_buckets_pool() {
+        let mut pool = CounterBucketsPool::new("buckets", 2);
+        let first = PoolStorageIndex::mock(0);
+        let second = PoolStorageIndex::mock(1);
+        let third = PoolStorageIndex::mock(2);
+
+        assert!(!pool.process(first, &vec![(0, 1), (1, 5)], 1.0).is_empty());
+        // 6 is in the same bucket as 5
+        assert!(pool.process(second, &vec![(1, 6)], 1.0).is_empty());
+        // 8 is in a new bucket
+        assert!(!pool.process(third, &vec![(1, 8)], 1.0).is_empty());
+        assert_eq!(pool.stats().size, 2);
+    }
+}
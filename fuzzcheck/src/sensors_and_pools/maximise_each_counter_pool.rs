@@ -126,6 +126,15 @@ impl Pool for MaximiseEachCounterPool {
         self.ranked_inputs.update(choice, delta);
         Some(input.idx)
     }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
 }
 
 impl SaveToStatsFolder for MaximiseEachCounterPool {
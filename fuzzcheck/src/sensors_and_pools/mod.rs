@@ -4,12 +4,15 @@ Types implementing the [Sensor](crate::Sensor) and [Pool](crate::Pool) traits.
 
 mod allocations_sensor;
 mod and_sensor_and_pool;
+mod counter_buckets_pool;
 mod map_sensor;
 mod maximise_each_counter_pool;
 mod maximise_observation_pool;
 mod most_n_diverse_pool;
 mod noop_sensor;
+mod rarity_pool;
 mod simplest_to_activate_counter_pool;
+mod staged_pool;
 mod static_value_sensor;
 mod test_failure_pool;
 mod unique_values_pool;
@@ -20,6 +23,8 @@ pub use allocations_sensor::{AllocationSensor, CountingAllocator};
 #[doc(inline)]
 pub use and_sensor_and_pool::{AndPool, AndSensor, AndSensorAndPool, DifferentObservations, SameObservations};
 #[doc(inline)]
+pub use counter_buckets_pool::{counter_bucket, CounterBucketsPool, NBR_COUNTER_BUCKETS};
+#[doc(inline)]
 pub use map_sensor::MapSensor;
 #[doc(inline)]
 pub use map_sensor::WrapperSensor;
@@ -32,8 +37,12 @@ pub use most_n_diverse_pool::MostNDiversePool;
 #[doc(inline)]
 pub use noop_sensor::NoopSensor;
 #[doc(inline)]
+pub use rarity_pool::RarityPool;
+#[doc(inline)]
 pub use simplest_to_activate_counter_pool::SimplestToActivateCounterPool;
 #[doc(inline)]
+pub use staged_pool::StagedPool;
+#[doc(inline)]
 pub use static_value_sensor::StaticValueSensor;
 #[doc(inline)]
 pub use test_failure_pool::TestFailure;
@@ -69,6 +78,22 @@ pub trait PoolExt: Pool + Sized {
         let p_weight = p.weight();
         AndPool::<_, _, SM>::new(self, p, self_weight, override_weight.unwrap_or(p_weight))
     }
+
+    /// Create a [`StagedPool`](crate::sensors_and_pools::StagedPool) where the test cases accepted by `self` are offered to `p`.
+    ///
+    /// ## Arguments
+    /// - `p` is the second stage of the pipeline, which processes the second observations of an
+    ///   [`AndSensor`](crate::sensors_and_pools::AndSensor) for the test cases accepted by `self`
+    /// - `override_weight` determines the relative chance of selecting `p` when the resulting [`StagedPool`](crate::sensors_and_pools::StagedPool)
+    ///   is asked to provide a test case. If `None`, [`p.weight()`](crate::Pool::weight) will be used. The weight of `self` is always `self.weight()`.
+    fn staged<P>(self, p: P, override_weight: Option<f64>) -> StagedPool<Self, P>
+    where
+        P: Pool,
+    {
+        let self_weight = self.weight();
+        let p_weight = p.weight();
+        StagedPool::new(self, p, self_weight, override_weight.unwrap_or(p_weight))
+    }
 }
 
 impl<P> PoolExt for P where P: Pool {}
@@ -114,6 +139,8 @@ pub mod stats {
     #[doc(inline)]
     pub use super::most_n_diverse_pool::MostNDiversePoolStats;
     #[doc(inline)]
+    pub use super::rarity_pool::RarityPoolStats;
+    #[doc(inline)]
     pub use super::simplest_to_activate_counter_pool::UniqueCoveragePoolStats;
     #[doc(inline)]
     pub use super::test_failure_pool::TestFailurePoolStats;
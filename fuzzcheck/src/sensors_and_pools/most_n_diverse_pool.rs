@@ -69,6 +69,15 @@ impl Pool for MostNDiversePool {
         let input = &self.inputs[choice];
         Some(input.pool_idx)
     }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
 }
 impl SaveToStatsFolder for MostNDiversePool {
     #[coverage(off)]
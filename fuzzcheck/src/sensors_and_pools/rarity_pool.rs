@@ -0,0 +1,299 @@
+use std::fmt::{Debug, Display};
+use std::path::Path;
+
+use nu_ansi_term::Color;
+
+use crate::fenwick_tree::FenwickTree;
+use crate::traits::{CorpusDelta, Pool, SaveToStatsFolder, Stats};
+use crate::{CSVField, CompatibleWithObservations, PoolStorageIndex, ToCSV};
+
+/// The number of calls to `process` after which the scores of the test cases are
+/// recomputed, if no test case was added in the meantime
+const RESCORE_INTERVAL: usize = 1024;
+
+/// The statistics of a [RarityPool]
+#[derive(Clone)]
+pub struct RarityPoolStats {
+    name: String,
+    size: usize,
+    rarest_hit_count: u64,
+}
+
+impl Display for RarityPoolStats {
+    #[coverage(off)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            Color::LightPurple.paint(format!(
+                "{}({} rarest: {})",
+                self.name, self.size, self.rarest_hit_count
+            ))
+        )
+    }
+}
+
+impl ToCSV for RarityPoolStats {
+    #[coverage(off)]
+    fn csv_headers(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::String(format!("{}-count", self.name)),
+            CSVField::String(format!("{}-rarest", self.name)),
+        ]
+    }
+    #[coverage(off)]
+    fn to_csv_record(&self) -> Vec<CSVField> {
+        vec![
+            CSVField::Integer(self.size as isize),
+            CSVField::Integer(self.rarest_hit_count as isize),
+        ]
+    }
+}
+impl Stats for RarityPoolStats {}
+
+#[derive(Debug)]
+struct Input {
+    counters: Vec<usize>,
+    idx: PoolStorageIndex,
+}
+
+/// A pool that favours the test cases hitting rarely hit counters.
+///
+/// The pool counts, for each counter of the sensor, the number of test cases that hit it.
+/// These counts are updated every time a test case is processed, whether it is added to
+/// the pool or not.
+///
+/// A test case is added to the pool if it hits a counter that is not hit by any test case
+/// of the pool. The score of a test case is inversely proportional to the hit count of the
+/// rarest counter it hits. Test cases with a higher score are chosen more often for
+/// mutation, so that the fuzzer spends more time on the rarely explored parts of the code.
+///
+/// It is [compatible with](crate::CompatibleWithObservations) the following sensors:
+/// * [`CodeCoverageSensor`](crate::sensors_and_pools::CodeCoverageSensor)
+/// * any other sensor whose [observations](crate::Sensor::Observations) are given by an iterator of `(usize, u64)`
+pub struct RarityPool {
+    name: String,
+    hit_counts: Vec<u64>,
+    covered: Vec<bool>,
+    inputs: Vec<Input>,
+    ranked_inputs: FenwickTree,
+    nbr_processed_since_rescore: usize,
+    stats: RarityPoolStats,
+    rng: fastrand::Rng,
+}
+
+impl Debug for RarityPool {
+    #[coverage(off)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RarityPool")
+            .field("hit_counts", &self.hit_counts)
+            .field("inputs", &self.inputs)
+            .finish()
+    }
+}
+
+impl RarityPool {
+    #[coverage(off)]
+    pub fn new(name: &str, size: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            hit_counts: vec![0; size],
+            covered: vec![false; size],
+            inputs: vec![],
+            ranked_inputs: FenwickTree::new(vec![]),
+            nbr_processed_since_rescore: 0,
+            stats: RarityPoolStats {
+                name: name.to_string(),
+                size: 0,
+                rarest_hit_count: 0,
+            },
+            rng: fastrand::Rng::new(),
+        }
+    }
+
+    /// The test cases of the pool and their current score, from the highest score to the lowest
+    #[coverage(off)]
+    pub fn ranked_test_cases(&self) -> Vec<(PoolStorageIndex, f64)> {
+        let mut ranked = self
+            .inputs
+            .iter()
+            .map(
+                #[coverage(off)]
+                |input| (input.idx, self.score(input)),
+            )
+            .collect::<Vec<_>>();
+        ranked.sort_by(
+            #[coverage(off)]
+            |a, b| b.1.total_cmp(&a.1),
+        );
+        ranked
+    }
+
+    /// The number of test cases that hit the rarest counter hit by the input
+    #[coverage(off)]
+    fn rarest_hit_count(&self, input: &Input) -> u64 {
+        input
+            .counters
+            .iter()
+            .map(
+                #[coverage(off)]
+                |&counter| self.hit_counts[counter],
+            )
+            .min()
+            .unwrap_or(u64::MAX)
+            .max(1)
+    }
+
+    #[coverage(off)]
+    fn score(&self, input: &Input) -> f64 {
+        1.0 / self.rarest_hit_count(input) as f64
+    }
+
+    #[coverage(off)]
+    fn rescore(&mut self) {
+        let scores = self
+            .inputs
+            .iter()
+            .map(
+                #[coverage(off)]
+                |input| self.score(input),
+            )
+            .collect();
+        self.ranked_inputs = FenwickTree::new(scores);
+        self.nbr_processed_since_rescore = 0;
+
+        self.stats.size = self.inputs.len();
+        self.stats.rarest_hit_count = self
+            .inputs
+            .iter()
+            .map(
+                #[coverage(off)]
+                |input| self.rarest_hit_count(input),
+            )
+            .min()
+            .unwrap_or(0);
+    }
+}
+
+impl Pool for RarityPool {
+    type Stats = RarityPoolStats;
+
+    #[coverage(off)]
+    fn stats(&self) -> Self::Stats {
+        self.stats.clone()
+    }
+
+    #[coverage(off)]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        let choice = self.ranked_inputs.sample(&self.rng)?;
+        Some(self.inputs[choice].idx)
+    }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
+}
+
+impl SaveToStatsFolder for RarityPool {
+    #[coverage(off)]
+    fn save_to_stats_folder(&self) -> Vec<(std::path::PathBuf, Vec<u8>)> {
+        vec![]
+    }
+}
+
+impl<O> CompatibleWithObservations<O> for RarityPool
+where
+    for<'a> &'a O: IntoIterator<Item = &'a (usize, u64)>,
+{
+    #[coverage(off)]
+    fn process(&mut self, input_id: PoolStorageIndex, observations: &O, _complexity: f64) -> Vec<CorpusDelta> {
+        let mut counters = vec![];
+        let mut is_new = false;
+        for &(index, count) in observations.into_iter() {
+            if count == 0 {
+                continue;
+            }
+            self.hit_counts[index] += 1;
+            is_new |= !self.covered[index];
+            counters.push(index);
+        }
+        self.nbr_processed_since_rescore += 1;
+        if !is_new {
+            if self.nbr_processed_since_rescore >= RESCORE_INTERVAL {
+                self.rescore();
+            }
+            return vec![];
+        }
+        for &counter in counters.iter() {
+            self.covered[counter] = true;
+        }
+        self.inputs.push(Input {
+            counters,
+            idx: input_id,
+        });
+        self.rescore();
+
+        vec![CorpusDelta {
+            path: Path::new(&self.name).to_path_buf(),
+            add: true,
+            remove: vec![],
+        }]
+    }
+
+    #[coverage(off)]
+    fn would_accept(&self, observations: &O, _complexity: f64) -> bool {
+        observations.into_iter().any(
+            #[coverage(off)]
+            |&(index, count)| count != 0 && !self.covered[index],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RarityPool;
+    use crate::traits::{CompatibleWithObservations, Pool};
+    use crate::PoolStorageIndex;
+
+    #[test]
+    #[coverage(off)]
+    fn test_rarity_pool() {
+        let mut pool = RarityPool::new("rarity", 4);
+        assert!(pool.get_random_index().is_none());
+
+        let common = PoolStorageIndex::mock(0);
+        let rare = PoolStorageIndex::mock(1);
+        // counter 0 is hit by every test case, counter 1 only by `rare`
+        assert!(!pool.process(common, &vec![(0, 1)], 1.0).is_empty());
+        assert!(!pool.process(rare, &vec![(0, 1), (1, 3)], 1.0).is_empty());
+        for i in 2..10 {
+            let observations = vec![(0, 1)];
+            assert!(!pool.would_accept(&observations, 1.0));
+            assert!(pool.process(PoolStorageIndex::mock(i), &observations, 1.0).is_empty());
+        }
+        assert!(pool.would_accept(&vec![(2, 1)], 1.0));
+        assert!(!pool.would_accept(&vec![(0, 1), (2, 0)], 1.0));
+
+        let ranked = pool.ranked_test_cases();
+        assert_eq!(ranked.len(), 2);
+        assert_eq!(ranked[0], (rare, 1.0));
+        assert_eq!(ranked[1], (common, 0.1));
+
+        // the scores used to choose test cases are updated when a test case is added
+        assert!(!pool.process(PoolStorageIndex::mock(10), &vec![(3, 1)], 1.0).is_empty());
+        let mut nbr_rare = 0;
+        for _ in 0..1000 {
+            if pool.get_random_index() == Some(rare) {
+                nbr_rare += 1;
+            }
+        }
+        // the weights are 1.0 for `rare`, 0.1 for `common`, and 1.0 for the last one
+        assert!((400..600).contains(&nbr_rare));
+        assert_eq!(pool.stats().size, 3);
+    }
+}
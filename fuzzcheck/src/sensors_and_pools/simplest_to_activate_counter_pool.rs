@@ -485,6 +485,15 @@ impl Pool for SimplestToActivateCounterPool {
         self.ranked_inputs.update(choice, delta);
         Some(input.data)
     }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
 }
 
 impl SaveToStatsFolder for SimplestToActivateCounterPool {
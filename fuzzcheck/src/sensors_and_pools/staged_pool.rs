@@ -0,0 +1,194 @@
+//! A pool that promotes the test cases of a first pool into a second one
+//!
+//! If `s1` and `p1` are a compatible sensor and pool, as well as `s2` and `p2`, then they
+//! can be combined into a two-stage pipeline as follows:
+//! ```
+//! use fuzzcheck::sensors_and_pools::{AndSensor, StagedPool};
+//! use fuzzcheck::PoolExt;
+//! # use fuzzcheck::sensors_and_pools::{NoopSensor, UniqueValuesPool};
+//! # let (s1, s2) = (NoopSensor, NoopSensor);
+//! # let (p1, p2) = (UniqueValuesPool::<u8>::new("a", 0), UniqueValuesPool::<bool>::new("b", 0));
+//! let s = AndSensor(s1, s2);
+//! let p = p1.staged(p2, Some(2.0));
+//! ```
+//! The observations of `s1` are given to `p1`. Only if `p1` accepts the test case are the
+//! observations of `s2` given to `p2`.
+use std::path::PathBuf;
+
+use super::and_sensor_and_pool::AndPoolStats;
+use crate::traits::{CompatibleWithObservations, CorpusDelta, Pool, SaveToStatsFolder};
+use crate::PoolStorageIndex;
+
+/// A pool where the test cases accepted by a first pool `A` are offered to a second pool `B`
+///
+/// A convenient way to create a `StagedPool` is to use [`a.staged(b, ..)`](crate::PoolExt::staged), but
+/// you are free to use [`StagedPool::new`](StagedPool::new) as well.
+///
+/// If `A` is [`CompatibleWithObservations<O1>`] and `B` is [`CompatibleWithObservations<O2>`], then
+/// `StagedPool<A, B>` is `CompatibleWithObservations<(O1, O2)>`, which are the observations of an
+/// [`AndSensor`](crate::sensors_and_pools::AndSensor). The first observations are always processed by `a`.
+/// The second observations are processed by `b` only if `a` added the test case to its pool. Therefore,
+/// `b` only contains test cases that were first found interesting by `a` and then also
+/// triggered the condition observed by the second sensor.
+///
+/// For example, `a` can keep the test cases reaching new code, and `b` the ones among them that reach
+/// a code region close to an assertion. Since `b` usually contains few test cases, giving it a
+/// large weight focuses the fuzzer on mutating them.
+///
+/// When the `StagedPool` is [asked to provide a test case](crate::Pool::get_random_index), it
+/// chooses between `a` and `b` randomly based on their weights, given by `self.a_weight` and `self.b_weight`.
+pub struct StagedPool<A, B>
+where
+    A: Pool,
+    B: Pool,
+{
+    pub a: A,
+    pub b: B,
+
+    pub a_weight: f64,
+    pub b_weight: f64,
+
+    rng: fastrand::Rng,
+}
+
+impl<A, B> StagedPool<A, B>
+where
+    A: Pool,
+    B: Pool,
+{
+    #[coverage(off)]
+    pub fn new(a: A, b: B, a_weight: f64, b_weight: f64) -> Self {
+        Self {
+            a,
+            b,
+            a_weight,
+            b_weight,
+            rng: fastrand::Rng::new(),
+        }
+    }
+}
+
+impl<A, B> Pool for StagedPool<A, B>
+where
+    A: Pool,
+    B: Pool,
+{
+    type Stats = AndPoolStats<A::Stats, B::Stats>;
+
+    #[coverage(off)]
+    fn stats(&self) -> Self::Stats {
+        AndPoolStats(self.a.stats(), self.b.stats())
+    }
+    #[coverage(off)]
+    fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
+        let choice = self.rng.f64() * self.weight();
+        if choice <= self.a_weight {
+            self.a.get_random_index().or_else(
+                #[coverage(off)]
+                || self.b.get_random_index(),
+            )
+        } else {
+            self.b.get_random_index().or_else(
+                #[coverage(off)]
+                || self.a.get_random_index(),
+            )
+        }
+    }
+    #[coverage(off)]
+    fn weight(&self) -> f64 {
+        self.a_weight + self.b_weight
+    }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_states(
+            &self.rng,
+            [self.a.rng_state(), self.b.rng_state()],
+        ))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        let mut states = crate::mutators::set_rng_states(&self.rng, state);
+        self.a.set_rng_state(states.next().unwrap_or_default());
+        self.b.set_rng_state(states.next().unwrap_or_default());
+    }
+}
+
+impl<A, B> SaveToStatsFolder for StagedPool<A, B>
+where
+    A: Pool,
+    B: Pool,
+{
+    #[coverage(off)]
+    fn save_to_stats_folder(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        let mut x = self.a.save_to_stats_folder();
+        x.extend(self.b.save_to_stats_folder());
+        x
+    }
+}
+
+impl<O1, O2, A, B> CompatibleWithObservations<(O1, O2)> for StagedPool<A, B>
+where
+    A: CompatibleWithObservations<O1>,
+    B: CompatibleWithObservations<O2>,
+{
+    #[coverage(off)]
+    fn process(&mut self, input_id: PoolStorageIndex, observations: &(O1, O2), complexity: f64) -> Vec<CorpusDelta> {
+        let mut deltas = self.a.process(input_id, &observations.0, complexity);
+        if deltas.iter().any(
+            #[coverage(off)]
+            |delta| delta.add,
+        ) {
+            deltas.extend(self.b.process(input_id, &observations.1, complexity));
+        }
+        deltas
+    }
+
+    #[coverage(off)]
+    fn would_accept(&self, observations: &(O1, O2), complexity: f64) -> bool {
+        self.a.would_accept(&observations.0, complexity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sensors_and_pools::UniqueValuesPool;
+
+    #[test]
+    #[coverage(off)]
+    fn test_staged_pool() {
+        let mut pool = StagedPool::new(
+            UniqueValuesPool::<u8>::new("coverage", 2),
+            UniqueValuesPool::<bool>::new("promoted", 1),
+            1.0,
+            3.0,
+        );
+        assert_eq!(pool.weight(), 4.0);
+        assert!(pool.get_random_index().is_none());
+
+        // rejected by `a`, so not offered to `b` even though it would accept it
+        let observations = (vec![], vec![(0, true)]);
+        assert!(!pool.would_accept(&observations, 1.0));
+        assert!(pool.process(PoolStorageIndex::mock(0), &observations, 1.0).is_empty());
+        assert_eq!(pool.stats().1.size, 0);
+
+        // accepted by `a` only
+        let deltas = pool.process(PoolStorageIndex::mock(1), &(vec![(0, 1)], vec![]), 1.0);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(pool.stats().0.size, 1);
+        assert_eq!(pool.stats().1.size, 0);
+        assert_eq!(pool.get_random_index(), Some(PoolStorageIndex::mock(1)));
+
+        // accepted by `a`, then promoted to `b`
+        let deltas = pool.process(PoolStorageIndex::mock(2), &(vec![(1, 1)], vec![(0, true)]), 1.0);
+        assert_eq!(deltas.len(), 2);
+        assert!(deltas.iter().all(
+            #[coverage(off)]
+            |delta| delta.add
+        ));
+        assert_eq!(pool.stats().0.size, 2);
+        assert_eq!(pool.stats().1.size, 1);
+        assert_eq!(pool.b.get_random_index(), Some(PoolStorageIndex::mock(2)));
+    }
+}
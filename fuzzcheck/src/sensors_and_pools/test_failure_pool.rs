@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::Display;
 use std::path::PathBuf;
 
@@ -7,6 +8,9 @@ use crate::traits::{CompatibleWithObservations, CorpusDelta, Pool, SaveToStatsFo
 use crate::{CSVField, PoolStorageIndex, ToCSV};
 
 const NBR_ARTIFACTS_PER_ERROR_AND_CPLX: usize = 8;
+/// The maximum number of distinct dropped errors that are counted, which bounds the memory
+/// used to remember them when the test function produces a new error for each failure
+const MAX_COUNTED_DROPPED_ERRORS: usize = 1024;
 
 pub(crate) static mut TEST_FAILURE: Option<TestFailure> = None;
 
@@ -58,25 +62,39 @@ impl SaveToStatsFolder for TestFailureSensor {
 #[derive(Clone, Copy)]
 pub struct TestFailurePoolStats {
     pub count: usize,
+    /// The number of distinct test failures that were ignored because the
+    /// maximum number of distinct errors was reached, up to 1024
+    pub dropped: usize,
 }
 impl Display for TestFailurePoolStats {
     #[coverage(off)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = if self.dropped == 0 {
+            format!("failures({})", self.count)
+        } else {
+            format!("failures({}, {} dropped)", self.count, self.dropped)
+        };
         if self.count == 0 {
-            write!(f, "failures({})", self.count)
+            write!(f, "{}", text)
         } else {
-            write!(f, "{}", Color::Red.paint(format!("failures({})", self.count)))
+            write!(f, "{}", Color::Red.paint(text))
         }
     }
 }
 impl ToCSV for TestFailurePoolStats {
     #[coverage(off)]
     fn csv_headers(&self) -> Vec<CSVField> {
-        vec![CSVField::String("test_failures_count".to_string())]
+        vec![
+            CSVField::String("test_failures_count".to_string()),
+            CSVField::String("test_failures_dropped".to_string()),
+        ]
     }
     #[coverage(off)]
     fn to_csv_record(&self) -> Vec<CSVField> {
-        vec![CSVField::Integer(self.count as isize)]
+        vec![
+            CSVField::Integer(self.count as isize),
+            CSVField::Integer(self.dropped as isize),
+        ]
     }
 }
 impl Stats for TestFailurePoolStats {}
@@ -94,21 +112,77 @@ struct TestFailureListForError {
 /// A pool that saves failing test cases.
 ///
 /// It categorizes the test cases by their failure information and sort them by complexity.
+///
+/// If a maximum number of distinct errors is given, then new errors are ignored once
+/// that number is reached, but simpler test cases are still saved for the errors that
+/// were already found.
 pub struct TestFailurePool {
     name: String,
     inputs: Vec<TestFailureList>,
+    max_distinct_errors: Option<usize>,
+    dropped_errors: HashSet<u64>,
     rng: fastrand::Rng,
 }
 
+enum PositionOfNewInput {
+    NewError,
+    ExistingErrorNewCplx(usize),
+    ExistingErrorAndCplx(usize),
+}
+
 impl TestFailurePool {
     #[coverage(off)]
-    pub(crate) fn new(name: &str) -> Self {
+    pub(crate) fn new(name: &str, max_distinct_errors: Option<usize>) -> Self {
         Self {
             name: name.to_string(),
             inputs: vec![],
+            max_distinct_errors,
+            dropped_errors: HashSet::new(),
             rng: fastrand::Rng::new(),
         }
     }
+
+    /// Where a test case causing `error` with the given complexity should be stored,
+    /// or `None` if it is not interesting
+    #[coverage(off)]
+    fn position_of_new_input(&self, error: &TestFailure, complexity: f64) -> Option<PositionOfNewInput> {
+        if let Some(list_index) = self.inputs.iter().position(
+            #[coverage(off)]
+            |xs| xs.error.id == error.id,
+        ) {
+            let list = &self.inputs[list_index];
+            if let Some(least_complex) = list.inputs.last() {
+                if least_complex.cplx > complexity {
+                    Some(PositionOfNewInput::ExistingErrorNewCplx(list_index))
+                } else if least_complex.cplx == complexity
+                    && least_complex.inputs.len() < NBR_ARTIFACTS_PER_ERROR_AND_CPLX
+                    && !self.inputs.iter().any(
+                        #[coverage(off)]
+                        |xs| xs.error.display == error.display,
+                    )
+                {
+                    Some(PositionOfNewInput::ExistingErrorAndCplx(list_index))
+                } else {
+                    None
+                }
+            } else {
+                Some(PositionOfNewInput::ExistingErrorNewCplx(list_index))
+            }
+        } else if self.has_reached_max_distinct_errors() {
+            None
+        } else {
+            // a new error we haven't seen before
+            Some(PositionOfNewInput::NewError)
+        }
+    }
+
+    #[coverage(off)]
+    fn has_reached_max_distinct_errors(&self) -> bool {
+        self.max_distinct_errors.is_some_and(
+            #[coverage(off)]
+            |max| self.inputs.len() >= max,
+        )
+    }
 }
 
 impl Pool for TestFailurePool {
@@ -118,6 +192,7 @@ impl Pool for TestFailurePool {
     fn stats(&self) -> Self::Stats {
         TestFailurePoolStats {
             count: self.inputs.len(),
+            dropped: self.dropped_errors.len(),
         }
     }
 
@@ -136,6 +211,15 @@ impl Pool for TestFailurePool {
         let input_choice = self.rng.usize(0..least_complexity.inputs.len());
         Some(least_complexity.inputs[input_choice])
     }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
 }
 impl SaveToStatsFolder for TestFailurePool {
     #[coverage(off)]
@@ -164,41 +248,8 @@ impl CompatibleWithObservations<Option<TestFailure>> for TestFailurePool {
         complexity: f64,
     ) -> Vec<CorpusDelta> {
         let error = observations;
-
-        enum PositionOfNewInput {
-            NewError,
-            ExistingErrorNewCplx(usize),
-            ExistingErrorAndCplx(usize),
-        }
-
-        let mut is_interesting = None;
         if let Some(error) = error {
-            if let Some(list_index) = self.inputs.iter().position(
-                #[coverage(off)]
-                |xs| xs.error.id == error.id,
-            ) {
-                let list = &self.inputs[list_index];
-                if let Some(least_complex) = list.inputs.last() {
-                    if least_complex.cplx > complexity {
-                        is_interesting = Some(PositionOfNewInput::ExistingErrorNewCplx(list_index));
-                    } else if least_complex.cplx == complexity {
-                        if least_complex.inputs.len() < NBR_ARTIFACTS_PER_ERROR_AND_CPLX
-                            && !self.inputs.iter().any(
-                                #[coverage(off)]
-                                |xs| xs.error.display == error.display,
-                            )
-                        {
-                            is_interesting = Some(PositionOfNewInput::ExistingErrorAndCplx(list_index));
-                        }
-                    }
-                } else {
-                    is_interesting = Some(PositionOfNewInput::ExistingErrorNewCplx(list_index));
-                }
-            } else {
-                // a new error we haven't seen before
-                is_interesting = Some(PositionOfNewInput::NewError);
-            }
-            if let Some(position) = is_interesting {
+            if let Some(position) = self.position_of_new_input(error, complexity) {
                 let mut path = PathBuf::new();
                 path.push(&self.name);
                 path.push(format!("{}", error.id));
@@ -234,8 +285,94 @@ impl CompatibleWithObservations<Option<TestFailure>> for TestFailurePool {
                     remove: vec![],
                 };
                 return vec![delta];
+            } else if self.has_reached_max_distinct_errors()
+                && !self.inputs.iter().any(
+                    #[coverage(off)]
+                    |xs| xs.error.id == error.id,
+                )
+                && self.dropped_errors.len() < MAX_COUNTED_DROPPED_ERRORS
+            {
+                self.dropped_errors.insert(error.id);
             }
         }
         vec![]
     }
+
+    #[coverage(off)]
+    fn would_accept(&self, observations: &Option<TestFailure>, complexity: f64) -> bool {
+        observations.as_ref().is_some_and(
+            #[coverage(off)]
+            |error| self.position_of_new_input(error, complexity).is_some(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[coverage(off)]
+    fn test_test_failure_pool_would_accept() {
+        let mut pool = TestFailurePool::new("test_failures", None);
+        let error = Some(TestFailure {
+            display: "error".to_owned(),
+            id: 1,
+        });
+        assert!(!pool.would_accept(&None, 1.0));
+        assert!(pool.would_accept(&error, 10.0));
+        assert!(pool.inputs.is_empty());
+
+        assert!(!pool.process(PoolStorageIndex::mock(0), &error, 10.0).is_empty());
+        assert!(!pool.would_accept(&error, 20.0));
+        assert!(pool.would_accept(&error, 5.0));
+        for cplx in [20.0, 5.0] {
+            let accepted = pool.would_accept(&error, cplx);
+            assert_eq!(
+                accepted,
+                !pool.process(PoolStorageIndex::mock(1), &error, cplx).is_empty()
+            );
+        }
+
+        let other_error = Some(TestFailure {
+            display: "other error".to_owned(),
+            id: 2,
+        });
+        assert!(pool.would_accept(&other_error, 100.0));
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_test_failure_pool_max_distinct_errors() {
+        let mut pool = TestFailurePool::new("test_failures", Some(2));
+        let error = |id: u64| {
+            Some(TestFailure {
+                display: format!("error {id}"),
+                id,
+            })
+        };
+        assert!(!pool.process(PoolStorageIndex::mock(0), &error(1), 10.0).is_empty());
+        assert!(!pool.process(PoolStorageIndex::mock(1), &error(2), 10.0).is_empty());
+        assert_eq!(pool.stats().dropped, 0);
+
+        // new errors are ignored once the limit is reached
+        assert!(!pool.would_accept(&error(3), 10.0));
+        assert!(pool.process(PoolStorageIndex::mock(2), &error(3), 10.0).is_empty());
+        assert!(pool.process(PoolStorageIndex::mock(3), &error(3), 5.0).is_empty());
+        assert!(pool.process(PoolStorageIndex::mock(4), &error(4), 10.0).is_empty());
+        assert_eq!(pool.stats().count, 2);
+        assert_eq!(pool.stats().dropped, 2);
+
+        // but simpler test cases are still saved for the existing errors
+        assert!(pool.would_accept(&error(1), 5.0));
+        assert!(!pool.process(PoolStorageIndex::mock(5), &error(1), 5.0).is_empty());
+        assert_eq!(pool.stats().count, 2);
+
+        // only a bounded number of dropped errors are remembered
+        for id in 5..5000 {
+            assert!(pool.process(PoolStorageIndex::mock(6), &error(id), 10.0).is_empty());
+        }
+        assert_eq!(pool.stats().dropped, MAX_COUNTED_DROPPED_ERRORS);
+        assert_eq!(pool.dropped_errors.len(), MAX_COUNTED_DROPPED_ERRORS);
+    }
 }
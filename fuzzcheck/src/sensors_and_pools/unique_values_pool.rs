@@ -1,12 +1,13 @@
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use ahash::{AHashMap, AHashSet};
 
 use crate::data_structures::{Slab, SlabKey};
 use crate::fenwick_tree::FenwickTree;
 use crate::traits::{CorpusDelta, Pool, SaveToStatsFolder, Stats};
+use crate::world::is_valid_file_stem;
 use crate::{CompatibleWithObservations, PoolStorageIndex, ToCSV};
 
 #[derive(Clone, Default)]
@@ -45,16 +46,23 @@ where
 {
     best_for_values: AHashSet<(usize, T)>,
     data: PoolStorageIndex,
+    /// the subfolder of the corpus where the input is saved
+    path: PathBuf,
     score: f64,
     number_times_chosen: usize,
 }
 
 /// A pool that stores an input for each different value of each sensor counter
+///
+/// By default, all the inputs of the pool are saved in the same subfolder of the corpus,
+/// named after the pool. Use [`with_label`](UniqueValuesPool::with_label) to save each
+/// input in a subfolder describing the counter and value it was added for.
 pub struct UniqueValuesPool<T>
 where
     T: Hash + Eq + Clone,
 {
     name: String,
+    label: Option<Box<dyn Fn(usize, &T) -> String>>,
     complexities: Vec<AHashMap<T, f64>>,
     inputs: Slab<Input<T>>,
     best_input_for_value: Vec<AHashMap<T, SlabKey<Input<T>>>>,
@@ -86,6 +94,7 @@ where
     pub fn new(name: &str, size: usize) -> Self {
         Self {
             name: name.to_string(),
+            label: None,
             complexities: vec![AHashMap::new(); size],
             inputs: Slab::new(),
             best_input_for_value: vec![AHashMap::new(); size],
@@ -97,6 +106,45 @@ where
             rng: fastrand::Rng::new(),
         }
     }
+
+    /// Save each input in the subfolder `<name>/<label>` of the corpus, where `label` is
+    /// computed from the index of the counter and the value that caused the input to be
+    /// added to the pool.
+    ///
+    /// If an input is added for several values at once, the label is computed from the
+    /// first one.
+    ///
+    /// The label may contain nested folders separated by `/`. Its components that are empty,
+    /// `.`, or `..`, or that contain a backslash, are ignored, so that the inputs are always
+    /// saved inside the folder of the pool.
+    /// ```
+    /// use fuzzcheck::sensors_and_pools::UniqueValuesPool;
+    ///
+    /// // inputs are saved in folders such as `lengths/counter_3/len_12`
+    /// let pool = UniqueValuesPool::<usize>::new("lengths", 8)
+    ///     .with_label(|counter, len| format!("counter_{counter}/len_{len}"));
+    /// ```
+    #[coverage(off)]
+    #[must_use]
+    pub fn with_label(mut self, label: impl Fn(usize, &T) -> String + 'static) -> Self {
+        self.label = Some(Box::new(label));
+        self
+    }
+
+    /// The subfolder of the corpus where an input added for the value `v` of the counter `index` is saved
+    #[coverage(off)]
+    fn path_for(&self, index: usize, v: &T) -> PathBuf {
+        let mut path = Path::new(&self.name).to_path_buf();
+        if let Some(label) = &self.label {
+            for component in label(index, v).split('/').filter(
+                #[coverage(off)]
+                |component| is_valid_file_stem(component),
+            ) {
+                path.push(component);
+            }
+        }
+        path
+    }
 }
 
 impl<T> Pool for UniqueValuesPool<T>
@@ -125,6 +173,15 @@ where
         let data = self.inputs[key].data;
         Some(data)
     }
+
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        Some(crate::mutators::rng_state(&self.rng))
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        crate::mutators::set_rng_state(&self.rng, state);
+    }
 }
 impl<T> SaveToStatsFolder for UniqueValuesPool<T>
 where
@@ -140,6 +197,18 @@ impl<T> UniqueValuesPool<T>
 where
     T: Hash + Eq + Clone,
 {
+    /// Whether the value `v` of the counter `index` was never observed, or only by
+    /// test cases more complex than `complexity`
+    #[coverage(off)]
+    fn is_new_observation(&self, index: usize, v: &T, complexity: f64) -> bool {
+        if let Some(&previous_cplx) = self.complexities[index].get(v) {
+            // already exists, but may be better
+            previous_cplx > complexity
+        } else {
+            true
+        }
+    }
+
     #[coverage(off)]
     fn update_stats(&mut self) {
         let inputs = &self.inputs;
@@ -164,19 +233,14 @@ where
 impl<T, O> CompatibleWithObservations<O> for UniqueValuesPool<T>
 where
     for<'a> &'a O: IntoIterator<Item = &'a (usize, T)>,
-    T: Hash + Eq + Clone + Copy + 'static,
+    T: Hash + Eq + Clone + 'static,
 {
     #[coverage(off)]
     fn process(&mut self, input_id: PoolStorageIndex, observations: &O, complexity: f64) -> Vec<CorpusDelta> {
         let mut state = vec![];
-        for &(index, v) in observations.into_iter() {
-            if let Some(&previous_cplx) = self.complexities[index].get(&v) {
-                if previous_cplx > complexity {
-                    // already exists but this one is better
-                    state.push((index, v));
-                }
-            } else {
-                state.push((index, v));
+        for (index, v) in observations.into_iter() {
+            if self.is_new_observation(*index, v, complexity) {
+                state.push((*index, v.clone()));
             }
         }
         if state.is_empty() {
@@ -186,10 +250,13 @@ where
         let new_observations = state;
         let score = new_observations.len() as f64;
         let cplx = complexity;
+        let (first_index, first_value) = &new_observations[0];
+        let path = self.path_for(*first_index, first_value);
         let input = input_id;
         let input = Input {
-            best_for_values: AHashSet::new(), // fill in later! with new_observations.into_iter().collect(),
+            best_for_values: new_observations.iter().cloned().collect(),
             data: input,
+            path: path.clone(),
             score,
             number_times_chosen: 1,
         };
@@ -199,12 +266,12 @@ where
         let mut removed_keys = vec![];
 
         for (counter, id) in &new_observations {
-            self.complexities[*counter].insert(*id, cplx);
+            self.complexities[*counter].insert(id.clone(), cplx);
 
             let previous_best_key = self.best_input_for_value[*counter].get_mut(id);
             if let Some(previous_best_key) = previous_best_key {
                 let previous_best = &mut self.inputs[*previous_best_key];
-                let was_present_in_set = previous_best.best_for_values.remove(&(*counter, *id));
+                let was_present_in_set = previous_best.best_for_values.remove(&(*counter, id.clone()));
                 assert!(was_present_in_set);
                 previous_best.score = previous_best.best_for_values.len() as f64;
                 if previous_best.best_for_values.is_empty() {
@@ -212,24 +279,181 @@ where
                 }
                 *previous_best_key = input_key;
             } else {
-                self.best_input_for_value[*counter].insert(*id, input_key);
+                self.best_input_for_value[*counter].insert(id.clone(), input_key);
             }
         }
-        for &removed_key in &removed_keys {
-            self.inputs.remove(removed_key);
-        }
-        let removed_keys = removed_keys
-            .into_iter()
-            .map(
+        let mut deltas = vec![CorpusDelta {
+            path,
+            add: true,
+            remove: vec![],
+        }];
+        for k in removed_keys {
+            let removed = &self.inputs[k];
+            let (data, removed_path) = (removed.data, removed.path.clone());
+            self.inputs.remove(k);
+            // the removed inputs must be deleted from the subfolder they were saved in
+            if let Some(delta) = deltas.iter_mut().find(
                 #[coverage(off)]
-                |k| self.inputs[k].data,
-            )
-            .collect();
+                |delta| delta.path == removed_path,
+            ) {
+                delta.remove.push(data);
+            } else {
+                deltas.push(CorpusDelta {
+                    path: removed_path,
+                    add: false,
+                    remove: vec![data],
+                });
+            }
+        }
         self.update_stats();
-        vec![CorpusDelta {
-            path: Path::new(&self.name).to_path_buf(),
-            add: true,
-            remove: removed_keys,
-        }]
+        deltas
+    }
+
+    #[coverage(off)]
+    fn would_accept(&self, observations: &O, complexity: f64) -> bool {
+        observations.into_iter().any(
+            #[coverage(off)]
+            |(index, v)| self.is_new_observation(*index, v, complexity),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[coverage(off)]
+    fn test_unique_values_pool_with_strings() {
+        let mut pool = UniqueValuesPool::<String>::new("strings", 2);
+        let first = PoolStorageIndex::mock(0);
+        let second = PoolStorageIndex::mock(1);
+        let third = PoolStorageIndex::mock(2);
+
+        let observations = vec![(0, "a".to_owned()), (1, "b".to_owned())];
+        assert!(!pool.process(first, &observations, 10.0).is_empty());
+        assert_eq!(pool.stats().size, 1);
+
+        // same values, higher complexity: ignored
+        assert!(pool.process(second, &observations, 20.0).is_empty());
+
+        // a new value for counter 0
+        let observations = vec![(0, "c".to_owned())];
+        assert!(!pool.process(second, &observations, 10.0).is_empty());
+        assert_eq!(pool.stats().size, 2);
+
+        // the same values as `first`, but simpler: `first` is removed
+        let observations = vec![(0, "a".to_owned()), (1, "b".to_owned())];
+        let deltas = pool.process(third, &observations, 5.0);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].remove, vec![first]);
+        assert_eq!(pool.stats().size, 2);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_unique_values_pool_records_the_values_of_each_input() {
+        let mut pool = UniqueValuesPool::<u8>::new("values", 2);
+        let first = PoolStorageIndex::mock(0);
+        let second = PoolStorageIndex::mock(1);
+
+        pool.process(first, &vec![(0, 1), (1, 2)], 10.0);
+        let first_key = pool.inputs.get_nth_key(0);
+        assert_eq!(pool.inputs[first_key].best_for_values.len(), 2);
+        assert_eq!(pool.inputs[first_key].score, 2.0);
+
+        // `second` is simpler for one of the values of `first`, which stays in the pool for the other one
+        let deltas = pool.process(second, &vec![(0, 1)], 5.0);
+        assert!(deltas[0].remove.is_empty());
+        assert_eq!(pool.stats().size, 2);
+        let expected = [(1, 2)].into_iter().collect::<AHashSet<_>>();
+        assert_eq!(pool.inputs[first_key].best_for_values, expected);
+        assert_eq!(pool.inputs[first_key].score, 1.0);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_unique_values_pool_reports_the_removed_inputs() {
+        let mut pool = UniqueValuesPool::<u8>::new("values", 2);
+        let first = PoolStorageIndex::mock(0);
+        let second = PoolStorageIndex::mock(1);
+        let third = PoolStorageIndex::mock(2);
+
+        pool.process(first, &vec![(0, 1)], 10.0);
+        pool.process(second, &vec![(1, 2)], 10.0);
+        // both inputs are replaced, and the delta refers to their storage indices
+        let deltas = pool.process(third, &vec![(0, 1), (1, 2)], 5.0);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].remove, vec![first, second]);
+        assert_eq!(pool.stats().size, 1);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_unique_values_pool_with_label() {
+        let mut pool = UniqueValuesPool::<u8>::new("values", 2).with_label(
+            #[coverage(off)]
+            |counter, v| format!("{counter}_{v}"),
+        );
+        let first = PoolStorageIndex::mock(0);
+        let second = PoolStorageIndex::mock(1);
+        let third = PoolStorageIndex::mock(2);
+
+        let deltas = pool.process(first, &vec![(1, 7)], 10.0);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].path, Path::new("values/1_7"));
+        assert!(deltas[0].add);
+
+        let deltas = pool.process(second, &vec![(0, 3)], 10.0);
+        assert_eq!(deltas[0].path, Path::new("values/0_3"));
+
+        // `first` and `second` are replaced and removed from their own subfolders
+        let deltas = pool.process(third, &vec![(0, 3), (1, 7)], 5.0);
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].path, Path::new("values/0_3"));
+        assert!(deltas[0].add);
+        assert_eq!(deltas[0].remove, vec![second]);
+        assert_eq!(deltas[1].path, Path::new("values/1_7"));
+        assert!(!deltas[1].add);
+        assert_eq!(deltas[1].remove, vec![first]);
+        assert_eq!(pool.stats().size, 1);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_unique_values_pool_with_label_stays_in_its_folder() {
+        let labels = ["../../outside", "/absolute/path", "a/./b//c", "..\\outside", ".."];
+        let mut pool = UniqueValuesPool::<u8>::new("values", labels.len()).with_label(
+            #[coverage(off)]
+            move |counter, _| labels[counter].to_string(),
+        );
+        let expected = [
+            "values/outside",
+            "values/absolute/path",
+            "values/a/b/c",
+            "values",
+            "values",
+        ];
+        for (counter, expected) in expected.into_iter().enumerate() {
+            let deltas = pool.process(PoolStorageIndex::mock(counter), &vec![(counter, 1)], 10.0);
+            assert_eq!(deltas[0].path, Path::new(expected));
+        }
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_unique_values_pool_would_accept() {
+        let mut pool = UniqueValuesPool::<u8>::new("values", 2);
+        let observations = vec![(0, 1), (1, 2)];
+        assert!(pool.would_accept(&observations, 10.0));
+        assert!(!pool.would_accept(&vec![], 10.0));
+        assert_eq!(pool.stats().size, 0);
+
+        assert!(!pool.process(PoolStorageIndex::mock(0), &observations, 10.0).is_empty());
+        assert!(!pool.would_accept(&observations, 10.0));
+        assert!(!pool.would_accept(&vec![(0, 1)], 20.0));
+        assert!(pool.would_accept(&vec![(0, 1)], 5.0));
+        assert!(pool.would_accept(&vec![(0, 1), (1, 3)], 20.0));
+        assert_eq!(pool.stats().size, 1);
     }
 }
@@ -43,4 +43,9 @@ impl<O> CompatibleWithObservations<O> for UnitPool {
     fn process<'a>(&'a mut self, _input_id: PoolStorageIndex, _observations: &O, _complexity: f64) -> Vec<CorpusDelta> {
         vec![]
     }
+
+    #[coverage(off)]
+    fn would_accept(&self, _observations: &O, _complexity: f64) -> bool {
+        false
+    }
 }
@@ -0,0 +1,132 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::traits::ArtifactMetadata;
+use crate::Serializer;
+
+/**
+A serializer that wraps another serializer and encodes its output in base64.
+
+The files have the extension of the wrapped serializer followed by `.b64`, e.g. `bin.b64`.
+They only contain ASCII text, which allows storing binary test cases in systems that
+only accept text. The files that are not valid base64, including those with an incorrect
+padding, are rejected without being passed to the wrapped serializer. Whitespace at the
+start and end of the files, such as a trailing newline, is ignored.
+```
+use fuzzcheck::{Base64Serializer, ByteSerializer, Serializer};
+
+let ser = Base64Serializer::new(ByteSerializer::new("bin"));
+assert_eq!(ser.extension(), "bin.b64");
+assert_eq!(ser.to_data(&vec![0, 1, 2, 3]), b"AAECAw==");
+```
+*/
+pub struct Base64Serializer<S>
+where
+    S: Serializer,
+{
+    serializer: S,
+    ext: String,
+}
+
+impl<S> Base64Serializer<S>
+where
+    S: Serializer,
+{
+    #[coverage(off)]
+    pub fn new(serializer: S) -> Self {
+        let ext = format!("{}.b64", serializer.extension());
+        Self { serializer, ext }
+    }
+}
+
+impl<S> Serializer for Base64Serializer<S>
+where
+    S: Serializer,
+{
+    type Value = S::Value;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        &self.ext
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        true
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        let decoded = STANDARD.decode(data.trim_ascii()).map_err(
+            #[coverage(off)]
+            |e| format!("invalid base64 data: {e}"),
+        )?;
+        self.serializer.from_data_result(&decoded)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        STANDARD.encode(self.serializer.to_data(value)).into_bytes()
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &Self::Value, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        self.serializer.sidecar_files(value, metadata)
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, value: &Self::Value, data: &[u8]) -> Option<String> {
+        self.serializer.suggested_file_stem(value, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Base64Serializer;
+    use crate::{ByteSerializer, Serializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_base64_serializer() {
+        let ser = Base64Serializer::new(ByteSerializer::new("bin"));
+        assert_eq!(ser.extension(), "bin.b64");
+        assert!(ser.is_utf8());
+        for bytes in [vec![], vec![0], vec![0, 1], vec![0, 1, 2, 255], (0..=255).collect()] {
+            let data = ser.to_data(&bytes);
+            assert!(std::str::from_utf8(&data).is_ok());
+            assert_eq!(ser.from_data(&data), Some(bytes));
+        }
+        assert_eq!(ser.to_data(&b"fuzz".to_vec()), b"ZnV6eg==");
+        assert_eq!(ser.from_data(b"ZnV6eg==\n"), Some(b"fuzz".to_vec()));
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_base64_serializer_rejects_invalid_data() {
+        let ser = Base64Serializer::new(ByteSerializer::new("bin"));
+        // missing, partial, and superfluous padding
+        assert_eq!(ser.from_data(b"ZnV6eg"), None);
+        assert_eq!(ser.from_data(b"ZnV6eg="), None);
+        assert_eq!(ser.from_data(b"ZnV6eg==="), None);
+        assert_eq!(ser.from_data(b"AAEC===="), None);
+        // characters outside of the base64 alphabet
+        assert_eq!(ser.from_data(b"ZnV6*g=="), None);
+        assert_eq!(ser.from_data(b"ZnV 6eg=="), None);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_base64_serializer_from_data_result() {
+        #[cfg(feature = "serde_json_serializer")]
+        {
+            let ser = Base64Serializer::new(crate::SerdeSerializer::<u8>::default());
+            // the errors of the wrapped serializer are passed on
+            let error = ser.from_data_result(b"MzAw").unwrap_err();
+            assert!(error.contains("u8"), "{error}");
+        }
+        let ser = Base64Serializer::new(ByteSerializer::new("bin"));
+        assert!(ser
+            .from_data_result(b"ZnV6*g==")
+            .unwrap_err()
+            .starts_with("invalid base64 data"));
+    }
+}
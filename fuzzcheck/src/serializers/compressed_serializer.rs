@@ -0,0 +1,176 @@
+use std::io::{Read, Write};
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use crate::traits::ArtifactMetadata;
+use crate::Serializer;
+
+/// The default maximum number of bytes read when decompressing a file, 256 MiB
+const DEFAULT_MAX_DECOMPRESSED_LEN: usize = 256 * 1024 * 1024;
+
+/**
+A serializer that wraps another serializer and compresses its output with gzip.
+
+The files have the extension of the wrapped serializer followed by `.gz`, e.g. `json.gz`,
+and can be decompressed with the usual tools. The files that are not valid gzip data are
+rejected without being passed to the wrapped serializer, as are the files that decompress
+to more than 256 MiB, which can be changed with [CompressedSerializer::with_max_len].
+
+It is useful to keep large corpora of structured test cases, which compress very well,
+in a version control system.
+```
+use fuzzcheck::{ByteSerializer, CompressedSerializer, Serializer};
+
+let ser = CompressedSerializer::new(ByteSerializer::new("bin"));
+assert_eq!(ser.extension(), "bin.gz");
+```
+*/
+pub struct CompressedSerializer<S>
+where
+    S: Serializer,
+{
+    serializer: S,
+    ext: String,
+    max_len: usize,
+}
+
+impl<S> CompressedSerializer<S>
+where
+    S: Serializer,
+{
+    #[coverage(off)]
+    pub fn new(serializer: S) -> Self {
+        Self::with_max_len(serializer, DEFAULT_MAX_DECOMPRESSED_LEN)
+    }
+
+    /// Create a compressed serializer that rejects the files decompressing to more than `max_len` bytes.
+    ///
+    /// Only `max_len + 1` bytes are decompressed before rejecting a file, so that a small
+    /// file that decompresses to a huge amount of data cannot exhaust the memory.
+    /// For example:
+    /// ```
+    /// use fuzzcheck::{ByteSerializer, CompressedSerializer, Serializer};
+    ///
+    /// let ser = CompressedSerializer::with_max_len(ByteSerializer::new("bin"), 4);
+    /// assert_eq!(ser.from_data(&ser.to_data(&b"abcd".to_vec())), Some(b"abcd".to_vec()));
+    /// assert_eq!(ser.from_data(&ser.to_data(&b"abcde".to_vec())), None);
+    /// ```
+    #[coverage(off)]
+    pub fn with_max_len(serializer: S, max_len: usize) -> Self {
+        let ext = format!("{}.gz", serializer.extension());
+        Self {
+            serializer,
+            ext,
+            max_len,
+        }
+    }
+}
+
+impl<S> Serializer for CompressedSerializer<S>
+where
+    S: Serializer,
+{
+    type Value = S::Value;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        &self.ext
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        false
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        let mut decompressed = vec![];
+        GzDecoder::new(data)
+            .take((self.max_len as u64).saturating_add(1))
+            .read_to_end(&mut decompressed)
+            .map_err(
+                #[coverage(off)]
+                |e| format!("invalid gzip data: {e}"),
+            )?;
+        if decompressed.len() > self.max_len {
+            return Err(format!("the decompressed data is larger than {} bytes", self.max_len));
+        }
+        self.serializer.from_data_result(&decompressed)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(&self.serializer.to_data(value)).unwrap();
+        encoder.finish().unwrap()
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &Self::Value, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        self.serializer.sidecar_files(value, metadata)
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, value: &Self::Value, data: &[u8]) -> Option<String> {
+        self.serializer.suggested_file_stem(value, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CompressedSerializer;
+    use crate::{ByteSerializer, Serializer, StringSerializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_compressed_serializer() {
+        let ser = CompressedSerializer::new(ByteSerializer::new("bin"));
+        assert_eq!(ser.extension(), "bin.gz");
+        assert!(!ser.is_utf8());
+        for bytes in [vec![], vec![0, 1, 2, 255], vec![b'a'; 10_000]] {
+            let data = ser.to_data(&bytes);
+            // the gzip magic number
+            assert_eq!(&data[..2], &[0x1f, 0x8b]);
+            assert_eq!(ser.from_data(&data), Some(bytes));
+        }
+        assert!(ser.to_data(&vec![b'a'; 10_000]).len() < 100);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_compressed_serializer_rejects_corrupt_data() {
+        let ser = CompressedSerializer::new(ByteSerializer::new("bin"));
+        let mut data = ser.to_data(&vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(ser.from_data(&data[..data.len() - 4]), None);
+        assert_eq!(ser.from_data(&[1, 2, 3]), None);
+        assert_eq!(ser.from_data(&[]), None);
+        // the checksum does not match
+        let len = data.len();
+        data[len - 8] ^= 1;
+        assert_eq!(ser.from_data(&data), None);
+
+        // the decompressed data is rejected by the wrapped serializer
+        let ser = CompressedSerializer::new(StringSerializer::<u8>::new("txt"));
+        let bytes_ser = CompressedSerializer::new(ByteSerializer::new("txt"));
+        assert_eq!(ser.from_data(&bytes_ser.to_data(&b"300".to_vec())), None);
+        assert_eq!(ser.from_data(&bytes_ser.to_data(&b"30".to_vec())), Some(30));
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_compressed_serializer_max_len() {
+        let ser = CompressedSerializer::with_max_len(ByteSerializer::new("bin"), 1000);
+        let unbounded_ser = CompressedSerializer::new(ByteSerializer::new("bin"));
+        // a small file that decompresses to a large amount of data
+        let data = unbounded_ser.to_data(&vec![0; 1_000_000]);
+        assert!(data.len() < 2000);
+        assert!(ser
+            .from_data_result(&data)
+            .unwrap_err()
+            .contains("larger than 1000 bytes"));
+        assert_eq!(unbounded_ser.from_data(&data), Some(vec![0; 1_000_000]));
+        assert_eq!(ser.from_data(&ser.to_data(&vec![0; 1000])), Some(vec![0; 1000]));
+        assert_eq!(ser.from_data(&ser.to_data(&vec![0; 1001])), None);
+    }
+}
@@ -0,0 +1,173 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use crate::traits::ArtifactMetadata;
+use crate::Serializer;
+
+/// The length, in bytes, of the nonce stored at the start of each encrypted file
+const NONCE_LEN: usize = 12;
+
+/**
+A serializer that wraps another serializer and encrypts its output with
+ChaCha20-Poly1305.
+
+The content of each file is a random nonce followed by the encrypted output of the
+wrapped serializer. Since the encryption is authenticated, files that were modified
+or encrypted with a different key are rejected: `from_data` returns `None` for them.
+
+The extension of the files is the extension of the wrapped serializer followed by `.enc`.
+The files are named as suggested by the wrapped serializer, and its sidecar files are
+encrypted as well.
+```
+use fuzzcheck::{ByteSerializer, EncryptedSerializer};
+
+let key = [0x42; 32];
+let ser = EncryptedSerializer::new(ByteSerializer::new("bin"), key);
+```
+*/
+#[doc(cfg(feature = "encrypted_serializer"))]
+pub struct EncryptedSerializer<S>
+where
+    S: Serializer,
+{
+    serializer: S,
+    cipher: ChaCha20Poly1305,
+    extension: String,
+}
+
+impl<S> EncryptedSerializer<S>
+where
+    S: Serializer,
+{
+    /// Create a new `EncryptedSerializer` using the given 256-bit key
+    #[coverage(off)]
+    pub fn new(serializer: S, key: [u8; 32]) -> Self {
+        let extension = format!("{}.enc", serializer.extension());
+        Self {
+            serializer,
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+            extension,
+        }
+    }
+
+    #[coverage(off)]
+    fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .expect("the plaintext is too large to be encrypted");
+        let mut data = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        data
+    }
+}
+
+impl<S> Serializer for EncryptedSerializer<S>
+where
+    S: Serializer,
+{
+    type Value = S::Value;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        &self.extension
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        if data.len() < NONCE_LEN {
+            return Err(format!("the {} bytes are too short to contain a nonce", data.len()));
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = self.cipher.decrypt(Nonce::from_slice(nonce), ciphertext).map_err(
+            #[coverage(off)]
+            |_| "the data could not be decrypted with the key of the serializer".to_owned(),
+        )?;
+        self.serializer.from_data_result(&plaintext)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        self.encrypt(&self.serializer.to_data(value))
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &Self::Value, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        self.serializer
+            .sidecar_files(value, metadata)
+            .into_iter()
+            .map(
+                #[coverage(off)]
+                |(extension, content)| (format!("{extension}.enc"), self.encrypt(&content)),
+            )
+            .collect()
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, value: &Self::Value, data: &[u8]) -> Option<String> {
+        self.serializer.suggested_file_stem(value, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::EncryptedSerializer;
+    use crate::traits::ArtifactMetadata;
+    use crate::{ByteSerializer, HashNamedSerializer, MetadataSerializer, Serializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_encrypted_serializer() {
+        let ser = EncryptedSerializer::new(ByteSerializer::new("bin"), [7; 32]);
+        assert_eq!(ser.extension(), "bin.enc");
+
+        let value = b"sensitive fixture".to_vec();
+        let data = ser.to_data(&value);
+        assert!(!data.windows(value.len()).any(|w| w == value.as_slice()));
+        assert_eq!(ser.from_data(&data), Some(value.clone()));
+        // a new nonce is used for each encryption
+        assert_ne!(ser.to_data(&value), data);
+
+        // tampered data is rejected
+        for i in [0, data.len() / 2, data.len() - 1] {
+            let mut tampered = data.clone();
+            tampered[i] ^= 1;
+            assert_eq!(ser.from_data(&tampered), None);
+        }
+        assert_eq!(ser.from_data(&data[..data.len() - 1]), None);
+        assert_eq!(ser.from_data(&[]), None);
+
+        // so is data encrypted with a different key
+        let other = EncryptedSerializer::new(ByteSerializer::new("bin"), [8; 32]);
+        assert_eq!(other.from_data(&data), None);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_encrypted_serializer_forwards_file_names_and_sidecar_files() {
+        let ser = EncryptedSerializer::new(
+            MetadataSerializer::new(HashNamedSerializer::new(ByteSerializer::new("bin"))),
+            [7; 32],
+        );
+        let value = b"a".to_vec();
+        assert_eq!(ser.suggested_file_stem(&value, b"a").unwrap(), "af63dc4c8601ec8c");
+
+        let metadata = ArtifactMetadata {
+            complexity: 8.0,
+            rng_state: None,
+            timestamp: UNIX_EPOCH,
+        };
+        let sidecar_files = ser.sidecar_files(&value, &metadata);
+        assert_eq!(sidecar_files.len(), 1);
+        let (extension, content) = &sidecar_files[0];
+        assert_eq!(extension, "meta.json.enc");
+        assert!(!content.windows(10).any(
+            #[coverage(off)]
+            |w| w == b"complexity"
+        ));
+    }
+}
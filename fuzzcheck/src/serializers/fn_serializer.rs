@@ -0,0 +1,156 @@
+use std::marker::PhantomData;
+
+use crate::Serializer;
+
+/**
+A serializer built from a pair of closures that encode and decode its values.
+
+It avoids defining a new type implementing [`Serializer`] for a one-off format,
+in the same way that [`MapMutator`](crate::mutators::map::MapMutator) avoids
+defining a new mutator.
+
+If the format is a text format, `is_utf8` should be true. Then, the files that are
+not valid utf-8 are rejected without being passed to the `decode` closure.
+```
+use fuzzcheck::{FnSerializer, Serializer};
+
+// a list of numbers separated by commas
+let ser = FnSerializer::new(
+    "csv",
+    true,
+    |xs: &Vec<u8>| xs.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(",").into_bytes(),
+    |data: &[u8]| {
+        let string = std::str::from_utf8(data).ok()?;
+        string.split(',').map(|x| x.parse().ok()).collect()
+    },
+);
+assert_eq!(ser.to_data(&vec![1, 2, 3]), b"1,2,3".to_vec());
+assert_eq!(ser.from_data(b"4,5"), Some(vec![4, 5]));
+```
+*/
+pub struct FnSerializer<V, Encode, Decode>
+where
+    Encode: Fn(&V) -> Vec<u8>,
+    Decode: Fn(&[u8]) -> Option<V>,
+{
+    extension: &'static str,
+    is_utf8: bool,
+    encode: Encode,
+    decode: Decode,
+    _phantom: PhantomData<V>,
+}
+
+impl<V, Encode, Decode> FnSerializer<V, Encode, Decode>
+where
+    Encode: Fn(&V) -> Vec<u8>,
+    Decode: Fn(&[u8]) -> Option<V>,
+{
+    /// Create a serializer writing files with the given extension, which encodes the
+    /// values with `encode` and decodes them with `decode`.
+    #[coverage(off)]
+    pub fn new(extension: &'static str, is_utf8: bool, encode: Encode, decode: Decode) -> Self {
+        Self {
+            extension,
+            is_utf8,
+            encode,
+            decode,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<V, Encode, Decode> Serializer for FnSerializer<V, Encode, Decode>
+where
+    Encode: Fn(&V) -> Vec<u8>,
+    Decode: Fn(&[u8]) -> Option<V>,
+{
+    type Value = V;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.extension
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        self.is_utf8
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        if self.is_utf8 && std::str::from_utf8(data).is_err() {
+            return None;
+        }
+        (self.decode)(data)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        (self.encode)(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FnSerializer;
+    use crate::Serializer;
+
+    /// A point encoded in a custom binary format: a tag byte, then the coordinates in little-endian
+    #[derive(Debug, Clone, PartialEq)]
+    struct Point {
+        x: i16,
+        y: i16,
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_fn_serializer() {
+        let ser = FnSerializer::new(
+            "point",
+            false,
+            #[coverage(off)]
+            |p: &Point| {
+                let mut data = vec![b'P'];
+                data.extend(p.x.to_le_bytes());
+                data.extend(p.y.to_le_bytes());
+                data
+            },
+            #[coverage(off)]
+            |data: &[u8]| match data {
+                [b'P', x0, x1, y0, y1] => Some(Point {
+                    x: i16::from_le_bytes([*x0, *x1]),
+                    y: i16::from_le_bytes([*y0, *y1]),
+                }),
+                _ => None,
+            },
+        );
+        assert_eq!(ser.extension(), "point");
+        assert!(!ser.is_utf8());
+        for p in [
+            Point { x: 0, y: 0 },
+            Point { x: -1, y: 300 },
+            Point {
+                x: i16::MIN,
+                y: i16::MAX,
+            },
+        ] {
+            let data = ser.to_data(&p);
+            assert_eq!(data.len(), 5);
+            assert_eq!(ser.from_data(&data), Some(p));
+        }
+        assert_eq!(ser.from_data(&[b'Q', 0, 0, 0, 0]), None);
+        assert_eq!(ser.from_data(&[b'P', 0, 0]), None);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_fn_serializer_rejects_invalid_utf8() {
+        let ser = FnSerializer::new(
+            "txt",
+            true,
+            #[coverage(off)]
+            |x: &Vec<u8>| x.clone(),
+            #[coverage(off)]
+            |data: &[u8]| Some(data.to_vec()),
+        );
+        assert_eq!(ser.from_data("é".as_bytes()), Some("é".as_bytes().to_vec()));
+        assert_eq!(ser.from_data(&[0xff, 0xfe]), None);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::traits::ArtifactMetadata;
+use crate::Serializer;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/**
+A serializer that wraps another serializer and names the files after a hash of their content.
+
+By default, the files of the corpus and the artifacts are named after a hash computed with
+the standard library’s `DefaultHasher`, which may change between versions of Rust. Instead, the
+`HashNamedSerializer` uses the 64-bit FNV-1a hash of the content of the file,
+formatted as 16 hexadecimal digits. So the same test case is saved in a file with the same name
+on every machine, which makes it easy to deduplicate corpora.
+
+Otherwise, it behaves exactly like the wrapped serializer.
+```
+use fuzzcheck::{ByteSerializer, HashNamedSerializer, Serializer};
+
+let ser = HashNamedSerializer::new(ByteSerializer::new("bin"));
+let value = b"a".to_vec();
+assert_eq!(ser.suggested_file_stem(&value, &ser.to_data(&value)).unwrap(), "af63dc4c8601ec8c");
+```
+*/
+pub struct HashNamedSerializer<S>
+where
+    S: Serializer,
+{
+    serializer: S,
+}
+
+impl<S> HashNamedSerializer<S>
+where
+    S: Serializer,
+{
+    #[coverage(off)]
+    pub fn new(serializer: S) -> Self {
+        Self { serializer }
+    }
+}
+
+#[coverage(off)]
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+impl<S> Serializer for HashNamedSerializer<S>
+where
+    S: Serializer,
+{
+    type Value = S::Value;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.serializer.extension()
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        self.serializer.is_utf8()
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.serializer.from_data(data)
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        self.serializer.from_data_result(data)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        self.serializer.to_data(value)
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &Self::Value, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        self.serializer.sidecar_files(value, metadata)
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, _value: &Self::Value, data: &[u8]) -> Option<String> {
+        Some(format!("{:016x}", fnv1a(data)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HashNamedSerializer;
+    use crate::{ByteSerializer, Serializer, StringSerializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_hash_named_serializer() {
+        let ser = HashNamedSerializer::new(StringSerializer::<u32>::new("txt"));
+        assert_eq!(ser.extension(), "txt");
+        assert_eq!(ser.to_data(&42), b"42");
+        assert_eq!(ser.from_data(b"42"), Some(42));
+        assert_eq!(ser.from_data(b"x"), None);
+        assert_eq!(
+            StringSerializer::<u32>::new("txt").suggested_file_stem(&42, b"42"),
+            None
+        );
+
+        // the same value always produces the same stem
+        let stem = ser.suggested_file_stem(&42, b"42").unwrap();
+        assert_eq!(stem.len(), 16);
+        assert!(stem.chars().all(
+            #[coverage(off)]
+            |c| c.is_ascii_hexdigit()
+        ));
+        assert_eq!(ser.suggested_file_stem(&42, b"42"), Some(stem.clone()));
+        let other = HashNamedSerializer::new(StringSerializer::<u32>::new("txt"));
+        assert_eq!(other.suggested_file_stem(&42, b"42"), Some(stem.clone()));
+        assert_ne!(ser.suggested_file_stem(&43, b"43"), Some(stem));
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_hash_named_serializer_stems_are_stable() {
+        let ser = HashNamedSerializer::new(ByteSerializer::new("bin"));
+        // the reference values of the 64-bit FNV-1a hash
+        assert_eq!(ser.suggested_file_stem(&vec![], b"").unwrap(), "cbf29ce484222325");
+        assert_eq!(
+            ser.suggested_file_stem(&b"a".to_vec(), b"a").unwrap(),
+            "af63dc4c8601ec8c"
+        );
+        assert_eq!(
+            ser.suggested_file_stem(&b"foobar".to_vec(), b"foobar").unwrap(),
+            "85944171f73967e8"
+        );
+    }
+}
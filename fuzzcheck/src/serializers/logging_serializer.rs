@@ -0,0 +1,159 @@
+use std::fmt;
+
+use crate::traits::ArtifactMetadata;
+use crate::Serializer;
+
+/// A call to a serializer wrapped by a [`LoggingSerializer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerializerEvent {
+    /// `from_data` was called on `len` bytes, and returned `Some` if `success` is true
+    FromData { len: usize, success: bool },
+    /// `to_data` was called and returned `len` bytes
+    ToData { len: usize },
+}
+
+impl fmt::Display for SerializerEvent {
+    #[coverage(off)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializerEvent::FromData { len, success: true } => write!(f, "from_data: read {len} bytes"),
+            SerializerEvent::FromData { len, success: false } => {
+                write!(f, "from_data: rejected {len} bytes")
+            }
+            SerializerEvent::ToData { len } => write!(f, "to_data: wrote {len} bytes"),
+        }
+    }
+}
+
+/**
+A serializer that wraps another serializer and reports each of its calls to
+`from_data` and `to_data` to a callback.
+
+It is useful to understand why a test case of the corpus is ignored by the fuzzer:
+each `from_data` call is reported with the number of bytes it read and whether it
+succeeded. Apart from calling the callback, it behaves exactly like the wrapped serializer.
+```
+use fuzzcheck::{ByteSerializer, LoggingSerializer};
+
+// print each event to stderr
+let ser = LoggingSerializer::to_stderr(ByteSerializer::new("bin"));
+// or handle it with a custom callback
+let ser = LoggingSerializer::new(ByteSerializer::new("bin"), |event| println!("corpus: {event}"));
+```
+*/
+pub struct LoggingSerializer<S, F = fn(&SerializerEvent)>
+where
+    S: Serializer,
+    F: Fn(&SerializerEvent),
+{
+    serializer: S,
+    callback: F,
+}
+
+impl<S, F> LoggingSerializer<S, F>
+where
+    S: Serializer,
+    F: Fn(&SerializerEvent),
+{
+    /// Create a `LoggingSerializer` that passes each event to `callback`
+    #[coverage(off)]
+    pub fn new(serializer: S, callback: F) -> Self {
+        Self { serializer, callback }
+    }
+}
+
+impl<S> LoggingSerializer<S>
+where
+    S: Serializer,
+{
+    /// Create a `LoggingSerializer` that prints each event to stderr
+    #[coverage(off)]
+    pub fn to_stderr(serializer: S) -> Self {
+        Self::new(serializer, print_event)
+    }
+}
+
+#[coverage(off)]
+fn print_event(event: &SerializerEvent) {
+    eprintln!("serializer {event}");
+}
+
+impl<S, F> Serializer for LoggingSerializer<S, F>
+where
+    S: Serializer,
+    F: Fn(&SerializerEvent),
+{
+    type Value = S::Value;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.serializer.extension()
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        self.serializer.is_utf8()
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        let value = self.serializer.from_data_result(data);
+        (self.callback)(&SerializerEvent::FromData {
+            len: data.len(),
+            success: value.is_ok(),
+        });
+        value
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        let data = self.serializer.to_data(value);
+        (self.callback)(&SerializerEvent::ToData { len: data.len() });
+        data
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &Self::Value, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        self.serializer.sidecar_files(value, metadata)
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, value: &Self::Value, data: &[u8]) -> Option<String> {
+        self.serializer.suggested_file_stem(value, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::{LoggingSerializer, SerializerEvent};
+    use crate::{Serializer, StringSerializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_logging_serializer() {
+        let events = RefCell::new(vec![]);
+        let ser = LoggingSerializer::new(StringSerializer::<u16>::new("txt"), |event: &SerializerEvent| {
+            events.borrow_mut().push(*event)
+        });
+        assert_eq!(ser.extension(), "txt");
+        assert_eq!(ser.to_data(&1234), b"1234".to_vec());
+        assert_eq!(ser.from_data(b"56"), Some(56));
+        assert_eq!(ser.from_data(b"not a number"), None);
+        assert_eq!(
+            events.into_inner(),
+            vec![
+                SerializerEvent::ToData { len: 4 },
+                SerializerEvent::FromData { len: 2, success: true },
+                SerializerEvent::FromData {
+                    len: 12,
+                    success: false
+                },
+            ]
+        );
+        assert_eq!(
+            SerializerEvent::FromData { len: 3, success: false }.to_string(),
+            "from_data: rejected 3 bytes"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::marker::PhantomData;
+
+use crate::traits::ArtifactMetadata;
+use crate::Serializer;
+
+/**
+A serializer that reuses a serializer of values of type `A` to serialize values of type `B`.
+
+It is analogous to [`MapMutator`](crate::mutators::map::MapMutator). The function `to_a`
+converts the test cases to the type of the wrapped serializer, and may fail if a value of type
+`B` has no equivalent of type `A`. The function `to_b` converts the decoded values back to
+the type of the test cases.
+
+A file is decoded by the wrapped serializer and then mapped with `to_b`. It is rejected if
+the resulting value cannot be mapped back with `to_a`, so that every value read from the
+corpus can also be written to it. A value that cannot be mapped with `to_a` is written as
+the value returned by the closure given to [`with_fallback`](MapSerializer::with_fallback),
+or as an empty file if there is none.
+
+The files have the extension and the encoding of the wrapped serializer, which can be
+accessed with [`inner`](MapSerializer::inner).
+```
+use fuzzcheck::{MapSerializer, Serializer, StringSerializer};
+
+#[derive(Debug, PartialEq)]
+struct Port(u16);
+
+let ser = MapSerializer::new(
+    StringSerializer::<u16>::new("txt"),
+    |port: &Port| (port.0 != 0).then_some(port.0),
+    |x: &u16| Port(*x),
+);
+assert_eq!(ser.to_data(&Port(8080)), b"8080");
+assert_eq!(ser.from_data(b"443"), Some(Port(443)));
+assert_eq!(ser.from_data(b"0"), None);
+```
+*/
+pub struct MapSerializer<S, A, B, ToA, ToB>
+where
+    S: Serializer<Value = A>,
+    ToA: Fn(&B) -> Option<A>,
+    ToB: Fn(&A) -> B,
+{
+    serializer: S,
+    to_a: ToA,
+    to_b: ToB,
+    fallback: Option<Box<dyn Fn(&B) -> A>>,
+    _phantom: PhantomData<(A, B)>,
+}
+
+impl<S, A, B, ToA, ToB> MapSerializer<S, A, B, ToA, ToB>
+where
+    S: Serializer<Value = A>,
+    ToA: Fn(&B) -> Option<A>,
+    ToB: Fn(&A) -> B,
+{
+    /// Create a serializer of values of type `B`, which are mapped to and from the values
+    /// of the given serializer with `to_a` and `to_b`
+    #[coverage(off)]
+    pub fn new(serializer: S, to_a: ToA, to_b: ToB) -> Self {
+        Self {
+            serializer,
+            to_a,
+            to_b,
+            fallback: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Write the values that cannot be mapped with `to_a` as the value returned by `fallback`,
+    /// which is usually the closest value of type `A`
+    #[coverage(off)]
+    #[must_use]
+    pub fn with_fallback(self, fallback: impl Fn(&B) -> A + 'static) -> Self {
+        Self {
+            fallback: Some(Box::new(fallback)),
+            ..self
+        }
+    }
+
+    /// The wrapped serializer
+    #[coverage(off)]
+    pub fn inner(&self) -> &S {
+        &self.serializer
+    }
+
+    /// The value of type `A` with which `value` is written, if any
+    #[coverage(off)]
+    fn to_a_or_fallback(&self, value: &B) -> Option<A> {
+        (self.to_a)(value).or_else(
+            #[coverage(off)]
+            || {
+                self.fallback.as_ref().map(
+                    #[coverage(off)]
+                    |fallback| fallback(value),
+                )
+            },
+        )
+    }
+}
+
+impl<S, A, B, ToA, ToB> Serializer for MapSerializer<S, A, B, ToA, ToB>
+where
+    S: Serializer<Value = A>,
+    ToA: Fn(&B) -> Option<A>,
+    ToB: Fn(&A) -> B,
+{
+    type Value = B;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.serializer.extension()
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        self.serializer.is_utf8()
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<B> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<B, String> {
+        let a = self.serializer.from_data_result(data)?;
+        let b = (self.to_b)(&a);
+        if (self.to_a)(&b).is_none() {
+            return Err("the decoded value cannot be mapped back to the type of the serializer".to_owned());
+        }
+        Ok(b)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &B) -> Vec<u8> {
+        match self.to_a_or_fallback(value) {
+            Some(a) => self.serializer.to_data(&a),
+            None => vec![],
+        }
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &B, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        match self.to_a_or_fallback(value) {
+            Some(a) => self.serializer.sidecar_files(&a, metadata),
+            None => vec![],
+        }
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, value: &B, data: &[u8]) -> Option<String> {
+        self.serializer
+            .suggested_file_stem(&self.to_a_or_fallback(value)?, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MapSerializer;
+    use crate::{Serializer, StringSerializer};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Username(String);
+
+    #[test]
+    #[coverage(off)]
+    fn test_map_serializer() {
+        let ser = MapSerializer::new(
+            StringSerializer::<String>::new("user"),
+            #[coverage(off)]
+            |u: &Username| (!u.0.contains(char::is_whitespace)).then(|| u.0.clone()),
+            #[coverage(off)]
+            |s: &String| Username(s.clone()),
+        );
+        assert_eq!(ser.extension(), "user");
+        assert_eq!(ser.inner().extension(), "user");
+
+        let value = Username("alice".to_owned());
+        let data = ser.to_data(&value);
+        assert_eq!(data, b"alice");
+        assert_eq!(ser.from_data(&data), Some(value));
+        assert_eq!(ser.from_data(b""), Some(Username(String::new())));
+
+        // decoded by the inner serializer, but rejected by `to_a`
+        assert_eq!(ser.from_data(b"alice smith"), None);
+        assert!(ser.from_data_result(b"alice smith").is_err());
+        // rejected by the inner serializer
+        assert_eq!(ser.from_data(&[0xff]), None);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_map_serializer_unmappable_value() {
+        let ser = MapSerializer::new(
+            StringSerializer::<String>::new("user"),
+            #[coverage(off)]
+            |u: &Username| (!u.0.contains(' ')).then(|| u.0.clone()),
+            #[coverage(off)]
+            |s: &String| Username(s.clone()),
+        );
+        assert!(ser.is_utf8());
+        assert_eq!(ser.to_data(&Username("alice smith".to_owned())), b"");
+
+        let ser = ser.with_fallback(
+            #[coverage(off)]
+            |u: &Username| u.0.replace(' ', "_"),
+        );
+        let data = ser.to_data(&Username("alice smith".to_owned()));
+        assert_eq!(data, b"alice_smith");
+        assert_eq!(ser.from_data(&data), Some(Username("alice_smith".to_owned())));
+    }
+}
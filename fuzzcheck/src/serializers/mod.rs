@@ -1,36 +1,173 @@
 //! Types implementing the [Serializer] trait.
 //!
-//! There are currently three implementations:
+//! There are currently nineteen implementations:
 //!
 //! * SerdeSerializer uses the `serde` and `serde_json` crate to serialize
-//! the test inputs (of arbitrary Serializable type) to a `.json` file.
+//!   the test inputs (of arbitrary Serializable type) to a `.json` file.
+//!
+//! * CanonicalizingSerializer is like SerdeSerializer, but writes a canonical
+//!   form of the json value, so that equivalent inputs produce identical files.
+//!
+//! * SerdeRonSerializer uses the `serde` and `ron` crates to serialize the
+//!   test inputs to a `.ron` file, which is easier to read for enum-heavy inputs.
+//!
+//! * CborSerializer uses the `serde` and `ciborium` crates to serialize the
+//!   test inputs to a binary `.cbor` file.
+//!
+//! * MsgPackSerializer uses the `serde` and `rmp-serde` crates to serialize
+//!   the test inputs to a compact, binary MessagePack file.
+//!
+//! * PostcardSerializer uses the `serde` and `postcard` crates to serialize
+//!   the test inputs to a binary file in the postcard wire format.
+//!
+//! * TomlSerializer uses the `serde` and `toml` crates to serialize the test
+//!   inputs to a `.toml` file, which is convenient to write seed inputs by hand.
 //!
 //! * [ByteSerializer] encodes and decodes values of type `Vec<u8>` by simply
-//! copy/pasting the bytes from/to the files. The extension is customizable.
+//!   copy/pasting the bytes from/to the files. The extension is customizable.
+//!   It can optionally normalize line endings, see [LineEndingNormalization].
+//!
+//! * [HexSerializer] encodes and decodes values of type `Vec<u8>` as hexadecimal
+//!   text, which is readable in a text editor.
 //!
 //! * [StringSerializer] encodes and decodes values of any type implementing
-//! `FromStr` and `ToString` into utf-8 encoded text files.
+//!   `FromStr` and `ToString` into utf-8 encoded text files.
+//!
+//! * [MetadataSerializer] wraps another serializer and saves a `.meta.json` file
+//!   containing the complexity, random number generator state, and timestamp of
+//!   each artifact next to it.
+//!
+//! * EncryptedSerializer wraps another serializer and encrypts its output with
+//!   ChaCha20-Poly1305, rejecting files that were tampered with.
+//!
+//! * [LoggingSerializer] wraps another serializer and reports each of its reads
+//!   and writes to a callback, to find out why test cases are rejected.
+//!
+//! * [FnSerializer] encodes and decodes values with a pair of user-provided
+//!   closures, for one-off formats.
+//!
+//! * [CompressedSerializer] wraps another serializer and compresses its output
+//!   with gzip, to keep large corpora small.
+//!
+//! * [Base64Serializer] wraps another serializer and encodes its output in
+//!   base64, to store binary test cases in systems that only accept text.
+//!
+//! * [MapSerializer] reuses a serializer of values of one type to serialize
+//!   values of another type, given conversion functions between the two.
+//!
+//! * [HashNamedSerializer] wraps another serializer and names the files after a
+//!   stable hash of their content, to deduplicate corpora across machines.
+//!
+//! * [VersionedSerializer] wraps another serializer and prepends a version
+//!   header to its output, to ignore or upgrade the files of an older corpus.
 
+mod base64_serializer;
+mod compressed_serializer;
+#[cfg(feature = "encrypted_serializer")]
+mod encrypted_serializer;
+mod fn_serializer;
+mod hash_named_serializer;
+mod logging_serializer;
+mod map_serializer;
+#[cfg(feature = "serde_cbor_serializer")]
+mod serde_cbor_serializer;
+#[cfg(feature = "serde_msgpack_serializer")]
+mod serde_msgpack_serializer;
+#[cfg(feature = "serde_postcard_serializer")]
+mod serde_postcard_serializer;
 #[cfg(feature = "serde_ron_serializer")]
 mod serde_ron_serializer;
 #[cfg(feature = "serde_json_serializer")]
 mod serde_serializer;
+#[cfg(feature = "serde_toml_serializer")]
+mod serde_toml_serializer;
+mod versioned_serializer;
 
+use std::fmt::Write;
 use std::marker::PhantomData;
 use std::str::FromStr;
+use std::time::UNIX_EPOCH;
 
+pub use base64_serializer::Base64Serializer;
+pub use compressed_serializer::CompressedSerializer;
+#[cfg(feature = "encrypted_serializer")]
+pub use encrypted_serializer::EncryptedSerializer;
+pub use fn_serializer::FnSerializer;
+pub use hash_named_serializer::HashNamedSerializer;
+pub use logging_serializer::{LoggingSerializer, SerializerEvent};
+pub use map_serializer::MapSerializer;
+#[cfg(feature = "serde_cbor_serializer")]
+pub use serde_cbor_serializer::CborSerializer;
+#[cfg(feature = "serde_msgpack_serializer")]
+pub use serde_msgpack_serializer::MsgPackSerializer;
+#[cfg(feature = "serde_postcard_serializer")]
+pub use serde_postcard_serializer::PostcardSerializer;
 #[cfg(feature = "serde_ron_serializer")]
 pub use serde_ron_serializer::SerdeRonSerializer;
 #[cfg(feature = "serde_json_serializer")]
-pub use serde_serializer::SerdeSerializer;
+pub use serde_serializer::{CanonicalizingSerializer, SerdeSerializer};
+#[cfg(feature = "serde_toml_serializer")]
+pub use serde_toml_serializer::TomlSerializer;
+pub use versioned_serializer::VersionedSerializer;
 
+use crate::traits::ArtifactMetadata;
 use crate::Serializer;
 
+/// How a [ByteSerializer] treats the line endings of the files it reads and writes.
+///
+/// Corpora containing text files may be checked out with different line endings on
+/// different platforms (e.g. because of git's `autocrlf` setting). Normalizing the
+/// line endings ensures that the same test case is read identically everywhere.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEndingNormalization {
+    /// The bytes are copied as-is
+    #[default]
+    None,
+    /// Every `\r\n` is replaced by `\n`
+    CrlfToLf,
+    /// Every `\r\n` is replaced by `\n` and every remaining `\r` is removed
+    StripCarriageReturns,
+}
+
+impl LineEndingNormalization {
+    #[coverage(off)]
+    fn normalize(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            LineEndingNormalization::None => bytes.to_vec(),
+            LineEndingNormalization::CrlfToLf => {
+                let mut result = Vec::with_capacity(bytes.len());
+                let mut iter = bytes.iter().copied().peekable();
+                while let Some(b) = iter.next() {
+                    if b == b'\r' && iter.peek() == Some(&b'\n') {
+                        continue;
+                    }
+                    result.push(b);
+                }
+                result
+            }
+            LineEndingNormalization::StripCarriageReturns => bytes
+                .iter()
+                .copied()
+                .filter(
+                    #[coverage(off)]
+                    |&b| b != b'\r',
+                )
+                .collect(),
+        }
+    }
+}
+
 /**
 A Serializer for `Vec<u8>` that simply copies the bytes from/to the files.
+
+By default, the bytes are copied exactly. Use [ByteSerializer::with_line_ending_normalization]
+to normalize the line endings of text-based corpora, and [ByteSerializer::with_max_len]
+to ignore the files that are too large.
 */
 pub struct ByteSerializer {
     ext: &'static str,
+    normalization: LineEndingNormalization,
+    max_len: Option<usize>,
 }
 
 impl ByteSerializer {
@@ -43,7 +180,45 @@ impl ByteSerializer {
     /// ````
     #[coverage(off)]
     pub fn new(ext: &'static str) -> Self {
-        Self { ext }
+        Self::with_line_ending_normalization(ext, LineEndingNormalization::None)
+    }
+
+    /// Create a byte serializer that normalizes the line endings of the files it reads
+    /// and writes, so that `\r\n` and `\n` are treated the same. For example:
+    /// ```
+    /// use fuzzcheck::serializers::LineEndingNormalization;
+    /// use fuzzcheck::{ByteSerializer, Serializer};
+    ///
+    /// let ser = ByteSerializer::with_line_ending_normalization("txt", LineEndingNormalization::CrlfToLf);
+    /// assert_eq!(ser.from_data(b"a\r\nb\n"), Some(b"a\nb\n".to_vec()));
+    /// ````
+    #[coverage(off)]
+    pub fn with_line_ending_normalization(ext: &'static str, normalization: LineEndingNormalization) -> Self {
+        Self {
+            ext,
+            normalization,
+            max_len: None,
+        }
+    }
+
+    /// Create a byte serializer that rejects the files containing more than `max_len` bytes.
+    ///
+    /// It prevents a few very large files from slowing down the fuzzer when the corpus is loaded.
+    /// For example:
+    /// ```
+    /// use fuzzcheck::{ByteSerializer, Serializer};
+    ///
+    /// let ser = ByteSerializer::with_max_len("png", 4);
+    /// assert_eq!(ser.from_data(b"abcd"), Some(b"abcd".to_vec()));
+    /// assert_eq!(ser.from_data(b"abcde"), None);
+    /// ````
+    #[coverage(off)]
+    pub fn with_max_len(ext: &'static str, max_len: usize) -> Self {
+        Self {
+            ext,
+            normalization: LineEndingNormalization::None,
+            max_len: Some(max_len),
+        }
     }
 }
 
@@ -56,17 +231,104 @@ impl crate::traits::Serializer for ByteSerializer {
     }
     #[coverage(off)]
     fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
-        Some(data.into())
+        if self.max_len.is_some_and(
+            #[coverage(off)]
+            |max_len| data.len() > max_len,
+        ) {
+            return None;
+        }
+        Some(self.normalization.normalize(data))
     }
     #[coverage(off)]
     fn to_data(&self, value: &Self::Value) -> Vec<u8> {
-        value.clone()
+        self.normalization.normalize(value)
+    }
+}
+
+/**
+A serializer that encodes values of type `Vec<u8>` as lowercase hexadecimal text,
+which is easier to read in a text editor and to review than raw bytes.
+
+When decoding a file, the whitespace characters, such as spaces and newlines, are
+ignored, such that the bytes can be grouped and split across lines by hand. The files
+containing any other non-hexadecimal character, or an odd number of digits, are rejected.
+```
+use fuzzcheck::{HexSerializer, Serializer};
+
+let ser = HexSerializer::default();
+assert_eq!(ser.to_data(&vec![0xca, 0xfe, 0x01]), b"cafe01".to_vec());
+assert_eq!(ser.from_data(b"CA FE\n01\n"), Some(vec![0xca, 0xfe, 0x01]));
+```
+*/
+pub struct HexSerializer {
+    ext: &'static str,
+}
+
+impl HexSerializer {
+    /// Create a hexadecimal serializer whose files have the given extension
+    #[coverage(off)]
+    pub fn new(ext: &'static str) -> Self {
+        Self { ext }
+    }
+}
+
+impl Default for HexSerializer {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new("hex")
+    }
+}
+
+impl crate::traits::Serializer for HexSerializer {
+    type Value = Vec<u8>;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.ext
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        true
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        let mut digits = data.iter().filter(
+            #[coverage(off)]
+            |c| !c.is_ascii_whitespace(),
+        );
+        let mut bytes = Vec::with_capacity(data.len() / 2);
+        while let Some(high) = digits.next() {
+            let low = digits.next()?;
+            let high = (*high as char).to_digit(16)?;
+            let low = (*low as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        }
+        Some(bytes)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        let mut hex = String::with_capacity(value.len() * 2);
+        for byte in value {
+            write!(hex, "{byte:02x}").unwrap();
+        }
+        hex.into_bytes()
     }
 }
 
 /**
 A serializer that encodes and decodes values of any type implementing
 `FromStr` and `ToString` into utf-8 encoded text files.
+
+For `String` values, the files contain exactly the bytes of the string, without quotes
+or escape sequences, which makes the artifacts of parsers and tokenizers easy to read.
+The files that are not valid utf-8 are rejected. By default, they have the extension `txt`.
+```
+use fuzzcheck::{Serializer, StringSerializer};
+
+let ser = StringSerializer::<String>::default();
+assert_eq!(ser.to_data(&"let x = \"é\";\n".to_owned()), "let x = \"é\";\n".as_bytes());
+assert_eq!(ser.from_data(&[b'a', 0xff]), None);
+```
  */
 pub struct StringSerializer<StringType>
 where
@@ -94,6 +356,16 @@ where
         }
     }
 }
+
+impl<StringType> Default for StringSerializer<StringType>
+where
+    StringType: ToString + FromStr,
+{
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::new("txt")
+    }
+}
 impl<StringType> Serializer for StringSerializer<StringType>
 where
     StringType: ToString + FromStr,
@@ -105,6 +377,10 @@ where
         self.extension
     }
     #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        true
+    }
+    #[coverage(off)]
     fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
         let string = String::from_utf8(data.to_vec()).ok()?;
         let value = Self::Value::from_str(&string).ok()?;
@@ -115,3 +391,255 @@ where
         value.to_string().into_bytes()
     }
 }
+
+/**
+A serializer that wraps another serializer and saves metadata about each
+artifact in a sidecar file with the `meta.json` extension.
+
+The artifact itself is encoded by the wrapped serializer. The sidecar file is
+a json object with the following fields:
+* `complexity`: the complexity of the test case
+* `rng_state`: the state of the mutator’s random number generators, encoded in
+  hexadecimal, or `null` if the mutator does not have any
+* `timestamp`: the number of seconds elapsed since the Unix epoch when the
+  artifact was saved
+
+When reading test cases, the sidecar files are ignored.
+```
+use fuzzcheck::{ByteSerializer, MetadataSerializer};
+
+let ser = MetadataSerializer::new(ByteSerializer::new("bin"));
+```
+*/
+pub struct MetadataSerializer<S>
+where
+    S: Serializer,
+{
+    serializer: S,
+}
+
+impl<S> MetadataSerializer<S>
+where
+    S: Serializer,
+{
+    #[coverage(off)]
+    pub fn new(serializer: S) -> Self {
+        Self { serializer }
+    }
+}
+
+impl<S> Serializer for MetadataSerializer<S>
+where
+    S: Serializer,
+{
+    type Value = S::Value;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.serializer.extension()
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        self.serializer.is_utf8()
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.serializer.from_data(data)
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        self.serializer.from_data_result(data)
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        self.serializer.to_data(value)
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &Self::Value, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        let mut sidecar_files = self.serializer.sidecar_files(value, metadata);
+        let rng_state = if let Some(rng_state) = metadata.rng_state {
+            let mut hex = String::with_capacity(rng_state.len() * 2 + 2);
+            hex.push('"');
+            for byte in rng_state {
+                write!(hex, "{byte:02x}").unwrap();
+            }
+            hex.push('"');
+            hex
+        } else {
+            "null".to_owned()
+        };
+        let timestamp = metadata
+            .timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(
+                #[coverage(off)]
+                |d| d.as_secs_f64(),
+            )
+            .unwrap_or(0.0);
+        let complexity = if metadata.complexity.is_finite() {
+            metadata.complexity.to_string()
+        } else {
+            "null".to_owned()
+        };
+        let content = format!("{{\"complexity\":{complexity},\"rng_state\":{rng_state},\"timestamp\":{timestamp}}}");
+        sidecar_files.push(("meta.json".to_owned(), content.into_bytes()));
+        sidecar_files
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, value: &Self::Value, data: &[u8]) -> Option<String> {
+        self.serializer.suggested_file_stem(value, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{ByteSerializer, HexSerializer, LineEndingNormalization, MetadataSerializer, StringSerializer};
+    use crate::traits::ArtifactMetadata;
+    use crate::{HashNamedSerializer, Serializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_metadata_serializer() {
+        let ser = MetadataSerializer::new(ByteSerializer::new("bin"));
+        let value = vec![1, 2, 3];
+        assert_eq!(ser.extension(), "bin");
+        assert_eq!(ser.to_data(&value), value);
+        assert_eq!(ser.from_data(&value), Some(value.clone()));
+
+        let metadata = ArtifactMetadata {
+            complexity: 25.0,
+            rng_state: Some(&[0x0a, 0xff]),
+            timestamp: UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        };
+        let sidecar_files = ser.sidecar_files(&value, &metadata);
+        assert_eq!(sidecar_files.len(), 1);
+        assert_eq!(sidecar_files[0].0, "meta.json");
+        assert_eq!(
+            String::from_utf8(sidecar_files[0].1.clone()).unwrap(),
+            r#"{"complexity":25,"rng_state":"0aff","timestamp":1700000000}"#
+        );
+
+        let metadata = ArtifactMetadata {
+            rng_state: None,
+            ..metadata
+        };
+        let sidecar_files = ser.sidecar_files(&value, &metadata);
+        assert_eq!(
+            String::from_utf8(sidecar_files[0].1.clone()).unwrap(),
+            r#"{"complexity":25,"rng_state":null,"timestamp":1700000000}"#
+        );
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_byte_serializer_line_ending_normalization() {
+        let data = b"a\r\nb\rc\n\r\r\n".to_vec();
+
+        let ser = ByteSerializer::new("txt");
+        assert_eq!(ser.from_data(&data), Some(data.clone()));
+        assert_eq!(ser.to_data(&data), data);
+
+        let ser = ByteSerializer::with_line_ending_normalization("txt", LineEndingNormalization::CrlfToLf);
+        assert_eq!(ser.from_data(&data), Some(b"a\nb\rc\n\r\n".to_vec()));
+        assert_eq!(ser.to_data(&data), b"a\nb\rc\n\r\n".to_vec());
+
+        let ser = ByteSerializer::with_line_ending_normalization("txt", LineEndingNormalization::StripCarriageReturns);
+        assert_eq!(ser.from_data(&data), Some(b"a\nbc\n\n".to_vec()));
+        assert_eq!(ser.to_data(&data), b"a\nbc\n\n".to_vec());
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_byte_serializer_max_len() {
+        let ser = ByteSerializer::with_max_len("bin", 8);
+        assert_eq!(ser.extension(), "bin");
+        assert_eq!(ser.from_data(&[]), Some(vec![]));
+        assert_eq!(ser.from_data(&[7; 8]), Some(vec![7; 8]));
+        assert_eq!(ser.from_data(&[7; 9]), None);
+
+        let ser = ByteSerializer::with_max_len("bin", 0);
+        assert_eq!(ser.from_data(&[]), Some(vec![]));
+        assert_eq!(ser.from_data(&[0]), None);
+
+        // unbounded by default
+        let ser = ByteSerializer::new("bin");
+        assert_eq!(ser.from_data(&[7; 100_000]).as_deref().map(<[u8]>::len), Some(100_000));
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_is_utf8_is_forwarded_by_wrapping_serializers() {
+        assert!(!ByteSerializer::new("bin").is_utf8());
+        assert!(HashNamedSerializer::new(HexSerializer::default()).is_utf8());
+        assert!(!HashNamedSerializer::new(ByteSerializer::new("bin")).is_utf8());
+        assert!(MetadataSerializer::new(StringSerializer::<u8>::default()).is_utf8());
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_hex_serializer() {
+        let ser = HexSerializer::default();
+        assert_eq!(ser.extension(), "hex");
+        assert!(ser.is_utf8());
+        assert_eq!(HexSerializer::new("txt").extension(), "txt");
+        for bytes in [vec![], vec![0x00, 0x0f, 0xa0, 0xff], (0..=255).collect()] {
+            let data = ser.to_data(&bytes);
+            assert_eq!(data.len(), bytes.len() * 2);
+            assert!(data.iter().all(
+                #[coverage(off)]
+                |c| c.is_ascii_digit() || (b'a'..=b'f').contains(c)
+            ));
+            assert_eq!(ser.from_data(&data), Some(bytes));
+        }
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_hex_serializer_whitespace_and_malformed_data() {
+        let ser = HexSerializer::default();
+        assert_eq!(ser.from_data(b"  0a Ff\n\t10\r\n"), Some(vec![0x0a, 0xff, 0x10]));
+        // the digits of a byte may be separated by whitespace
+        assert_eq!(ser.from_data(b"0\na"), Some(vec![0x0a]));
+        assert_eq!(ser.from_data(b" \n"), Some(vec![]));
+
+        assert_eq!(ser.from_data(b"0a1"), None);
+        assert_eq!(ser.from_data(b"0g"), None);
+        assert_eq!(ser.from_data(b"0x0a"), None);
+        assert_eq!(ser.from_data(b"0a,0b"), None);
+        assert_eq!(ser.from_data("0a\u{e9}".as_bytes()), None);
+        assert_eq!(ser.from_data(&[b'0', 0xff]), None);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_string_serializer() {
+        let ser = StringSerializer::<String>::default();
+        assert_eq!(ser.extension(), "txt");
+        assert!(ser.is_utf8());
+        assert_eq!(StringSerializer::<String>::new("js").extension(), "js");
+        for string in [
+            "",
+            "fn main() {}\n",
+            "\"quoted\" \\ back\tslash",
+            "caf\u{e9} \u{1f980} \u{65e5}\u{672c}",
+        ] {
+            let data = ser.to_data(&string.to_owned());
+            assert_eq!(data, string.as_bytes());
+            assert_eq!(ser.from_data(&data), Some(string.to_owned()));
+        }
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_string_serializer_rejects_invalid_utf8() {
+        let ser = StringSerializer::<String>::default();
+        assert_eq!(ser.from_data(&[0xff]), None);
+        assert_eq!(ser.from_data(&[b'a', 0xc3]), None);
+        // a truncated multi-byte character
+        let crab = "\u{1f980}".as_bytes();
+        assert_eq!(ser.from_data(&crab[..3]), None);
+        assert_eq!(ser.from_data(crab), Some("\u{1f980}".to_owned()));
+    }
+}
@@ -0,0 +1,123 @@
+use std::marker::PhantomData;
+
+/// A serializer that uses [`serde`] and [`ciborium`] to serialize the test
+/// inputs (of arbitrary type `T: Serializable + for<'e> Deserializable<'e>`)
+/// to a binary [CBOR](https://cbor.io) file.
+///
+/// Unlike json, CBOR encodes byte arrays compactly and losslessly, which makes
+/// it better suited to the test inputs of binary formats. The files have the
+/// extension `cbor` by default, which can be changed with
+/// [`with_extension`](CborSerializer::with_extension).
+#[doc(cfg(feature = "serde_cbor_serializer"))]
+pub struct CborSerializer<S> {
+    ext: &'static str,
+    phantom: PhantomData<S>,
+}
+
+impl<S> Default for CborSerializer<S> {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::with_extension("cbor")
+    }
+}
+
+impl<S> CborSerializer<S> {
+    /// Create a CBOR serializer whose files have the given extension
+    #[coverage(off)]
+    pub fn with_extension(ext: &'static str) -> Self {
+        Self {
+            ext,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S> crate::traits::Serializer for CborSerializer<S>
+where
+    S: serde::Serialize + for<'e> serde::Deserialize<'e>,
+{
+    type Value = S;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.ext
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        false
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<S> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<S, String> {
+        ciborium::from_reader(data).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        let mut data = vec![];
+        ciborium::into_writer(value, &mut data).unwrap();
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::CborSerializer;
+    use crate::Serializer;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Packet {
+        id: u32,
+        payload: Vec<u8>,
+        tags: Vec<String>,
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_cbor_serializer_round_trip() {
+        let ser = CborSerializer::<Packet>::default();
+        assert_eq!(ser.extension(), "cbor");
+        assert!(!ser.is_utf8());
+        for packet in [
+            Packet {
+                id: 0,
+                payload: vec![],
+                tags: vec![],
+            },
+            Packet {
+                id: u32::MAX,
+                payload: (0..=255).collect(),
+                tags: vec!["a".to_owned(), "é\u{0}".to_owned()],
+            },
+        ] {
+            let data = ser.to_data(&packet);
+            assert_eq!(ser.from_data(&data), Some(packet));
+        }
+        let ser = CborSerializer::<Vec<u8>>::with_extension("bin");
+        assert_eq!(ser.extension(), "bin");
+        let bytes = vec![0, 1, 254, 255];
+        assert_eq!(ser.from_data(&ser.to_data(&bytes)), Some(bytes));
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_cbor_serializer_rejects_malformed_data() {
+        let ser = CborSerializer::<Packet>::default();
+        let data = ser.to_data(&Packet {
+            id: 1,
+            payload: vec![1, 2, 3],
+            tags: vec!["x".to_owned()],
+        });
+        assert_eq!(ser.from_data(&data[..data.len() - 1]), None);
+        assert_eq!(ser.from_data(&[]), None);
+        assert_eq!(ser.from_data(&[0xff, 0xff, 0xff]), None);
+        assert_eq!(ser.from_data(b"{\"id\": 1}"), None);
+    }
+}
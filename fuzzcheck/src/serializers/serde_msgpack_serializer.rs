@@ -0,0 +1,104 @@
+use std::marker::PhantomData;
+
+/// A serializer that uses [`serde`] and [`rmp_serde`] to serialize the test
+/// inputs (of arbitrary type `T: Serializable + for<'e> Deserializable<'e>`)
+/// to a binary [MessagePack](https://msgpack.org) file.
+///
+/// The files are much smaller than the json files written by `SerdeSerializer`,
+/// which keeps large corpora compact. They have the extension `mp` by default,
+/// which can be changed with [`with_extension`](MsgPackSerializer::with_extension).
+#[doc(cfg(feature = "serde_msgpack_serializer"))]
+pub struct MsgPackSerializer<S> {
+    ext: &'static str,
+    phantom: PhantomData<S>,
+}
+
+impl<S> Default for MsgPackSerializer<S> {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::with_extension("mp")
+    }
+}
+
+impl<S> MsgPackSerializer<S> {
+    /// Create a MessagePack serializer whose files have the given extension
+    #[coverage(off)]
+    pub fn with_extension(ext: &'static str) -> Self {
+        Self {
+            ext,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S> crate::traits::Serializer for MsgPackSerializer<S>
+where
+    S: serde::Serialize + for<'e> serde::Deserialize<'e>,
+{
+    type Value = S;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.ext
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        false
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<S> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<S, String> {
+        rmp_serde::from_slice(data).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        rmp_serde::to_vec(value).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MsgPackSerializer;
+    use crate::Serializer;
+
+    #[test]
+    #[coverage(off)]
+    fn test_msgpack_serializer() {
+        let ser = MsgPackSerializer::<(u8, Vec<u32>, String)>::default();
+        assert_eq!(ser.extension(), "mp");
+        assert!(!ser.is_utf8());
+        let value = (7, vec![0, 1, u32::MAX], "hello".to_owned());
+        let data = ser.to_data(&value);
+        assert_eq!(ser.from_data(&data), Some(value));
+        assert_eq!(ser.from_data(&data[..data.len() - 1]), None);
+        assert_eq!(ser.from_data(&[]), None);
+        assert_eq!(ser.from_data(&[0xc1]), None);
+
+        let ser = MsgPackSerializer::<Vec<u8>>::with_extension("msgpack");
+        assert_eq!(ser.extension(), "msgpack");
+    }
+
+    #[cfg(feature = "serde_json_serializer")]
+    #[test]
+    #[coverage(off)]
+    fn test_msgpack_serializer_is_smaller_than_json() {
+        use crate::SerdeSerializer;
+
+        let value = (0..10_000u32)
+            .map(
+                #[coverage(off)]
+                |x| x.wrapping_mul(2_654_435_761),
+            )
+            .collect::<Vec<_>>();
+        let msgpack = MsgPackSerializer::default().to_data(&value);
+        let json = SerdeSerializer::default().to_data(&value);
+        assert!(msgpack.len() * 2 < json.len(), "{} {}", msgpack.len(), json.len());
+        assert_eq!(MsgPackSerializer::default().from_data(&msgpack), Some(value));
+    }
+}
@@ -0,0 +1,124 @@
+use std::marker::PhantomData;
+
+/// A serializer that uses [`serde`] and [`postcard`] to serialize the test
+/// inputs (of arbitrary type `T: Serializable + for<'e> Deserializable<'e>`)
+/// to a binary file in the [postcard](https://postcard.jamesmunns.com) wire format.
+///
+/// It is useful to fuzz values that are also exchanged as postcard messages, since the
+/// files of the corpus can then be used as such. They have the extension `postcard` by
+/// default, which can be changed with [`with_extension`](PostcardSerializer::with_extension).
+///
+/// A file is rejected if it cannot be deserialized or if it contains trailing bytes
+/// after the serialized value.
+#[doc(cfg(feature = "serde_postcard_serializer"))]
+pub struct PostcardSerializer<S> {
+    ext: &'static str,
+    phantom: PhantomData<S>,
+}
+
+impl<S> Default for PostcardSerializer<S> {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self::with_extension("postcard")
+    }
+}
+
+impl<S> PostcardSerializer<S> {
+    /// Create a postcard serializer whose files have the given extension
+    #[coverage(off)]
+    pub fn with_extension(ext: &'static str) -> Self {
+        Self {
+            ext,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S> crate::traits::Serializer for PostcardSerializer<S>
+where
+    S: serde::Serialize + for<'e> serde::Deserialize<'e>,
+{
+    type Value = S;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.ext
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        false
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<S> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<S, String> {
+        let (value, rest) = postcard::take_from_bytes(data).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )?;
+        if rest.is_empty() {
+            Ok(value)
+        } else {
+            Err(format!("{} trailing bytes after the serialized value", rest.len()))
+        }
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        postcard::to_allocvec(value).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::PostcardSerializer;
+    use crate::Serializer;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Message {
+        id: u16,
+        payload: Vec<u8>,
+        reply: Option<bool>,
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_postcard_serializer() {
+        let ser = PostcardSerializer::<Message>::default();
+        assert_eq!(ser.extension(), "postcard");
+        assert!(!ser.is_utf8());
+        let value = Message {
+            id: 300,
+            payload: vec![0, 1, 255],
+            reply: Some(false),
+        };
+        let data = ser.to_data(&value);
+        // varint id, length-prefixed payload, tagged option
+        assert_eq!(data, [0xac, 0x02, 3, 0, 1, 255, 1, 0]);
+        assert_eq!(ser.from_data(&data), Some(value));
+        assert_eq!(ser.from_data(&data[..data.len() - 1]), None);
+        assert_eq!(ser.from_data(&[]), None);
+        // an invalid option tag
+        assert_eq!(ser.from_data(&[1, 0, 2]), None);
+
+        let ser = PostcardSerializer::<u8>::with_extension("bin");
+        assert_eq!(ser.extension(), "bin");
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_postcard_serializer_rejects_trailing_bytes() {
+        let ser = PostcardSerializer::<(u8, Vec<u8>)>::default();
+        let mut data = ser.to_data(&(7, vec![1, 2]));
+        assert_eq!(ser.from_data(&data), Some((7, vec![1, 2])));
+        data.push(0);
+        assert_eq!(ser.from_data(&data), None);
+        assert_eq!(
+            ser.from_data_result(&data).unwrap_err(),
+            "1 trailing bytes after the serialized value"
+        );
+    }
+}
@@ -3,15 +3,32 @@ use std::marker::PhantomData;
 /// A serializer that uses [`serde`] and [`ron`] to serialize the test
 /// inputs (of arbitrary type `T: Serializable + for<'e> Deserializable<'e>`)
 /// to a "rusty object notation" file.
+///
+/// Unlike json, RON writes enum variants and structs the way they are written in Rust,
+/// which makes the files of enum-heavy inputs much easier to read. The
+/// [`pretty`](SerdeRonSerializer::pretty) builder method writes indented RON instead
+/// of a single line.
 #[doc(cfg(feature = "serde_ron_serializer"))]
 pub struct SerdeRonSerializer<S> {
+    pretty: bool,
     phantom: PhantomData<S>,
 }
 
 impl<S> Default for SerdeRonSerializer<S> {
     #[coverage(off)]
     fn default() -> Self {
-        Self { phantom: PhantomData }
+        Self {
+            pretty: false,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S> SerdeRonSerializer<S> {
+    /// Whether to write pretty-printed RON, with one field or element per line
+    #[coverage(off)]
+    pub fn pretty(self, pretty: bool) -> Self {
+        Self { pretty, ..self }
     }
 }
 
@@ -26,12 +43,94 @@ where
         "ron"
     }
     #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        true
+    }
+    #[coverage(off)]
     fn from_data(&self, data: &[u8]) -> Option<S> {
-        let utf8_encoded = std::str::from_utf8(data).ok()?;
-        ron::from_str(utf8_encoded).ok()
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<S, String> {
+        let utf8_encoded = std::str::from_utf8(data).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )?;
+        ron::from_str(utf8_encoded).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )
     }
     #[coverage(off)]
     fn to_data(&self, value: &Self::Value) -> Vec<u8> {
-        ron::to_string(value).unwrap().into_bytes()
+        if self.pretty {
+            ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+                .unwrap()
+                .into_bytes()
+        } else {
+            ron::to_string(value).unwrap().into_bytes()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::SerdeRonSerializer;
+    use crate::Serializer;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Command {
+        Stop,
+        Move { x: i32, y: i32 },
+        Say(String),
+        Batch(Vec<Command>),
+    }
+
+    #[coverage(off)]
+    fn command() -> Command {
+        Command::Batch(vec![
+            Command::Move { x: 1, y: -2 },
+            Command::Say("hi".to_owned()),
+            Command::Stop,
+        ])
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_ron_serializer() {
+        let ser = SerdeRonSerializer::<Command>::default();
+        assert_eq!(ser.extension(), "ron");
+        assert!(ser.is_utf8());
+        let data = ser.to_data(&command());
+        // the variants are written as in Rust
+        assert_eq!(
+            std::str::from_utf8(&data).unwrap(),
+            "Batch([Move(x:1,y:-2),Say(\"hi\"),Stop])"
+        );
+        assert_eq!(ser.from_data(&data), Some(command()));
+        assert_eq!(ser.from_data(b"Batch([Jump])"), None);
+        assert!(ser.from_data_result(b"Move(x: 1)").unwrap_err().contains("y"));
+        assert_eq!(ser.from_data(&[0xff]), None);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_ron_serializer_pretty() {
+        let ser = SerdeRonSerializer::<Command>::default().pretty(true);
+        let data = ser.to_data(&command());
+        let text = std::str::from_utf8(&data).unwrap();
+        assert!(text.lines().count() > 1);
+        assert!(text.contains("Move(\n"));
+        assert_eq!(ser.from_data(&data), Some(command()));
+        // both formats can be read by either serializer
+        assert_eq!(
+            SerdeRonSerializer::<Command>::default().from_data(&data),
+            Some(command())
+        );
+
+        let ser = ser.pretty(false);
+        assert!(!ser.to_data(&command()).contains(&b'\n'));
     }
 }
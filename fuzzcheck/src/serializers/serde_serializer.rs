@@ -3,15 +3,34 @@ use std::marker::PhantomData;
 /// A serializer that uses `serde` and `serde_json` to serialize the test
 /// inputs (of arbitrary type `T: Serializable + for<'e> Deserializable<'e>`)
 /// to a json file.
+///
+/// By default, the json is compact. A serializer created with
+/// [`new_pretty`](SerdeSerializer::new_pretty) writes indented json instead,
+/// which is easier to read and edit by hand.
 #[doc(cfg(feature = "serde_json_serializer"))]
 pub struct SerdeSerializer<S> {
+    pretty: bool,
     phantom: PhantomData<S>,
 }
 
 impl<S> Default for SerdeSerializer<S> {
     #[coverage(off)]
     fn default() -> Self {
-        Self { phantom: PhantomData }
+        Self {
+            pretty: false,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<S> SerdeSerializer<S> {
+    /// Create a serializer that writes pretty-printed json
+    #[coverage(off)]
+    pub fn new_pretty() -> Self {
+        Self {
+            pretty: true,
+            phantom: PhantomData,
+        }
     }
 }
 
@@ -26,11 +45,184 @@ where
         "json"
     }
     #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        true
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<S> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<S, String> {
+        serde_json::from_slice(data).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        if self.pretty {
+            serde_json::to_vec_pretty(value).unwrap()
+        } else {
+            serde_json::to_vec(value).unwrap()
+        }
+    }
+}
+
+/// A serializer that uses `serde` and `serde_json` to serialize the test
+/// inputs to a json file, in a canonical form.
+///
+/// Object keys are sorted and floating point numbers with an integral value
+/// are written as integers. Values that are equivalent once deserialized
+/// therefore produce byte-identical files, which lets the corpus directory
+/// deduplicate them naturally. Deserialization is the same as [`SerdeSerializer`].
+#[doc(cfg(feature = "serde_json_serializer"))]
+pub struct CanonicalizingSerializer<S> {
+    phantom: PhantomData<S>,
+}
+
+impl<S> Default for CanonicalizingSerializer<S> {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self { phantom: PhantomData }
+    }
+}
+
+impl<S> crate::traits::Serializer for CanonicalizingSerializer<S>
+where
+    S: serde::Serialize + for<'e> serde::Deserialize<'e>,
+{
+    type Value = S;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        "json"
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        true
+    }
+    #[coverage(off)]
     fn from_data(&self, data: &[u8]) -> Option<S> {
-        serde_json::from_slice(data).ok()
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<S, String> {
+        serde_json::from_slice(data).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )
     }
     #[coverage(off)]
     fn to_data(&self, value: &Self::Value) -> Vec<u8> {
-        serde_json::to_vec(value).unwrap()
+        let value = serde_json::to_value(value).unwrap();
+        serde_json::to_vec(&canonicalize(value)).unwrap()
+    }
+}
+
+/// Sorts the keys of all objects and rewrites integral floating point numbers as integers
+#[coverage(off)]
+fn canonicalize(value: serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Number(n) if n.is_f64() => {
+            let f = n.as_f64().unwrap();
+            if f.fract() == 0.0 && f.is_sign_positive() && f < u64::MAX as f64 {
+                Value::from(f as u64)
+            } else if f.fract() == 0.0 && f < 0.0 && f >= i64::MIN as f64 {
+                Value::from(f as i64)
+            } else {
+                Value::Number(n)
+            }
+        }
+        Value::Array(xs) => Value::Array(xs.into_iter().map(canonicalize).collect()),
+        Value::Object(map) => {
+            let mut entries = map.into_iter().collect::<Vec<_>>();
+            entries.sort_by(
+                #[coverage(off)]
+                |(k1, _), (k2, _)| k1.cmp(k2),
+            );
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(
+                        #[coverage(off)]
+                        |(k, v)| (k, canonicalize(v)),
+                    )
+                    .collect(),
+            )
+        }
+        value => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Serializer;
+
+    #[test]
+    #[coverage(off)]
+    fn test_serde_serializer_pretty() {
+        let compact = SerdeSerializer::<(u8, Vec<String>, Option<bool>)>::default();
+        let pretty = SerdeSerializer::<(u8, Vec<String>, Option<bool>)>::new_pretty();
+        let value = (3, vec!["a".to_owned(), "b".to_owned()], Some(true));
+        let compact_data = compact.to_data(&value);
+        let pretty_data = pretty.to_data(&value);
+        assert_eq!(compact_data, br#"[3,["a","b"],true]"#);
+        assert!(pretty_data.contains(&b'\n'));
+        assert!(pretty_data.len() > compact_data.len());
+        for ser in [&compact, &pretty] {
+            assert_eq!(ser.from_data(&compact_data).as_ref(), Some(&value));
+            assert_eq!(ser.from_data(&pretty_data).as_ref(), Some(&value));
+        }
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_serde_serializer_from_data_result() {
+        let ser = SerdeSerializer::<(u8, bool)>::default();
+        assert_eq!(ser.from_data_result(b"[1, true]"), Ok((1, true)));
+        assert_eq!(ser.from_data(b"[1, true]"), Some((1, true)));
+        let error = ser.from_data_result(b"[300, true]").unwrap_err();
+        assert!(error.contains("300"), "{error}");
+        assert!(error.contains("line 1 column"), "{error}");
+        assert_eq!(ser.from_data(b"[300, true]"), None);
+        assert!(ser.from_data_result(b"{").is_err());
+        assert!(CanonicalizingSerializer::<(u8, bool)>::default()
+            .from_data_result(b"[1]")
+            .unwrap_err()
+            .contains("invalid length"));
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_canonicalizing_serializer() {
+        let ser = CanonicalizingSerializer::<serde_json::Value>::default();
+        let a = ser
+            .from_data(br#"{"b": 1.0, "a": [2, -3.0, 0.5, {"y": null, "x": true}]}"#)
+            .unwrap();
+        let b = ser
+            .from_data(br#"{"a":[2.0,-3,0.5,{"x":true,"y":null}],"b":1}"#)
+            .unwrap();
+        let expected = br#"{"a":[2,-3,0.5,{"x":true,"y":null}],"b":1}"#;
+        assert_eq!(ser.to_data(&a), expected);
+        assert_eq!(ser.to_data(&b), expected);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_canonicalizing_serializer_roundtrip() {
+        let ser = CanonicalizingSerializer::<(f64, Vec<u8>, Option<i32>)>::default();
+        for value in [
+            (1.0, vec![1, 2], Some(-4)),
+            (-0.0, vec![], None),
+            (2.5, vec![255], Some(0)),
+        ] {
+            let data = ser.to_data(&value);
+            let roundtrip = ser.from_data(&data).unwrap();
+            assert_eq!(roundtrip.0.to_bits(), value.0.to_bits());
+            assert_eq!((roundtrip.1, roundtrip.2), (value.1, value.2));
+        }
     }
 }
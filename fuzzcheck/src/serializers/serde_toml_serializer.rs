@@ -0,0 +1,185 @@
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+/// A serializer that uses [`serde`] and [`toml`] to serialize the test
+/// inputs (of arbitrary type `T: Serializable + for<'e> Deserializable<'e>`)
+/// to a `.toml` file.
+///
+/// TOML is easier to write by hand than json, which makes it convenient for
+/// seed inputs. However, a TOML document is always a table. So the values whose
+/// type is serialized as something else, such as a vector, a tuple, or an integer,
+/// are written as the single entry of a table, under the key `value`:
+/// ```toml
+/// value = [1, 2, 3]
+/// ```
+/// `from_data` reads them back from such a table.
+///
+/// A few values cannot be represented in TOML at all, even in a table, such as `()`
+/// or maps whose keys are not strings. `to_data` then writes a document containing only
+/// a comment with the reason, which `from_data` rejects.
+#[doc(cfg(feature = "serde_toml_serializer"))]
+pub struct TomlSerializer<S> {
+    phantom: PhantomData<S>,
+}
+
+impl<S> Default for TomlSerializer<S> {
+    #[coverage(off)]
+    fn default() -> Self {
+        Self { phantom: PhantomData }
+    }
+}
+
+/// The table in which the values that are not tables are written
+#[derive(Serialize)]
+struct WrappedRef<'a, S> {
+    value: &'a S,
+}
+#[derive(Deserialize)]
+struct Wrapped<S> {
+    value: S,
+}
+
+impl<S> crate::traits::Serializer for TomlSerializer<S>
+where
+    S: serde::Serialize + for<'e> serde::Deserialize<'e>,
+{
+    type Value = S;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        "toml"
+    }
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        true
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<S> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<S, String> {
+        let utf8_encoded = std::str::from_utf8(data).map_err(
+            #[coverage(off)]
+            |e| e.to_string(),
+        )?;
+        toml::from_str(utf8_encoded).or_else(
+            #[coverage(off)]
+            |e| {
+                toml::from_str::<Wrapped<S>>(utf8_encoded)
+                    .map(
+                        #[coverage(off)]
+                        |wrapped| wrapped.value,
+                    )
+                    .map_err(
+                        #[coverage(off)]
+                        |_| e.to_string(),
+                    )
+            },
+        )
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        let result = toml::to_string(value).or_else(
+            #[coverage(off)]
+            |_| toml::to_string(&WrappedRef { value }),
+        );
+        match result {
+            Ok(s) => s.into_bytes(),
+            Err(e) => format!(
+                "# {} cannot be serialized to TOML: {}\n",
+                std::any::type_name::<S>(),
+                e.to_string().replace('\n', " ")
+            )
+            .into_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::TomlSerializer;
+    use crate::Serializer;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Input {
+        name: String,
+        bytes: Vec<u8>,
+        flag: Option<bool>,
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_toml_serializer() {
+        let ser = TomlSerializer::<Input>::default();
+        assert_eq!(ser.extension(), "toml");
+        assert!(ser.is_utf8());
+        let value = Input {
+            name: "seed".to_owned(),
+            bytes: vec![0, 1, 255],
+            flag: Some(true),
+        };
+        let data = ser.to_data(&value);
+        assert_eq!(
+            std::str::from_utf8(&data).unwrap(),
+            "name = \"seed\"\nbytes = [0, 1, 255]\nflag = true\n"
+        );
+        assert_eq!(ser.from_data(&data), Some(value));
+
+        // a hand-written input
+        let data = b"# a comment\nbytes = [\n  7,\n  8,\n]\nname = 'x'\n";
+        let expected = Input {
+            name: "x".to_owned(),
+            bytes: vec![7, 8],
+            flag: None,
+        };
+        assert_eq!(ser.from_data(data), Some(expected));
+        assert_eq!(ser.from_data(b"name = \"x\""), None);
+        assert!(ser.from_data_result(b"name = ").is_err());
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_toml_serializer_wraps_non_table_values() {
+        let ser = TomlSerializer::<Vec<u8>>::default();
+        let data = ser.to_data(&vec![1, 2, 3]);
+        assert_eq!(std::str::from_utf8(&data).unwrap(), "value = [1, 2, 3]\n");
+        assert_eq!(ser.from_data(&data), Some(vec![1, 2, 3]));
+        assert_eq!(ser.from_data(b"[1, 2, 3]"), None);
+        assert_eq!(ser.from_data(b"x = [1, 2, 3]"), None);
+        assert_eq!(ser.from_data(b""), None);
+
+        let ser = TomlSerializer::<(u8, u8)>::default();
+        assert_eq!(ser.from_data(&ser.to_data(&(1, 2))), Some((1, 2)));
+
+        let ser = TomlSerializer::<u32>::default();
+        assert_eq!(ser.from_data(b"value = 12"), Some(12));
+        assert_eq!(ser.from_data(b"12"), None);
+        assert_eq!(ser.from_data(b"x = 12"), None);
+
+        // a struct with a field named `value` is still a table
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Value {
+            value: u8,
+        }
+        let ser = TomlSerializer::<Value>::default();
+        assert_eq!(
+            ser.from_data(&ser.to_data(&Value { value: 3 })),
+            Some(Value { value: 3 })
+        );
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_toml_serializer_does_not_panic_on_unrepresentable_values() {
+        let ser = TomlSerializer::<()>::default();
+        let data = ser.to_data(&());
+        assert!(std::str::from_utf8(&data)
+            .unwrap()
+            .starts_with("# () cannot be serialized to TOML"));
+        assert_eq!(ser.from_data(&data), None);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::traits::ArtifactMetadata;
+use crate::Serializer;
+
+/// The bytes at the start of every file written by a [`VersionedSerializer`]
+const MAGIC: &[u8; 4] = b"FZCV";
+/// The length of the magic bytes followed by the little-endian version number
+const HEADER_LEN: usize = MAGIC.len() + 4;
+
+/**
+A serializer that wraps another serializer and prepends a version header to the files it writes.
+
+The header is made of the four bytes `FZCV` followed by the version number, encoded as a
+little-endian `u32`. The rest of the file is written by the wrapped serializer.
+
+When the mutator of the test cases changes, the files of an existing corpus may no longer
+be valid, or may be interpreted differently. Increasing the version number makes this
+explicit: by default, the files whose version differs from the current one are ignored.
+Alternatively, they can be upgraded by a migration closure created with
+[`with_migration`](VersionedSerializer::with_migration). It receives the version of the file
+and the bytes that follow its header. The files that have no header, such as those written
+before the `VersionedSerializer` was used, are passed to the migration closure in their
+entirety, with the version `0`.
+```
+use fuzzcheck::{ByteSerializer, Serializer, StringSerializer, VersionedSerializer};
+
+// a stale file is rejected
+let v1 = VersionedSerializer::new(ByteSerializer::new("bin"), 1);
+let v2 = VersionedSerializer::new(ByteSerializer::new("bin"), 2);
+assert_eq!(v2.from_data(&v1.to_data(&vec![1, 2])), None);
+
+// or upgraded: version 1 of the corpus stored numbers in decimal, and version 2 in hexadecimal
+let v1 = VersionedSerializer::new(StringSerializer::<u32>::new("txt"), 1);
+let v2 = VersionedSerializer::with_migration(ByteSerializer::new("txt"), 2, |version, data| match version {
+    1 => {
+        let x: u32 = std::str::from_utf8(data).ok()?.parse().ok()?;
+        Some(format!("{x:x}").into_bytes())
+    }
+    _ => None,
+});
+assert_eq!(v2.from_data(&v1.to_data(&255)), Some(b"ff".to_vec()));
+```
+*/
+pub struct VersionedSerializer<S, F = fn(u32, &[u8]) -> Option<<S as Serializer>::Value>>
+where
+    S: Serializer,
+    F: Fn(u32, &[u8]) -> Option<S::Value>,
+{
+    serializer: S,
+    version: u32,
+    migrate: F,
+}
+
+impl<S> VersionedSerializer<S>
+where
+    S: Serializer,
+{
+    /// Create a `VersionedSerializer` writing files with the given version, and ignoring
+    /// the files with a different version
+    #[coverage(off)]
+    pub fn new(serializer: S, version: u32) -> Self {
+        Self::with_migration(serializer, version, no_migration::<S::Value>)
+    }
+}
+
+#[coverage(off)]
+fn no_migration<V>(_version: u32, _data: &[u8]) -> Option<V> {
+    None
+}
+
+impl<S, F> VersionedSerializer<S, F>
+where
+    S: Serializer,
+    F: Fn(u32, &[u8]) -> Option<S::Value>,
+{
+    /// Create a `VersionedSerializer` writing files with the given version, and passing
+    /// the files with a different version to `migrate`
+    #[coverage(off)]
+    pub fn with_migration(serializer: S, version: u32, migrate: F) -> Self {
+        Self {
+            serializer,
+            version,
+            migrate,
+        }
+    }
+
+    /// The version written in the header of the files
+    #[coverage(off)]
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+}
+
+impl<S, F> Serializer for VersionedSerializer<S, F>
+where
+    S: Serializer,
+    F: Fn(u32, &[u8]) -> Option<S::Value>,
+{
+    type Value = S::Value;
+
+    #[coverage(off)]
+    fn extension(&self) -> &str {
+        self.serializer.extension()
+    }
+    #[coverage(off)]
+    fn from_data(&self, data: &[u8]) -> Option<Self::Value> {
+        self.from_data_result(data).ok()
+    }
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        let migrate = #[coverage(off)]
+        |version: u32, data: &[u8]| {
+            (self.migrate)(version, data).ok_or_else(
+                #[coverage(off)]
+                || {
+                    format!(
+                        "the data of version {version} could not be migrated to version {}",
+                        self.version
+                    )
+                },
+            )
+        };
+        if data.len() < HEADER_LEN || !data.starts_with(MAGIC) {
+            return migrate(0, data);
+        }
+        let version = u32::from_le_bytes(data[MAGIC.len()..HEADER_LEN].try_into().unwrap());
+        let data = &data[HEADER_LEN..];
+        if version == self.version {
+            self.serializer.from_data_result(data)
+        } else {
+            migrate(version, data)
+        }
+    }
+    #[coverage(off)]
+    fn to_data(&self, value: &Self::Value) -> Vec<u8> {
+        let mut data = Vec::with_capacity(HEADER_LEN);
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&self.version.to_le_bytes());
+        data.extend(self.serializer.to_data(value));
+        data
+    }
+    #[coverage(off)]
+    fn sidecar_files(&self, value: &Self::Value, metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        self.serializer.sidecar_files(value, metadata)
+    }
+    #[coverage(off)]
+    fn suggested_file_stem(&self, value: &Self::Value, data: &[u8]) -> Option<String> {
+        self.serializer.suggested_file_stem(value, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VersionedSerializer;
+    use crate::{ByteSerializer, Serializer, StringSerializer};
+
+    #[test]
+    #[coverage(off)]
+    fn test_versioned_serializer() {
+        let ser = VersionedSerializer::new(StringSerializer::<u16>::new("txt"), 3);
+        assert_eq!(ser.extension(), "txt");
+        assert_eq!(ser.version(), 3);
+        let data = ser.to_data(&1234);
+        assert_eq!(data, b"FZCV\x03\x00\x00\x001234".to_vec());
+        assert_eq!(ser.from_data(&data), Some(1234));
+
+        // stale, headerless, and truncated files are ignored
+        assert_eq!(ser.from_data(b"FZCV\x02\x00\x00\x001234"), None);
+        assert_eq!(ser.from_data(b"1234"), None);
+        assert_eq!(ser.from_data(b"FZCV\x03"), None);
+        assert_eq!(ser.from_data(b""), None);
+    }
+
+    #[test]
+    #[coverage(off)]
+    fn test_versioned_serializer_migration() {
+        let v1 = VersionedSerializer::new(ByteSerializer::new("bin"), 1);
+        // version 2 appends a checksum byte to the files of version 1
+        let v2 = VersionedSerializer::with_migration(
+            ByteSerializer::new("bin"),
+            2,
+            #[coverage(off)]
+            |version, data: &[u8]| match version {
+                0 | 1 => {
+                    let mut data = data.to_vec();
+                    data.push(data.iter().fold(
+                        0u8,
+                        #[coverage(off)]
+                        |sum, x| sum.wrapping_add(*x),
+                    ));
+                    Some(data)
+                }
+                _ => None,
+            },
+        );
+        assert_eq!(v2.from_data(&v1.to_data(&vec![1, 2, 3])), Some(vec![1, 2, 3, 6]));
+        assert_eq!(v2.from_data(&[250, 10]), Some(vec![250, 10, 4]));
+        assert_eq!(v2.from_data(&v2.to_data(&vec![7])), Some(vec![7]));
+        let v3 = VersionedSerializer::new(ByteSerializer::new("bin"), 3);
+        assert_eq!(v2.from_data(&v3.to_data(&vec![1])), None);
+    }
+}
@@ -210,6 +210,19 @@ fn ordered_mutate(&self, value: &mut S, cache: &mut Self::Cache, step: &mut Self
     }
 }
 ```
+
+## Thread safety
+
+The `Mutator` trait does not require `Send` or `Sync`. Most mutators provided by fuzzcheck
+own a [`fastrand::Rng`], which is `Send` but not `Sync`, so they can be moved to another
+thread but not shared between threads. The exceptions are the mutators based on
+[`Rc`](std::rc::Rc), such as [`RecursiveMutator`](crate::mutators::recursive::RecursiveMutator)
+and [`UniqueMutator`](crate::mutators::unique::UniqueMutator), which are not `Send` at all.
+They must be built on the thread that uses them, for example with
+[`ParallelMutator::from_constructor`](crate::mutators::parallel::ParallelMutator::from_constructor).
+
+To fuzz from multiple threads, give each thread its own mutator, for example with
+[`ParallelMutator::fork`](crate::mutators::parallel::ParallelMutator::fork).
 **/
 pub trait Mutator<Value: Clone + 'static>: 'static {
     /// Accompanies each value to help compute its complexity and mutate it efficiently.
@@ -279,6 +292,30 @@ pub trait Mutator<Value: Clone + 'static>: 'static {
     /// [`self.complexity(value, cache)`](Mutator::complexity)
     fn random_arbitrary(&self, max_cplx: f64) -> (Value, f64);
 
+    /// Generates an entirely new value whose complexity is close to `target_cplx`.
+    ///
+    /// The complexity of the returned value differs from `target_cplx` by at most
+    /// [`arbitrary_at_complexity_tolerance(target_cplx)`](crate::mutators::arbitrary_at_complexity_tolerance).
+    /// Returns `None` if no such value could be found, for example because `target_cplx`
+    /// is outside the range of complexities that the mutator can produce.
+    ///
+    /// This is useful to build a seed corpus with values of different sizes, since
+    /// [`random_arbitrary`](Mutator::random_arbitrary) only bounds the complexity from above.
+    ///
+    /// The default implementation calls `random_arbitrary(target_cplx)` a few times, until
+    /// it returns a value that is close enough to the target. Mutators made of several
+    /// submutators may override it to distribute the target complexity among them.
+    #[coverage(off)]
+    fn arbitrary_at_complexity(&self, target_cplx: f64) -> Option<(Value, f64)> {
+        crate::mutators::arbitrary_at_complexity_by_sampling(
+            target_cplx,
+            self.min_complexity(),
+            self.max_complexity(),
+            #[coverage(off)]
+            |max_cplx| self.random_arbitrary(max_cplx),
+        )
+    }
+
     /// Mutates a value (and optionally its cache) based on the given
     /// [`MutationStep`](Mutator::MutationStep).
     ///
@@ -298,6 +335,49 @@ pub trait Mutator<Value: Clone + 'static>: 'static {
         max_cplx: f64,
     ) -> Option<(Self::UnmutateToken, f64)>;
 
+    /// Mutates a value (and optionally its cache) like [`ordered_mutate`](Mutator::ordered_mutate),
+    /// but tells apart the two reasons why the value may not be mutated.
+    ///
+    /// It returns [`MutateResult::Exhausted`] if all the ordered mutations of the value were
+    /// tried, and [`MutateResult::BudgetTooSmall`] if there are mutations left, but none
+    /// of them keeps the value under `max_cplx`.
+    ///
+    /// The default implementation calls `ordered_mutate`. If it returns `None`, it calls it
+    /// again on a copy of the mutation step it left behind, with a budget of
+    /// [`larger_mutation_budget(max_cplx)`](crate::mutators::larger_mutation_budget), and
+    /// undoes the resulting mutation. So it reports the mutations that need a much larger
+    /// budget as exhausted. Mutators that know exactly why they cannot mutate a value may
+    /// override it.
+    #[coverage(off)]
+    fn ordered_mutate_with_status(
+        &self,
+        value: &mut Value,
+        cache: &mut Self::Cache,
+        step: &mut Self::MutationStep,
+        subvalue_provider: &dyn SubValueProvider,
+        max_cplx: f64,
+    ) -> MutateResult<Self::UnmutateToken> {
+        if let Some((t, cplx)) = self.ordered_mutate(value, cache, step, subvalue_provider, max_cplx) {
+            return MutateResult::Mutated(t, cplx);
+        }
+        if max_cplx < self.min_complexity() {
+            return MutateResult::BudgetTooSmall;
+        }
+        if max_cplx >= self.max_complexity() {
+            return MutateResult::Exhausted;
+        }
+        // the step is only cloned here, so that the successful mutations stay cheap
+        let mut larger_budget_step = step.clone();
+        let larger_budget = crate::mutators::larger_mutation_budget(max_cplx);
+        match self.ordered_mutate(value, cache, &mut larger_budget_step, subvalue_provider, larger_budget) {
+            Some((t, _)) => {
+                self.unmutate(value, cache, t);
+                MutateResult::BudgetTooSmall
+            }
+            None => MutateResult::Exhausted,
+        }
+    }
+
     /// Mutates a value (and optionally its cache).
     ///
     /// The mutated value should be within the given `max_cplx`. But if that
@@ -313,6 +393,102 @@ pub trait Mutator<Value: Clone + 'static>: 'static {
 
     /// Call the given closure on all subvalues and their complexities.
     fn visit_subvalues<'a>(&self, value: &'a Value, cache: &'a Self::Cache, visit: &mut dyn FnMut(&'a dyn Any, f64));
+
+    /// Returns a snapshot of the state of the mutator’s random number generators, if it has any.
+    ///
+    /// Together with [`set_rng_state`](Mutator::set_rng_state), it can be used to replay the
+    /// exact sequence of random decisions made by the mutator. Before generating each test case,
+    /// the fuzzer takes a snapshot of its own random number generator and of the ones of the pool
+    /// and the mutator. If the test case fails, the snapshot is saved next to the artifact, in a
+    /// file with the `rng` extension.
+    ///
+    /// The default implementation returns `None`.
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Restores the state of the mutator’s random number generators from a snapshot
+    /// returned by [`rng_state`](Mutator::rng_state).
+    ///
+    /// The default implementation does nothing.
+    #[coverage(off)]
+    fn set_rng_state(&self, _state: &[u8]) {}
+
+    /// Returns how different two values are, as a number between `0.0` (identical values)
+    /// and `1.0` (completely different values).
+    ///
+    /// Mutators of collections, such as [`VecMutator`](crate::mutators::vector::VecMutator),
+    /// return a normalized edit distance between the two values. Mutators of simple values
+    /// return `0.0` if the two values are equal and `1.0` otherwise.
+    ///
+    /// The default implementation returns `None`, which means that the mutator does not
+    /// provide a notion of distance between its values.
+    #[coverage(off)]
+    fn distance(&self, _a: &Value, _b: &Value) -> Option<f64> {
+        None
+    }
+
+    /// Tells the mutator how costly it is to run the test function on one of its values.
+    ///
+    /// When the test function is [expensive](EffortHint::Expensive), mutators built from
+    /// several submutators may change more of the value in each call to `ordered_mutate`,
+    /// so that each execution of the test function explores a bit more. Mutators wrapping
+    /// other mutators should pass the hint on to them.
+    ///
+    /// The default implementation does nothing.
+    #[coverage(off)]
+    fn set_effort_hint(&self, _hint: EffortHint) {}
+
+    /// Returns whether [`unmutate`](Mutator::unmutate) is cheap compared to cloning the value.
+    ///
+    /// After testing a mutated value, the fuzzer must restore the original one. When this
+    /// method returns `true`, it does so by calling `unmutate` with the token returned by the
+    /// mutation. When it returns `false`, the fuzzer instead clones the value and its cache
+    /// before mutating it, and puts the clones back afterwards, discarding the unmutate token.
+    /// This is better for mutators whose `unmutate` does a lot of work, such as a
+    /// [`MapMutator`](crate::mutators::map::MapMutator), which recomputes the mapped value
+    /// from scratch.
+    ///
+    /// The default implementation returns `true`. Mutators wrapping other mutators should
+    /// return `false` if any of them does.
+    #[coverage(off)]
+    fn unmutate_is_cheap(&self) -> bool {
+        true
+    }
+
+    /// Called by the fuzzer when the test function fails on a value.
+    ///
+    /// The value was produced by this mutator and `cache` is its cache. Mutators may use it
+    /// to avoid producing the same value again, such as a
+    /// [`FailureAvoidingMutator`](crate::mutators::failure_avoiding::FailureAvoidingMutator).
+    /// It is only called on the mutator given to the fuzzer, not on its submutators.
+    ///
+    /// The default implementation does nothing.
+    #[coverage(off)]
+    fn record_test_failure(&self, _value: &Value, _cache: &Self::Cache) {}
+}
+
+/// The result of [`Mutator::ordered_mutate_with_status`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MutateResult<UnmutateToken> {
+    /// The value was mutated. Contains the token describing how to undo the mutation,
+    /// and the new complexity of the value.
+    Mutated(UnmutateToken, f64),
+    /// All the ordered mutations of the value were tried
+    Exhausted,
+    /// The value could still be mutated, but not without exceeding the maximum complexity
+    BudgetTooSmall,
+}
+
+/// How costly it is to run the test function, given to [`Mutator::set_effort_hint`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EffortHint {
+    /// The test function is fast, so the mutators should favour small, incremental changes
+    #[default]
+    Cheap,
+    /// The test function is slow, so the mutators should make each mutation count
+    Expensive,
 }
 
 /// A [Serializer] is used to encode and decode test cases into bytes.
@@ -325,16 +501,80 @@ pub trait Serializer {
     /// The extension of the file containing the serialized value
     fn extension(&self) -> &str;
 
+    /// Whether the encoded values are always utf-8 encoded text, which can be read and
+    /// edited with a text editor.
+    ///
+    /// The default implementation returns `false`. Serializers wrapping another serializer
+    /// without changing its encoding should return the value of the wrapped serializer.
+    #[coverage(off)]
+    fn is_utf8(&self) -> bool {
+        false
+    }
+
     #[allow(clippy::wrong_self_convention)]
     /// Deserialize the bytes into the value.
     ///
-    /// This method can fail by returning `None`
+    /// This method can fail by returning `None`.
     fn from_data(&self, data: &[u8]) -> Option<Self::Value>;
 
+    #[allow(clippy::wrong_self_convention)]
+    /// Deserialize the bytes into the value, or return a human-readable description
+    /// of the reason why they could not be deserialized.
+    ///
+    /// The default implementation calls [`from_data`](Serializer::from_data), and returns a
+    /// generic error message if it fails. Serializers whose format can give a more precise
+    /// reason should implement this method too, and may implement `from_data` by discarding
+    /// its error.
+    #[coverage(off)]
+    fn from_data_result(&self, data: &[u8]) -> Result<Self::Value, String> {
+        self.from_data(data).ok_or_else(
+            #[coverage(off)]
+            || format!("the {} bytes could not be decoded by the serializer", data.len()),
+        )
+    }
+
     /// Serialize the value into bytes
     ///
     /// This method should never fail.
     fn to_data(&self, value: &Self::Value) -> Vec<u8>;
+
+    /// Additional files to save next to an artifact containing the given value.
+    ///
+    /// Each returned element is a pair of a file extension and the content of the file.
+    /// The files are given the same name as the artifact, but with the given extension.
+    ///
+    /// The default implementation does not save any additional file.
+    #[coverage(off)]
+    fn sidecar_files(&self, _value: &Self::Value, _metadata: &ArtifactMetadata) -> Vec<(String, Vec<u8>)> {
+        vec![]
+    }
+
+    /// The name, without extension, of the file in which the given value should be saved.
+    ///
+    /// `data` is the content of the file, as returned by [`to_data`](Serializer::to_data).
+    ///
+    /// It is used for the files of the output corpus and for the artifacts. When it returns
+    /// `None`, which is the default, the files are named after a hash of their content that
+    /// is only stable for a given version of the Rust standard library. The hash is also used
+    /// when the suggested stem is not a plain file name, e.g. when it is `..` or contains a path
+    /// separator, or when a different test case was already saved under that name.
+    #[coverage(off)]
+    fn suggested_file_stem(&self, _value: &Self::Value, _data: &[u8]) -> Option<String> {
+        None
+    }
+}
+
+/// Information about a test case that is being saved as an artifact.
+///
+/// It is passed to [`Serializer::sidecar_files`].
+#[derive(Debug, Clone)]
+pub struct ArtifactMetadata<'a> {
+    /// The complexity of the test case
+    pub complexity: f64,
+    /// The state of the mutator’s random number generators, as returned by [`Mutator::rng_state`]
+    pub rng_state: Option<&'a [u8]>,
+    /// The time at which the artifact is saved
+    pub timestamp: std::time::SystemTime,
 }
 
 /// A [CorpusDelta] describes how to reflect a change in the pool’s content to the corpus on the file system.
@@ -424,6 +664,14 @@ pub trait SensorAndPool: SaveToStatsFolder {
     fn stop_recording(&mut self);
     fn process(&mut self, input_id: PoolStorageIndex, cplx: f64) -> Vec<CorpusDelta>;
     fn get_random_index(&mut self) -> Option<PoolStorageIndex>;
+    /// See [`Pool::rng_state`]. The default implementation returns `None`.
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+    /// See [`Pool::set_rng_state`]. The default implementation does nothing.
+    #[coverage(off)]
+    fn set_rng_state(&self, _state: &[u8]) {}
 }
 impl<A, B> SaveToStatsFolder for (A, B)
 where
@@ -464,6 +712,14 @@ where
     fn get_random_index(&mut self) -> Option<PoolStorageIndex> {
         self.1.get_random_index()
     }
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        self.1.rng_state()
+    }
+    #[coverage(off)]
+    fn set_rng_state(&self, state: &[u8]) {
+        self.1.set_rng_state(state)
+    }
 }
 
 pub enum CSVField {
@@ -557,6 +813,24 @@ pub trait Pool: SaveToStatsFolder {
     fn weight(&self) -> f64 {
         1.0
     }
+
+    /// Returns a snapshot of the state of the pool’s random number generators, if it has any.
+    ///
+    /// It is saved next to the artifacts along with the state of the mutator’s random number
+    /// generators. See [`Mutator::rng_state`].
+    ///
+    /// The default implementation returns `None`.
+    #[coverage(off)]
+    fn rng_state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Restores the state of the pool’s random number generators from a snapshot
+    /// returned by [`rng_state`](Pool::rng_state).
+    ///
+    /// The default implementation does nothing.
+    #[coverage(off)]
+    fn set_rng_state(&self, _state: &[u8]) {}
 }
 
 /**
@@ -571,6 +845,16 @@ file system, which reflects the content of the pool, can be properly updated.
 */
 pub trait CompatibleWithObservations<O>: Pool {
     fn process(&mut self, input_id: PoolStorageIndex, observations: &O, complexity: f64) -> Vec<CorpusDelta>;
+
+    /// Return whether a test case with the given observations and complexity would be added to the pool
+    /// by [`process`](CompatibleWithObservations::process), without modifying the pool.
+    ///
+    /// The default implementation conservatively returns `true`. Pools that can cheaply perform the same check as
+    /// `process` should override it.
+    #[coverage(off)]
+    fn would_accept(&self, _observations: &O, _complexity: f64) -> bool {
+        true
+    }
 }
 
 /// A trait for types that want to save their content to the `stats` folder which is created after a fuzzing run.
@@ -5,7 +5,7 @@ use std::fs;
 use std::fs::{File, OpenOptions};
 use std::hash::{Hash, Hasher};
 use std::io::{self, Result, Write};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::time::{Duration, Instant, SystemTime};
 
 use fuzzcheck_common::arg::{Arguments, FuzzerCommand};
@@ -33,6 +33,18 @@ impl ToCSV for FuzzerStats {
     }
 }
 
+/// Whether the file stem suggested by a serializer can be used as the name of a file inside a folder,
+/// i.e. it is not empty, `.`, or `..`, and it does not contain a path separator
+#[coverage(off)]
+pub(crate) fn is_valid_file_stem(stem: &str) -> bool {
+    let mut components = Path::new(stem).components();
+    !stem.contains(['/', '\\', '\0'])
+        && matches!(
+            (components.next(), components.next()),
+            (Some(Component::Normal(_)), None)
+        )
+}
+
 pub struct World {
     settings: Arguments,
     initial_instant: Instant,
@@ -91,6 +103,7 @@ impl World {
         &mut self,
         idx: PoolStorageIndex,
         content: Vec<u8>,
+        file_stem: Option<String>,
         deltas: &[CorpusDelta],
         extension: &str,
     ) -> Result<()> {
@@ -102,7 +115,23 @@ impl World {
             }
 
             if *add {
-                let hash = self.hash(&content);
+                // the suggested stem is ignored if it is already the name of another input of the same folder
+                let hash = file_stem
+                    .clone()
+                    .filter(
+                        #[coverage(off)]
+                        |stem| {
+                            is_valid_file_stem(stem)
+                                && !self.corpus.iter().any(
+                                    #[coverage(off)]
+                                    |((p, i), name)| p == path && *i != idx && name == stem,
+                                )
+                        },
+                    )
+                    .unwrap_or_else(
+                        #[coverage(off)]
+                        || self.hash(&content),
+                    );
                 let _old = self.corpus.insert((path.to_path_buf(), idx), hash.clone());
                 self.add_to_output_corpus(path, hash.clone(), content.clone(), extension)?;
             }
@@ -279,7 +308,15 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
     }
 
     #[coverage(off)]
-    pub fn save_artifact(&mut self, content: Vec<u8>, cplx: f64, extension: &str) -> Result<()> {
+    pub fn save_artifact(
+        &mut self,
+        content: Vec<u8>,
+        cplx: f64,
+        file_stem: Option<String>,
+        extension: &str,
+        rng_state: Option<Vec<u8>>,
+        sidecar_files: Vec<(String, Vec<u8>)>,
+    ) -> Result<()> {
         let artifacts_folder = self.settings.artifacts_folder.as_ref();
         if artifacts_folder.is_none() {
             return Ok(());
@@ -290,20 +327,53 @@ This should never happen, and is probably a bug in fuzzcheck. Sorry :("#
             std::fs::create_dir_all(artifacts_folder)?;
         }
 
-        let mut hasher = DefaultHasher::new();
-        content.hash(&mut hasher);
-        let hash = hasher.finish();
-
-        let name = if let FuzzerCommand::MinifyInput { .. } | FuzzerCommand::Read { .. } = self.settings.command {
-            format!("{:.0}--{:x}", cplx * 100.0, hash)
-        } else {
-            format!("{:x}", hash)
+        let name_with_stem = #[coverage(off)]
+        |stem: String| {
+            if let FuzzerCommand::MinifyInput { .. } | FuzzerCommand::Read { .. } = self.settings.command {
+                format!("{:.0}--{}", cplx * 100.0, stem)
+            } else {
+                stem
+            }
         };
+        // the suggested stem is ignored if a different artifact was already saved under that name
+        let name = file_stem
+            .filter(
+                #[coverage(off)]
+                |stem| is_valid_file_stem(stem),
+            )
+            .map(name_with_stem)
+            .filter(
+                #[coverage(off)]
+                |name| {
+                    fs::read(artifacts_folder.join(name).with_extension(extension)).map_or(
+                        true,
+                        #[coverage(off)]
+                        |existing| existing == content,
+                    )
+                },
+            )
+            .unwrap_or_else(
+                #[coverage(off)]
+                || name_with_stem(self.hash(&content)),
+            );
 
         let path = artifacts_folder.join(&name).with_extension(extension);
         fs::write(&path, &content)?;
         println!("Failing test case found. Saving at {:?}", path);
 
+        if let Some(rng_state) = rng_state.filter(
+            #[coverage(off)]
+            |rng_state| !rng_state.is_empty(),
+        ) {
+            fs::write(artifacts_folder.join(&name).with_extension("rng"), rng_state)?;
+        }
+        for (sidecar_extension, sidecar_content) in sidecar_files {
+            fs::write(
+                artifacts_folder.join(&name).with_extension(sidecar_extension),
+                sidecar_content,
+            )?;
+        }
+
         Result::Ok(())
     }
 
@@ -0,0 +1,41 @@
+use fuzzcheck::mutators::arbitrary_at_complexity_tolerance;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+/// Checks that the values returned by `arbitrary_at_complexity` have the right complexity
+fn check_tiers<T: Clone + 'static, M: Mutator<T>>(m: M, targets: &[f64]) {
+    m.initialize();
+    for &target in targets {
+        let (value, cplx) = m.arbitrary_at_complexity(target).unwrap_or_else(|| {
+            panic!(
+                "no value found with complexity {target} for {}",
+                std::any::type_name::<T>()
+            )
+        });
+        let cache = m.validate_value(&value).unwrap();
+        assert_eq!(m.complexity(&value, &cache), cplx);
+        assert!(
+            (cplx - target).abs() <= arbitrary_at_complexity_tolerance(target),
+            "complexity {cplx} is too far from {target}"
+        );
+    }
+}
+
+#[test]
+fn test_arbitrary_at_complexity() {
+    check_tiers(<Vec<u8>>::default_mutator(), &[1.0, 10.0, 50.0, 200.0, 1000.0]);
+    check_tiers(<Vec<Vec<u16>>>::default_mutator(), &[1.0, 10.0, 100.0, 500.0]);
+    check_tiers::<Box<(u8, Vec<bool>)>, _>(<Box<(u8, Vec<bool>)>>::default_mutator(), &[10.0, 20.0, 100.0]);
+    check_tiers::<(u8, Vec<u8>, Vec<u16>), _>(<(u8, Vec<u8>, Vec<u16>)>::default_mutator(), &[10.0, 100.0, 300.0]);
+}
+
+#[test]
+fn test_arbitrary_at_complexity_infeasible() {
+    let m = bool::default_mutator();
+    m.initialize();
+    assert!(m.arbitrary_at_complexity(1.0).is_some());
+    assert!(m.arbitrary_at_complexity(50.0).is_none());
+
+    let m = <Vec<u8>>::default_mutator();
+    m.initialize();
+    assert!(m.arbitrary_at_complexity(-10.0).is_none());
+}
@@ -0,0 +1,128 @@
+use fuzzcheck::mutators::balanced_tree::{is_approximately_balanced, BalancedTreeMutator, BinaryTree};
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Tree {
+    Leaf,
+    Node(Box<Tree>, u8, Box<Tree>),
+}
+
+impl BinaryTree for Tree {
+    type Item = u8;
+
+    fn empty() -> Self {
+        Tree::Leaf
+    }
+    fn node(left: Self, item: u8, right: Self) -> Self {
+        Tree::Node(Box::new(left), item, Box::new(right))
+    }
+    fn as_node(&self) -> Option<(&Self, &u8, &Self)> {
+        match self {
+            Tree::Leaf => None,
+            Tree::Node(left, item, right) => Some((left, item, right)),
+        }
+    }
+    fn into_node(self) -> Option<(Self, u8, Self)> {
+        match self {
+            Tree::Leaf => None,
+            Tree::Node(left, item, right) => Some((*left, item, *right)),
+        }
+    }
+}
+
+fn items(tree: &Tree, items_acc: &mut Vec<u8>) {
+    if let Tree::Node(left, item, right) = tree {
+        items(left, items_acc);
+        items_acc.push(*item);
+        items(right, items_acc);
+    }
+}
+
+#[test]
+fn test_balanced_tree_mutator() {
+    let m = BalancedTreeMutator::<Tree, _>::new(U8Mutator::default());
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_balanced_tree_mutator_stays_balanced() {
+    let m = BalancedTreeMutator::<Tree, _>::new(U8Mutator::default());
+    m.initialize();
+    let mut max_len = 0;
+    for _ in 0..1000 {
+        let (tree, _) = m.random_arbitrary(2000.0);
+        assert!(is_approximately_balanced(&tree), "{tree:?}");
+        let mut xs = vec![];
+        items(&tree, &mut xs);
+        max_len = max_len.max(xs.len());
+    }
+    assert!(max_len > 50, "{max_len}");
+
+    let (mut tree, _) = m.random_arbitrary(2000.0);
+    let mut cache = m.validate_value(&tree).unwrap();
+    let mut step = m.default_mutation_step(&tree, &cache);
+    let mut nbr_rotations = 0;
+    for i in 0..2000 {
+        let original = tree.clone();
+        let (token, _) = if i % 2 == 0 {
+            m.random_mutate(&mut tree, &mut cache, 2000.0)
+        } else {
+            m.ordered_mutate(&mut tree, &mut cache, &mut step, &EmptySubValueProvider, 2000.0)
+                .unwrap()
+        };
+        assert!(is_approximately_balanced(&tree), "{tree:?}");
+        let (mut original_items, mut new_items) = (vec![], vec![]);
+        items(&original, &mut original_items);
+        items(&tree, &mut new_items);
+        if original_items == new_items && original != tree {
+            nbr_rotations += 1;
+        }
+        m.unmutate(&mut tree, &mut cache, token);
+        assert_eq!(tree, original);
+    }
+    assert!(nbr_rotations > 50, "{nbr_rotations}");
+}
+
+#[test]
+fn test_balanced_tree_mutator_accepts_degenerate_trees() {
+    let m = BalancedTreeMutator::<Tree, _>::new(U8Mutator::default());
+    m.initialize();
+    // a left-leaning chain of 64 nodes
+    let mut tree = Tree::Leaf;
+    for i in 0..64 {
+        tree = Tree::node(tree, i, Tree::Leaf);
+    }
+    assert!(!is_approximately_balanced(&tree));
+    let mut cache = m.validate_value(&tree).unwrap();
+    let mut step = m.default_mutation_step(&tree, &cache);
+    let (token, _) = m
+        .ordered_mutate(&mut tree, &mut cache, &mut step, &EmptySubValueProvider, 2000.0)
+        .unwrap();
+    assert!(is_approximately_balanced(&tree), "{tree:?}");
+    m.unmutate(&mut tree, &mut cache, token);
+    let mut xs = vec![];
+    items(&tree, &mut xs);
+    assert_eq!(xs, (0..64).collect::<Vec<_>>());
+    assert!(!is_approximately_balanced(&tree));
+}
+
+#[test]
+fn test_balanced_tree_mutator_validates_deep_trees() {
+    let m = BalancedTreeMutator::<Tree, _>::new(U8Mutator::default());
+    m.initialize();
+    // a chain that would overflow the stack if it was traversed recursively
+    let mut tree = Tree::Leaf;
+    for i in 0..1_000_000 {
+        tree = Tree::node(tree, i as u8, Tree::Leaf);
+    }
+    assert!(!is_approximately_balanced(&tree));
+    assert!(m.validate_value(&tree).is_some());
+    assert!(m.is_valid(&tree));
+    // dropping the chain recursively would overflow the stack too
+    while let Tree::Node(left, _, _) = tree {
+        tree = *left;
+    }
+}
@@ -0,0 +1,41 @@
+#![allow(clippy::let_unit_value)]
+use fuzzcheck::mutators::bit_vec::BitVecMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{FixedBitSet, Mutator};
+
+#[test]
+fn test_bit_vec_mutator() {
+    let m = BitVecMutator::new(0..=200);
+    test_mutator(m, 100.0, 100.0, false, true, 100, 200);
+    let m = BitVecMutator::new(5..=10);
+    test_mutator(m, 100.0, 100.0, false, true, 100, 200);
+}
+
+#[test]
+fn test_bit_vec_mutator_respects_length_range() {
+    let m = BitVecMutator::new(3..=70);
+    let mut value = FixedBitSet::with_capacity(3);
+    let mut cache = m.validate_value(&value).unwrap();
+    for _ in 0..10_000 {
+        let old_value = value.clone();
+        let (token, cplx) = m.random_mutate(&mut value, &mut cache, 1000.0);
+        assert!((3..=70).contains(&value.len()), "{}", value.len());
+        assert_eq!(cplx, 1.0 + value.len() as f64);
+        if fastrand::bool() {
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, old_value);
+        }
+    }
+    assert!(m.validate_value(&FixedBitSet::with_capacity(2)).is_none());
+    assert!(m.validate_value(&FixedBitSet::with_capacity(71)).is_none());
+}
+
+#[test]
+fn test_fixed_bit_set_ranges() {
+    let mut bits = FixedBitSet::with_capacity(130);
+    bits.insert_range(60..70);
+    assert_eq!(bits.count_ones(), 10);
+    assert!(bits.contains(60) && bits.contains(69) && !bits.contains(70));
+    bits.set_range(62..130, false);
+    assert_eq!(bits.ones().collect::<Vec<_>>(), vec![60, 61]);
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use fuzzcheck::mutators::btree_map::BTreeMapMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[track_caller]
+fn assert_sorted<K: Ord, V>(map: &BTreeMap<K, V>) {
+    assert!(map.keys().zip(map.keys().skip(1)).all(|(a, b)| a < b));
+}
+
+/// Check that the map is equal to the one built again from its entries, which fails if an entry
+/// was moved without updating its position in the tree
+#[track_caller]
+fn assert_consistent<K: Ord + Clone + std::fmt::Debug, V: Clone + PartialEq + std::fmt::Debug>(map: &BTreeMap<K, V>) {
+    assert_sorted(map);
+    let rebuilt = map
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect::<BTreeMap<_, _>>();
+    assert_eq!(*map, rebuilt);
+    for (k, v) in map.iter() {
+        assert_eq!(map.get(k), Some(v));
+    }
+}
+
+#[test]
+fn test_btree_map_mutator() {
+    let m = <BTreeMap<u8, u16>>::default_mutator();
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+    let m = <BTreeMap<Vec<u8>, BTreeMap<bool, u8>>>::default_mutator();
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+    let m = BTreeMapMutator::new(bool::default_mutator(), <()>::default_mutator(), 1..=2);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_btree_map_mutator_keeps_maps_sorted_and_unmutates_exactly() {
+    let m = <BTreeMap<u8, Vec<u8>>>::default_mutator();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    for i in 0..200 {
+        let (original, cplx) = if i % 2 == 0 {
+            m.ordered_arbitrary(&mut step, 300.0).unwrap()
+        } else {
+            m.random_arbitrary(300.0)
+        };
+        assert_sorted(&original);
+        let original_cache = m.validate_value(&original).unwrap();
+        assert_eq!(cplx, m.complexity(&original, &original_cache));
+
+        let mut value = original.clone();
+        let mut cache = original_cache.clone();
+        let mut mutation_step = m.default_mutation_step(&value, &cache);
+        for j in 0..50 {
+            let (token, cplx) = if j % 2 == 0 {
+                m.ordered_mutate(
+                    &mut value,
+                    &mut cache,
+                    &mut mutation_step,
+                    &EmptySubValueProvider,
+                    300.0,
+                )
+                .unwrap()
+            } else {
+                m.random_mutate(&mut value, &mut cache, 300.0)
+            };
+            assert_consistent(&value);
+            let new_cache = m.validate_value(&value).unwrap();
+            assert_eq!(cplx, m.complexity(&value, &new_cache));
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, original);
+            assert_eq!(value.iter().collect::<Vec<_>>(), original.iter().collect::<Vec<_>>());
+            assert_eq!(m.complexity(&value, &cache), m.complexity(&original, &original_cache));
+        }
+    }
+}
+
+#[test]
+fn test_btree_map_mutator_mutates_keys() {
+    let m = BTreeMapMutator::new(u8::default_mutator(), <()>::default_mutator(), 3..=3);
+    m.initialize();
+    let original = BTreeMap::from([(10, ()), (20, ()), (30, ())]);
+    let mut value = original.clone();
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut keys_changed = false;
+    for _ in 0..20 {
+        // insertions and removals are not possible, since the length must stay 3
+        let (token, cplx) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        assert_eq!(value.len(), 3);
+        assert_consistent(&value);
+        let new_cache = m.validate_value(&value).unwrap();
+        assert_eq!(cplx, m.complexity(&value, &new_cache));
+        assert_eq!(m.complexity(&value, &cache), cplx);
+        keys_changed |= value != original;
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+    }
+    assert!(keys_changed);
+}
@@ -0,0 +1,39 @@
+use fuzzcheck::mutators::burst::BurstMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_burst_mutator() {
+    let m = BurstMutator::new(<Vec<u8>>::default_mutator(), 5);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_burst_mutator_round_trip() {
+    let m = BurstMutator::new(<Vec<u16>>::default_mutator(), 5);
+    m.initialize();
+    for _ in 0..100 {
+        let (mut value, _) = m.random_arbitrary(500.0);
+        let original = value.clone();
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        for _ in 0..20 {
+            let Some((token, cplx)) =
+                m.ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 500.0)
+            else {
+                break;
+            };
+            assert_eq!(token.len(), 5);
+            assert_eq!(cplx, m.complexity(&value, &m.validate_value(&value).unwrap()));
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, original);
+        }
+        for _ in 0..20 {
+            let (token, _) = m.random_mutate(&mut value, &mut cache, 500.0);
+            assert_eq!(token.len(), 5);
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, original);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::ffi::CString;
+
+use fuzzcheck::mutators::c_string::{CStringBytesMutator, CStringMutator};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_c_string_mutator() {
+    let m = CString::default_mutator();
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+
+    let m = CStringMutator::new();
+    m.initialize();
+    let mut max_len = 0;
+    for _ in 0..1000 {
+        let (s, _) = m.random_arbitrary(1000.0);
+        max_len = max_len.max(s.as_bytes().len());
+        // the conversion checks that there is no interior NUL
+        assert_eq!(CString::new(s.as_bytes()).unwrap(), s);
+    }
+    assert!(max_len > 10);
+    for s in [
+        CString::default(),
+        CString::new("hello").unwrap(),
+        CString::new(vec![255; 100]).unwrap(),
+    ] {
+        assert!(m.validate_value(&s).is_some());
+    }
+}
+
+#[test]
+fn test_c_string_bytes_mutator() {
+    let m = CStringBytesMutator::new(false);
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+
+    let m = CStringBytesMutator::new(false);
+    m.initialize();
+    for _ in 0..1000 {
+        let (bytes, _) = m.random_arbitrary(1000.0);
+        assert!(CString::new(bytes).is_ok());
+    }
+    // vectors with NUL bytes are accepted, but replaced by vectors without NUL bytes
+    let mut bytes = vec![1, 0, 2];
+    let mut cache = m.validate_value(&bytes).unwrap();
+    for _ in 0..100 {
+        let (token, _) = m.random_mutate(&mut bytes, &mut cache, 1000.0);
+        assert!(!bytes.contains(&0));
+        m.unmutate(&mut bytes, &mut cache, token);
+        assert_eq!(bytes, vec![1, 0, 2]);
+    }
+
+    let m = CStringBytesMutator::new(true);
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+
+    let m = CStringBytesMutator::new(true);
+    m.initialize();
+    let mut nbr_errors = 0;
+    for _ in 0..1000 {
+        let (bytes, _) = m.random_arbitrary(1000.0);
+        if CString::new(bytes).is_err() {
+            nbr_errors += 1;
+        }
+    }
+    assert!((100..500).contains(&nbr_errors), "{nbr_errors}");
+}
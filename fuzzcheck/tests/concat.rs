@@ -0,0 +1,76 @@
+use fuzzcheck::mutators::concat::ConcatMutator;
+use fuzzcheck::mutators::either::Either;
+use fuzzcheck::mutators::fixed_len_vector::FixedLenVecMutator;
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_concat_mutator() {
+    let m = ConcatMutator::with_fixed_sizes(
+        vec![
+            Either::Left(FixedLenVecMutator::new_with_repeated_mutator(U8Mutator::default(), 4)),
+            Either::Right(<Vec<u8>>::default_mutator()),
+        ],
+        vec![4],
+    );
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+}
+
+fn bytes_within(range: std::ops::RangeInclusive<u8>, len: usize) -> FixedLenVecMutator<u8, U8WithinRangeMutator> {
+    FixedLenVecMutator::new((0..len).map(|_| U8WithinRangeMutator::new(range.clone())).collect())
+}
+
+#[test]
+fn test_concat_mutator_sections() {
+    // a header of 2 bytes in 'A'..='Z', then a payload of digits terminated by a 0 byte, then anything
+    let m = ConcatMutator::new(
+        vec![
+            bytes_within(b'A'..=b'Z', 2),
+            bytes_within(b'0'..=b'9', 3),
+            bytes_within(0..=255, 1),
+        ],
+        |bytes| (bytes.len() >= 5).then(|| vec![2, 3, bytes.len() - 5]),
+    );
+    m.initialize();
+    assert!(m.validate_value(&b"AB123\xff".to_vec()).is_some());
+    assert!(m.validate_value(&b"Ab123\xff".to_vec()).is_none());
+    assert!(m.validate_value(&b"AB1x3\xff".to_vec()).is_none());
+    assert!(m.validate_value(&b"AB123".to_vec()).is_none());
+    assert!(m.validate_value(&b"AB".to_vec()).is_none());
+
+    for _ in 0..100 {
+        let (mut x, cplx) = m.random_arbitrary(100.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        assert_eq!(m.complexity(&x, &cache), cplx);
+        for _ in 0..50 {
+            let _ = m.random_mutate(&mut x, &mut cache, 100.0);
+            assert_eq!(x.len(), 6);
+            assert!(x[..2].iter().all(|b| b.is_ascii_uppercase()), "{x:?}");
+            assert!(x[2..5].iter().all(|b| b.is_ascii_digit()), "{x:?}");
+            cache = m.validate_value(&x).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_concat_mutator_complexity_is_sum() {
+    let sections = || {
+        vec![
+            VecMutator::new(U8Mutator::default(), 0..=10),
+            VecMutator::new(U8Mutator::default(), 0..=10),
+        ]
+    };
+    let m = ConcatMutator::with_fixed_sizes(sections(), vec![3]);
+    m.initialize();
+    let vm = VecMutator::new(U8Mutator::default(), 0..=10);
+    vm.initialize();
+    let value = vec![1, 2, 3, 4, 5];
+    let cache = m.validate_value(&value).unwrap();
+    let (a, b) = (vec![1, 2, 3], vec![4, 5]);
+    let expected =
+        vm.complexity(&a, &vm.validate_value(&a).unwrap()) + vm.complexity(&b, &vm.validate_value(&b).unwrap());
+    assert_eq!(m.complexity(&value, &cache), expected);
+}
@@ -0,0 +1,62 @@
+use fuzzcheck::mutators::conditional::ConditionalMutator;
+use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+fn is_numeric(x: &[u8]) -> bool {
+    x.iter().all(u8::is_ascii_digit)
+}
+
+#[test]
+fn test_conditional_mutator() {
+    let m = ConditionalMutator::new(
+        <Vec<u8>>::default_mutator(),
+        VecMutator::new(U8WithinRangeMutator::new(b'0'..=b'9'), 0..=usize::MAX),
+        |x: &Vec<u8>| is_numeric(x),
+        0.5,
+    );
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_conditional_mutator_unmutates_both_branches() {
+    let m = ConditionalMutator::new(
+        <Vec<u8>>::default_mutator(),
+        VecMutator::new(U8WithinRangeMutator::new(b'0'..=b'9'), 1..=10),
+        |x: &Vec<u8>| is_numeric(x),
+        1.0,
+    );
+    m.initialize();
+
+    // the predicate holds, so every mutation is performed by the extra mutator
+    let original = b"123".to_vec();
+    let mut value = original.clone();
+    let mut cache = m.validate_value(&value).unwrap();
+    for _ in 0..1000 {
+        let (token, cplx) = m.random_mutate(&mut value, &mut cache, 1000.0);
+        assert!(is_numeric(&value) && !value.is_empty());
+        assert_eq!(cplx, m.complexity(&value, &cache));
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+        assert_eq!(
+            m.complexity(&value, &cache),
+            m.complexity(&value, &m.validate_value(&value).unwrap())
+        );
+    }
+
+    // the predicate does not hold, so every mutation is performed by the base mutator
+    let original = b"abc".to_vec();
+    let mut value = original.clone();
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut nbr_non_numeric = 0;
+    for _ in 0..1000 {
+        let (token, _) = m.random_mutate(&mut value, &mut cache, 1000.0);
+        if !is_numeric(&value) {
+            nbr_non_numeric += 1;
+        }
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+    }
+    assert!(nbr_non_numeric > 900);
+}
@@ -2,7 +2,7 @@
 use std::collections::HashSet;
 use std::ops::RangeBounds;
 
-use fuzzcheck::mutators::integer_within_range::I8WithinRangeMutator;
+use fuzzcheck::mutators::integer_within_range::{I8WithinRangeMutator, U32WithinRangeMutator};
 use fuzzcheck::mutators::testing_utilities::test_mutator;
 use fuzzcheck::Mutator;
 
@@ -80,3 +80,44 @@ fn test_mutate_constrained_signed_integer_8() {
     set.sort_unstable();
     println!("{} {set:?}", set.len());
 }
+
+#[test]
+fn test_log_scale_constrained_integer() {
+    let m = U32WithinRangeMutator::log_scale(..);
+    // each number of bits is about as likely, so that small values are common
+    let mut small = 0;
+    for _ in 0..10_000 {
+        if m.random_arbitrary(100.0).0 < 1 << 16 {
+            small += 1;
+        }
+    }
+    assert!((4000..6000).contains(&small), "{small}");
+
+    let mut step = 0;
+    let first = (0..8)
+        .map(|_| m.ordered_arbitrary(&mut step, 100.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(first, vec![0, u32::MAX, 1, 2, 3, 4, 7, 8]);
+
+    let m = U32WithinRangeMutator::log_scale(1000..=1_000_000);
+    assert!(m.validate_value(&1000).is_some());
+    assert!(m.validate_value(&999).is_none());
+    for _ in 0..1000 {
+        let x = m.random_arbitrary(100.0).0;
+        assert!((1000..=1_000_000).contains(&x), "{x}");
+    }
+    test_mutator(m, 1000., 1000., false, true, 100, 100);
+
+    // every value is still generated exactly once by ordered_arbitrary
+    let m = I8WithinRangeMutator::log_scale(-100..50);
+    let mut step = 0;
+    let mut all_generated = HashSet::new();
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 100.0) {
+        assert!(all_generated.insert(x));
+    }
+    assert_eq!(all_generated.len(), 150);
+    let m = I8WithinRangeMutator::log_scale(3..=3);
+    let mut step = 0;
+    assert_eq!(m.ordered_arbitrary(&mut step, 100.0), Some((3, 8.0)));
+    assert_eq!(m.ordered_arbitrary(&mut step, 100.0), None);
+}
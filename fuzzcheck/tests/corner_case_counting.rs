@@ -0,0 +1,53 @@
+use fuzzcheck::mutators::corner_case_counting::CornerCaseCountingMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+fn classify_i8(x: &i8) -> Option<&'static str> {
+    match *x {
+        i8::MIN => Some("MIN"),
+        i8::MAX => Some("MAX"),
+        0 => Some("zero"),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_corner_case_counting_mutator() {
+    let m = CornerCaseCountingMutator::new(<Vec<i8>>::default_mutator(), |v: &Vec<i8>| {
+        v.is_empty().then_some("empty")
+    });
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_corner_case_counting_mutator_counts_labels() {
+    let m = CornerCaseCountingMutator::new(i8::default_mutator(), classify_i8);
+    m.initialize();
+    assert!(m.corner_case_counts().is_empty());
+
+    // the ordered arbitrary values of an i8 mutator are all distinct
+    let mut step = m.default_arbitrary_step();
+    let mut nbr_values = 0;
+    while m.ordered_arbitrary(&mut step, 8.0).is_some() {
+        nbr_values += 1;
+    }
+    assert_eq!(nbr_values, 256);
+    let counts = m.corner_case_counts();
+    assert_eq!(counts.len(), 3);
+    assert_eq!(counts["MIN"], 1);
+    assert_eq!(counts["MAX"], 1);
+    assert_eq!(counts["zero"], 1);
+
+    // mutated values are counted too
+    let mut value = 1;
+    m.validate_value(&value).unwrap();
+    let mut nbr_zeros = 0;
+    for _ in 0..1000 {
+        let (token, _) = m.random_mutate(&mut value, &mut (), 8.0);
+        if value == 0 {
+            nbr_zeros += 1;
+        }
+        m.unmutate(&mut value, &mut (), token);
+    }
+    assert_eq!(m.corner_case_counts()["zero"], 1 + nbr_zeros);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use fuzzcheck::mutators::date_time::{DateComponentsMutator, NaiveDateMutator};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_date_time_mutators() {
+    test_mutator(NaiveDate::default_mutator(), 100.0, 100.0, false, true, 100, 100);
+    test_mutator(NaiveDateTime::default_mutator(), 200.0, 200.0, false, true, 100, 100);
+    test_mutator(
+        DateComponentsMutator::new(1900..=2100, false),
+        100.0,
+        100.0,
+        false,
+        true,
+        100,
+        100,
+    );
+    test_mutator(
+        DateComponentsMutator::new(1900..=2100, true),
+        100.0,
+        100.0,
+        false,
+        true,
+        100,
+        100,
+    );
+}
+
+#[test]
+fn test_naive_date_mutator_boundaries() {
+    let m = NaiveDateMutator::new(1999..=2001);
+    m.initialize();
+    assert!(m
+        .validate_value(&NaiveDate::from_ymd_opt(2000, 2, 29).unwrap())
+        .is_some());
+    assert!(m
+        .validate_value(&NaiveDate::from_ymd_opt(2002, 1, 1).unwrap())
+        .is_none());
+
+    let mut leap_days = 0;
+    for _ in 0..200 {
+        let (mut date, _) = m.random_arbitrary(100.0);
+        let mut cache = m.validate_value(&date).unwrap();
+        let mut step = m.default_mutation_step(&date, &cache);
+        for _ in 0..50 {
+            let old_date = date;
+            let Some((token, _)) = m.ordered_mutate(&mut date, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            else {
+                break;
+            };
+            assert!((1999..=2001).contains(&date.year()));
+            if date.month() == 2 && date.day() == 29 {
+                leap_days += 1;
+            }
+            m.unmutate(&mut date, &mut cache, token);
+            assert_eq!(date, old_date);
+        }
+    }
+    assert!(leap_days > 0);
+}
+
+#[test]
+fn test_date_components_mutator() {
+    let valid = DateComponentsMutator::new(2023..=2024, false);
+    let invalid = DateComponentsMutator::new(2023..=2024, true);
+    valid.initialize();
+    invalid.initialize();
+    assert!(valid.validate_value(&(2024, 2, 29)).is_some());
+    assert!(valid.validate_value(&(2023, 2, 29)).is_none());
+    assert!(valid.validate_value(&(2023, 13, 1)).is_none());
+    assert!(invalid.validate_value(&(2023, 2, 29)).is_some());
+    assert!(invalid.validate_value(&(2023, 13, 0)).is_some());
+
+    let mut nbr_invalid = 0;
+    for _ in 0..1000 {
+        let ((year, month, day), _) = valid.random_arbitrary(100.0);
+        assert!(NaiveDate::from_ymd_opt(year, month, day).is_some());
+        let ((year, month, day), _) = invalid.random_arbitrary(100.0);
+        if NaiveDate::from_ymd_opt(year, month, day).is_none() {
+            nbr_invalid += 1;
+        }
+    }
+    assert!(nbr_invalid > 0);
+}
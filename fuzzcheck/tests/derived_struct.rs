@@ -16,3 +16,102 @@ fn test_derived_struct() {
     let mutator = <Vec<SampleStruct<u8, u8>>>::default_mutator();
     test_mutator(mutator, 1000., 1000., false, true, 100, 100);
 }
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+struct FlagAndData {
+    flag: bool,
+    data: Vec<u8>,
+}
+
+#[test]
+fn test_derived_struct_field_selection_is_weighted_by_complexity() {
+    use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+    use fuzzcheck::Mutator;
+
+    let mutator = FlagAndData::default_mutator();
+    mutator.initialize();
+    let mut value = FlagAndData {
+        flag: false,
+        data: vec![0; 16],
+    };
+    let mut cache = mutator.validate_value(&value).unwrap();
+    let mut step = mutator.default_mutation_step(&value, &cache);
+    let (mut flag_mutations, mut data_mutations) = (0, 0);
+    for i in 0..2000 {
+        let original = value.clone();
+        let (token, _) = if i % 2 == 0 {
+            mutator.random_mutate(&mut value, &mut cache, 1000.)
+        } else {
+            mutator
+                .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 1000.)
+                .unwrap()
+        };
+        if value.flag != original.flag {
+            flag_mutations += 1;
+        }
+        if value.data != original.data {
+            data_mutations += 1;
+        }
+        mutator.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+    }
+    assert!(
+        data_mutations > 10 * flag_mutations,
+        "data mutated {} times, flag mutated {} times",
+        data_mutations,
+        flag_mutations
+    );
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+struct ThreeFields {
+    a: Vec<u8>,
+    b: Vec<u8>,
+    c: Vec<u8>,
+}
+
+#[test]
+fn test_derived_struct_expensive_effort_hint() {
+    use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+    use fuzzcheck::{EffortHint, Mutator};
+
+    let mutator = ThreeFields::default_mutator();
+    mutator.initialize();
+    mutator.set_effort_hint(EffortHint::Expensive);
+    let mut value = ThreeFields {
+        a: vec![1; 8],
+        b: vec![2; 8],
+        c: vec![3; 8],
+    };
+    let mut cache = mutator.validate_value(&value).unwrap();
+    let mut step = mutator.default_mutation_step(&value, &cache);
+    let mut nbr_stacked = 0;
+    for _ in 0..200 {
+        let original = value.clone();
+        let (token, _) = mutator
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 1000.)
+            .unwrap();
+        let nbr_changed = [value.a != original.a, value.b != original.b, value.c != original.c]
+            .into_iter()
+            .filter(|&changed| changed)
+            .count();
+        if nbr_changed >= 2 {
+            nbr_stacked += 1;
+        }
+        mutator.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+    }
+    // the crossover mutations and those that leave a field unchanged are not stacked
+    assert!(nbr_stacked > 100, "{nbr_stacked} stacked mutations");
+}
+
+#[test]
+fn test_derived_struct_rng_state() {
+    use fuzzcheck::Mutator;
+    let m = <Vec<(SampleStruct<u8, Option<u16>>, bool, Vec<u8>)>>::default_mutator();
+    let state = m.rng_state().unwrap();
+    let first = (0..20).map(|_| m.random_arbitrary(500.0).0).collect::<Vec<_>>();
+    m.set_rng_state(&state);
+    let second = (0..20).map(|_| m.random_arbitrary(500.0).0).collect::<Vec<_>>();
+    assert_eq!(first, second);
+}
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use fuzzcheck::mutators::duration::DurationMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_duration_mutator() {
+    let m = Duration::default_mutator();
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_duration_mutator_interesting_values_come_first() {
+    let m = DurationMutator::default();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    let values = (0..11)
+        .map(|_| m.ordered_arbitrary(&mut step, 96.0).unwrap().0)
+        .collect::<HashSet<_>>();
+    for interesting in [
+        Duration::ZERO,
+        Duration::from_nanos(1),
+        Duration::from_secs(1),
+        Duration::MAX,
+    ] {
+        assert!(values.contains(&interesting));
+    }
+    assert_eq!(m.complexity(&Duration::MAX, &()), 96.0);
+}
+
+// `Duration::new` panics or carries over to the seconds if the nanoseconds are not below one
+// billion, so the invariant is checked by rebuilding each duration from its parts
+#[track_caller]
+fn assert_nanos_invariant(d: Duration) {
+    assert!(d.subsec_nanos() < 1_000_000_000);
+    assert_eq!(Duration::new(d.as_secs(), d.subsec_nanos()), d);
+}
+
+#[test]
+fn test_duration_mutator_keeps_nanos_invariant() {
+    let m = DurationMutator::default();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    for i in 0..200 {
+        let (original, _) = if i % 2 == 0 {
+            m.ordered_arbitrary(&mut step, 96.0).unwrap()
+        } else {
+            m.random_arbitrary(96.0)
+        };
+        assert_nanos_invariant(original);
+        let mut value = original;
+        let mut mutation_step = m.default_mutation_step(&value, &());
+        let (mut secs_changed, mut nanos_changed) = (false, false);
+        for j in 0..100 {
+            let (token, _) = if j % 2 == 0 {
+                m.ordered_mutate(&mut value, &mut (), &mut mutation_step, &EmptySubValueProvider, 96.0)
+                    .unwrap()
+            } else {
+                m.random_mutate(&mut value, &mut (), 96.0)
+            };
+            assert_nanos_invariant(value);
+            secs_changed |= value.as_secs() != original.as_secs() && value.subsec_nanos() == original.subsec_nanos();
+            nanos_changed |= value.as_secs() == original.as_secs() && value.subsec_nanos() != original.subsec_nanos();
+            m.unmutate(&mut value, &mut (), token);
+            assert_eq!(value, original);
+            assert_nanos_invariant(value);
+        }
+        assert!(secs_changed && nanos_changed);
+    }
+}
+
+#[test]
+fn test_duration_mutator_rng_state() {
+    let m = Duration::default_mutator();
+    m.initialize();
+    let state = m.rng_state().unwrap();
+    let generate = || {
+        (0..50)
+            .map(|_| {
+                let (mut value, _) = m.random_arbitrary(100.0);
+                m.random_mutate(&mut value, &mut (), 100.0);
+                value
+            })
+            .collect::<Vec<_>>()
+    };
+    let first = generate();
+    m.set_rng_state(&state);
+    assert_eq!(generate(), first);
+}
@@ -0,0 +1,80 @@
+#![feature(coverage_attribute)]
+
+use std::collections::HashSet;
+
+use fuzzcheck::mutators::exhaustive::ExhaustiveMutator;
+use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+use fuzzcheck::DefaultMutator;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+#[test]
+fn test_exhaustive_total_count() {
+    assert_eq!(ExhaustiveMutator::new(bool::default_mutator()).total_count(), Some(2));
+    assert_eq!(ExhaustiveMutator::new(u8::default_mutator()).total_count(), Some(256));
+    assert_eq!(
+        ExhaustiveMutator::new(u16::default_mutator()).total_count(),
+        Some(65536)
+    );
+    assert_eq!(
+        ExhaustiveMutator::new(U8WithinRangeMutator::new(0..=9)).total_count(),
+        Some(10)
+    );
+    assert_eq!(
+        ExhaustiveMutator::<Direction, _>::new(Direction::default_mutator()).total_count(),
+        Some(4)
+    );
+
+    assert_eq!(ExhaustiveMutator::new(u32::default_mutator()).total_count(), None);
+    assert_eq!(
+        ExhaustiveMutator::with_max_count(u8::default_mutator(), 255).total_count(),
+        None
+    );
+    // tuple mutators are never exhausted
+    assert_eq!(
+        ExhaustiveMutator::<(bool, bool), _>::with_max_count(<(bool, bool)>::default_mutator(), 100).total_count(),
+        None
+    );
+}
+
+#[test]
+fn test_exhaustive_values_are_distinct() {
+    let m = ExhaustiveMutator::new(i16::default_mutator());
+    let values = m.values().collect::<HashSet<_>>();
+    assert_eq!(values.len(), 65536);
+
+    let m = ExhaustiveMutator::<Direction, _>::new(Direction::default_mutator());
+    let values = m.values().collect::<Vec<_>>();
+    assert_eq!(
+        values,
+        [Direction::North, Direction::East, Direction::South, Direction::West]
+    );
+}
+
+#[test]
+fn test_exhaustive_find_failure() {
+    let m = ExhaustiveMutator::new(u8::default_mutator());
+    assert_eq!(m.find_failure(|x| x.checked_mul(2).is_some() || *x > 127), None);
+    assert_eq!(m.find_failure(|x| *x != 200), Some(200));
+    // panics are test failures too
+    assert_eq!(
+        m.find_failure(|x| {
+            assert!(*x != 3);
+            true
+        }),
+        Some(3)
+    );
+
+    let m = ExhaustiveMutator::new(U8WithinRangeMutator::new(0..=9));
+    assert_eq!(m.find_failure(|x| *x != 7), Some(7));
+    assert_eq!(m.find_failure(|x| *x < 10), None);
+
+    let m = ExhaustiveMutator::<Direction, _>::new(Direction::default_mutator());
+    assert_eq!(m.find_failure(|d| *d != Direction::West), Some(Direction::West));
+}
@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+static NBR_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+#[fuzzcheck(
+    complexity = "Message::complexity",
+    max_complexity = "Message::max_complexity",
+    min_complexity = "Message::min_complexity"
+)]
+pub struct Message {
+    header: u8,
+    payload: Vec<u8>,
+}
+
+impl Message {
+    #[coverage(off)]
+    fn complexity(&self, _cplx: f64) -> f64 {
+        NBR_CALLS.fetch_add(1, Ordering::Relaxed);
+        1.0 + self.payload.len() as f64
+    }
+    #[coverage(off)]
+    fn max_complexity(_cplx: f64) -> f64 {
+        f64::INFINITY
+    }
+    #[coverage(off)]
+    fn min_complexity(_cplx: f64) -> f64 {
+        1.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+#[fuzzcheck(complexity = "size_of_choice")]
+pub enum Choice {
+    A(u8),
+    B(Vec<bool>),
+}
+
+#[coverage(off)]
+fn size_of_choice(choice: &Choice, cplx: f64) -> f64 {
+    match choice {
+        Choice::A(_) => 1.0,
+        Choice::B(_) => cplx,
+    }
+}
+
+// its complexity always exceeds the maximum complexity given to the mutator
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+#[fuzzcheck(complexity = "always_too_complex")]
+pub struct TooComplex {
+    payload: Vec<u8>,
+}
+
+#[coverage(off)]
+fn always_too_complex(_value: &TooComplex, _cplx: f64) -> f64 {
+    f64::INFINITY
+}
+
+#[test]
+#[coverage(off)]
+fn test_custom_complexity_is_used() {
+    let m = Message::default_mutator();
+    assert_eq!(m.min_complexity(), 1.0);
+    assert_eq!(m.max_complexity(), f64::INFINITY);
+
+    let before = NBR_CALLS.load(Ordering::Relaxed);
+    let (value, cplx) = m.random_arbitrary(100.0);
+    assert!(NBR_CALLS.load(Ordering::Relaxed) > before);
+    assert_eq!(cplx, 1.0 + value.payload.len() as f64);
+    let cache = m.validate_value(&value).unwrap();
+    assert_eq!(m.complexity(&value, &cache), cplx);
+
+    let m = Choice::default_mutator();
+    let value = Choice::A(200);
+    let cache = m.validate_value(&value).unwrap();
+    assert_eq!(m.complexity(&value, &cache), 1.0);
+}
+
+#[test]
+#[coverage(off)]
+fn test_custom_complexity_ordered_arbitrary_respects_max_cplx() {
+    let m = Message::default_mutator();
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..100 {
+        let Some((value, cplx)) = m.ordered_arbitrary(&mut step, 5.0) else {
+            break;
+        };
+        assert!(cplx <= 5.0);
+        assert!(value.payload.len() <= 4);
+    }
+}
+
+#[test]
+#[coverage(off)]
+fn test_custom_complexity_mutator() {
+    test_mutator(Message::default_mutator(), 100.0, 100.0, false, true, 100, 100);
+    test_mutator(Choice::default_mutator(), 100.0, 100.0, false, true, 100, 100);
+}
+
+#[test]
+#[coverage(off)]
+fn test_custom_complexity_ordered_methods_give_up() {
+    let m = TooComplex::default_mutator();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    assert!(m.ordered_arbitrary(&mut step, 100.0).is_none());
+
+    let mut value = TooComplex { payload: vec![1, 2, 3] };
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let result = m.ordered_mutate(
+        &mut value,
+        &mut cache,
+        &mut step,
+        &fuzzcheck::subvalue_provider::EmptySubValueProvider,
+        100.0,
+    );
+    assert!(result.is_none());
+    assert_eq!(value.payload, vec![1, 2, 3]);
+}
@@ -1,4 +1,5 @@
 #![allow(clippy::nonstandard_macro_braces, clippy::too_many_arguments)]
+mod custom_complexity;
 mod empty_structs;
 mod one_field_structs;
 mod structs_with_generic_type_params;
@@ -14,3 +15,6 @@ mod enums_with_one_item_one_field;
 
 mod recursive_enum;
 mod recursive_struct;
+
+#[cfg(feature = "regex_grammar")]
+mod regex_string_field;
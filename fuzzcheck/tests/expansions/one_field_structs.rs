@@ -8,6 +8,10 @@ pub struct Y {
     x: bool,
 }
 
+#[derive(Clone, DefaultMutator)]
+#[repr(transparent)]
+pub struct Z(Vec<u8>);
+
 #[cfg(test)]
 mod test {
     use fuzzcheck::Mutator;
@@ -22,4 +26,26 @@ mod test {
         let (_y, _) = m.random_arbitrary(10.0);
         // assert!(false, "{}", y.x);
     }
+
+    #[test]
+    #[coverage(off)]
+    fn test_transparent_newtype_complexity() {
+        let m = Z::default_mutator();
+        let inner = <Vec<u8>>::default_mutator();
+        m.initialize();
+        inner.initialize();
+        assert_eq!(m.min_complexity(), inner.min_complexity());
+        assert_eq!(m.max_complexity(), inner.max_complexity());
+        for _ in 0..100 {
+            let (mut z, cplx) = m.random_arbitrary(100.0);
+            let inner_cache = inner.validate_value(&z.0).unwrap();
+            assert_eq!(cplx, inner.complexity(&z.0, &inner_cache));
+
+            let mut cache = m.validate_value(&z).unwrap();
+            let _ = m.random_mutate(&mut z, &mut cache, 100.0);
+            let cache = m.validate_value(&z).unwrap();
+            let inner_cache = inner.validate_value(&z.0).unwrap();
+            assert_eq!(m.complexity(&z, &cache), inner.complexity(&z.0, &inner_cache));
+        }
+    }
 }
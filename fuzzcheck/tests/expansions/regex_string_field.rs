@@ -0,0 +1,69 @@
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+pub struct Account {
+    #[field_mutator(regex = "[a-z]{1,8}@[a-z]{1,8}\\.(com|org)")]
+    email: String,
+    age: u8,
+}
+
+#[coverage(off)]
+fn is_email(s: &str) -> bool {
+    let Some((user, domain)) = s.split_once('@') else {
+        return false;
+    };
+    let Some(host) = domain.strip_suffix(".com").or_else(|| domain.strip_suffix(".org")) else {
+        return false;
+    };
+    [user, host]
+        .iter()
+        .all(|part| (1..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+#[test]
+#[coverage(off)]
+fn test_regex_field_mutator() {
+    let m = Account::default_mutator();
+    test_mutator(m, 1000.0, 1000.0, false, true, 50, 50);
+
+    let m = Account::default_mutator();
+    m.initialize();
+    for _ in 0..100 {
+        let (mut x, _) = m.random_arbitrary(1000.0);
+        assert!(is_email(&x.email), "{}", x.email);
+        let mut cache = m.validate_value(&x).unwrap();
+        let mut step = m.default_mutation_step(&x, &cache);
+        for _ in 0..50 {
+            if let Some((token, _)) = m.ordered_mutate(
+                &mut x,
+                &mut cache,
+                &mut step,
+                &fuzzcheck::subvalue_provider::EmptySubValueProvider,
+                1000.0,
+            ) {
+                assert!(is_email(&x.email), "{}", x.email);
+                m.unmutate(&mut x, &mut cache, token);
+            }
+        }
+    }
+}
+
+#[test]
+#[coverage(off)]
+fn test_regex_field_validate_value() {
+    let m = Account::default_mutator();
+    m.initialize();
+    let valid = Account {
+        email: "abc@xyz.org".to_owned(),
+        age: 3,
+    };
+    assert!(m.validate_value(&valid).is_some());
+    for email in ["", "abc@xyz.net", "ABC@xyz.com", "abcdefghi@xyz.com", "abc@xyz.com "] {
+        let invalid = Account {
+            email: email.to_owned(),
+            age: 3,
+        };
+        assert!(m.validate_value(&invalid).is_none(), "{email}");
+    }
+}
@@ -0,0 +1,67 @@
+use fuzzcheck::mutators::fixed_point::FixedPointMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+/// A decimal number with a fixed scale of 2
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Cents(i64);
+
+#[test]
+fn test_fixed_point_mutator() {
+    let m = FixedPointMutator::new(2, .., Cents, |x: &Cents| Some(x.0));
+    test_mutator(m, 65.0, 65.0, false, true, 100, 200);
+    let m = FixedPointMutator::new(2, .., Cents, |x: &Cents| Some(x.0));
+    test_mutator(m, 10.0, 10.0, false, true, 100, 200);
+    let m = FixedPointMutator::new(4, 5_000..=20_000, Cents, |x: &Cents| Some(x.0));
+    test_mutator(m, 65.0, 65.0, false, true, 100, 200);
+}
+
+#[test]
+fn test_fixed_point_interesting_values() {
+    // only the values between -1000.00 and 1000.00 are valid
+    let m = FixedPointMutator::new(2, -100_000..=100_000, Cents, |x: &Cents| Some(x.0));
+    assert!(m.validate_value(&Cents(100_000)).is_some());
+    assert!(m.validate_value(&Cents(100_001)).is_none());
+
+    let mut step = m.default_arbitrary_step();
+    let first = (0..8)
+        .map(|_| m.ordered_arbitrary(&mut step, 65.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(first[..5], [Cents(0), Cents(1), Cents(-1), Cents(100), Cents(-100)]);
+    assert!(first.contains(&Cents(50)));
+
+    let mut value = Cents(1234);
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut mutated = vec![];
+    for _ in 0..30 {
+        let (token, cplx) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 65.0)
+            .unwrap();
+        assert_eq!(cplx, m.complexity(&value, &cache));
+        mutated.push(value.clone());
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, Cents(1234));
+    }
+    // 12.00, 12.50, 13.00, 12.35, 12.33
+    for expected in [1200, 1250, 1300, 1235, 1233] {
+        assert!(mutated.contains(&Cents(expected)), "{expected} was not generated");
+    }
+
+    // the complexity is the bit width of the mantissa
+    assert_eq!(m.complexity(&Cents(0), &0), 1.0);
+    assert_eq!(m.complexity(&Cents(-4), &-4), 4.0);
+    let mut value = Cents(0);
+    let mut cache = m.validate_value(&value).unwrap();
+    for _ in 0..1000 {
+        let _ = m.random_mutate(&mut value, &mut cache, 65.0);
+        assert!(m.validate_value(&value).is_some());
+        assert_eq!(cache, value.0);
+    }
+    for _ in 0..100 {
+        let (x, cplx) = m.random_arbitrary(8.0);
+        assert!(cplx <= 8.0);
+        assert!(x.0.abs() < 128);
+    }
+}
@@ -0,0 +1,110 @@
+use fuzzcheck::mutators::float::{F32Mutator, F64Mutator};
+use fuzzcheck::mutators::map::MapMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+// floats are not `Eq`, so the mutators are tested on the bits of the values
+#[test]
+fn test_float_mutators() {
+    let m = MapMutator::new(
+        f64::default_mutator(),
+        |x: &u64| Some(f64::from_bits(*x)),
+        |x: &f64| x.to_bits(),
+        |_, cplx| cplx,
+    );
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+    let m = MapMutator::new(
+        f32::default_mutator(),
+        |x: &u32| Some(f32::from_bits(*x)),
+        |x: &f32| x.to_bits(),
+        |_, cplx| cplx,
+    );
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_float_arbitrary_interesting_values_first() {
+    let m = F64Mutator::default();
+    let mut step = m.default_arbitrary_step();
+    let values = (0..14)
+        .map(|_| m.ordered_arbitrary(&mut step, 64.0).unwrap())
+        .collect::<Vec<_>>();
+    assert!(values.iter().all(|(_, cplx)| *cplx == 64.0));
+    let values = values.into_iter().map(|(x, _)| x).collect::<Vec<_>>();
+    assert_eq!(values[0].to_bits(), 0.0f64.to_bits());
+    assert_eq!(values[1].to_bits(), (-0.0f64).to_bits());
+    assert!(values.contains(&1.0) && values.contains(&-1.0));
+    assert!(values.iter().any(|x| x.is_nan()));
+    assert!(values.contains(&f64::INFINITY) && values.contains(&f64::NEG_INFINITY));
+    assert!(values.iter().any(|x| x.is_subnormal()));
+    assert!(values.contains(&f64::MAX) && values.contains(&f64::MIN));
+
+    // then arbitrary bit patterns
+    let more = (0..1000)
+        .map(|_| m.ordered_arbitrary(&mut step, 64.0).unwrap().0.to_bits())
+        .collect::<std::collections::HashSet<_>>();
+    assert!(more.len() > 990);
+    assert!(m.ordered_arbitrary(&mut m.default_arbitrary_step(), 63.0).is_none());
+
+    let m = F32Mutator::default();
+    let (x, cplx) = m.ordered_arbitrary(&mut m.default_arbitrary_step(), 32.0).unwrap();
+    assert_eq!((x, cplx), (0.0, 32.0));
+}
+
+#[test]
+fn test_float_ordered_mutate_nudges_and_flips_bits() {
+    let m = F64Mutator::default();
+    let mut value = 1.0f64;
+    m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &());
+    let mut mutate = |value: &mut f64| {
+        let (token, cplx) = m
+            .ordered_mutate(value, &mut (), &mut step, &EmptySubValueProvider, 64.0)
+            .unwrap();
+        assert_eq!(cplx, 64.0);
+        let new_value = *value;
+        m.unmutate(value, &mut (), token);
+        assert_eq!(*value, 1.0);
+        new_value
+    };
+    // nudges by ±1, ±2, ±3, ±4 ULPs
+    let ulp = f64::EPSILON;
+    let expected = [
+        1.0 + ulp,
+        1.0 - ulp / 2.0,
+        1.0 + 2.0 * ulp,
+        1.0 - ulp,
+        1.0 + 3.0 * ulp,
+        1.0 - 3.0 * ulp / 2.0,
+        1.0 + 4.0 * ulp,
+        1.0 - 2.0 * ulp,
+    ];
+    for expected in expected {
+        assert_eq!(mutate(&mut value), expected);
+    }
+    // then flips the sign, exponent, and mantissa bits
+    assert_eq!(mutate(&mut value), -1.0);
+    for bit in 1..64 {
+        let flipped = mutate(&mut value);
+        assert_eq!((flipped.to_bits() ^ 1.0f64.to_bits()).leading_zeros(), bit);
+    }
+    // then tries the interesting values
+    assert_eq!(mutate(&mut value), 0.0);
+
+    // nudging crosses zero and reaches the infinities
+    let mut value = -0.0f64;
+    let mut step = 0;
+    m.ordered_mutate(&mut value, &mut (), &mut step, &EmptySubValueProvider, 64.0)
+        .unwrap();
+    assert_eq!(value.to_bits(), 0.0f64.to_bits());
+    let mut value = -0.0f64;
+    m.ordered_mutate(&mut value, &mut (), &mut step, &EmptySubValueProvider, 64.0)
+        .unwrap();
+    assert_eq!(value, -f64::from_bits(1));
+    let mut value = f32::MAX;
+    let m = F32Mutator::default();
+    m.ordered_mutate(&mut value, &mut (), &mut 0, &EmptySubValueProvider, 32.0)
+        .unwrap();
+    assert_eq!(value, f32::INFINITY);
+}
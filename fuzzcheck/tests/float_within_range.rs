@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use fuzzcheck::mutators::float_within_range::{F32WithinRangeMutator, F64WithinRangeMutator};
+use fuzzcheck::mutators::map::MapMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+#[track_caller]
+fn assert_within_range(m: &F64WithinRangeMutator, start: f64, end: f64) {
+    let check = |x: f64| {
+        assert!(!x.is_nan());
+        assert!(start <= x && x <= end, "{x} is not within {start}..={end}");
+        assert!(m.validate_value(&x).is_some());
+    };
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..1000 {
+        let Some((x, _)) = m.ordered_arbitrary(&mut step, 64.0) else {
+            break;
+        };
+        check(x);
+    }
+    for _ in 0..1000 {
+        let (mut x, _) = m.random_arbitrary(64.0);
+        check(x);
+        let mut step = m.default_mutation_step(&x, &());
+        for _ in 0..20 {
+            let Some((token, _)) = m.ordered_mutate(&mut x, &mut (), &mut step, &EmptySubValueProvider, 64.0) else {
+                break;
+            };
+            check(x);
+            m.unmutate(&mut x, &mut (), token);
+        }
+        m.random_mutate(&mut x, &mut (), 64.0);
+        check(x);
+    }
+}
+
+#[test]
+fn test_float_within_range_values_are_within_range() {
+    let cases = [
+        (0.0, 1.0),
+        (-1e10, 1e-3),
+        (f64::MIN, f64::MAX),
+        (f64::NEG_INFINITY, f64::INFINITY),
+        (1.0, f64::INFINITY),
+        (f64::NEG_INFINITY, -5.0),
+        (1e-310, 1e-300),
+        (2.5, 2.5),
+        (1.0, 1.0 + 3.0 * f64::EPSILON),
+    ];
+    for (start, end) in cases {
+        assert_within_range(&F64WithinRangeMutator::new(start..=end), start, end);
+    }
+    assert_within_range(&F64WithinRangeMutator::new(..), f64::NEG_INFINITY, f64::INFINITY);
+    // the excluded end is never produced
+    let m = F64WithinRangeMutator::new(0.0..1.0);
+    assert_within_range(&m, 0.0, 1.0 - f64::EPSILON / 2.0);
+    assert!(m.validate_value(&1.0).is_none());
+}
+
+#[test]
+fn test_float_within_range_validate_value() {
+    let m = F64WithinRangeMutator::new(-1.0..=1.0);
+    assert!(m.validate_value(&-1.0).is_some());
+    assert!(m.validate_value(&1.0).is_some());
+    assert!(m.validate_value(&-0.0).is_some());
+    assert!(m.validate_value(&1.0000001).is_none());
+    assert!(m.validate_value(&f64::NAN).is_none());
+    assert!(m.validate_value(&f64::INFINITY).is_none());
+
+    let m = F64WithinRangeMutator::new(..);
+    assert!(m.validate_value(&f64::NEG_INFINITY).is_some());
+    assert!(m.validate_value(&f64::NAN).is_none());
+}
+
+#[test]
+fn test_float_within_range_ordered_arbitrary() {
+    // the bounds first, then the midpoints
+    let m = F64WithinRangeMutator::new(-2.0..=6.0);
+    let mut step = m.default_arbitrary_step();
+    let values = (0..7)
+        .map(|_| m.ordered_arbitrary(&mut step, 64.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert_eq!(values, [-2.0, 6.0, 2.0, 0.0, 4.0, -1.0, 1.0]);
+
+    // a single point
+    let m = F64WithinRangeMutator::new(2.5..=2.5);
+    let mut step = m.default_arbitrary_step();
+    assert_eq!(m.ordered_arbitrary(&mut step, 64.0), Some((2.5, 64.0)));
+    assert_eq!(m.ordered_arbitrary(&mut step, 64.0), None);
+    let mut x = 2.5;
+    assert!(m
+        .ordered_mutate(&mut x, &mut (), &mut 0, &EmptySubValueProvider, 64.0)
+        .is_none());
+
+    // a range containing few floats is enumerated entirely
+    let m = F64WithinRangeMutator::new(1.0..=1.0 + 3.0 * f64::EPSILON);
+    let mut step = m.default_arbitrary_step();
+    let mut values = HashSet::new();
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 64.0) {
+        assert!(values.insert(x.to_bits()));
+    }
+    assert_eq!(values.len(), 4);
+
+    // infinite bounds
+    let m = F32WithinRangeMutator::new(..);
+    let mut step = m.default_arbitrary_step();
+    let (start, _) = m.ordered_arbitrary(&mut step, 32.0).unwrap();
+    let (end, _) = m.ordered_arbitrary(&mut step, 32.0).unwrap();
+    assert_eq!((start, end), (f32::NEG_INFINITY, f32::INFINITY));
+    let (mid, _) = m.ordered_arbitrary(&mut step, 32.0).unwrap();
+    assert!(mid.is_finite());
+}
+
+#[test]
+#[should_panic]
+fn test_float_within_range_empty_range() {
+    F64WithinRangeMutator::new(1.0..1.0);
+}
+
+#[test]
+#[should_panic]
+fn test_float_within_range_empty_range_of_zeros() {
+    F64WithinRangeMutator::new(0.0..0.0);
+}
+
+#[test]
+#[should_panic]
+fn test_float_within_range_empty_range_of_signed_zeros() {
+    F64WithinRangeMutator::new(-0.0..0.0);
+}
+
+#[test]
+#[should_panic]
+fn test_float_within_range_reversed_zeros() {
+    F32WithinRangeMutator::new(0.0..=-0.0);
+}
+
+#[test]
+fn test_float_within_range_zeros() {
+    let m = F64WithinRangeMutator::new(-0.0..=0.0);
+    assert!(m.validate_value(&-0.0).is_some());
+    assert!(m.validate_value(&0.0).is_some());
+    let mut step = m.default_arbitrary_step();
+    let mut values = vec![];
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 64.0) {
+        values.push(x.to_bits());
+    }
+    values.sort();
+    assert_eq!(values, [0.0f64.to_bits(), (-0.0f64).to_bits()]);
+
+    // the zero of the other sign is outside of the range
+    let m = F64WithinRangeMutator::new(0.0..=1.0);
+    assert!(m.validate_value(&-0.0).is_none());
+    let m = F64WithinRangeMutator::new(-1.0..=-0.0);
+    assert!(m.validate_value(&0.0).is_none());
+    // an excluded zero excludes both zeros
+    let m = F64WithinRangeMutator::new(-1.0..0.0);
+    assert!(m.validate_value(&-0.0).is_none());
+    assert!(m.validate_value(&-f64::from_bits(1)).is_some());
+}
+
+#[test]
+#[should_panic]
+fn test_float_within_range_nan_bound() {
+    F64WithinRangeMutator::new(0.0..=f64::NAN);
+}
+
+// floats are not `Eq`, so the mutators are tested on the bits of the values
+#[test]
+fn test_float_within_range_mutator() {
+    let m = MapMutator::new(
+        F64WithinRangeMutator::new(-100.0..=100.0),
+        |x: &u64| Some(f64::from_bits(*x)),
+        |x: &f64| x.to_bits(),
+        |_, cplx| cplx,
+    );
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+    let m = MapMutator::new(
+        F32WithinRangeMutator::new(0.0..),
+        |x: &u32| Some(f32::from_bits(*x)),
+        |x: &f32| x.to_bits(),
+        |_, cplx| cplx,
+    );
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
@@ -182,3 +182,204 @@ fn test_grammar_based_ast_mutator() {
     let mutator = grammar_based_ast_mutator(markdown());
     test_mutator(mutator, 500., 500., false, true, 60, 100);
 }
+
+#[cfg(feature = "identifier_grammar")]
+#[test]
+fn test_identifier_mutator() {
+    let mutator = IdentifierMutator::new(RUST_KEYWORDS.iter().copied(), false);
+    test_mutator(mutator, 100., 100., false, true, 60, 100);
+    let mutator = IdentifierMutator::new(C_KEYWORDS.iter().copied(), true);
+    test_mutator(mutator, 100., 100., false, true, 60, 100);
+}
+
+#[cfg(feature = "identifier_grammar")]
+#[test]
+fn test_identifier_mutator_rejects_keywords() {
+    use fuzzcheck::Mutator;
+
+    let mutator = IdentifierMutator::new(["fn", "let"], false);
+    mutator.initialize();
+    for _ in 0..1000 {
+        let ((string, ast), _) = mutator.random_arbitrary(20.);
+        assert!(string != "fn" && string != "let");
+        assert!(!string.starts_with(|c: char| c.is_ascii_digit()));
+        assert!(string.chars().all(|c| c == '_' || c.is_ascii_alphanumeric()));
+        assert!(mutator.validate_value(&(string, ast.clone())).is_some());
+        assert!(mutator.validate_value(&("fn".to_owned(), ast.clone())).is_none());
+        assert!(mutator.validate_value(&("1abc".to_owned(), ast)).is_none());
+    }
+}
+
+#[test]
+fn test_minimizing_grammar_mutator() {
+    use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+    use fuzzcheck::Mutator;
+
+    let grammar = repetition(alternation([regex("[a-z]"), regex("[0-9]{3}")]), 1..20);
+    let mutator = grammar_based_ast_mutator(grammar.clone()).minimizing().with_string();
+    test_mutator(mutator, 100., 100., false, true, 50, 100);
+
+    let mutator = grammar_based_ast_mutator(grammar).minimizing().with_string();
+    mutator.initialize();
+    for _ in 0..100 {
+        let (mut value, cplx) = mutator.random_arbitrary(100.);
+        let mut cache = mutator.validate_value(&value).unwrap();
+        let mut step = mutator.default_mutation_step(&value, &cache);
+        let original = value.0.clone();
+        let (token, new_cplx) = mutator
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.)
+            .unwrap();
+        if original.len() > 1 {
+            assert!(
+                new_cplx < cplx,
+                "{} >= {} for {} mutated from {}",
+                new_cplx,
+                cplx,
+                value.0,
+                original
+            );
+        }
+        assert!(mutator.validate_value(&value).is_some());
+        mutator.unmutate(&mut value, &mut cache, token);
+    }
+}
+
+#[test]
+fn test_url_mutator() {
+    let mutator = UrlMutator::new(["http", "https"]);
+    test_mutator(mutator, 200., 200., false, true, 60, 100);
+    let mutator = UrlMutator::new(Vec::<String>::new());
+    test_mutator(mutator, 200., 200., false, true, 60, 100);
+}
+
+#[test]
+fn test_url_mutator_schemes() {
+    use fuzzcheck::Mutator;
+
+    let mutator = UrlMutator::new(["http", "https"]);
+    mutator.initialize();
+    for _ in 0..1000 {
+        let ((string, ast), _) = mutator.random_arbitrary(100.);
+        assert!(string.starts_with("http:") || string.starts_with("https:"));
+        assert!(string.bytes().all(|b| b.is_ascii_graphic()));
+        assert!(mutator.validate_value(&(string, ast.clone())).is_some());
+        assert!(mutator
+            .validate_value(&("ftp://example.com".to_owned(), ast.clone()))
+            .is_none());
+        assert!(mutator.validate_value(&("http://a b".to_owned(), ast)).is_none());
+    }
+}
+
+#[test]
+fn test_xml_like_mutator() {
+    let mutator = XmlLikeMutator::new(["p", "b", "br"]);
+    test_mutator(mutator, 200., 200., false, true, 60, 100);
+}
+
+#[test]
+fn test_xml_like_mutator_generates_matched_tags() {
+    use fuzzcheck::Mutator;
+
+    let mutator = XmlLikeMutator::new(["p", "b", "br"]);
+    mutator.initialize();
+    for _ in 0..1000 {
+        let ((string, ast), _) = mutator.random_arbitrary(100.);
+        // every opening tag that is not self-closing has a closing tag
+        assert_eq!(
+            string.matches("</").count(),
+            string.matches('<').count() - string.matches("/>").count() - string.matches("</").count()
+        );
+        assert!(mutator.validate_value(&(string, ast.clone())).is_some());
+        assert!(mutator
+            .validate_value(&("<p><b></p></b>".to_owned(), ast.clone()))
+            .is_none());
+        assert!(mutator.validate_value(&("<p>".to_owned(), ast)).is_none());
+    }
+}
+
+#[test]
+fn test_numeric_string_mutator() {
+    let mutator = NumericStringMutator::new(false);
+    test_mutator(mutator, 200., 200., false, true, 60, 100);
+    let mutator = NumericStringMutator::new(true);
+    test_mutator(mutator, 200., 200., false, true, 60, 100);
+}
+
+#[test]
+fn test_numeric_string_mutator_validity() {
+    use fuzzcheck::Mutator;
+
+    let mutator = NumericStringMutator::new(false);
+    mutator.initialize();
+    for _ in 0..1000 {
+        let ((string, _), _) = mutator.random_arbitrary(200.);
+        assert!(string.parse::<f64>().is_ok(), "{string:?}");
+    }
+
+    let mutator = NumericStringMutator::new(true);
+    mutator.initialize();
+    let mut nbr_invalid = 0;
+    for _ in 0..1000 {
+        let ((string, _), _) = mutator.random_arbitrary(200.);
+        if string.parse::<f64>().is_err() {
+            nbr_invalid += 1;
+        }
+    }
+    assert!(nbr_invalid > 100);
+}
+
+#[test]
+fn test_regex_pattern_mutator() {
+    let mutator = RegexPatternMutator::new(false);
+    test_mutator(mutator, 200., 200., false, true, 60, 100);
+    let mutator = RegexPatternMutator::new(true);
+    test_mutator(mutator, 200., 200., false, true, 60, 100);
+}
+
+#[test]
+fn test_grammar_based_string_mutator() {
+    use fuzzcheck::Mutator;
+
+    let parens = recursive(|g| {
+        repetition(
+            alternation([concatenation([literal('('), recurse(g), literal(')')]), regex("[a-c]")]),
+            0..4,
+        )
+    });
+    let mutator = grammar_based_string_mutator(parens.clone());
+    test_mutator(mutator, 100., 100., false, true, 50, 50);
+
+    let mutator = grammar_based_string_mutator(parens);
+    mutator.initialize();
+    for valid in ["", "a", "(b)c", "((a)(b))", "(()c)"] {
+        assert!(mutator.validate_value(&valid.to_owned()).is_some(), "{valid:?}");
+    }
+    for invalid in ["d", "(a", "a)", "abcd", "((a)b"] {
+        assert!(mutator.validate_value(&invalid.to_owned()).is_none(), "{invalid:?}");
+    }
+    for _ in 0..100 {
+        let (string, cplx) = mutator.random_arbitrary(100.);
+        let cache = mutator.validate_value(&string).unwrap();
+        assert_eq!(mutator.complexity(&string, &cache), cplx);
+    }
+}
+
+#[test]
+fn test_repetition_with_distribution() {
+    use fuzzcheck::mutators::vector::ComplexityDistribution;
+    use fuzzcheck::Mutator;
+
+    let item = concatenation([repetition(literal('a'), 1..), literal(',')]);
+    let grammar = repetition_with_distribution(item, 8..=8, ComplexityDistribution::OneLarge(0.8));
+    let mutator = grammar_based_string_mutator(grammar.clone());
+    test_mutator(mutator, 1000., 1000., false, true, 50, 50);
+
+    let mutator = grammar_based_string_mutator(grammar);
+    mutator.initialize();
+    for _ in 0..100 {
+        let (string, cplx) = mutator.random_arbitrary(1000.);
+        assert_eq!(string.split_terminator(',').count(), 8);
+        let cache = mutator.validate_value(&string).unwrap();
+        assert_eq!(mutator.complexity(&string, &cache), cplx);
+    }
+}
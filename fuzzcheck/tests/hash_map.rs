@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use fuzzcheck::mutators::hash_map::HashMapMutator;
+use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+use fuzzcheck::mutators::map::MapMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+// `HashMap` is not `Hash`, so the mutators are tested on the sorted entries of the maps
+#[test]
+fn test_hash_map_mutator() {
+    let m = MapMutator::new(
+        <HashMap<u8, u16>>::default_mutator(),
+        |entries: &Vec<(u8, u16)>| Some(entries.iter().copied().collect()),
+        |map: &HashMap<u8, u16>| {
+            let mut entries = map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+            entries.sort();
+            entries
+        },
+        |_, cplx| cplx,
+    );
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+    let m = MapMutator::new(
+        HashMapMutator::new(bool::default_mutator(), <Vec<u8>>::default_mutator(), 1..=2),
+        |entries: &Vec<(bool, Vec<u8>)>| Some(entries.iter().cloned().collect()),
+        |map: &HashMap<bool, Vec<u8>>| {
+            let mut entries = map.iter().map(|(k, v)| (*k, v.clone())).collect::<Vec<_>>();
+            entries.sort();
+            entries
+        },
+        |_, cplx| cplx,
+    );
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_hash_map_mutator_complexity() {
+    let m = <HashMap<u8, u16>>::default_mutator();
+    m.initialize();
+    assert_eq!(m.min_complexity(), 1.0);
+    let value = HashMap::from([(1, 2), (3, 4)]);
+    let cache = m.validate_value(&value).unwrap();
+    assert_eq!(m.complexity(&value, &cache), 1.0 + 2.0 * (8.0 + 16.0));
+    for _ in 0..100 {
+        let (value, cplx) = m.random_arbitrary(200.0);
+        let cache = m.validate_value(&value).unwrap();
+        assert_eq!(cplx, m.complexity(&value, &cache));
+    }
+}
+
+#[test]
+fn test_hash_map_mutator_validate_value() {
+    let m = HashMapMutator::new(u8::default_mutator(), U8WithinRangeMutator::new(0..=10), 1..=3);
+    m.initialize();
+    assert!(m.validate_value(&HashMap::from([(1, 2), (3, 10)])).is_some());
+    // a value out of range
+    assert!(m.validate_value(&HashMap::from([(1, 2), (3, 11)])).is_none());
+    // too few or too many entries
+    assert!(m.validate_value(&HashMap::new()).is_none());
+    assert!(m.validate_value(&(0..4).map(|k| (k, 0)).collect()).is_none());
+}
+
+#[test]
+fn test_hash_map_mutator_operations() {
+    let m = HashMapMutator::new(u8::default_mutator(), u8::default_mutator(), 0..=10);
+    m.initialize();
+    let original = HashMap::from([(1, 2), (3, 4), (5, 6)]);
+    let mut value = original.clone();
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let (mut inserted, mut removed, mut mutated) = (false, false, false);
+    for _ in 0..100 {
+        let (token, cplx) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        let new_cache = m.validate_value(&value).unwrap();
+        assert_eq!(cplx, m.complexity(&value, &new_cache));
+        inserted |= value.len() > original.len();
+        removed |= value.len() < original.len();
+        mutated |= value.len() == original.len() && value.keys().all(|k| original.contains_key(k)) && value != original;
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+        assert_eq!(m.complexity(&value, &cache), 1.0 + 3.0 * 16.0);
+    }
+    assert!(inserted && removed && mutated);
+}
+
+#[test]
+fn test_hash_map_mutator_rng_state() {
+    let m = <HashMap<u8, Vec<u8>>>::default_mutator();
+    m.initialize();
+    let state = m.rng_state().unwrap();
+    let first = (0..20).map(|_| m.random_arbitrary(200.0).0).collect::<Vec<_>>();
+    m.set_rng_state(&state);
+    let second = (0..20).map(|_| m.random_arbitrary(200.0).0).collect::<Vec<_>>();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn test_hash_map_mutator_retries_colliding_keys() {
+    // two of the three possible keys are in the map, so half of the mutated keys collide
+    let m = HashMapMutator::new(U8WithinRangeMutator::new(0..=2), <()>::default_mutator(), 2..=2);
+    m.initialize();
+    let original = HashMap::from([(0, ()), (1, ())]);
+    let mut value = original.clone();
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut nbr_keys_changed = 0;
+    for _ in 0..200 {
+        // the mutations alternate between mutating a value and mutating a key
+        let (token, _) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        assert_eq!(value.len(), 2);
+        if value != original {
+            nbr_keys_changed += 1;
+        }
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, original);
+    }
+    assert!(nbr_keys_changed > 90, "{nbr_keys_changed}");
+}
@@ -0,0 +1,35 @@
+use fuzzcheck::mutators::index_into::{CollectionLength, IndexIntoMutator, IndexedCollectionMutator};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_index_into_mutator() {
+    let length = CollectionLength::default();
+    let m = IndexedCollectionMutator::new(
+        VecMutator::new(VecMutator::new(IndexIntoMutator::new(&length), 0..=4), 0..=16),
+        &length,
+    );
+    assert!(m.validate_value(&vec![vec![0, 1], vec![]]).is_some());
+    assert!(m.validate_value(&vec![vec![0, 2], vec![]]).is_none());
+
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_index_into_mutator_produces_valid_indices() {
+    let length = CollectionLength::default();
+    let m = IndexedCollectionMutator::new(
+        VecMutator::new(VecMutator::new(IndexIntoMutator::new(&length), 0..=4), 0..=16),
+        &length,
+    );
+    for _ in 0..100 {
+        let (mut x, _) = m.random_arbitrary(1000.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        for _ in 0..100 {
+            m.random_mutate(&mut x, &mut cache, 1000.0);
+            assert!(x.iter().flatten().all(|&idx| idx < x.len()), "{:?}", x);
+            cache = m.validate_value(&x).unwrap();
+        }
+    }
+}
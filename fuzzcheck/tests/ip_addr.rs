@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_ip_addr_mutators() {
+    test_mutator(Ipv4Addr::default_mutator(), 1000.0, 1000.0, false, true, 100, 100);
+    test_mutator(Ipv6Addr::default_mutator(), 1000.0, 1000.0, false, true, 100, 100);
+    test_mutator(IpAddr::default_mutator(), 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_ip_addr_mutators_generate_boundary_addresses() {
+    let m = Ipv4Addr::default_mutator();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    let values = (0..20)
+        .map(|_| m.ordered_arbitrary(&mut step, 32.0).unwrap().0)
+        .collect::<HashSet<_>>();
+    for addr in [Ipv4Addr::UNSPECIFIED, Ipv4Addr::BROADCAST, Ipv4Addr::LOCALHOST] {
+        assert!(values.contains(&addr));
+    }
+
+    let m = Ipv6Addr::default_mutator();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    let values = (0..20)
+        .map(|_| m.ordered_arbitrary(&mut step, 128.0).unwrap().0)
+        .collect::<HashSet<_>>();
+    for addr in [Ipv6Addr::UNSPECIFIED, Ipv6Addr::LOCALHOST] {
+        assert!(values.contains(&addr));
+    }
+
+    // both variants of `IpAddr` are generated
+    let m = IpAddr::default_mutator();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    let values = (0..100)
+        .map(|_| m.ordered_arbitrary(&mut step, 200.0).unwrap().0)
+        .collect::<HashSet<_>>();
+    assert!(values.iter().any(IpAddr::is_ipv4));
+    assert!(values.iter().any(IpAddr::is_ipv6));
+}
+
+#[test]
+fn test_ip_addr_mutators_change_one_segment_at_a_time() {
+    let m = Ipv4Addr::default_mutator();
+    m.initialize();
+    let original = Ipv4Addr::new(192, 168, 1, 20);
+    let mut value = original;
+    let mut step = m.default_mutation_step(&value, &());
+    let mut changed_octets = HashSet::new();
+    for _ in 0..20 {
+        let (token, _) = m
+            .ordered_mutate(&mut value, &mut (), &mut step, &EmptySubValueProvider, 32.0)
+            .unwrap();
+        let diff = value
+            .octets()
+            .iter()
+            .zip(original.octets())
+            .enumerate()
+            .filter(|(_, (a, b))| **a != *b)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        assert_eq!(diff.len(), 1);
+        changed_octets.insert(diff[0]);
+        m.unmutate(&mut value, &mut (), token);
+        assert_eq!(value, original);
+    }
+    assert_eq!(changed_octets.len(), 4);
+    // the first mutation flips the bits of the first octet
+    let mut value = original;
+    m.ordered_mutate(&mut value, &mut (), &mut 0, &EmptySubValueProvider, 32.0);
+    assert_eq!(value, Ipv4Addr::new(!192, 168, 1, 20));
+
+    let m = Ipv6Addr::default_mutator();
+    m.initialize();
+    let original = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    let mut value = original;
+    let mut step = m.default_mutation_step(&value, &());
+    // the mutations that would not change a hextet, like setting a zero hextet to zero, are skipped
+    for _ in 0..30 {
+        let (token, _) = m
+            .ordered_mutate(&mut value, &mut (), &mut step, &EmptySubValueProvider, 128.0)
+            .unwrap();
+        let nbr_changed = value
+            .segments()
+            .iter()
+            .zip(original.segments())
+            .filter(|(a, b)| **a != *b)
+            .count();
+        assert_eq!(nbr_changed, 1);
+        m.unmutate(&mut value, &mut (), token);
+        assert_eq!(value, original);
+    }
+}
+
+#[test]
+fn test_ip_addr_mutator_rng_state() {
+    let m = IpAddr::default_mutator();
+    let state = m.rng_state().unwrap();
+    let first = (0..20).map(|_| m.random_arbitrary(128.0).0).collect::<Vec<_>>();
+    m.set_rng_state(&state);
+    let second = (0..20).map(|_| m.random_arbitrary(128.0).0).collect::<Vec<_>>();
+    assert_eq!(first, second);
+}
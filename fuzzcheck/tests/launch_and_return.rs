@@ -0,0 +1,48 @@
+use std::ptr::addr_of_mut;
+
+use fuzzcheck::sensors_and_pools::{MaximiseObservationPool, StaticValueSensor};
+use fuzzcheck::{Arguments, ReasonForStopping};
+
+static mut COUNT: usize = 0;
+
+fn test_function(x: &u8) -> bool {
+    if *x == 7 {
+        panic!("seven");
+    }
+    *x < 200
+}
+
+#[test]
+fn test_launch_and_return_collects_test_failures() {
+    let sensor = unsafe { StaticValueSensor::new(&mut *addr_of_mut!(COUNT), 0) };
+    let pool = MaximiseObservationPool::<usize>::new("maximise_count");
+    let result = fuzzcheck::fuzz_test(test_function)
+        .default_mutator()
+        .serde_serializer()
+        .sensor_and_pool(sensor, pool)
+        .arguments(Arguments::for_internal_documentation_test())
+        .stop_after_first_test_failure(false)
+        .stop_after_iterations(2000)
+        .launch_and_return();
+
+    // all 256 values of a u8 are tested before the maximum number of iterations is reached
+    assert!(matches!(
+        result.reason_for_stopping,
+        ReasonForStopping::ExhaustedAllPossibleMutations
+    ));
+    assert!(result.total_number_of_runs >= 256 && result.total_number_of_runs <= 2000);
+    assert!(result.found_test_failure());
+    assert!(!result.pool_stats.is_empty());
+
+    // one failure for the panic, and another one for the inputs that return false
+    assert_eq!(result.test_failures.len(), 2);
+    for failure in result.test_failures.iter() {
+        assert!(!test_function_passes(failure.input));
+    }
+    assert!(result.test_failures.iter().any(|f| f.input == 7));
+    assert!(result.test_failures.iter().any(|f| f.input >= 200));
+}
+
+fn test_function_passes(x: u8) -> bool {
+    std::panic::catch_unwind(|| test_function(&x)).unwrap_or(false)
+}
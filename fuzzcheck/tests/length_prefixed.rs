@@ -0,0 +1,59 @@
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::length_prefixed::{LengthPrefixMode, LengthPrefixedMutator};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_length_prefixed_mutator() {
+    for mode in [
+        LengthPrefixMode::Consistent,
+        LengthPrefixMode::SometimesInconsistent,
+        LengthPrefixMode::Adversarial,
+    ] {
+        let m = LengthPrefixedMutator::new(VecMutator::new(U8Mutator::default(), 0..=usize::MAX), mode);
+        test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+    }
+}
+
+#[test]
+fn test_consistent_length_prefixed_mutator() {
+    let m = LengthPrefixedMutator::new(
+        VecMutator::new(U8Mutator::default(), 0..=usize::MAX),
+        LengthPrefixMode::Consistent,
+    );
+    assert!(m.validate_value(&(2, vec![1, 2])).is_some());
+    assert!(m.validate_value(&(3, vec![1, 2])).is_none());
+
+    for _ in 0..100 {
+        let (mut x, _) = m.random_arbitrary(100.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        for _ in 0..100 {
+            let (_, _) = m.random_mutate(&mut x, &mut cache, 100.0);
+            assert_eq!(x.0, x.1.len());
+            cache = m.validate_value(&x).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_adversarial_length_prefixed_mutator() {
+    let m = LengthPrefixedMutator::new(
+        VecMutator::new(U8Mutator::default(), 0..=usize::MAX),
+        LengthPrefixMode::Adversarial,
+    );
+    assert!(m.validate_value(&(3, vec![1, 2])).is_some());
+
+    let mut x = (2, vec![1, 2]);
+    let mut cache = m.validate_value(&x).unwrap();
+    let mut inconsistent = 0;
+    for _ in 0..100 {
+        let (t, _) = m.random_mutate(&mut x, &mut cache, 100.0);
+        if x.0 != x.1.len() {
+            inconsistent += 1;
+        }
+        m.unmutate(&mut x, &mut cache, t);
+        assert_eq!(x, (2, vec![1, 2]));
+    }
+    assert!(inconsistent > 0);
+}
@@ -0,0 +1,99 @@
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::max_serialized_size::MaxSerializedSizeMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{ByteSerializer, Mutator};
+
+#[test]
+fn test_max_serialized_size_mutator() {
+    let m = MaxSerializedSizeMutator::new(
+        VecMutator::new(U8Mutator::default(), 0..=usize::MAX),
+        ByteSerializer::new("bin"),
+        20,
+    );
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_max_serialized_size_mutator_limit() {
+    let m = MaxSerializedSizeMutator::with_size_hint(
+        VecMutator::new(U8Mutator::default(), 0..=usize::MAX),
+        |x: &Vec<u8>| x.len(),
+        10,
+    );
+    m.initialize();
+    assert!(m.validate_value(&vec![0; 10]).is_some());
+    assert!(m.validate_value(&vec![0; 11]).is_none());
+
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..100 {
+        let (x, _) = m.ordered_arbitrary(&mut step, 1000.0).unwrap();
+        assert!(x.len() <= 10);
+        let (mut x, _) = m.random_arbitrary(1000.0);
+        assert!(x.len() <= 10);
+        let mut cache = m.validate_value(&x).unwrap();
+        let mut step = m.default_mutation_step(&x, &cache);
+        for _ in 0..100 {
+            let old_x = x.clone();
+            let (t, _) = m
+                .ordered_mutate(&mut x, &mut cache, &mut step, &EmptySubValueProvider, 1000.0)
+                .unwrap();
+            assert!(x.len() <= 10);
+            m.unmutate(&mut x, &mut cache, t);
+            assert_eq!(x, old_x);
+            let (t, _) = m.random_mutate(&mut x, &mut cache, 1000.0);
+            assert!(x.len() <= 10);
+            m.unmutate(&mut x, &mut cache, t);
+            assert_eq!(x, old_x);
+        }
+    }
+}
+
+#[test]
+fn test_max_serialized_size_mutator_gives_up() {
+    // only the empty vector is small enough
+    let m = MaxSerializedSizeMutator::with_size_hint(
+        VecMutator::new(U8Mutator::default(), 0..=usize::MAX),
+        |x: &Vec<u8>| if x.is_empty() { 0 } else { 100 },
+        10,
+    );
+    m.initialize();
+    let mut x = vec![];
+    let mut cache = m.validate_value(&x).unwrap();
+    let mut step = m.default_mutation_step(&x, &cache);
+    // the mutations terminate even though almost all of them produce values that are too large
+    for _ in 0..10 {
+        if let Some((t, _)) = m.ordered_mutate(&mut x, &mut cache, &mut step, &EmptySubValueProvider, 1000.0) {
+            assert!(x.is_empty());
+            m.unmutate(&mut x, &mut cache, t);
+        }
+    }
+    let (t, _) = m.random_mutate(&mut x, &mut cache, 1000.0);
+    assert!(x.is_empty());
+    m.unmutate(&mut x, &mut cache, t);
+    let (x, _) = m.random_arbitrary(1000.0);
+    assert!(x.is_empty());
+
+    // no value is small enough
+    let m = MaxSerializedSizeMutator::with_size_hint(
+        VecMutator::new(U8Mutator::default(), 1..=usize::MAX),
+        |x: &Vec<u8>| x.len(),
+        0,
+    );
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    assert!(m.ordered_arbitrary(&mut step, 1000.0).is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_max_serialized_size_mutator_no_small_value() {
+    let m = MaxSerializedSizeMutator::with_size_hint(
+        VecMutator::new(U8Mutator::default(), 1..=usize::MAX),
+        |x: &Vec<u8>| x.len(),
+        0,
+    );
+    m.initialize();
+    m.random_arbitrary(1000.0);
+}
@@ -0,0 +1,52 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use fuzzcheck::mutators::memoizing::MemoizingMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator, MutatorExt};
+
+#[test]
+fn test_memoizing_mutator() {
+    let m = MemoizingMutator::new(<Vec<u8>>::default_mutator(), 100);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+}
+
+#[test]
+fn test_memoizing_mutator_validates_each_value_once() {
+    let nbr_validations = Rc::new(Cell::new(0));
+    let counter = nbr_validations.clone();
+    let inner = u16::default_mutator().map(
+        |x: &u16| x.to_string(),
+        move |s: &String| {
+            counter.set(counter.get() + 1);
+            s.parse::<u16>().ok()
+        },
+    );
+    let m = MemoizingMutator::new(inner, 2);
+
+    let one = "1".to_owned();
+    let cache = m.validate_value(&one).unwrap();
+    assert_eq!(nbr_validations.get(), 1);
+    assert_eq!(m.complexity(&one, &cache), 16.0);
+    assert!(m.validate_value(&one).is_some());
+    assert_eq!(nbr_validations.get(), 1);
+
+    // rejected values are remembered too
+    let invalid = "not a number".to_owned();
+    assert!(m.validate_value(&invalid).is_none());
+    assert!(m.validate_value(&invalid).is_none());
+    assert_eq!(nbr_validations.get(), 2);
+    assert_eq!(m.memoized_len(), 2);
+
+    // the oldest value is forgotten when the capacity is reached
+    assert!(m.validate_value(&"2".to_owned()).is_some());
+    assert_eq!(nbr_validations.get(), 3);
+    assert_eq!(m.memoized_len(), 2);
+    assert!(m.validate_value(&one).is_some());
+    assert_eq!(nbr_validations.get(), 4);
+
+    m.clear();
+    assert_eq!(m.memoized_len(), 0);
+    assert!(m.validate_value(&one).is_some());
+    assert_eq!(nbr_validations.get(), 5);
+}
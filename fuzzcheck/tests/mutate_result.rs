@@ -0,0 +1,35 @@
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, MutateResult, Mutator};
+
+#[test]
+fn test_ordered_mutate_with_status() {
+    let m = bool::default_mutator();
+    m.initialize();
+    let mut value = false;
+    let mut cache = ();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let result = m.ordered_mutate_with_status(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0);
+    let MutateResult::Mutated(token, cplx) = result else {
+        panic!()
+    };
+    assert!(value);
+    assert_eq!(cplx, 1.0);
+    m.unmutate(&mut value, &mut cache, token);
+    assert!(!value);
+    // there is only one other boolean
+    let result = m.ordered_mutate_with_status(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0);
+    assert!(matches!(result, MutateResult::Exhausted));
+
+    // every option is more complex than the budget
+    let m = <Option<u8>>::default_mutator();
+    m.initialize();
+    let mut value = None;
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let result = m.ordered_mutate_with_status(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 0.5);
+    assert!(matches!(result, MutateResult::BudgetTooSmall));
+    assert_eq!(value, None);
+
+    let result = m.ordered_mutate_with_status(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0);
+    assert!(matches!(result, MutateResult::Mutated(..)));
+}
@@ -0,0 +1,32 @@
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator, MutatorExt};
+
+#[test]
+fn test_mutation_outcome() {
+    let m = <Vec<u8>>::default_mutator();
+    m.initialize();
+    let mut value = vec![1, 2, 3];
+    let mut cache = m.validate_value(&value).unwrap();
+    let cplx = m.complexity(&value, &cache);
+    let mut step = m.default_mutation_step(&value, &cache);
+    for _ in 0..100 {
+        let outcome = m
+            .ordered_mutate_with_outcome(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        assert_eq!(outcome.old_cplx, cplx);
+        let new_cplx = m.complexity(&value, &m.validate_value(&value).unwrap());
+        assert_eq!(outcome.new_cplx, new_cplx);
+        assert_eq!(outcome.cplx_delta(), new_cplx - cplx);
+        m.unmutate(&mut value, &mut cache, outcome.token);
+        assert_eq!(value, vec![1, 2, 3]);
+
+        let outcome = m.random_mutate_with_outcome(&mut value, &mut cache, 100.0);
+        assert_eq!(outcome.old_cplx, cplx);
+        assert_eq!(
+            outcome.new_cplx,
+            m.complexity(&value, &m.validate_value(&value).unwrap())
+        );
+        m.unmutate(&mut value, &mut cache, outcome.token);
+        assert_eq!(value, vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,53 @@
+use std::any::{Any, TypeId};
+
+use fuzzcheck::mutators::no_crossover::NoCrossoverMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::{Generation, SubValueProviderId};
+use fuzzcheck::{DefaultMutator, Mutator, SubValueProvider};
+
+/// A subvalue provider that fails the test if it is ever used
+struct PanickingSubValueProvider;
+
+impl SubValueProvider for PanickingSubValueProvider {
+    fn identifier(&self) -> SubValueProviderId {
+        SubValueProviderId {
+            idx: 1,
+            generation: Generation(0),
+        }
+    }
+
+    fn get_random_subvalue(&self, _typeid: TypeId, _max_cplx: f64) -> Option<(&dyn Any, f64)> {
+        panic!("the subvalue provider should not be used")
+    }
+
+    fn get_subvalue(&self, _typeid: TypeId, _max_cplx: f64, _index: &mut usize) -> Option<(&dyn Any, f64)> {
+        panic!("the subvalue provider should not be used")
+    }
+}
+
+#[test]
+fn test_no_crossover_mutator() {
+    let m = NoCrossoverMutator::new(<Vec<Vec<u8>>>::default_mutator());
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_no_crossover_mutator_ignores_subvalue_provider() {
+    let m = NoCrossoverMutator::new(<Vec<(u8, Vec<u16>)>>::default_mutator());
+    m.initialize();
+    for _ in 0..100 {
+        let (mut value, _) = m.random_arbitrary(200.0);
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        for _ in 0..100 {
+            let old_value = value.clone();
+            let Some((token, _)) =
+                m.ordered_mutate(&mut value, &mut cache, &mut step, &PanickingSubValueProvider, 200.0)
+            else {
+                break;
+            };
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, old_value);
+        }
+    }
+}
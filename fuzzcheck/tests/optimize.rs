@@ -0,0 +1,38 @@
+use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::optimize::hill_climb;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+/// The number of inversions of the vector, which is maximal when it is sorted in decreasing order
+fn nbr_inversions(xs: &[u8]) -> f64 {
+    let mut count = 0;
+    for i in 0..xs.len() {
+        for j in i + 1..xs.len() {
+            if xs[i] > xs[j] {
+                count += 1;
+            }
+        }
+    }
+    count as f64
+}
+
+#[test]
+fn test_hill_climb() {
+    let m = VecMutator::new(U8WithinRangeMutator::new(0..=9), 10..=10);
+    let (xs, cost) = hill_climb(m, |xs: &Vec<u8>| nbr_inversions(xs), 10_000, 1000.0);
+    assert_eq!(cost, nbr_inversions(&xs));
+    assert_eq!(xs, vec![9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
+    assert_eq!(cost, 45.0);
+}
+
+#[test]
+fn test_hill_climb_respects_max_cplx() {
+    let m = <Vec<u8>>::default_mutator();
+    // longer vectors have more inversions, but the complexity limits their length
+    let (xs, cost) = hill_climb(m, |xs: &Vec<u8>| nbr_inversions(xs), 2_000, 50.0);
+    assert!(cost > 0.0);
+    let m = <Vec<u8>>::default_mutator();
+    m.initialize();
+    let cache = m.validate_value(&xs).unwrap();
+    assert!(m.complexity(&xs, &cache) <= 50.0);
+}
@@ -2,7 +2,8 @@ use std::any::TypeId;
 
 use fuzzcheck::mutators::integer::U8Mutator;
 use fuzzcheck::mutators::option::OptionMutator;
-use fuzzcheck::subvalue_provider::{CrossoverSubValueProvider, Generation, SubValueProviderId};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::{CrossoverSubValueProvider, EmptySubValueProvider, Generation, SubValueProviderId};
 use fuzzcheck::{DefaultMutator, Mutator, SubValueProvider};
 
 #[test]
@@ -60,3 +61,78 @@ fn test_option() {
     let m = OptionMutator::new(U8Mutator::default());
     fuzzcheck::mutators::testing_utilities::test_mutator(m, 100.0, 100.0, false, true, 500, 500);
 }
+
+#[test]
+fn test_inner_mutator_accessors() {
+    use fuzzcheck::mutators::boxed::BoxMutator;
+    use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+    use fuzzcheck::mutators::map::MapMutator;
+
+    let mut m = OptionMutator::new(BoxMutator::new(MapMutator::new(
+        U8WithinRangeMutator::new(1..=10),
+        |x: &u16| u8::try_from(*x).ok(),
+        |x: &u8| *x as u16,
+        |_, cplx| cplx,
+    )));
+    let within_range = m.inner().inner().inner();
+    assert!(within_range.validate_value(&5).is_some());
+    assert!(within_range.validate_value(&11).is_none());
+
+    // the innermost mutator can be replaced before the mutator is initialized
+    *m.inner_mut().inner_mut().inner_mut() = U8WithinRangeMutator::new(20..=30);
+    m.initialize();
+    for _ in 0..100 {
+        let (x, _) = m.random_arbitrary(100.0);
+        if let Some(x) = x {
+            assert!((20..=30).contains(&*x));
+        }
+    }
+    assert!(m.validate_value(&Some(Box::new(25))).is_some());
+    assert!(m.validate_value(&Some(Box::new(5))).is_none());
+}
+
+#[test]
+fn test_always_some_option_mutator() {
+    let m = OptionMutator::always_some(U8Mutator::default());
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+
+    let m = OptionMutator::always_some(U8Mutator::default());
+    m.initialize();
+    for _ in 0..1000 {
+        assert!(m.random_arbitrary(100.0).0.is_some());
+    }
+    let mut step = m.default_arbitrary_step();
+    let mut nbr_values = 0;
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 100.0) {
+        assert!(x.is_some());
+        nbr_values += 1;
+    }
+    assert_eq!(nbr_values, 256);
+
+    // `None` is valid, is simpler than any `Some` value, and is mutated into `Some`
+    let mut none = None;
+    let mut cache = m.validate_value(&none).unwrap();
+    assert!(m.complexity(&none, &cache) < m.complexity(&Some(0), &m.validate_value(&Some(0)).unwrap()));
+    let mut step = m.default_mutation_step(&none, &cache);
+    for _ in 0..100 {
+        let (token, _) = m
+            .ordered_mutate(&mut none, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        assert!(none.is_some());
+        m.unmutate(&mut none, &mut cache, token);
+        assert!(none.is_none());
+    }
+
+    let mut value = Some(8);
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    for _ in 0..1000 {
+        let (token, _) = m.random_mutate(&mut value, &mut cache, 100.0);
+        assert!(value.is_some());
+        m.unmutate(&mut value, &mut cache, token);
+        if let Some((token, _)) = m.ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0) {
+            assert!(value.is_some());
+            m.unmutate(&mut value, &mut cache, token);
+        }
+    }
+}
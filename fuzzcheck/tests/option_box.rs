@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+
+use fuzzcheck::mutators::boxed::BoxMutator;
+use fuzzcheck::mutators::option::OptionMutator;
+use fuzzcheck::mutators::option_box::OptionBoxMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_option_box_mutator() {
+    let m = OptionBoxMutator::new(<Vec<u8>>::default_mutator());
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+    let m = OptionBoxMutator::new(bool::default_mutator());
+    test_mutator(m, 500.0, 500.0, false, true, 3, 3);
+}
+
+#[test]
+fn test_option_box_mutator_matches_option_of_box_mutator() {
+    let fused = OptionBoxMutator::new(<Vec<u8>>::default_mutator());
+    let stacked = OptionMutator::new(BoxMutator::new(<Vec<u8>>::default_mutator()));
+    fused.initialize();
+    stacked.initialize();
+
+    assert_eq!(fused.min_complexity(), stacked.min_complexity());
+    assert_eq!(fused.max_complexity(), stacked.max_complexity());
+    assert_eq!(
+        fused.global_search_space_complexity(),
+        stacked.global_search_space_complexity()
+    );
+
+    // the values generated by each mutator are valid for the other, with the same complexity
+    for _ in 0..1000 {
+        let (value, cplx) = fused.random_arbitrary(100.0);
+        let cache = stacked.validate_value(&value).unwrap();
+        assert_eq!(stacked.complexity(&value, &cache), cplx);
+
+        let (value, cplx) = stacked.random_arbitrary(100.0);
+        let cache = fused.validate_value(&value).unwrap();
+        assert_eq!(fused.complexity(&value, &cache), cplx);
+    }
+
+    // both mutators enumerate the same values in order
+    let fused = OptionBoxMutator::new(bool::default_mutator());
+    let stacked = OptionMutator::new(BoxMutator::new(bool::default_mutator()));
+    fused.initialize();
+    stacked.initialize();
+    let mut fused_step = fused.default_arbitrary_step();
+    let mut stacked_step = stacked.default_arbitrary_step();
+    loop {
+        let x = fused.ordered_arbitrary(&mut fused_step, 10.0);
+        let y = stacked.ordered_arbitrary(&mut stacked_step, 10.0);
+        assert_eq!(x, y);
+        if x.is_none() {
+            break;
+        }
+    }
+
+    // and reach the same values by mutation
+    assert_eq!(reachable_values(&fused), reachable_values(&stacked));
+}
+
+type Value = Option<Box<bool>>;
+
+/// The pairs `(start, mutated)` of distinct values reached by ordered mutations
+fn reachable_values<M: Mutator<Option<Box<bool>>>>(m: &M) -> HashSet<(Value, Value)> {
+    let mut values = HashSet::new();
+    for start in [None, Some(Box::new(false)), Some(Box::new(true))] {
+        let mut value = start.clone();
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        while let Some((token, _)) = m.ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 10.0) {
+            if value != start {
+                values.insert((start.clone(), value.clone()));
+            }
+            m.unmutate(&mut value, &mut cache, token);
+        }
+    }
+    values
+}
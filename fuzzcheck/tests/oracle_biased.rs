@@ -0,0 +1,62 @@
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::oracle_biased::OracleBiasedMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_oracle_biased_mutator() {
+    let m = OracleBiasedMutator::new(
+        VecMutator::new(U8Mutator::default(), 0..=usize::MAX),
+        |x: &Vec<u8>| x.len() & 1 == 0,
+        0.9,
+    );
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+}
+
+#[test]
+fn test_oracle_biased_mutator_proportions() {
+    // accepts roughly half of the values
+    let oracle = |x: &Vec<u8>| x.iter().fold(0u8, |acc, b| acc ^ b) & 1 == 0;
+    let count_valid = |probability: f64| {
+        let m = OracleBiasedMutator::new(VecMutator::new(U8Mutator::default(), 1..=10), oracle, probability);
+        let (mut x, _) = m.random_arbitrary(100.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        let mut nbr_valid = 0;
+        for _ in 0..1000 {
+            let (_, _) = m.random_mutate(&mut x, &mut cache, 100.0);
+            if oracle(&x) {
+                nbr_valid += 1;
+            }
+            cache = m.validate_value(&x).unwrap();
+        }
+        nbr_valid
+    };
+    assert_eq!(count_valid(1.0), 1000);
+    assert!(count_valid(0.9) > 850);
+    assert!((300..700).contains(&count_valid(0.0)));
+}
+
+#[test]
+fn test_oracle_biased_mutator_ordered_methods_do_not_stall() {
+    // rejects nearly every value, and the ordered mutations of a vector are never exhausted
+    let oracle = |x: &Vec<u8>| x.starts_with(b"FRAME:v1");
+    let m = OracleBiasedMutator::new(VecMutator::new(U8Mutator::default(), 0..=usize::MAX), oracle, 1.0);
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..10 {
+        let _ = m.ordered_arbitrary(&mut step, 100.0);
+    }
+    let (mut x, _) = m.random_arbitrary(100.0);
+    let mut cache = m.validate_value(&x).unwrap();
+    let mut step = m.default_mutation_step(&x, &cache);
+    for _ in 0..10 {
+        let original = x.clone();
+        let (token, _) = m
+            .ordered_mutate(&mut x, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        m.unmutate(&mut x, &mut cache, token);
+        assert_eq!(x, original);
+    }
+}
@@ -0,0 +1,59 @@
+use fuzzcheck::mutators::overflow_seeking::{overflows, OverflowSeekingMutator};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_overflow_seeking_mutator() {
+    let m = OverflowSeekingMutator::<u8>::new();
+    test_mutator(m, 100.0, 100.0, false, true, 50, 100);
+    let m = OverflowSeekingMutator::<i32>::new();
+    test_mutator(m, 100.0, 100.0, false, true, 50, 100);
+}
+
+#[test]
+fn test_overflow_seeking_arbitrary_values_overflow_first() {
+    let m = OverflowSeekingMutator::<i16>::new();
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    let first = (0..10)
+        .map(|_| m.ordered_arbitrary(&mut step, 100.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert!(first.contains(&(i16::MAX, 1)));
+    assert!(first.contains(&(i16::MIN, -1)));
+    assert!(first.iter().filter(|(a, b)| overflows(*a, *b)).count() >= 5);
+
+    let m = OverflowSeekingMutator::<u32>::new();
+    let mut step = m.default_arbitrary_step();
+    let values = (0..30)
+        .map(|_| m.ordered_arbitrary(&mut step, 100.0).unwrap().0)
+        .collect::<Vec<_>>();
+    assert!(values.contains(&(65536, 65536)));
+    assert!(values.contains(&(0, 1)));
+}
+
+#[test]
+fn test_overflow_seeking_mutations_reach_boundaries() {
+    let m = OverflowSeekingMutator::<u8>::new();
+    m.initialize();
+    let mut value = (200, 3);
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut mutated = vec![];
+    for _ in 0..20 {
+        let (token, _) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        mutated.push(value);
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, (200, 3));
+    }
+    // 200 + 56 overflows, 200 + 55 does not
+    assert!(mutated.contains(&(200, 56)));
+    assert!(mutated.contains(&(200, 55)));
+    // 3 - 4 overflows
+    assert!(mutated.contains(&(3, 4)) || mutated.contains(&(200, 201)));
+    // 3 * 86 overflows, 3 * 85 does not
+    assert!(mutated.contains(&(86, 3)));
+    assert!(mutated.contains(&(85, 3)));
+}
@@ -0,0 +1,87 @@
+#![feature(coverage_attribute)]
+use std::thread;
+
+use fuzzcheck::mutators::boxed::BoxMutator;
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::option::OptionMutator;
+use fuzzcheck::mutators::parallel::ParallelMutator;
+use fuzzcheck::mutators::recursive::{RecurToMutator, RecursiveMutator};
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::{make_mutator, DefaultMutator, Mutator};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct S {
+    content: bool,
+    next: Option<Box<S>>,
+}
+
+make_mutator! {
+    name: SMutator,
+    recursive: true,
+    default: false,
+    type: struct S {
+        content: bool,
+        #[field_mutator(OptionMutator<Box<S>, BoxMutator<RecurToMutator<SMutator<M0>>>>)]
+        next: Option<Box<S>>
+    }
+}
+
+fn assert_send<T: Send>(_x: &T) {}
+
+#[test]
+fn test_core_mutators_are_send() {
+    assert_send(&bool::default_mutator());
+    assert_send(&U8Mutator::default());
+    assert_send(&<Option<u16>>::default_mutator());
+    assert_send(&VecMutator::new(U8Mutator::default(), 0..=10));
+    assert_send(&<(u8, Vec<bool>)>::default_mutator());
+    assert_send(&ParallelMutator::new(<Vec<u8>>::default_mutator).fork());
+    // recursive mutators are not `Send`, but the function building them is
+    assert_send(&ParallelMutator::new(s_mutator).constructor());
+}
+
+#[test]
+fn test_parallel_mutator() {
+    let m = ParallelMutator::new(|| VecMutator::new(U8Mutator::default(), 0..=10));
+    let workers = (0..4)
+        .map(|_| {
+            let worker = m.fork();
+            thread::spawn(move || {
+                let (x, _) = worker.random_arbitrary(100.0);
+                assert!(worker.validate_value(&x).is_some());
+            })
+        })
+        .collect::<Vec<_>>();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+}
+
+fn s_mutator() -> RecursiveMutator<SMutator<<bool as DefaultMutator>::Mutator>> {
+    RecursiveMutator::new(|mutator| {
+        SMutator::new(
+            bool::default_mutator(),
+            OptionMutator::new(BoxMutator::new(RecurToMutator::from(mutator))),
+        )
+    })
+}
+
+#[test]
+fn test_parallel_recursive_mutator() {
+    let m = ParallelMutator::new(s_mutator);
+    test_mutator(m.fork(), 100.0, 100.0, false, true, 50, 50);
+
+    let workers = (0..4)
+        .map(|_| {
+            let constructor = m.constructor();
+            thread::spawn(move || {
+                let worker = ParallelMutator::from_constructor(constructor);
+                test_mutator(worker, 100.0, 100.0, false, true, 50, 50);
+            })
+        })
+        .collect::<Vec<_>>();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+}
@@ -0,0 +1,37 @@
+use fuzzcheck::mutators::pascal_string::PascalStringMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_pascal_string_mutator() {
+    let m = PascalStringMutator::new();
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+    let m = PascalStringMutator::<u16>::with_length_prefix();
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+}
+
+#[test]
+fn test_pascal_string_prefix() {
+    let m = PascalStringMutator::new();
+    assert!(m.validate_value(&vec![0]).is_some());
+    assert!(m.validate_value(&vec![2, 7, 8]).is_some());
+    assert!(m.validate_value(&vec![3, 7, 8]).is_none());
+    assert!(m.validate_value(&vec![]).is_none());
+    for _ in 0..100 {
+        let (mut x, _) = m.random_arbitrary(5000.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        for _ in 0..100 {
+            let _ = m.random_mutate(&mut x, &mut cache, 5000.0);
+            assert!(x.len() <= 256);
+            assert_eq!(x[0] as usize, x.len() - 1);
+            cache = m.validate_value(&x).unwrap();
+        }
+    }
+
+    let m = PascalStringMutator::<u32>::with_length_prefix();
+    assert!(m.validate_value(&vec![2, 0, 0, 0, 7, 8]).is_some());
+    assert!(m.validate_value(&vec![2, 0, 7, 8]).is_none());
+    let (x, _) = m.random_arbitrary(100.0);
+    let len = u32::from_le_bytes(x[..4].try_into().unwrap()) as usize;
+    assert_eq!(len, x.len() - 4);
+}
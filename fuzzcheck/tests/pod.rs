@@ -0,0 +1,43 @@
+#![cfg(feature = "bytemuck")]
+
+use bytemuck::{Pod, Zeroable};
+use fuzzcheck::mutators::pod::PodMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Pod, Zeroable)]
+struct Header {
+    magic: u32,
+    length: u16,
+    flags: u16,
+}
+
+#[test]
+fn test_pod_mutator() {
+    let m = PodMutator::<Header>::new();
+    test_mutator(m, 64.0, 64.0, false, true, 100, 200);
+}
+
+#[test]
+fn test_pod_mutator_flips_every_bit_in_order() {
+    let m = PodMutator::<Header>::new();
+    let mut step = m.default_arbitrary_step();
+    let (mut x, cplx) = m.ordered_arbitrary(&mut step, 64.0).unwrap();
+    assert_eq!(x, Header::zeroed());
+    assert_eq!(cplx, 64.0);
+
+    m.validate_value(&x).unwrap();
+    let mut step = m.default_mutation_step(&x, &());
+    for i in 0..64 {
+        let (token, _) = m
+            .ordered_mutate(&mut x, &mut (), &mut step, &EmptySubValueProvider, 64.0)
+            .unwrap();
+        let bytes = bytemuck::bytes_of(&x);
+        assert_eq!(bytes.iter().map(|b| b.count_ones()).sum::<u32>(), 1);
+        assert_eq!(bytes[i / 8], 1 << (i % 8));
+        m.unmutate(&mut x, &mut (), token);
+        assert_eq!(x, Header::zeroed());
+    }
+}
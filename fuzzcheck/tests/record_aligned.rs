@@ -0,0 +1,38 @@
+use fuzzcheck::mutators::record_aligned::RecordAlignedByteMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_record_aligned_mutator() {
+    let m = RecordAlignedByteMutator::new(16);
+    test_mutator(m, 1000.0, 1000.0, false, true, 100, 150);
+    let m = RecordAlignedByteMutator::with_nbr_records(3, 1..=4);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+}
+
+#[test]
+fn test_record_aligned_lengths() {
+    let m = RecordAlignedByteMutator::new(4);
+    m.initialize();
+    assert_eq!(m.record_size(), 4);
+    assert!(m.validate_value(&vec![]).is_some());
+    assert!(m.validate_value(&vec![1, 2, 3, 4, 5, 6, 7, 8]).is_some());
+    assert!(m.validate_value(&vec![1, 2, 3, 4, 5]).is_none());
+    assert!(m.validate_value(&vec![1, 2, 3]).is_none());
+
+    for _ in 0..100 {
+        let (mut x, _) = m.random_arbitrary(1000.0);
+        let mut cache = m.validate_value(&x).unwrap();
+        for _ in 0..100 {
+            let _ = m.random_mutate(&mut x, &mut cache, 1000.0);
+            assert_eq!(x.len() % 4, 0);
+            cache = m.validate_value(&x).unwrap();
+        }
+    }
+
+    let m = RecordAlignedByteMutator::with_nbr_records(2, 1..=2);
+    m.initialize();
+    assert!(m.validate_value(&vec![]).is_none());
+    assert!(m.validate_value(&vec![1, 2]).is_some());
+    assert!(m.validate_value(&vec![1, 2, 3, 4, 5, 6]).is_none());
+}
@@ -0,0 +1,97 @@
+#![allow(unused_attributes)]
+#![feature(coverage_attribute)]
+
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::recursive::RecurToMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{make_mutator, DefaultMutator, Mutator};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Tree {
+    label: u8,
+    children: Vec<Tree>,
+}
+
+make_mutator! {
+    name: TreeMutator,
+    recursive: true,
+    default: true,
+    type:
+    struct Tree {
+        #[field_mutator(U8Mutator = { u8::default_mutator() })]
+        label: u8,
+        #[field_mutator(
+            VecMutator<Tree, RecurToMutator<TreeMutator>> = {
+                VecMutator::new(self_.into(), 0..=4)
+            }
+        )]
+        children: Vec<Tree>,
+    }
+}
+
+impl Tree {
+    fn depth(&self) -> usize {
+        self.children.iter().map(|c| c.depth() + 1).max().unwrap_or(0)
+    }
+    fn nested(depth: usize) -> Self {
+        let children = if depth == 0 {
+            vec![]
+        } else {
+            vec![Self::nested(depth - 1)]
+        };
+        Tree { label: 0, children }
+    }
+}
+
+#[test]
+fn test_recursive_mutator_max_depth() {
+    let m = Tree::default_mutator().with_max_depth(3);
+    assert_eq!(m.max_depth(), Some(3));
+    m.initialize();
+
+    assert!(m.validate_value(&Tree::nested(3)).is_some());
+    assert!(m.validate_value(&Tree::nested(4)).is_none());
+    assert!(Tree::default_mutator().validate_value(&Tree::nested(4)).is_some());
+
+    for _ in 0..1000 {
+        let (value, _) = m.random_arbitrary(2000.0);
+        assert!(value.depth() <= 3);
+    }
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..1000 {
+        let (value, _) = m.ordered_arbitrary(&mut step, 2000.0).unwrap();
+        assert!(value.depth() <= 3);
+    }
+
+    let mut value = Tree::nested(3);
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    for _ in 0..1000 {
+        let (token, _) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 2000.0)
+            .unwrap();
+        assert!(value.depth() <= 3, "{value:?}");
+        m.unmutate(&mut value, &mut cache, token);
+
+        let (token, _) = m.random_mutate(&mut value, &mut cache, 2000.0);
+        assert!(value.depth() <= 3, "{value:?}");
+        m.unmutate(&mut value, &mut cache, token);
+    }
+    assert_eq!(value, Tree::nested(3));
+}
+
+#[test]
+fn test_recursive_mutator_max_depth_zero() {
+    let m = Tree::default_mutator().with_max_depth(0);
+    m.initialize();
+    for _ in 0..100 {
+        let (value, _) = m.random_arbitrary(2000.0);
+        assert!(value.children.is_empty());
+    }
+    test_mutator(m, 500.0, 500.0, false, true, 50, 100);
+
+    let m = Tree::default_mutator().with_max_depth(2);
+    test_mutator(m, 500.0, 500.0, false, true, 50, 100);
+}
@@ -0,0 +1,71 @@
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::result::ResultMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_result_fuzz_err_only() {
+    let m = ResultMutator::<String, u8, _, _>::fuzz_err_only("ok".to_owned(), U8Mutator::default());
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+
+    let m = ResultMutator::<String, u8, _, _>::fuzz_err_only("ok".to_owned(), U8Mutator::default());
+    m.initialize();
+    for _ in 0..1000 {
+        assert!(m.random_arbitrary(100.0).0.is_err());
+    }
+    let mut step = m.default_arbitrary_step();
+    let mut nbr_values = 0;
+    while let Some((x, _)) = m.ordered_arbitrary(&mut step, 100.0) {
+        assert!(x.is_err());
+        nbr_values += 1;
+    }
+    assert_eq!(nbr_values, 256);
+
+    // any `Ok` value is valid, is simpler than any `Err` value, and is mutated into `Err`
+    let mut ok = Ok("not the pinned value".to_owned());
+    let mut cache = m.validate_value(&ok).unwrap();
+    let err = Err(0);
+    assert!(m.complexity(&ok, &cache) < m.complexity(&err, &m.validate_value(&err).unwrap()));
+    let mut step = m.default_mutation_step(&ok, &cache);
+    for _ in 0..100 {
+        let (token, _) = m
+            .ordered_mutate(&mut ok, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        assert!(ok.is_err());
+        m.unmutate(&mut ok, &mut cache, token);
+        assert!(ok.is_ok());
+    }
+
+    let mut value = Err(8);
+    let mut cache = m.validate_value(&value).unwrap();
+    for _ in 0..1000 {
+        let (token, _) = m.random_mutate(&mut value, &mut cache, 100.0);
+        assert!(value.is_err());
+        m.unmutate(&mut value, &mut cache, token);
+    }
+}
+
+#[test]
+fn test_result_fuzz_ok_only() {
+    let m = ResultMutator::<u8, String, _, _>::fuzz_ok_only(U8Mutator::default(), "error".to_owned());
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+
+    let m = ResultMutator::<u8, String, _, _>::fuzz_ok_only(U8Mutator::default(), "error".to_owned());
+    m.initialize();
+    for _ in 0..1000 {
+        assert!(m.random_arbitrary(100.0).0.is_ok());
+    }
+
+    let mut err = Err("error".to_owned());
+    let mut cache = m.validate_value(&err).unwrap();
+    let mut step = m.default_mutation_step(&err, &cache);
+    for _ in 0..100 {
+        let (token, _) = m
+            .ordered_mutate(&mut err, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        assert!(err.is_ok());
+        m.unmutate(&mut err, &mut cache, token);
+        assert_eq!(err, Err("error".to_owned()));
+    }
+}
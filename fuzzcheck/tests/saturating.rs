@@ -0,0 +1,65 @@
+use std::num::Saturating;
+
+use fuzzcheck::mutators::integer_within_range::U8WithinRangeMutator;
+use fuzzcheck::mutators::saturating::SaturatingMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_saturating_mutator() {
+    let m = <Saturating<u8>>::default_mutator();
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+    let m = <Saturating<i64>>::default_mutator();
+    test_mutator(m, 100.0, 100.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_saturating_mutator_boundaries() {
+    let m = <Saturating<i32>>::default_mutator();
+    m.initialize();
+    let inner = i32::default_mutator();
+    let value = Saturating(12);
+    assert!(m.validate_value(&value).is_some());
+    assert_eq!(m.complexity(&value, &()), inner.complexity(&12, &()));
+
+    let mut step = m.default_arbitrary_step();
+    assert_eq!(
+        m.ordered_arbitrary(&mut step, 100.0),
+        Some((Saturating(i32::MAX), 32.0))
+    );
+    assert_eq!(
+        m.ordered_arbitrary(&mut step, 100.0),
+        Some((Saturating(i32::MIN), 32.0))
+    );
+
+    let nbr_boundaries = (0..1000)
+        .filter(|_| {
+            let x = m.random_arbitrary(100.0).0;
+            x.0 == i32::MAX || x.0 == i32::MIN
+        })
+        .count();
+    assert!(nbr_boundaries > 150, "{nbr_boundaries}");
+
+    let mut value = Saturating(12);
+    let mut cache = ();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let mut mutated = vec![];
+    for _ in 0..10 {
+        let (token, _) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        mutated.push(value.0);
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, Saturating(12));
+    }
+    assert_eq!(&mutated[..2], &[i32::MAX, i32::MIN]);
+
+    // the boundaries rejected by the inner mutator are ignored
+    let m = SaturatingMutator::new(U8WithinRangeMutator::new(10..=20), vec![u8::MAX, 20]);
+    let mut step = m.default_arbitrary_step();
+    assert_eq!(m.ordered_arbitrary(&mut step, 100.0).unwrap().0, Saturating(20));
+    for _ in 0..1000 {
+        assert!((10..=20).contains(&m.random_arbitrary(100.0).0 .0));
+    }
+}
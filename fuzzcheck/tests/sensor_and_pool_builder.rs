@@ -0,0 +1,43 @@
+use std::ptr::addr_of_mut;
+
+use fuzzcheck::builder::SensorAndPoolBuilder;
+use fuzzcheck::sensors_and_pools::{MaximiseObservationPool, StaticValueSensor};
+use fuzzcheck::Arguments;
+
+static mut VALUE: u8 = 0;
+static mut LEADING_ONES: u32 = 0;
+
+fn test_function(x: &u8) -> bool {
+    unsafe {
+        VALUE = *x;
+        LEADING_ONES = x.leading_ones();
+    }
+    true
+}
+
+#[test]
+fn test_sensor_and_pool_builder() {
+    let value_sensor = unsafe { StaticValueSensor::new(&mut *addr_of_mut!(VALUE), 0) };
+    let leading_ones_sensor = unsafe { StaticValueSensor::new(&mut *addr_of_mut!(LEADING_ONES), 0) };
+    let (sensor, pool) = SensorAndPoolBuilder::new(value_sensor, MaximiseObservationPool::<u8>::new("max_value"))
+        .with_sensor(leading_ones_sensor)
+        .with_weighted_pool(MaximiseObservationPool::<u32>::new("max_leading_ones"), Some(2.0))
+        .finish();
+    assert_eq!(pool.p2_weight, 2.0);
+
+    let result = fuzzcheck::fuzz_test(test_function)
+        .default_mutator()
+        .serde_serializer()
+        .sensor_and_pool(sensor, pool)
+        .arguments(Arguments::for_internal_documentation_test())
+        .stop_after_iterations(2000)
+        .launch_and_return();
+
+    // both pools processed the observations of their own sensor
+    assert!(result.pool_stats.contains("max_value(255)"), "{}", result.pool_stats);
+    assert!(
+        result.pool_stats.contains("max_leading_ones(8)"),
+        "{}",
+        result.pool_stats
+    );
+}
@@ -0,0 +1,56 @@
+#![allow(unused_attributes)]
+#![feature(coverage_attribute)]
+
+use fuzzcheck::mutators::sequence::SequenceMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+enum Message {
+    Open,
+    Send(u8),
+    Close,
+}
+
+/// Every message is sent within a session, and sessions are not nested
+fn is_valid_session(messages: &[Message]) -> bool {
+    let mut is_open = false;
+    messages.iter().all(|message| match message {
+        Message::Open => !std::mem::replace(&mut is_open, true),
+        Message::Send(_) => is_open,
+        Message::Close => std::mem::replace(&mut is_open, false),
+    })
+}
+
+#[test]
+fn test_sequence_mutator() {
+    let m = SequenceMutator::new(Message::default_mutator(), 0..=usize::MAX, is_valid_session);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_sequence_mutator_keeps_invariant() {
+    let m = SequenceMutator::new(Message::default_mutator(), 0..=10, is_valid_session);
+    m.initialize();
+
+    assert!(m
+        .validate_value(&vec![Message::Open, Message::Send(1), Message::Close])
+        .is_some());
+    assert!(m.validate_value(&vec![Message::Send(1)]).is_none());
+    assert!(m.validate_value(&vec![Message::Open, Message::Open]).is_none());
+
+    let mut value = vec![Message::Open, Message::Send(1), Message::Send(2), Message::Close];
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut nbr_long_sequences = 0;
+    for _ in 0..1000 {
+        let (_, _) = m.random_mutate(&mut value, &mut cache, 1000.0);
+        assert!(is_valid_session(&value), "{value:?}");
+        assert!(value.len() <= 10);
+        if value.len() > 4 {
+            nbr_long_sequences += 1;
+        }
+        cache = m.validate_value(&value).unwrap();
+    }
+    // messages are inserted, not only removed or mutated
+    assert!(nbr_long_sequences > 0);
+}
@@ -0,0 +1,46 @@
+use fuzzcheck::mutators::shrink_toward::ShrinkTowardMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_shrink_toward_mutator() {
+    let m = ShrinkTowardMutator::new(<Vec<u8>>::default_mutator(), vec![1, 2, 3]);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+    let m = ShrinkTowardMutator::new(bool::default_mutator(), true);
+    test_mutator(m, 500.0, 500.0, false, true, 2, 2);
+}
+
+/// Keeps the first ordered mutation of each value only if it brings the value closer
+/// to the target, and returns the final distance to the target
+fn shrink<M: Mutator<Vec<u8>>>(m: &M, target: &Vec<u8>, rounds: usize) -> f64 {
+    let inner = <Vec<u8>>::default_mutator();
+    inner.initialize();
+    m.initialize();
+    let mut value = (0..40).map(|i| i as u8).collect::<Vec<_>>();
+    for _ in 0..rounds {
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        let before = inner.distance(&value, target).unwrap();
+        match m.ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 1000.0) {
+            Some((token, _)) if inner.distance(&value, target).unwrap() >= before => {
+                m.unmutate(&mut value, &mut cache, token);
+            }
+            _ => {}
+        }
+    }
+    inner.distance(&value, target).unwrap()
+}
+
+#[test]
+fn test_shrink_toward_mutator_moves_closer_to_target() {
+    let target = b"GET / HTTP/1.1".to_vec();
+    let m = ShrinkTowardMutator::new(<Vec<u8>>::default_mutator(), target.clone());
+    let distance = shrink(&m, &target, 200);
+    let distance_without_target = shrink(&<Vec<u8>>::default_mutator(), &target, 200);
+    assert!(distance < 0.8, "{distance}");
+    assert!(
+        distance < distance_without_target,
+        "{distance} {distance_without_target}"
+    );
+}
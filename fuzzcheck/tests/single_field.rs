@@ -0,0 +1,62 @@
+#![allow(unused_attributes)]
+#![feature(coverage_attribute)]
+use fuzzcheck::mutators::single_field::SingleFieldMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DefaultMutator)]
+struct Config {
+    name: String,
+    retries: Vec<u8>,
+    verbose: bool,
+}
+
+#[coverage(off)]
+fn base() -> Config {
+    Config {
+        name: "server".to_owned(),
+        retries: vec![1, 2, 3],
+        verbose: true,
+    }
+}
+
+#[test]
+fn test_single_field_mutator() {
+    let m = SingleFieldMutator::new(
+        base(),
+        <Vec<u8>>::default_mutator(),
+        |c: &Config| &c.retries,
+        |c: &mut Config| &mut c.retries,
+    );
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+}
+
+#[test]
+fn test_single_field_mutator_keeps_other_fields() {
+    let m = SingleFieldMutator::new(
+        base(),
+        <Vec<u8>>::default_mutator(),
+        |c: &Config| &c.retries,
+        |c: &mut Config| &mut c.retries,
+    );
+    m.initialize();
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..100 {
+        let (x, _) = m.ordered_arbitrary(&mut step, 100.0).unwrap();
+        assert_eq!((x.name.as_str(), x.verbose), ("server", true));
+        let (x, _) = m.random_arbitrary(100.0);
+        assert_eq!((x.name.as_str(), x.verbose), ("server", true));
+    }
+    let mut x = base();
+    let mut cache = m.validate_value(&x).unwrap();
+    let mut step = m.default_mutation_step(&x, &cache);
+    for _ in 0..100 {
+        let (token, _) = m
+            .ordered_mutate(&mut x, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        assert_eq!((x.name.as_str(), x.verbose), ("server", true));
+        m.unmutate(&mut x, &mut cache, token);
+        assert_eq!(x, base());
+    }
+}
@@ -0,0 +1,71 @@
+use fuzzcheck::mutators::fixed_len_vector::FixedLenVecMutator;
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::tagged_union::TaggedUnionMutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::Mutator;
+
+fn mutator() -> TaggedUnionMutator<FixedLenVecMutator<u8, U8Mutator>> {
+    TaggedUnionMutator::new(vec![
+        (
+            7,
+            FixedLenVecMutator::new_with_repeated_mutator(U8Mutator::default(), 2),
+        ),
+        (
+            42,
+            FixedLenVecMutator::new_with_repeated_mutator(U8Mutator::default(), 5),
+        ),
+        (
+            200,
+            FixedLenVecMutator::new_with_repeated_mutator(U8Mutator::default(), 1),
+        ),
+    ])
+}
+
+fn payload_len(tag: u8) -> usize {
+    match tag {
+        7 => 2,
+        42 => 5,
+        200 => 1,
+        _ => panic!("unknown tag {tag}"),
+    }
+}
+
+#[test]
+fn test_tagged_union_mutator() {
+    test_mutator(mutator(), 100.0, 100.0, false, true, 100, 200);
+}
+
+#[test]
+fn test_tagged_union_mutator_layouts() {
+    let m = mutator();
+    m.initialize();
+    assert!(m.validate_value(&vec![]).is_none());
+    assert!(m.validate_value(&vec![3, 1, 2]).is_none());
+    assert!(m.validate_value(&vec![7, 1]).is_none());
+    assert!(m.validate_value(&vec![7, 1, 2]).is_some());
+    assert!(m.validate_value(&vec![200, 0]).is_some());
+
+    let mut tags = vec![];
+    for _ in 0..100 {
+        let (mut value, _) = m.random_arbitrary(100.0);
+        assert_eq!(value.len(), 1 + payload_len(value[0]));
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        for _ in 0..20 {
+            let old_value = value.clone();
+            let (token, cplx) = m
+                .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+                .unwrap();
+            assert_eq!(value.len(), 1 + payload_len(value[0]));
+            let other_cache = m.validate_value(&value).unwrap();
+            assert_eq!(cplx, m.complexity(&value, &other_cache));
+            tags.push(value[0]);
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, old_value);
+        }
+    }
+    for tag in [7, 42, 200] {
+        assert!(tags.contains(&tag));
+    }
+}
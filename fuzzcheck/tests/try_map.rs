@@ -0,0 +1,59 @@
+use fuzzcheck::mutators::integer::U16Mutator;
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{Mutator, MutatorExt};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct Even(u16);
+
+impl Even {
+    fn new(x: u16) -> Option<Self> {
+        if x & 1 == 0 {
+            Some(Self(x))
+        } else {
+            None
+        }
+    }
+}
+
+#[test]
+fn test_try_map_mutator() {
+    let m = VecMutator::new(U16Mutator::default().try_map(|x| Even::new(*x), |e| Some(e.0)), 0..=10);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 150);
+}
+
+#[test]
+fn test_try_map_mutator_skips_unmappable_values() {
+    let m = U16Mutator::default().try_map(|x| Even::new(*x), |e| Some(e.0));
+    let mut step = m.default_arbitrary_step();
+    for _ in 0..100 {
+        let (x, _) = m.ordered_arbitrary(&mut step, 16.0).unwrap();
+        assert_eq!(x.0 % 2, 0);
+    }
+    for _ in 0..100 {
+        let (mut x, _) = m.random_arbitrary(16.0);
+        assert_eq!(x.0 % 2, 0);
+        let mut cache = m.validate_value(&x).unwrap();
+        let mut step = m.default_mutation_step(&x, &cache);
+        for _ in 0..20 {
+            let old = x.clone();
+            let (token, _) = m.random_mutate(&mut x, &mut cache, 16.0);
+            assert_eq!(x.0 % 2, 0);
+            m.unmutate(&mut x, &mut cache, token);
+            assert_eq!(x, old);
+            if let Some((_, _)) = m.ordered_mutate(&mut x, &mut cache, &mut step, &EmptySubValueProvider, 16.0) {
+                assert_eq!(x.0 % 2, 0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_try_map_mutator_distance() {
+    let m = U16Mutator::default().try_map(|x| Even::new(*x), |e| Some(e.0));
+    let inner = U16Mutator::default();
+    assert!(m.distance(&Even(2), &Even(200)).is_some());
+    assert_eq!(m.distance(&Even(2), &Even(200)), inner.distance(&2, &200));
+    assert_eq!(m.distance(&Even(4), &Even(4)), Some(0.0));
+}
@@ -0,0 +1,61 @@
+#![allow(unused_attributes)]
+#![feature(coverage_attribute)]
+use fuzzcheck::mutators::boxed::BoxMutator;
+use fuzzcheck::mutators::integer::U8Mutator;
+use fuzzcheck::mutators::map::MapMutator;
+use fuzzcheck::mutators::tuples::{Tuple2Mutator, TupleMutatorWrapper};
+use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::{make_mutator, DefaultMutator, Mutator, MutatorExt};
+
+type U8ToStringMutator =
+    MapMutator<u8, String, U8Mutator, fn(&String) -> Option<u8>, fn(&u8) -> String, fn(&String, f64) -> f64>;
+
+fn u8_to_string_mutator() -> U8ToStringMutator {
+    MapMutator::new(
+        u8::default_mutator(),
+        |s: &String| s.parse::<u8>().ok(),
+        |x: &u8| x.to_string(),
+        |_: &String, cplx: f64| cplx,
+    )
+}
+
+#[derive(Clone, Debug)]
+struct FlagAndNumber {
+    flag: bool,
+    number: String,
+}
+
+make_mutator! {
+    name: FlagAndNumberMutator,
+    default: false,
+    type: struct FlagAndNumber {
+        flag: bool,
+        #[field_mutator(U8ToStringMutator = { u8_to_string_mutator() })]
+        number: String,
+    }
+}
+
+#[test]
+fn test_unmutate_is_cheap() {
+    assert!(<Vec<u8>>::default_mutator().unmutate_is_cheap());
+    assert!(<Option<Box<u16>>>::default_mutator().unmutate_is_cheap());
+
+    // a map mutator recomputes the mapped value in unmutate
+    let m = u8::default_mutator().map(|x: &u8| x.to_string(), |s: &String| s.parse::<u8>().ok());
+    assert!(!m.unmutate_is_cheap());
+
+    // the hint is passed on by the mutators wrapping it
+    let m = VecMutator::new(BoxMutator::new(m), 0..=10);
+    assert!(!m.unmutate_is_cheap());
+}
+
+#[test]
+fn test_unmutate_is_cheap_tuple_and_derived_struct() {
+    let m = TupleMutatorWrapper::new(Tuple2Mutator::new(bool::default_mutator(), u8_to_string_mutator()));
+    assert!(!Mutator::<(bool, String)>::unmutate_is_cheap(&m));
+
+    let m = FlagAndNumberMutator::new(bool::default_mutator(), u8_to_string_mutator());
+    assert!(!m.unmutate_is_cheap());
+    let m = <(bool, u8)>::default_mutator();
+    assert!(Mutator::<(bool, u8)>::unmutate_is_cheap(&m));
+}
@@ -0,0 +1,56 @@
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::utf8::Utf8FuzzMutator;
+use fuzzcheck::Mutator;
+
+#[test]
+fn test_utf8_fuzz_mutator() {
+    let m = Utf8FuzzMutator::new();
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_utf8_fuzz_mutator_mixes_valid_and_invalid_utf8() {
+    let m = Utf8FuzzMutator::new();
+    m.initialize();
+    let mut nbr_valid_non_ascii = 0;
+    let mut nbr_invalid = 0;
+    for _ in 0..1000 {
+        let (value, _) = m.random_arbitrary(200.0);
+        match std::str::from_utf8(&value) {
+            Ok(s) if !s.is_ascii() => nbr_valid_non_ascii += 1,
+            Ok(_) => {}
+            Err(_) => nbr_invalid += 1,
+        }
+    }
+    assert!(nbr_valid_non_ascii > 0);
+    assert!(nbr_invalid > 0);
+}
+
+#[test]
+fn test_utf8_fuzz_mutator_accepts_any_bytes() {
+    let m = Utf8FuzzMutator::new();
+    m.initialize();
+    for bytes in [
+        vec![],
+        b"hello".to_vec(),
+        "h\u{e9}llo \u{1F600}".as_bytes().to_vec(),
+        vec![0xFF, 0xC0, 0xAF, 0xED, 0xA0, 0x80, b'a', 0xE2, 0x82],
+    ] {
+        let mut value = bytes.clone();
+        let mut cache = m.validate_value(&value).unwrap();
+        let mut step = m.default_mutation_step(&value, &cache);
+        for _ in 0..100 {
+            let Some((token, _)) = m.ordered_mutate(
+                &mut value,
+                &mut cache,
+                &mut step,
+                &fuzzcheck::subvalue_provider::EmptySubValueProvider,
+                200.0,
+            ) else {
+                break;
+            };
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value, bytes);
+        }
+    }
+}
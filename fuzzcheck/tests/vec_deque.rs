@@ -0,0 +1,84 @@
+use std::collections::VecDeque;
+
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::vec_deque::VecDequeMutator;
+use fuzzcheck::subvalue_provider::EmptySubValueProvider;
+use fuzzcheck::{DefaultMutator, Mutator};
+
+#[test]
+fn test_vec_deque_mutator() {
+    let m = <VecDeque<u8>>::default_mutator();
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+    let m = <VecDeque<VecDeque<u16>>>::default_mutator();
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+    let m = <VecDeque<Vec<u8>>>::default_mutator();
+    test_mutator(m, 200.0, 200.0, false, true, 100, 100);
+    let m = <VecDeque<()>>::default_mutator();
+    test_mutator(m, 20.0, 20.0, false, true, 100, 100);
+    let m = VecDequeMutator::new(bool::default_mutator(), 2..=4);
+    test_mutator(m, 500.0, 500.0, false, true, 100, 100);
+}
+
+#[test]
+fn test_vec_deque_mutator_has_vector_complexity() {
+    let m = <VecDeque<u8>>::default_mutator();
+    let vec_m = <Vec<u8>>::default_mutator();
+    m.initialize();
+    vec_m.initialize();
+    assert_eq!(m.min_complexity(), vec_m.min_complexity());
+    for _ in 0..100 {
+        let (value, cplx) = m.random_arbitrary(200.0);
+        let vec = value.iter().copied().collect::<Vec<_>>();
+        let vec_cache = vec_m.validate_value(&vec).unwrap();
+        assert_eq!(cplx, vec_m.complexity(&vec, &vec_cache));
+    }
+}
+
+#[test]
+fn test_vec_deque_mutator_changes_both_ends() {
+    let m = VecDequeMutator::new(u8::default_mutator(), 0..=10);
+    m.initialize();
+    let mut value = VecDeque::from(vec![1, 2, 3, 4]);
+    let mut cache = m.validate_value(&value).unwrap();
+    let mut step = m.default_mutation_step(&value, &cache);
+    let (mut front_changed, mut back_changed) = (false, false);
+    for _ in 0..100 {
+        let (token, _) = m
+            .ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 100.0)
+            .unwrap();
+        front_changed |= value.front() != Some(&1);
+        back_changed |= value.back() != Some(&4);
+        m.unmutate(&mut value, &mut cache, token);
+        assert_eq!(value, [1, 2, 3, 4]);
+    }
+    assert!(front_changed && back_changed);
+}
+
+#[test]
+fn test_vec_deque_mutator_unmutate_restores_both_ends() {
+    let m = <VecDeque<Vec<u8>>>::default_mutator();
+    m.initialize();
+    for _ in 0..100 {
+        let (original, _) = m.random_arbitrary(300.0);
+        let original_cache = m.validate_value(&original).unwrap();
+        let original_cplx = m.complexity(&original, &original_cache);
+        let mut value = original.clone();
+        let mut cache = original_cache.clone();
+        let mut step = m.default_mutation_step(&value, &cache);
+        for j in 0..50 {
+            let (token, _) = if j % 2 == 0 {
+                match m.ordered_mutate(&mut value, &mut cache, &mut step, &EmptySubValueProvider, 300.0) {
+                    Some(result) => result,
+                    None => break,
+                }
+            } else {
+                m.random_mutate(&mut value, &mut cache, 300.0)
+            };
+            m.unmutate(&mut value, &mut cache, token);
+            assert_eq!(value.front(), original.front());
+            assert_eq!(value.back(), original.back());
+            assert_eq!(value, original);
+            assert_eq!(m.complexity(&value, &cache), original_cplx);
+        }
+    }
+}
@@ -1,11 +1,28 @@
 use fuzzcheck::mutators::integer::U8Mutator;
-use fuzzcheck::mutators::vector::VecMutator;
+use fuzzcheck::mutators::vector::{ComplexityDistribution, VecMutator};
+use fuzzcheck::{DefaultMutator, Mutator};
 #[test]
 fn test_vector_mutator() {
     let m = VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=usize::MAX), 0..=usize::MAX);
     fuzzcheck::mutators::testing_utilities::test_mutator(m, 500.0, 500.0, false, true, 100, 150);
 }
 
+#[test]
+fn test_vector_mutator_complexity_consistency() {
+    let m = VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=10), 0..=10);
+    fuzzcheck::mutators::testing_utilities::check_complexity_consistency(&m, 1000, 500.0);
+}
+
+#[test]
+fn test_vector_mutator_rng_state() {
+    let m = VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=usize::MAX), 0..=usize::MAX);
+    let state = m.rng_state().unwrap();
+    let first = (0..10).map(|_| m.random_arbitrary(500.0).0).collect::<Vec<_>>();
+    m.set_rng_state(&state);
+    let second = (0..10).map(|_| m.random_arbitrary(500.0).0).collect::<Vec<_>>();
+    assert_eq!(first, second);
+}
+
 // #[test]
 // fn test_vector_explore() {
 //     // let m = VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=5), 0..=5);
@@ -76,3 +93,46 @@ fn test_vector_mutator() {
 //         }
 //     }
 // }
+
+#[test]
+fn test_vector_mutator_distance() {
+    use fuzzcheck::DefaultMutator;
+
+    let m = VecMutator::new(U8Mutator::default(), 0..=usize::MAX);
+    assert_eq!(m.distance(&vec![], &vec![]), Some(0.0));
+    assert_eq!(m.distance(&vec![1, 2, 3], &vec![1, 2, 3]), Some(0.0));
+    assert_eq!(m.distance(&vec![1, 2, 3], &vec![]), Some(1.0));
+    assert_eq!(m.distance(&vec![1, 2, 3, 4], &vec![1, 9, 3, 4]), Some(0.25));
+    assert_eq!(m.distance(&vec![1, 2, 3, 4], &vec![2, 3, 4]), Some(0.25));
+
+    let m = VecMutator::new(VecMutator::new(U8Mutator::default(), 0..=usize::MAX), 0..=usize::MAX);
+    assert_eq!(m.distance(&vec![vec![1, 2]], &vec![vec![1, 3]]), Some(0.5));
+
+    let m = String::default_mutator();
+    assert_eq!(m.distance(&"kitten".to_owned(), &"sitting".to_owned()), Some(3.0 / 7.0));
+}
+
+#[test]
+fn test_vector_mutator_complexity_distribution() {
+    // the average share of the largest element among vectors of exactly 8 elements
+    let largest_share = |distribution| {
+        let m = VecMutator::new(<Vec<u8>>::default_mutator(), 8..=8).with_complexity_distribution(distribution);
+        m.initialize();
+        let (mut nbr_values, mut sum_shares) = (0, 0.0);
+        for _ in 0..2000 {
+            let (v, cplx) = m.random_arbitrary(1000.0);
+            let cache = m.validate_value(&v).unwrap();
+            assert_eq!(m.complexity(&v, &cache), cplx);
+            let lengths = v.iter().map(Vec::len).collect::<Vec<_>>();
+            let total = lengths.iter().sum::<usize>();
+            if total >= 16 {
+                nbr_values += 1;
+                sum_shares += *lengths.iter().max().unwrap() as f64 / total as f64;
+            }
+        }
+        sum_shares / nbr_values as f64
+    };
+    let even = largest_share(ComplexityDistribution::Even);
+    let one_large = largest_share(ComplexityDistribution::OneLarge(0.8));
+    assert!(one_large > even + 0.15, "{one_large} {even}");
+}
@@ -0,0 +1,61 @@
+use fuzzcheck::mutators::testing_utilities::test_mutator;
+use fuzzcheck::mutators::weighted_constructor::WeightedConstructorMutator;
+use fuzzcheck::Mutator;
+
+type Constructor = Box<dyn Fn(&fastrand::Rng) -> u32>;
+
+fn mutator() -> WeightedConstructorMutator<u32> {
+    WeightedConstructorMutator::new(vec![
+        (0.7, Box::new(|rng: &fastrand::Rng| rng.u32(0..100)) as Constructor),
+        (0.2, Box::new(|rng: &fastrand::Rng| rng.u32(100..200))),
+        (0.1, Box::new(|_: &fastrand::Rng| 1000)),
+        (0.0, Box::new(|_: &fastrand::Rng| 2000)),
+    ])
+}
+
+#[test]
+fn test_weighted_constructor_mutator() {
+    test_mutator(mutator(), 100.0, 100.0, false, true, 50, 50);
+}
+
+#[test]
+fn test_weighted_constructor_weights() {
+    let m = mutator();
+    let mut counts = [0usize; 4];
+    for _ in 0..10_000 {
+        let (x, cplx) = m.random_arbitrary(10.0);
+        assert_eq!(cplx, 1.0);
+        let idx = match x {
+            0..=99 => 0,
+            100..=199 => 1,
+            1000 => 2,
+            _ => 3,
+        };
+        counts[idx] += 1;
+    }
+    assert!((6500..7500).contains(&counts[0]), "{counts:?}");
+    assert!((1500..2500).contains(&counts[1]), "{counts:?}");
+    assert!((700..1300).contains(&counts[2]), "{counts:?}");
+    assert_eq!(counts[3], 0);
+}
+
+#[test]
+fn test_weighted_constructor_ordered_arbitrary_cycles() {
+    let m = mutator();
+    let mut step = m.default_arbitrary_step();
+    let values = (0..8)
+        .map(|_| m.ordered_arbitrary(&mut step, 10.0).unwrap().0)
+        .collect::<Vec<_>>();
+    // the constructor with a weight of 0 is skipped
+    assert!(values[0] < 100 && values[3] < 100 && values[6] < 100);
+    assert!((100..200).contains(&values[1]) && (100..200).contains(&values[4]));
+    assert_eq!((values[2], values[5]), (1000, 1000));
+    assert!(m.ordered_arbitrary(&mut step, 0.5).is_none());
+
+    let mut value = 2000;
+    m.validate_value(&value).unwrap();
+    let (token, _) = m.random_mutate(&mut value, &mut (), 10.0);
+    assert_eq!(token, 2000);
+    m.unmutate(&mut value, &mut (), token);
+    assert_eq!(value, 2000);
+}
@@ -20,6 +20,7 @@ pub const COMMAND_FLAG: &str = "command";
 pub const MAX_DURATION_FLAG: &str = "stop-after-duration";
 pub const MAX_ITERATIONS_FLAG: &str = "stop-after-iterations";
 pub const STOP_AFTER_FIRST_FAILURE_FLAG: &str = "stop-after-first-failure";
+pub const MAX_DISTINCT_ERRORS_FLAG: &str = "max-distinct-errors";
 
 pub const DETECT_INFINITE_LOOP_FLAG: &str = "detect-infinite-loop";
 
@@ -60,6 +61,8 @@ pub struct Arguments {
     pub maximum_duration: Duration,
     pub maximum_iterations: usize,
     pub stop_after_first_failure: bool,
+    /// The maximum number of distinct test failures that are tracked, or `None` if there is no limit
+    pub max_distinct_errors: Option<usize>,
     pub corpus_in: Option<PathBuf>,
     pub corpus_out: Option<PathBuf>,
     pub artifacts_folder: Option<PathBuf>,
@@ -74,6 +77,7 @@ impl Arguments {
             maximum_duration: Duration::MAX,
             maximum_iterations: usize::MAX,
             stop_after_first_failure: true,
+            max_distinct_errors: None,
             corpus_in: None,
             corpus_out: None,
             artifacts_folder: None,
@@ -117,6 +121,12 @@ pub fn options_parser() -> Options {
         STOP_AFTER_FIRST_FAILURE_FLAG,
         "stop the fuzzer after the first test failure is found",
     );
+    options.optopt(
+        "",
+        MAX_DISTINCT_ERRORS_FLAG,
+        "maximum number of distinct test failures to keep track of (default: no limit)",
+        "N",
+    );
 
     options.optopt("", IN_CORPUS_FLAG, "folder for the input corpus", "PATH");
     options.optflag(
@@ -316,6 +326,10 @@ impl Arguments {
             )
             .unwrap_or(usize::MAX);
         let stop_after_first_failure = matches.opt_present(STOP_AFTER_FIRST_FAILURE_FLAG);
+        let max_distinct_errors = matches.opt_str(MAX_DISTINCT_ERRORS_FLAG).and_then(
+            #[coverage(off)]
+            |x| x.parse::<usize>().ok(),
+        );
 
         let defaults = DefaultArguments::default();
         let max_input_cplx: f64 = max_input_cplx.unwrap_or(defaults.max_input_cplx as f64);
@@ -331,6 +345,7 @@ impl Arguments {
             maximum_duration,
             maximum_iterations,
             stop_after_first_failure,
+            max_distinct_errors,
             max_input_cplx,
             corpus_in,
             corpus_out,
@@ -82,7 +82,7 @@ pub fn make_mutator(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     derive_default_mutator_(settings).into()
 }
 
-#[proc_macro_derive(DefaultMutator, attributes(field_mutator, ignore_variant))]
+#[proc_macro_derive(DefaultMutator, attributes(field_mutator, ignore_variant, fuzzcheck))]
 pub fn derive_default_mutator(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let settings = MakeMutatorSettings {
         name: None,
@@ -229,10 +229,12 @@ impl Parse for MakeMutatorSettings {
 pub(crate) struct Common {
     AlternationMutator: TokenStream,
     Any: TokenStream,
+    Cell: TokenStream,
     Clone: TokenStream,
     Default: TokenStream,
     DefaultMutator: TokenStream,
     CrossoverStep: TokenStream,
+    EffortHint: TokenStream,
     fastrand_Rng: TokenStream,
     mutators: TokenStream,
     // fuzzcheck_mutator_traits_Mutator: TokenStream,
@@ -264,6 +266,7 @@ pub(crate) struct Common {
     Box: TokenStream,
     SubValueProvider: TokenStream,
     NeverMutator: TokenStream,
+    OptionBoxMutator: TokenStream,
 }
 impl Common {
     #[allow(non_snake_case)]
@@ -295,8 +298,10 @@ impl Common {
         Self {
             AlternationMutator: ts!(mutators "::alternation::AlternationMutator"),
             Any: ts!("::std::any::Any"),
+            Cell: ts!("::std::cell::Cell"),
             Clone: ts!("::std::clone::Clone"),
             CrossoverStep: ts!("fuzzcheck::mutators::CrossoverStep"),
+            EffortHint: ts!("fuzzcheck::EffortHint"),
             Default: ts!("::std::default::Default"),
             DefaultMutator: ts!(mutators "::DefaultMutator"),
             fastrand_Rng,
@@ -328,6 +333,7 @@ impl Common {
             RecursiveMutator: ts!(mutators "::recursive::RecursiveMutator"),
             Box: ts!("::std::boxed::Box"),
             NeverMutator: ts!("::fuzzcheck::mutators::never::NeverMutator"),
+            OptionBoxMutator: ts!(mutators "::option_box::OptionBoxMutator"),
             SubValueProvider: ts!("fuzzcheck::SubValueProvider"),
         }
     }
@@ -343,6 +349,59 @@ fn has_ignore_variant_attribute(attribute: &Attribute) -> bool {
     }
 }
 
+/// The functions given by `#[fuzzcheck(complexity = "..", max_complexity = "..", min_complexity = "..")]`
+///
+/// Each function receives the complexity that would have been computed without the
+/// attribute and returns the actual complexity. The `complexity` function also receives
+/// the value, as `fn(&T, f64) -> f64`, but not the cache of the generated mutator.
+#[derive(Default)]
+struct ComplexityAttribute {
+    complexity: Option<syn::Path>,
+    max_complexity: Option<syn::Path>,
+    min_complexity: Option<syn::Path>,
+}
+
+fn read_complexity_attribute(attributes: &[Attribute]) -> Result<ComplexityAttribute, syn::Error> {
+    let mut result = ComplexityAttribute::default();
+    for attribute in attributes {
+        if !attribute.path.is_ident("fuzzcheck") {
+            continue;
+        }
+        let syn::Meta::List(list) = attribute.parse_meta()? else {
+            return Err(Error::new_spanned(attribute, "Expected #[fuzzcheck(..)]"));
+        };
+        for nested in list.nested.iter() {
+            let syn::NestedMeta::Meta(syn::Meta::NameValue(name_value)) = nested else {
+                return Err(Error::new_spanned(
+                    nested,
+                    "Expected a setting of the form `name = \"value\"`",
+                ));
+            };
+            let syn::Lit::Str(lit) = &name_value.lit else {
+                return Err(Error::new_spanned(
+                    &name_value.lit,
+                    "Expected a string containing the path to a function",
+                ));
+            };
+            let path = lit.parse::<syn::Path>()?;
+            let setting = if name_value.path.is_ident("complexity") {
+                &mut result.complexity
+            } else if name_value.path.is_ident("max_complexity") {
+                &mut result.max_complexity
+            } else if name_value.path.is_ident("min_complexity") {
+                &mut result.min_complexity
+            } else {
+                return Err(Error::new_spanned(
+                    &name_value.path,
+                    "Expected one of `complexity`, `max_complexity`, or `min_complexity`",
+                ));
+            };
+            *setting = Some(path);
+        }
+    }
+    Ok(result)
+}
+
 struct FieldMutatorAttribute {
     ty: syn::Type,
     equal: Option<TokenStream>,
@@ -353,6 +412,30 @@ impl syn::parse::Parse for FieldMutatorAttribute {
         let _ = parenthesized!(content in input);
         let input = content;
 
+        // #[field_mutator(regex = "...")] uses a grammar-based string mutator
+        if input.peek(syn::Ident) && input.peek2(Token![=]) && input.peek3(syn::LitStr) {
+            let fork = input.fork();
+            if fork.parse::<Ident>()? == "regex" {
+                let _ = input.parse::<Ident>()?;
+                let _ = input.parse::<Token![=]>()?;
+                let regex = input.parse::<syn::LitStr>()?;
+                if !input.is_empty() {
+                    return Err(syn::Error::new(
+                        input.span(),
+                        "Expected nothing after the regular expression of field_mutator",
+                    ));
+                }
+                return Ok(Self {
+                    ty: parse2(ts!("fuzzcheck::mutators::grammar::GrammarStringMutator"))?,
+                    equal: Some(ts!(
+                        "fuzzcheck::mutators::grammar::grammar_based_string_mutator(fuzzcheck::mutators::grammar::regex("
+                        q!(regex)
+                        "))"
+                    )),
+                });
+            }
+        }
+
         let ty = input.parse::<syn::Type>()?;
         if input.is_empty() {
             return Ok(Self { ty, equal: None });
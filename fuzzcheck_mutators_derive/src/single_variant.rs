@@ -398,6 +398,46 @@ pub fn make_single_variant_mutator(
             )" _ => unreachable!()
             }
         }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn set_effort_hint(&self, hint: " cm.EffortHint ") {
+            match self {"
+                join_ts!(&enu.variants, variant,
+                    EnumSingleVariant "::" variant.ident "(m) => { m.set_effort_hint(hint) }"
+                )
+            "}
+        }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn unmutate_is_cheap(&self) -> bool {
+            match self {"
+                join_ts!(&enu.variants, variant,
+                    EnumSingleVariant "::" variant.ident "(m) => { m.unmutate_is_cheap() }"
+                )
+            "}
+        }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn rng_state(&self) -> " cm.Option "<" cm.Vec "<u8>> {
+            match self {"
+                join_ts!(&enu.variants, variant,
+                    EnumSingleVariant "::" variant.ident "(m) => { m.rng_state() }"
+                )
+            "}
+        }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn set_rng_state(&self, state: &[u8]) {
+            match self {"
+                join_ts!(&enu.variants, variant,
+                    EnumSingleVariant "::" variant.ident "(m) => { m.set_rng_state(state) }"
+                )
+            "}
+        }
     }
     ");
 }
@@ -6,6 +6,10 @@ use syn::{parse2, Field, Generics, Visibility, WhereClause};
 use crate::token_builder::{ident, join_ts, ts};
 use crate::{q, Common, MakeMutatorSettings};
 
+/// The maximum number of values that the ordered methods of a mutator with a `complexity`
+/// attribute generate while looking for one whose adjusted complexity is below `max_cplx`
+const MAX_COMPLEXITY_ATTEMPTS: usize = 100;
+
 // This file hosts the common code for generating default mutators for enums and structs
 
 #[derive(Clone)]
@@ -46,6 +50,48 @@ impl FieldMutator {
             FieldMutatorKind::Ignore => ts!(),
         }
     }
+
+    /// The type of the default mutator of a field with a generic mutator
+    fn default_mutator_type(&self, cm: &Common) -> TokenStream {
+        if let Some(inner) = option_box_inner_type(&self.field.ty) {
+            ts!(cm.OptionBoxMutator "<" q!(inner) "," "<" q!(inner) "as" cm.DefaultMutator ">::Mutator" ">")
+        } else {
+            ts!("<" q!(self.field.ty) "as" cm.DefaultMutator ">::Mutator")
+        }
+    }
+
+    /// The expression creating the default mutator of a field with a generic mutator
+    fn default_mutator_expr(&self, cm: &Common) -> TokenStream {
+        if let Some(inner) = option_box_inner_type(&self.field.ty) {
+            ts!(cm.OptionBoxMutator "::new(<" q!(inner) "as" cm.DefaultMutator ">::default_mutator())")
+        } else {
+            ts!("<" q!(self.field.ty) "as" cm.DefaultMutator ">::default_mutator()")
+        }
+    }
+}
+
+/// Returns `T` if the type is written `Option<Box<T>>`.
+///
+/// Such fields are mutated by an `OptionBoxMutator`, which handles both layers at once,
+/// instead of an `OptionMutator` wrapping a `BoxMutator`.
+fn option_box_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    fn single_type_argument<'a>(ty: &'a syn::Type, name: &str) -> Option<&'a syn::Type> {
+        let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+            return None;
+        };
+        let segment = path.segments.last()?;
+        if segment.ident != name {
+            return None;
+        }
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        match args.args.iter().collect::<Vec<_>>().as_slice() {
+            [syn::GenericArgument::Type(ty)] => Some(ty),
+            _ => None,
+        }
+    }
+    single_type_argument(single_type_argument(ty, "Option")?, "Box")
 }
 
 #[allow(non_snake_case)]
@@ -73,6 +119,26 @@ pub(crate) fn make_mutator_type_and_impl(params: CreateWrapperMutatorParams) ->
         settings,
     } = params;
 
+    let complexity_attribute = match crate::read_complexity_attribute(&settings.ty.attrs) {
+        Ok(complexity_attribute) => complexity_attribute,
+        Err(e) => return e.to_compile_error(),
+    };
+    // the user-provided complexity function, applied to the complexity computed by the inner mutator
+    let adjusted_cplx = |value: &str, cplx: &str| {
+        if let Some(complexity) = &complexity_attribute.complexity {
+            ts!(q!(complexity) "(" value "," cplx ")")
+        } else {
+            ts!(cplx)
+        }
+    };
+    let adjusted_bound = |bound: &Option<syn::Path>, cplx: TokenStream| {
+        if let Some(bound) = bound {
+            ts!(q!(bound) "(" cplx ")")
+        } else {
+            cplx
+        }
+    };
+
     let NameMutator = if let Some(name) = &settings.name {
         name.clone()
     } else {
@@ -154,7 +220,7 @@ pub(crate) fn make_mutator_type_and_impl(params: CreateWrapperMutatorParams) ->
                 ","
             )
             join_ts!(field_generic_mutators, field_mutator,
-                "<" q!(field_mutator.field.ty) "as" cm.DefaultMutator ">::Mutator ,"
+                field_mutator.default_mutator_type(cm) ","
             )
         ">"
     );
@@ -299,35 +365,48 @@ Generated by a procedural macro of [`fuzzcheck`]",
             #[doc(hidden)]
             #[coverage(off)]
             fn max_complexity(&self) -> f64 {
-                " InnerMutator_as_Mutator "::max_complexity(&self.mutator)
+                " adjusted_bound(&complexity_attribute.max_complexity, ts!(InnerMutator_as_Mutator "::max_complexity(&self.mutator)")) "
             }
 
             #[doc(hidden)]
             #[coverage(off)]
             fn min_complexity(&self) -> f64 {
-                " InnerMutator_as_Mutator "::min_complexity(&self.mutator)
+                " adjusted_bound(&complexity_attribute.min_complexity, ts!(InnerMutator_as_Mutator "::min_complexity(&self.mutator)")) "
             }
 
             #[doc(hidden)]
             #[coverage(off)]
             fn complexity(&self, value: &" selfty ", cache: &Self::Cache) -> f64 {
-                " InnerMutator_as_Mutator "::complexity(&self.mutator, value, &cache.inner)
+                let cplx = " InnerMutator_as_Mutator "::complexity(&self.mutator, value, &cache.inner);
+                " adjusted_cplx("value", "cplx") "
             }
 
             #[doc(hidden)]
             #[coverage(off)]
-            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(" selfty ", f64)> {
-                if let " cm.Some "((value, cplx)) = " InnerMutator_as_Mutator "::ordered_arbitrary(&self.mutator, &mut step.inner, max_cplx) {"
-                cm.Some "((value, cplx))"
-            "} else {"
-                cm.None
-            "}
+            fn ordered_arbitrary(&self, step: &mut Self::ArbitraryStep, max_cplx: f64) -> Option<(" selfty ", f64)> {"
+            if complexity_attribute.complexity.is_some() {
+                ts!("for _ in 0.." q!(MAX_COMPLEXITY_ATTEMPTS) " {
+                    let (value, cplx) = " InnerMutator_as_Mutator "::ordered_arbitrary(&self.mutator, &mut step.inner, max_cplx)?;
+                    let cplx = " adjusted_cplx("&value", "cplx") ";
+                    if cplx <= max_cplx {
+                        return " cm.Some "((value, cplx));
+                    }
+                }"
+                cm.None)
+            } else {
+                ts!("if let " cm.Some "((value, cplx)) = " InnerMutator_as_Mutator "::ordered_arbitrary(&self.mutator, &mut step.inner, max_cplx) {"
+                    cm.Some "((value, cplx))"
+                "} else {"
+                    cm.None
+                "}")
             }
+            "}
 
             #[doc(hidden)]
             #[coverage(off)]
             fn random_arbitrary(&self, max_cplx: f64) -> (" selfty ", f64) {
                 let (value, cplx) = " InnerMutator_as_Mutator "::random_arbitrary(&self.mutator, max_cplx) ;
+                let cplx = " adjusted_cplx("&value", "cplx") ";
                 (value, cplx)
             }
 
@@ -340,8 +419,26 @@ Generated by a procedural macro of [`fuzzcheck`]",
                 step: &mut Self::MutationStep,
                 subvalue_provider: &dyn " cm.SubValueProvider ",
                 max_cplx: f64,
-            ) -> Option<(Self::UnmutateToken, f64)> {
-                if let " cm.Some "((t, c)) = " InnerMutator_as_Mutator "::ordered_mutate(
+            ) -> Option<(Self::UnmutateToken, f64)> {"
+            if complexity_attribute.complexity.is_some() {
+                ts!("for _ in 0.." q!(MAX_COMPLEXITY_ATTEMPTS) " {
+                    let (t, c) = " InnerMutator_as_Mutator "::ordered_mutate(
+                        &self.mutator,
+                        value,
+                        &mut cache.inner,
+                        &mut step.inner,
+                        subvalue_provider,
+                        max_cplx,
+                    )?;
+                    let c = " adjusted_cplx("value", "c") ";
+                    if c <= max_cplx {
+                        return " cm.Some "((Self::UnmutateToken::new(t), c));
+                    }
+                    " InnerMutator_as_Mutator "::unmutate(&self.mutator, value, &mut cache.inner, t);
+                }"
+                cm.None)
+            } else {
+                ts!("if let " cm.Some "((t, c)) = " InnerMutator_as_Mutator "::ordered_mutate(
                     &self.mutator,
                     value,
                     &mut cache.inner,
@@ -352,13 +449,15 @@ Generated by a procedural macro of [`fuzzcheck`]",
                     " cm.Some "((Self::UnmutateToken::new(t), c))
                 } else {"
                     cm.None
-                "}
+                "}")
             }
+            "}
 
             #[doc(hidden)]
             #[coverage(off)]
             fn random_mutate(&self, value: &mut " selfty ", cache: &mut Self::Cache, max_cplx: f64) -> (Self::UnmutateToken, f64) {
                 let (t, c) =" InnerMutator_as_Mutator "::random_mutate(&self.mutator, value, &mut cache.inner, max_cplx);
+                let c = " adjusted_cplx("value", "c") ";
                 (Self::UnmutateToken::new(t), c)
             }
 
@@ -376,6 +475,26 @@ Generated by a procedural macro of [`fuzzcheck`]",
             fn visit_subvalues<'__fuzzcheck_derive_lt>(&self, value: &'__fuzzcheck_derive_lt " selfty ", cache: &'__fuzzcheck_derive_lt Self::Cache, visit: &mut dyn FnMut(&'__fuzzcheck_derive_lt dyn " cm.Any ", f64)) {
                 " InnerMutator_as_Mutator "::visit_subvalues(&self.mutator, value, &cache.inner, visit);
             }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_effort_hint(&self, hint: " cm.EffortHint ") {
+                " InnerMutator_as_Mutator "::set_effort_hint(&self.mutator, hint)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn unmutate_is_cheap(&self) -> bool {
+                " InnerMutator_as_Mutator "::unmutate_is_cheap(&self.mutator)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn rng_state(&self) -> " cm.Option "<" cm.Vec "<u8>> {
+                " InnerMutator_as_Mutator "::rng_state(&self.mutator)
+            }
+            #[doc(hidden)]
+            #[coverage(off)]
+            fn set_rng_state(&self, state: &[u8]) {
+                " InnerMutator_as_Mutator "::set_rng_state(&self.mutator, state)
+            }
         }"
         if settings.default {
             ts!("impl" q!(type_generics_split.0) cm.DefaultMutator "for" selfty q!(DefaultMutator_Mutator_generics.where_clause) "{"
@@ -396,9 +515,7 @@ Generated by a procedural macro of [`fuzzcheck`]",
                         !variant.kind.is_ignore()
                     }), field_mutator,
                         match &field_mutator.kind {
-                            FieldMutatorKind::Generic => {
-                                ts!("<" q!(field_mutator.field.ty) "as" cm.DefaultMutator ">::default_mutator()")
-                            }
+                            FieldMutatorKind::Generic => field_mutator.default_mutator_expr(cm),
                             FieldMutatorKind::Prescribed(_, Some(init)) => {
                                 ts!("{" init "}")
                             }
@@ -264,6 +264,7 @@ fn declare_tuple_mutator(tb: &mut TokenBuilder, nbr_elements: usize) {
                 ident!("mutator_" i) ":" ident!("M" i) ","
             )
             "rng :" cm.fastrand_Rng ",
+            effort_hint :" cm.Cell "<" cm.EffortHint ">,
         }
 
         impl < " type_params " >" cm.TupleNMutator_ident "<" type_params "> {
@@ -274,6 +275,7 @@ fn declare_tuple_mutator(tb: &mut TokenBuilder, nbr_elements: usize) {
                         ident!("mutator_" i) ","
                     )
                     "rng: <_>::default() ,
+                    effort_hint: <_>::default() ,
                     "
                 "}
             }"
@@ -474,9 +476,11 @@ fn impl_mutator_trait(tb: &mut TokenBuilder, nbr_elements: usize) {
                     ident!("cplx_" i)
                 , separator: "+") ";
 
+            // each field is chosen for mutation with a probability proportional to its
+            // share of the total complexity, with a small floor so that no field is ignored
             let mut probabilities = vec!["
                 join_ts!(0..nbr_elements, i,
-                    "10. +" ident!("cplx_" i)
+                    "1. +" ident!("cplx_" i)
                 , separator: ",") "
             ];
             let vose_alias = " cm.VoseAlias "::new(probabilities);
@@ -559,6 +563,46 @@ fn impl_mutator_trait(tb: &mut TokenBuilder, nbr_elements: usize) {
         }
         #[doc(hidden)]
         #[coverage(off)]
+        fn arbitrary_at_complexity(&self, target_cplx: f64) -> " cm.Option "<(T, f64)> {
+            let tolerance = " cm.mutators "::arbitrary_at_complexity_tolerance(target_cplx);
+            if target_cplx + tolerance < " SelfAsTupleMutator "::min_complexity(self)
+                || target_cplx - tolerance > " SelfAsTupleMutator "::max_complexity(self)
+            {
+                return " cm.None ";
+            }
+            let min_cplxs = ["
+                join_ts!(0..nbr_elements, i, "self." mutator_i(i) ".min_complexity()", separator: ",")
+            "];
+            let max_cplxs = ["
+                join_ts!(0..nbr_elements, i, "self." mutator_i(i) ".max_complexity()", separator: ",")
+            "];
+            for _ in 0.." cm.mutators "::ARBITRARY_AT_COMPLEXITY_ATTEMPTS {
+                let mut sum_cplx = 0.0;"
+                // share the remaining complexity between the remaining fields, while
+                // staying within the complexities that each of them can reach
+                join_ts!(0..nbr_elements, i,
+                    "let remaining_cplx = target_cplx - sum_cplx;
+                    let share = f64::min(
+                        f64::max(
+                            remaining_cplx / (" q!(nbr_elements - i) " as f64),
+                            remaining_cplx - max_cplxs[" q!(i + 1) "..].iter().sum::<f64>(),
+                        ),
+                        remaining_cplx - min_cplxs[" q!(i + 1) "..].iter().sum::<f64>(),
+                    );
+                    let share = f64::min(f64::max(share, min_cplxs[" i "]), max_cplxs[" i "]);
+                    let (" ti_value(i) ", cplx) = self." mutator_i(i) "
+                        .arbitrary_at_complexity(share)
+                        .unwrap_or_else(#[coverage(off)] || self." mutator_i(i) ".random_arbitrary(share));
+                    sum_cplx += cplx;"
+                )
+                "if (sum_cplx - target_cplx).abs() <= tolerance {
+                    return " cm.Some "((T::new((" join_ts!(0..nbr_elements, i, ti_value(i) ",") ")), sum_cplx));
+                }
+            }
+            " cm.None "
+        }
+        #[doc(hidden)]
+        #[coverage(off)]
         fn ordered_mutate<'__fuzzcheck_derive_lt>(
             &self,
             value: " tuple_mut ",
@@ -597,7 +641,7 @@ fn impl_mutator_trait(tb: &mut TokenBuilder, nbr_elements: usize) {
             }
             if max_cplx < <Self as" cm.TupleMutator "<T , " cm.TupleN_ident "<" tuple_type_params "> > >::min_complexity(self) { return " cm.None " }
             if step.inner.is_empty() || step.vose_alias.is_none() {
-                let idx1 = self.rng.usize(.." q!(nbr_elements) ");
+                let idx1 = cache.vose_alias.sample_with(&self.rng);
                 let mut idx2 = self.rng.usize(.." q!(nbr_elements) " - 1);
                 if idx2 >= idx1 {
                     idx2 += 1;
@@ -623,10 +667,10 @@ fn impl_mutator_trait(tb: &mut TokenBuilder, nbr_elements: usize) {
                 return " cm.Some "( (whole_token, current_cplx) );
             }
             let vose_alias = step.vose_alias.as_ref().unwrap();
-            let step_idx = vose_alias.sample();
+            let step_idx = vose_alias.sample_with(&self.rng);
 
             let current_cplx = " SelfAsTupleMutator "::complexity(self, " TupleNAsRefTypes "::get_ref_from_mut(&value), cache); 
-            let inner_step_to_remove: usize;
+            let mut mutated: " cm.Option "<(Self::UnmutateToken, f64, usize)> = " cm.None ";
 
             match step.inner[step_idx] {"
             join_ts!(0..nbr_elements, i,
@@ -637,18 +681,44 @@ fn impl_mutator_trait(tb: &mut TokenBuilder, nbr_elements: usize) {
                         self." mutator_i(i) "
                             .ordered_mutate(value." i ", &mut cache." ti(i) ", &mut step." ti(i) ", subvalue_provider, max_field_cplx)
                     {
-                        return " cm.Some "((Self::UnmutateToken {
+                        mutated = " cm.Some "((Self::UnmutateToken {
                             " ti(i) ": " cm.Some "(UnmutateElementToken::Unmutate(token)),
                             ..Self::UnmutateToken::default()
-                        }, current_cplx - old_field_cplx + new_field_cplx));
-                    } else {
-                        inner_step_to_remove = step_idx;
+                        }, current_cplx - old_field_cplx + new_field_cplx, " i "));
                     }
                 }"
             )"
             }
+            if let " cm.Some "((mut whole_token, mut current_cplx, mutated_idx)) = mutated {"
+            if nbr_elements > 1 {
+                ts!("
+                // when the test function is expensive, also mutate another field at random
+                if self.effort_hint.get() == " cm.EffortHint "::Expensive {
+                    let mut idx = self.rng.usize(.." q!(nbr_elements) " - 1);
+                    if idx >= mutated_idx {
+                        idx += 1;
+                    }
+                    match idx {"
+                    join_ts!(0..nbr_elements, i,
+                        i "=> {
+                            let old_field_cplx = self." mutator_i(i) ".complexity(value." i ", &cache." ti(i) ");
+                            let max_field_cplx = max_cplx - current_cplx + old_field_cplx;
+                            let (token, new_field_cplx) = self." mutator_i(i) "
+                                .random_mutate(value." i ", &mut cache." ti(i) ", max_field_cplx);
+                            whole_token. " ti(i) " = " cm.Some "(UnmutateElementToken::Unmutate(token));
+                            current_cplx = current_cplx - old_field_cplx + new_field_cplx;
+                        }"
+                    )
+                    "_ => unreachable!()"
+                    "}
+                }")
+            } else {
+                ts!("let _ = mutated_idx;")
+            }
+                "return " cm.Some "((whole_token, current_cplx));
+            }
             let mut prob = vose_alias.original_probabilities.clone();
-            prob[inner_step_to_remove] = 0.0;
+            prob[step_idx] = 0.0;
             let sum = prob.iter().sum::<f64>();
             if sum == 0.0 {
                 step.vose_alias = " cm.None ";
@@ -661,7 +731,7 @@ fn impl_mutator_trait(tb: &mut TokenBuilder, nbr_elements: usize) {
         #[coverage(off)]
         fn random_mutate<'__fuzzcheck_derive_lt>(&self, value: " tuple_mut ", cache: &'__fuzzcheck_derive_lt mut Self::Cache, max_cplx: f64, ) -> (Self::UnmutateToken, f64) {
             let current_cplx = " SelfAsTupleMutator "::complexity(self, " TupleNAsRefTypes "::get_ref_from_mut(&value), cache);
-            match cache.vose_alias.sample() {"
+            match cache.vose_alias.sample_with(&self.rng) {"
                 join_ts!(0..nbr_elements, i,
                     i "=> {
                         let old_field_cplx = self." mutator_i(i) ".complexity(value." i ", &cache." ti(i) ");
@@ -707,6 +777,43 @@ fn impl_mutator_trait(tb: &mut TokenBuilder, nbr_elements: usize) {
             )
             "
         }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn set_effort_hint(&self, hint: " cm.EffortHint ") {
+            self.effort_hint.set(hint);"
+            join_ts!(0..nbr_elements, i,
+                "self." mutator_i(i) ".set_effort_hint(hint);"
+            )
+        "}
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn unmutate_is_cheap(&self) -> bool {
+            true"
+            join_ts!(0..nbr_elements, i,
+                "&& self." mutator_i(i) ".unmutate_is_cheap()"
+            )
+        "}
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn rng_state(&self) -> " cm.Option "<" cm.Vec "<u8>> {
+            " cm.Some "(" cm.mutators "::rng_states(&self.rng, ["
+                join_ts!(0..nbr_elements, i,
+                    "self." mutator_i(i) ".rng_state()"
+                , separator: ",")
+            "]))
+        }
+
+        #[doc(hidden)]
+        #[coverage(off)]
+        fn set_rng_state(&self, state: &[u8]) {
+            let mut _states = " cm.mutators "::set_rng_states(&self.rng, state);"
+            join_ts!(0..nbr_elements, i,
+                "self." mutator_i(i) ".set_rng_state(_states.next().unwrap_or_default());"
+            )
+        "}
     }"
     )
 }